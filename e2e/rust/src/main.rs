@@ -18,7 +18,7 @@ use constellation_sdk::{
     create_currency_transaction, key_pair_from_private_key, verify_currency_transaction,
     wallet::generate_key_pair, CurrencyTransaction, TransactionReference, TransferParams,
 };
-use constellation_sdk::network::{CurrencyL1Client, NetworkConfig};
+use constellation_sdk::network::NetworkConfig;
 
 #[derive(Deserialize)]
 struct Config {
@@ -96,7 +96,7 @@ async fn send_transaction(config: Config) {
         l1_url: Some(currency_l1_url.clone()),
         ..Default::default()
     };
-    let client = match CurrencyL1Client::new(network_config) {
+    let client = match network_config.currency_l1_client() {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Error creating client: {}", e);
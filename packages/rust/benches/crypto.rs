@@ -0,0 +1,137 @@
+//! Benchmarks for the crypto hot paths
+//!
+//! Run with `cargo bench --bench crypto`. Pass a filter to run one group,
+//! e.g. `cargo bench --bench crypto -- sign`.
+
+use constellation_sdk::{
+    canonicalize, canonicalize_bytes, encode_data_update, generate_key_pair, hash_bytes, hash_data,
+    key_cache, sign, sign_data_update, to_bytes_into, verify_signature,
+};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use serde_json::{json, Value};
+
+/// A typical sensor-style data update
+fn small() -> Value {
+    json!({
+        "id": "sensor-7",
+        "reading": 21.5,
+        "unit": "celsius",
+        "tags": ["roof", "north"]
+    })
+}
+
+/// A thousand flat records, around 100 KB of canonical JSON
+fn large() -> Value {
+    let records: Vec<Value> = (0..1000)
+        .map(|i| {
+            json!({
+                "address": format!("DAG{:040}", i),
+                "amount": i * 100_000_000u64,
+                "memo": "x".repeat(16),
+                "ordinal": i
+            })
+        })
+        .collect();
+    json!({ "records": records })
+}
+
+/// Objects nested 64 deep with unsorted keys at every level
+fn nested() -> Value {
+    (0..64).fold(
+        json!({"leaf": true}),
+        |inner, depth| json!({ "z": depth, "child": inner, "a": [depth, depth + 1] }),
+    )
+}
+
+fn payloads() -> [(&'static str, Value); 3] {
+    [("small", small()), ("large", large()), ("nested", nested())]
+}
+
+fn bench_canonicalize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("canonicalize");
+    for (name, payload) in payloads() {
+        let size = canonicalize_bytes(&payload).unwrap().len();
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(name), &payload, |b, payload| {
+            b.iter(|| canonicalize(black_box(payload)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_hash(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash");
+    for (name, payload) in payloads() {
+        let bytes = canonicalize_bytes(&payload).unwrap();
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::new("bytes", name), &bytes, |b, bytes| {
+            b.iter(|| hash_bytes(black_box(bytes)))
+        });
+        group.bench_with_input(BenchmarkId::new("data", name), &payload, |b, payload| {
+            b.iter(|| hash_data(black_box(payload), false).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_encode_data_update(c: &mut Criterion) {
+    let mut group = c.benchmark_group("encode_data_update");
+    for (name, payload) in payloads() {
+        group.bench_with_input(BenchmarkId::new("alloc", name), &payload, |b, payload| {
+            b.iter(|| encode_data_update(black_box(payload)).unwrap())
+        });
+        let mut buf = Vec::new();
+        group.bench_with_input(BenchmarkId::new("reuse", name), &payload, |b, payload| {
+            b.iter(|| {
+                buf.clear();
+                to_bytes_into(black_box(payload), true, &mut buf).unwrap();
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_sign(c: &mut Criterion) {
+    let key_pair = generate_key_pair();
+    let mut group = c.benchmark_group("sign");
+    for (name, payload) in payloads() {
+        group.bench_with_input(BenchmarkId::new("regular", name), &payload, |b, payload| {
+            b.iter(|| sign(black_box(payload), &key_pair.private_key).unwrap())
+        });
+        group.bench_with_input(
+            BenchmarkId::new("data_update", name),
+            &payload,
+            |b, payload| {
+                b.iter(|| sign_data_update(black_box(payload), &key_pair.private_key).unwrap())
+            },
+        );
+    }
+    group.finish();
+}
+
+fn bench_verify(c: &mut Criterion) {
+    let key_pair = generate_key_pair();
+    let payload = small();
+    let proof = sign(&payload, &key_pair.private_key).unwrap();
+
+    let mut group = c.benchmark_group("verify");
+    group.bench_function("uncached", |b| {
+        b.iter(|| assert!(verify_signature(black_box(&payload), &proof, false).unwrap()))
+    });
+    key_cache::enable_public_key_cache(16);
+    group.bench_function("key_cache", |b| {
+        b.iter(|| assert!(verify_signature(black_box(&payload), &proof, false).unwrap()))
+    });
+    key_cache::disable_public_key_cache();
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_canonicalize,
+    bench_hash,
+    bench_encode_data_update,
+    bench_sign,
+    bench_verify
+);
+criterion_main!(benches);
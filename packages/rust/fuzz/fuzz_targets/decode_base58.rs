@@ -0,0 +1,10 @@
+#![no_main]
+
+use constellation_sdk::codec::decode_base58;
+use constellation_sdk::is_valid_dag_address;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = decode_base58(input);
+    let _ = is_valid_dag_address(input);
+});
@@ -0,0 +1,9 @@
+#![no_main]
+
+use constellation_sdk::codec::decode_data_update;
+use libfuzzer_sys::fuzz_target;
+use serde_json::Value;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = decode_data_update::<Value>(data);
+});
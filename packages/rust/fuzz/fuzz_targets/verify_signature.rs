@@ -0,0 +1,13 @@
+#![no_main]
+
+use constellation_sdk::try_get_address;
+use constellation_sdk::verify::{parse_signature, verify_hash};
+use libfuzzer_sys::fuzz_target;
+
+// Signature and public key as an indexer would read them from a proof
+fuzz_target!(|input: (&str, &str)| {
+    let (signature, public_key_id) = input;
+    let _ = parse_signature(signature);
+    let _ = try_get_address(public_key_id);
+    let _ = verify_hash(&"0".repeat(64), signature, public_key_id);
+});
@@ -0,0 +1,75 @@
+/*
+ * C interface of the Constellation Metagraph SDK.
+ *
+ * Build the Rust crate with
+ * `cargo rustc --lib --release --features ffi --crate-type cdylib` and link
+ * against the resulting libconstellation_sdk (.so / .dylib / .dll).
+ *
+ * Every function returns a constellation_status. Results are written through
+ * out pointers, which are left untouched on failure. Strings and buffers
+ * written by the library are owned by the caller and released with
+ * constellation_string_free / constellation_bytes_free. After a failure,
+ * constellation_last_error() returns the message of the last error on the
+ * calling thread and constellation_last_error_code() its stable SDK error
+ * code (see SdkError::code in the Rust docs).
+ */
+
+#ifndef CONSTELLATION_SDK_H
+#define CONSTELLATION_SDK_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef int32_t constellation_status;
+
+#define CONSTELLATION_OK 0
+#define CONSTELLATION_INVALID_ARGUMENT 1
+#define CONSTELLATION_FAILED 2
+#define CONSTELLATION_PANIC 3
+
+/* Message of the last error on this thread, or NULL. Free with constellation_string_free. */
+char *constellation_last_error(void);
+/* Stable SDK error code of the last error on this thread, or 0. */
+uint32_t constellation_last_error_code(void);
+
+void constellation_string_free(char *s);
+void constellation_bytes_free(uint8_t *bytes, size_t len);
+
+/* Keys: private keys are 64 hex chars, public keys 130 hex chars (04 prefix). */
+constellation_status constellation_generate_key_pair(char **private_key, char **public_key,
+                                                     char **address);
+constellation_status constellation_key_pair_from_private_key(const char *private_key,
+                                                             char **public_key, char **address);
+
+/* Signing: values and signed objects are JSON. */
+constellation_status constellation_create_signed_object(const char *value_json,
+                                                        const char *private_key,
+                                                        bool is_data_update, char **signed_json);
+constellation_status constellation_add_signature(const char *signed_json, const char *private_key,
+                                                 bool is_data_update, char **out_json);
+constellation_status constellation_sign_hash(const char *hash_hex, const char *private_key,
+                                             char **signature);
+
+/* Verification */
+constellation_status constellation_verify(const char *signed_json, bool is_data_update,
+                                          bool *valid);
+constellation_status constellation_verify_hash(const char *hash_hex, const char *signature,
+                                               const char *public_key_id, bool *valid);
+
+/* Encoding */
+constellation_status constellation_to_bytes(const char *value_json, bool is_data_update,
+                                            uint8_t **bytes, size_t *len);
+constellation_status constellation_encode_data_update(const char *value_json, uint8_t **bytes,
+                                                      size_t *len);
+constellation_status constellation_hash_bytes(const uint8_t *bytes, size_t len, char **hash);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CONSTELLATION_SDK_H */
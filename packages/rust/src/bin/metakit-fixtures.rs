@@ -0,0 +1,113 @@
+//! Writes Scala-compatibility fixtures for values given as JSON
+//!
+//! The input is a JSON object of fixture name to value. Each value is signed
+//! as regular data, or as a DataUpdate with `--data-update`, by the key from
+//! `--private-key` or `METAKIT_PRIVATE_KEY`, and the fixtures are printed as
+//! JSON (see `constellation_sdk::fixtures`). With `--check FILE` they are
+//! compared with a committed fixture file instead, exiting with 1 on drift.
+//!
+//! ```bash
+//! cargo run --features fixtures-cli --bin metakit-fixtures -- \
+//!     samples.json --data-update > src/test/resources/fixtures.json
+//! ```
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::error::Error;
+use std::io::Read;
+use std::process::ExitCode;
+
+use constellation_sdk::data_update::DataUpdate;
+use constellation_sdk::fixtures::FixtureSet;
+use constellation_sdk::LocalSigner;
+
+const USAGE: &str =
+    "usage: metakit-fixtures [INPUT] [--data-update] [--private-key HEX] [--check FILE]";
+
+/// A JSON value signed with the default DataUpdate encoding
+#[derive(Serialize)]
+#[serde(transparent)]
+struct JsonUpdate(Value);
+
+impl DataUpdate for JsonUpdate {}
+
+#[derive(Default)]
+struct Options {
+    input: Option<String>,
+    data_update: bool,
+    private_key: Option<String>,
+    check: Option<String>,
+}
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run() -> Result<ExitCode, Box<dyn Error>> {
+    let options = parse_args(std::env::args().skip(1))?;
+    let private_key = options
+        .private_key
+        .or_else(|| std::env::var("METAKIT_PRIVATE_KEY").ok())
+        .ok_or("a private key is required (--private-key or METAKIT_PRIVATE_KEY)")?;
+    let signer = LocalSigner::new(&private_key)?;
+
+    let input = read_input(options.input.as_deref().unwrap_or("-"))?;
+    let samples: Map<String, Value> =
+        serde_json::from_str(&input).map_err(|e| format!("input is not a JSON object: {e}"))?;
+
+    let mut fixtures = FixtureSet::new(&signer);
+    for (name, value) in samples {
+        if options.data_update {
+            fixtures.data_update(&name, &JsonUpdate(value))?;
+        } else {
+            fixtures.data(&name, &value)?;
+        }
+    }
+
+    match options.check {
+        Some(path) => {
+            if let Err(e) = fixtures.check(&std::fs::read_to_string(path)?) {
+                eprintln!("{e}");
+                return Ok(ExitCode::from(1));
+            }
+        }
+        None => print!("{}", fixtures.to_json()?),
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, Box<dyn Error>> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--data-update" => options.data_update = true,
+            "--private-key" => options.private_key = Some(args.next().ok_or(USAGE)?),
+            "--check" => options.check = Some(args.next().ok_or(USAGE)?),
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                std::process::exit(0);
+            }
+            _ if options.input.is_none() && (arg == "-" || !arg.starts_with('-')) => {
+                options.input = Some(arg);
+            }
+            _ => return Err(format!("unexpected argument {arg}\n{USAGE}").into()),
+        }
+    }
+    Ok(options)
+}
+
+fn read_input(path: &str) -> std::io::Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        std::fs::read_to_string(path)
+    }
+}
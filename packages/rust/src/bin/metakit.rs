@@ -0,0 +1,328 @@
+//! Command-line interface to the SDK for operators and scripts
+//!
+//! Every command prints JSON on stdout. Commands that sign read the private
+//! key from `--private-key` or `METAKIT_PRIVATE_KEY`; commands that read JSON
+//! take a file path or `-` for stdin.
+//!
+//! ```bash
+//! cargo install constellation-metagraph-sdk --features cli
+//! metakit keygen
+//! metakit sign update.json --data-update
+//! metakit --network mainnet balance DAG...
+//! metakit --network mainnet send --to DAG... --amount 1.5
+//! ```
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use futures_util::{pin_mut, StreamExt};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::error::Error;
+use std::io::Read;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use constellation_sdk::network::{Account, NetworkConfig};
+use constellation_sdk::{
+    create_signed_object, generate_key_pair, key_pair_from_private_key, try_get_address, verify,
+    Signed, TransferParams,
+};
+
+type CliResult<T> = Result<T, Box<dyn Error>>;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "metakit",
+    version,
+    about = "Sign and submit to Constellation metagraphs"
+)]
+struct Cli {
+    #[command(flatten)]
+    endpoints: Endpoints,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Generate a random key pair
+    Keygen,
+    /// Print the DAG address of a public key, or of the private key
+    Address {
+        /// Public key in hex
+        public_key: Option<String>,
+        #[command(flatten)]
+        key: KeyArg,
+    },
+    /// Sign a JSON value and print the signed object
+    Sign {
+        /// JSON file, or `-` for stdin
+        #[arg(default_value = "-")]
+        input: String,
+        /// Sign as a DataUpdate
+        #[arg(long)]
+        data_update: bool,
+        #[command(flatten)]
+        key: KeyArg,
+    },
+    /// Verify a signed object; exits with 1 if any proof is invalid
+    Verify {
+        /// JSON file, or `-` for stdin
+        #[arg(default_value = "-")]
+        input: String,
+        /// Verify as a DataUpdate
+        #[arg(long)]
+        data_update: bool,
+    },
+    /// Send tokens to an address
+    Send {
+        /// Destination DAG address
+        #[arg(long)]
+        to: String,
+        /// Amount in tokens
+        #[arg(long)]
+        amount: f64,
+        /// Fee in tokens
+        #[arg(long, default_value_t = 0.0)]
+        fee: f64,
+        #[command(flatten)]
+        key: KeyArg,
+    },
+    /// Sign a JSON value as a DataUpdate and submit it to the Data L1
+    SubmitData {
+        /// JSON file, or `-` for stdin
+        #[arg(default_value = "-")]
+        input: String,
+        #[command(flatten)]
+        key: KeyArg,
+    },
+    /// Print the balance of an address, or of the private key's address
+    Balance {
+        /// DAG address
+        address: Option<String>,
+        #[command(flatten)]
+        key: KeyArg,
+    },
+    /// Print each transaction of an address as it is confirmed, one per line
+    Watch {
+        /// DAG address
+        address: Option<String>,
+        /// Seconds between polls of the block explorer
+        #[arg(long, default_value_t = 10)]
+        interval: u64,
+        #[command(flatten)]
+        key: KeyArg,
+    },
+}
+
+#[derive(Debug, Args)]
+struct KeyArg {
+    /// Private key in hex
+    #[arg(long, env = "METAKIT_PRIVATE_KEY", hide_env_values = true)]
+    private_key: Option<String>,
+}
+
+impl KeyArg {
+    fn require(&self) -> CliResult<&str> {
+        self.private_key
+            .as_deref()
+            .ok_or_else(|| "no private key; pass --private-key or set METAKIT_PRIVATE_KEY".into())
+    }
+
+    fn address(&self, address: Option<String>) -> CliResult<String> {
+        match address {
+            Some(address) => Ok(address),
+            None => Ok(key_pair_from_private_key(self.require()?)?.address),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Network {
+    Mainnet,
+    Testnet,
+    Integrationnet,
+}
+
+#[derive(Debug, Args)]
+struct Endpoints {
+    /// Public network preset; the URLs below add to or override it
+    #[arg(long, global = true, env = "METAKIT_NETWORK")]
+    network: Option<Network>,
+    /// Global L0 URL
+    #[arg(long, global = true, env = "METAKIT_L0_URL")]
+    l0_url: Option<String>,
+    /// Metagraph L0 URL
+    #[arg(long, global = true, env = "METAKIT_CURRENCY_L0_URL")]
+    currency_l0_url: Option<String>,
+    /// Currency L1 URL
+    #[arg(long, global = true, env = "METAKIT_L1_URL")]
+    l1_url: Option<String>,
+    /// Data L1 URL
+    #[arg(long, global = true, env = "METAKIT_DATA_L1_URL")]
+    data_l1_url: Option<String>,
+    /// Block explorer URL
+    #[arg(long, global = true, env = "METAKIT_BLOCK_EXPLORER_URL")]
+    block_explorer_url: Option<String>,
+    /// Metagraph ID, for the block explorer's token history
+    #[arg(long, global = true, env = "METAKIT_METAGRAPH_ID")]
+    metagraph_id: Option<String>,
+}
+
+impl Endpoints {
+    fn to_config(&self) -> NetworkConfig {
+        let mut config = match self.network {
+            None => NetworkConfig::default(),
+            Some(Network::Mainnet) => NetworkConfig::mainnet(),
+            Some(Network::Testnet) => NetworkConfig::testnet(),
+            Some(Network::Integrationnet) => NetworkConfig::integrationnet(),
+        };
+        if let Some(metagraph_id) = &self.metagraph_id {
+            config = config.for_metagraph(metagraph_id);
+        }
+        let urls = [
+            (&self.l0_url, &mut config.l0_url),
+            (&self.currency_l0_url, &mut config.currency_l0_url),
+            (&self.l1_url, &mut config.l1_url),
+            (&self.data_l1_url, &mut config.data_l1_url),
+            (&self.block_explorer_url, &mut config.block_explorer_url),
+        ];
+        for (url, field) in urls {
+            if url.is_some() {
+                field.clone_from(url);
+            }
+        }
+        config
+    }
+
+    fn account(&self, key: &KeyArg) -> CliResult<Account> {
+        Ok(Account::from_private_key(key.require()?)?.with_config(&self.to_config())?)
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli) {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(cli: Cli) -> CliResult<ExitCode> {
+    let endpoints = &cli.endpoints;
+    match cli.command {
+        Command::Keygen => {
+            let key_pair = generate_key_pair();
+            print(&json!({
+                "privateKey": key_pair.private_key,
+                "publicKey": key_pair.public_key,
+                "address": key_pair.address,
+            }))?;
+        }
+        Command::Address { public_key, key } => {
+            let address = match public_key {
+                Some(public_key) => try_get_address(&public_key)?,
+                None => key.address(None)?,
+            };
+            print(&json!({ "address": address }))?;
+        }
+        Command::Sign {
+            input,
+            data_update,
+            key,
+        } => {
+            let value: Value = serde_json::from_str(&read_input(&input)?)?;
+            print(&create_signed_object(&value, key.require()?, data_update)?)?;
+        }
+        Command::Verify { input, data_update } => {
+            let signed: Signed<Value> = serde_json::from_str(&read_input(&input)?)?;
+            let result = verify(&signed, data_update);
+            print(&json!({
+                "isValid": result.is_valid,
+                "validProofs": result.valid_proofs,
+                "invalidProofs": result.invalid_proofs,
+            }))?;
+            if !result.is_valid {
+                return Ok(ExitCode::from(1));
+            }
+        }
+        Command::Send {
+            to,
+            amount,
+            fee,
+            key,
+        } => {
+            let account = endpoints.account(&key)?;
+            let params = TransferParams {
+                destination: to,
+                amount,
+                fee,
+            };
+            print(&block_on(account.send(params))??)?;
+        }
+        Command::SubmitData { input, key } => {
+            let value: Value = serde_json::from_str(&read_input(&input)?)?;
+            let account = endpoints.account(&key)?;
+            print(&block_on(account.submit_data(&value))??)?;
+        }
+        Command::Balance { address, key } => {
+            let address = key.address(address)?;
+            let config = endpoints.to_config();
+            let balance = if config.currency_l0_url.is_some() {
+                let client = config.currency_l0_client()?;
+                block_on(async { client.get_balance(&address).await })??
+            } else {
+                let client = config.global_l0_client()?;
+                block_on(async { client.get_balance(&address).await })??
+            };
+            print(&json!({
+                "address": address,
+                "balance": balance.amount,
+                "tokens": balance.to_tokens(),
+                "ordinal": balance.ordinal,
+            }))?;
+        }
+        Command::Watch {
+            address,
+            interval,
+            key,
+        } => {
+            let address = key.address(address)?;
+            let explorer = endpoints.to_config().block_explorer_client()?;
+            block_on(async {
+                let transactions =
+                    explorer.watch_transactions(&address, Duration::from_secs(interval));
+                pin_mut!(transactions);
+                while let Some(tx) = transactions.next().await {
+                    match tx {
+                        Ok(tx) => println!("{}", serde_json::to_string(&tx)?),
+                        Err(e) => eprintln!("error: {e}"),
+                    }
+                }
+                Ok::<_, serde_json::Error>(())
+            })??;
+        }
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn read_input(input: &str) -> CliResult<String> {
+    if input == "-" {
+        let mut buffer = String::new();
+        std::io::stdin().read_to_string(&mut buffer)?;
+        Ok(buffer)
+    } else {
+        Ok(std::fs::read_to_string(input)?)
+    }
+}
+
+fn print<T: Serialize>(value: &T) -> CliResult<()> {
+    println!("{}", serde_json::to_string_pretty(value)?);
+    Ok(())
+}
+
+fn block_on<F: std::future::Future>(future: F) -> CliResult<F::Output> {
+    Ok(tokio::runtime::Runtime::new()?.block_on(future))
+}
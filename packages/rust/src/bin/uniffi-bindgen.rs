@@ -0,0 +1,11 @@
+//! Generates the Kotlin and Swift sources for the `uniffi` feature
+//!
+//! ```bash
+//! cargo rustc --lib --release --features uniffi --crate-type cdylib
+//! cargo run --features uniffi-cli --bin uniffi-bindgen -- generate \
+//!     --library target/release/libconstellation_sdk.so --language kotlin --out-dir out
+//! ```
+
+fn main() {
+    uniffi::uniffi_bindgen_main()
+}
@@ -10,14 +10,32 @@ use crate::types::{Result, SdkError, CONSTELLATION_PREFIX};
 // Re-export binary encoding functions
 pub use crate::binary::{encode_data_update, to_bytes};
 
+/// Largest encoded DataUpdate [`decode_data_update`] accepts, in bytes
+///
+/// Well above the payloads nodes accept; input this large is rejected before
+/// any allocation proportional to it.
+pub const MAX_DATA_UPDATE_SIZE: usize = 4 * 1024 * 1024;
+
+/// Longest base58 string [`decode_base58`] accepts, in characters
+pub const MAX_BASE58_LENGTH: usize = 128;
+
 /// Decode a DataUpdate back to JSON
 ///
+/// Safe to call on untrusted input: it never panics, rejects input larger
+/// than [`MAX_DATA_UPDATE_SIZE`], and checks the declared length against the
+/// payload before decoding it.
+///
 /// # Arguments
 /// * `data` - UTF-8 bytes with Constellation prefix
 ///
 /// # Returns
 /// Decoded data
 ///
+/// # Errors
+///
+/// Returns `SerializationError` naming the first malformed part, or `Json`
+/// if the payload is not the expected JSON
+///
 /// # Example
 /// ```
 /// use constellation_sdk::codec::{encode_data_update, decode_data_update};
@@ -29,42 +47,90 @@ pub use crate::binary::{encode_data_update, to_bytes};
 /// assert_eq!(decoded, data);
 /// ```
 pub fn decode_data_update<T: DeserializeOwned>(data: &[u8]) -> Result<T> {
-    let s = String::from_utf8(data.to_vec())
-        .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+    decode_data_update_with_limit(data, MAX_DATA_UPDATE_SIZE)
+}
 
-    // Check for Constellation prefix
-    if !s.starts_with(CONSTELLATION_PREFIX) {
-        return Err(SdkError::SerializationError(
-            "Invalid DataUpdate format: missing Constellation prefix".to_string(),
-        ));
+/// [`decode_data_update`] with a caller-chosen size limit in bytes
+pub fn decode_data_update_with_limit<T: DeserializeOwned>(data: &[u8], limit: usize) -> Result<T> {
+    if data.len() > limit {
+        return Err(invalid(format!(
+            "{} bytes exceeds the limit of {limit}",
+            data.len()
+        )));
     }
 
-    // Remove prefix and parse
-    let rest = &s[CONSTELLATION_PREFIX.len()..];
-
-    // Find the length line
-    let parts: Vec<&str> = rest.splitn(2, '\n').collect();
-    if parts.len() != 2 {
-        return Err(SdkError::SerializationError(
-            "Invalid DataUpdate format: missing length separator".to_string(),
-        ));
-    }
+    // Check for Constellation prefix
+    let rest = data
+        .strip_prefix(CONSTELLATION_PREFIX.as_bytes())
+        .ok_or_else(|| invalid("missing Constellation prefix".to_string()))?;
 
-    let _length: usize = parts[0]
-        .parse()
-        .map_err(|_| SdkError::SerializationError("Invalid length in DataUpdate".to_string()))?;
+    // Split the length line from the payload
+    let separator = rest
+        .iter()
+        .position(|&b| b == b'\n')
+        .ok_or_else(|| invalid("missing length separator".to_string()))?;
+    let (length_line, payload) = (&rest[..separator], &rest[separator + 1..]);
 
-    let base64_data = parts[1];
+    if length_line.is_empty() || !length_line.iter().all(u8::is_ascii_digit) {
+        return Err(invalid(format!(
+            "length {:?} is not a decimal number",
+            String::from_utf8_lossy(&length_line[..length_line.len().min(20)])
+        )));
+    }
+    // All digits, so only overflow can fail; such a length never matches
+    let declared = core::str::from_utf8(length_line)
+        .ok()
+        .and_then(|length| length.parse::<usize>().ok());
+    if declared != Some(payload.len()) {
+        return Err(invalid(format!(
+            "declared length {} does not match the {}-byte payload",
+            String::from_utf8_lossy(&length_line[..length_line.len().min(20)]),
+            payload.len()
+        )));
+    }
 
     // Decode base64
     let decoded_bytes = base64::engine::general_purpose::STANDARD
-        .decode(base64_data)
-        .map_err(|e| SdkError::SerializationError(format!("Invalid base64: {e}")))?;
+        .decode(payload)
+        .map_err(|e| invalid(format!("invalid base64: {e}")))?;
 
     // Parse JSON
     serde_json::from_slice(&decoded_bytes).map_err(|e| e.into())
 }
 
+/// Decode a base58 string (Bitcoin alphabet, as in DAG addresses)
+///
+/// Safe to call on untrusted input: strings longer than
+/// [`MAX_BASE58_LENGTH`] are rejected before decoding, since base58 decoding
+/// takes time quadratic in the length.
+///
+/// # Errors
+///
+/// Returns `SerializationError` naming the offending character or length
+///
+/// # Example
+/// ```
+/// use constellation_sdk::codec::decode_base58;
+///
+/// assert_eq!(decode_base58("2NEpo7TZRRrLZSi2U").unwrap(), b"Hello World!");
+/// assert!(decode_base58("0OIl").is_err());
+/// ```
+pub fn decode_base58(input: &str) -> Result<Vec<u8>> {
+    if input.len() > MAX_BASE58_LENGTH {
+        return Err(SdkError::SerializationError(format!(
+            "base58 string of {} characters exceeds the limit of {MAX_BASE58_LENGTH}",
+            input.len()
+        )));
+    }
+    bs58::decode(input)
+        .into_vec()
+        .map_err(|e| SdkError::SerializationError(format!("invalid base58: {e}")))
+}
+
+fn invalid(reason: String) -> SdkError {
+    SdkError::SerializationError(format!("Invalid DataUpdate format: {reason}"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -91,4 +157,36 @@ mod tests {
         let result: Result<Value> = decode_data_update(data.as_bytes());
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_decode_rejects_length_mismatch() {
+        let data = format!("{CONSTELLATION_PREFIX}4\neyJpZCI6MX0=");
+        let err = decode_data_update::<Value>(data.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("declared length 4"));
+
+        let data = format!("{CONSTELLATION_PREFIX}99999999999999999999999\neyJpZCI6MX0=");
+        assert!(decode_data_update::<Value>(data.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_oversized_input() {
+        let encoded = encode_data_update(&json!({"id": "x".repeat(100)})).unwrap();
+        let err = decode_data_update_with_limit::<Value>(&encoded, 64).unwrap_err();
+        assert!(err.to_string().contains("exceeds the limit of 64"));
+    }
+
+    #[test]
+    fn test_decode_truncations_never_panic() {
+        let encoded = encode_data_update(&json!({"id": "test", "value": [1, 2, 3]})).unwrap();
+        for end in 0..encoded.len() {
+            assert!(decode_data_update::<Value>(&encoded[..end]).is_err());
+        }
+    }
+
+    #[test]
+    fn test_decode_base58() {
+        assert_eq!(decode_base58("").unwrap(), Vec::<u8>::new());
+        assert!(decode_base58("DAG0O").is_err());
+        assert!(decode_base58(&"1".repeat(MAX_BASE58_LENGTH + 1)).is_err());
+    }
 }
@@ -0,0 +1,349 @@
+//! Binary Encoding
+//!
+//! Functions for encoding data to binary format for signing.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use base64::Engine;
+use serde::Serialize;
+
+use super::canonicalize::canonicalize_into;
+use crate::types::{Result, SdkError, CONSTELLATION_PREFIX};
+
+/// Convert data to bytes for signing
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `is_data_update` - Whether to encode as a DataUpdate (with Constellation prefix)
+///
+/// # Returns
+/// UTF-8 bytes ready for hashing
+///
+/// # Example
+/// ```
+/// use constellation_sdk::binary::to_bytes;
+/// use serde_json::json;
+///
+/// let data = json!({"id": "test"});
+/// let bytes = to_bytes(&data, false).unwrap();
+/// ```
+pub fn to_bytes<T: Serialize>(data: &T, is_data_update: bool) -> Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    to_bytes_into(data, is_data_update, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Append the bytes for signing to a buffer
+///
+/// Produces the same bytes as [`to_bytes`], but lets a loop over many
+/// payloads reuse one buffer, clearing it between payloads. A DataUpdate is
+/// encoded in place, so once the buffer has grown to fit the largest payload
+/// nothing further is allocated for either form.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `is_data_update` - Whether to encode as a DataUpdate (with Constellation prefix)
+/// * `out` - Buffer the bytes are appended to
+///
+/// # Example
+/// ```
+/// use constellation_sdk::binary::{to_bytes, to_bytes_into};
+/// use constellation_sdk::hash::hash_bytes;
+/// use serde_json::json;
+///
+/// let mut buf = Vec::new();
+/// for i in 0..3 {
+///     buf.clear();
+///     to_bytes_into(&json!({"n": i}), true, &mut buf).unwrap();
+///     let hash = hash_bytes(&buf);
+/// #   assert_eq!(buf, to_bytes(&json!({"n": i}), true).unwrap());
+/// }
+/// ```
+pub fn to_bytes_into<T: Serialize>(
+    data: &T,
+    is_data_update: bool,
+    out: &mut Vec<u8>,
+) -> Result<()> {
+    let start = out.len();
+    canonicalize_into(data, out)?;
+    if !is_data_update {
+        return Ok(());
+    }
+
+    // Wrap as `<prefix><base64 length>\n<base64>` by moving the JSON past
+    // the space the wrapped form needs, then encoding it back into that space
+    let json_len = out.len() - start;
+    let encoded_len = encoded_len(json_len)?;
+    let header_len = header_len(encoded_len);
+    let end = start + header_len + encoded_len;
+
+    out.resize(end + json_len, 0);
+    out.copy_within(start..start + json_len, end);
+
+    let (wrapped, json) = out.split_at_mut(end);
+    let (header, encoded) = wrapped[start..].split_at_mut(header_len);
+    write_header(header, encoded_len);
+    base64::engine::general_purpose::STANDARD
+        .encode_slice(json, encoded)
+        .map_err(|e| SdkError::SerializationError(e.to_string()))?;
+
+    out.truncate(end);
+    Ok(())
+}
+
+/// Encode data as a DataUpdate (convenience wrapper)
+///
+/// Produces the same bytes as `to_bytes(data, true)`. With `std` the
+/// canonical JSON is streamed through the base64 encoder instead of being
+/// kept next to its encoding, so beyond the canonicalizer's own buffering
+/// only the result is held in memory. That takes a second pass to measure
+/// the JSON first; loops over small payloads are faster with
+/// [`to_bytes_into`].
+///
+/// # Arguments
+/// * `data` - Any serializable data
+///
+/// # Returns
+/// UTF-8 bytes with Constellation prefix
+pub fn encode_data_update<T: Serialize>(data: &T) -> Result<Vec<u8>> {
+    #[cfg(feature = "std")]
+    return stream::encode_data_update(data);
+    #[cfg(not(feature = "std"))]
+    to_bytes(data, true)
+}
+
+/// Length of the base64 encoding of `json_len` bytes
+fn encoded_len(json_len: usize) -> Result<usize> {
+    base64::encoded_len(json_len, true)
+        .ok_or_else(|| SdkError::SerializationError("payload too large to encode".into()))
+}
+
+/// Length of `<prefix><encoded_len>\n`
+fn header_len(encoded_len: usize) -> usize {
+    let digits = encoded_len.checked_ilog10().unwrap_or(0) as usize + 1;
+    CONSTELLATION_PREFIX.len() + digits + 1
+}
+
+/// Write `<prefix><encoded_len>\n` into a slice of [`header_len`] bytes
+fn write_header(header: &mut [u8], encoded_len: usize) {
+    let (prefix, rest) = header.split_at_mut(CONSTELLATION_PREFIX.len());
+    prefix.copy_from_slice(CONSTELLATION_PREFIX.as_bytes());
+    if let Some((newline, digits)) = rest.split_last_mut() {
+        let mut n = encoded_len;
+        for digit in digits.iter_mut().rev() {
+            *digit = b'0' + (n % 10) as u8;
+            n /= 10;
+        }
+        *newline = b'\n';
+    }
+}
+
+/// DataUpdate encoding that never holds the JSON and its base64 at once
+#[cfg(feature = "std")]
+mod stream {
+    use std::io;
+
+    use super::*;
+    use crate::canonicalize::canonicalize_to_io;
+
+    pub(super) fn encode_data_update<T: Serialize>(data: &T) -> Result<Vec<u8>> {
+        // First pass: measure the canonical JSON without keeping it
+        let mut counter = ByteCounter(0);
+        canonicalize_to_io(data, &mut counter)?;
+        let encoded_len = encoded_len(counter.0)?;
+        let header_len = header_len(encoded_len);
+
+        let mut out = Vec::with_capacity(header_len + encoded_len);
+        out.resize(header_len, 0);
+        write_header(&mut out, encoded_len);
+
+        // Second pass: encode the canonical JSON as it is produced
+        let mut encoder = Base64Writer::new(&mut out);
+        canonicalize_to_io(data, &mut encoder)?;
+        encoder.finish();
+
+        if out.len() != header_len + encoded_len {
+            return Err(SdkError::SerializationError(
+                "canonical JSON changed between passes".into(),
+            ));
+        }
+        Ok(out)
+    }
+
+    /// Writer that only counts bytes
+    struct ByteCounter(usize);
+
+    impl io::Write for ByteCounter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0 += buf.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Writer that appends the standard base64 encoding of its input
+    ///
+    /// Input is encoded in 3-byte groups; up to two trailing bytes wait for
+    /// the next write, and [`finish`](Self::finish) pads whatever is left.
+    pub(super) struct Base64Writer<'a> {
+        out: &'a mut Vec<u8>,
+        pending: [u8; 3],
+        pending_len: usize,
+    }
+
+    impl<'a> Base64Writer<'a> {
+        pub(super) fn new(out: &'a mut Vec<u8>) -> Self {
+            Self {
+                out,
+                pending: [0; 3],
+                pending_len: 0,
+            }
+        }
+
+        /// Encode and pad the trailing bytes
+        pub(super) fn finish(mut self) {
+            let pending = self.pending;
+            self.encode(&pending[..self.pending_len]);
+        }
+
+        fn encode(&mut self, input: &[u8]) {
+            let start = self.out.len();
+            self.out.resize(start + (input.len() + 2) / 3 * 4, 0);
+            let written = base64::engine::general_purpose::STANDARD
+                .encode_slice(input, &mut self.out[start..])
+                .expect("output is sized for the input");
+            self.out.truncate(start + written);
+        }
+    }
+
+    impl io::Write for Base64Writer<'_> {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            let mut input = buf;
+            if self.pending_len > 0 {
+                let take = (3 - self.pending_len).min(input.len());
+                self.pending[self.pending_len..self.pending_len + take]
+                    .copy_from_slice(&input[..take]);
+                self.pending_len += take;
+                input = &input[take..];
+                if self.pending_len < 3 {
+                    return Ok(buf.len());
+                }
+                let pending = self.pending;
+                self.encode(&pending);
+                self.pending_len = 0;
+            }
+            let whole = input.len() - input.len() % 3;
+            self.encode(&input[..whole]);
+            let rest = &input[whole..];
+            self.pending[..rest.len()].copy_from_slice(rest);
+            self.pending_len = rest.len();
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_to_bytes_regular() {
+        let data = json!({"id": "test", "value": 42});
+        let bytes = to_bytes(&data, false).unwrap();
+        let s = String::from_utf8(bytes).unwrap();
+        assert_eq!(s, r#"{"id":"test","value":42}"#);
+    }
+
+    #[test]
+    fn test_to_bytes_data_update() {
+        let data = json!({"id": "test"});
+        let bytes = to_bytes(&data, true).unwrap();
+        let s = String::from_utf8(bytes).unwrap();
+        assert!(s.starts_with("\x19Constellation Signed Data:\n"));
+        assert!(s.contains('\n'));
+    }
+
+    #[test]
+    fn test_to_bytes_into_matches_to_bytes() {
+        let mut buf = Vec::new();
+        for data in [
+            json!({}),
+            json!({"id": "test"}),
+            json!({"payload": "x".repeat(1000)}),
+        ] {
+            for is_data_update in [false, true] {
+                buf.clear();
+                to_bytes_into(&data, is_data_update, &mut buf).unwrap();
+                let s = String::from_utf8(buf.clone()).unwrap();
+                let expected = if is_data_update {
+                    let encoded = base64::engine::general_purpose::STANDARD
+                        .encode(crate::canonicalize::canonicalize_bytes(&data).unwrap());
+                    format!("{}{}\n{}", CONSTELLATION_PREFIX, encoded.len(), encoded)
+                } else {
+                    crate::canonicalize::canonicalize(&data).unwrap()
+                };
+                assert_eq!(s, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_bytes_into_appends() {
+        let mut buf = b"kept".to_vec();
+        to_bytes_into(&json!({"id": "test"}), true, &mut buf).unwrap();
+        assert!(buf.starts_with(b"kept\x19Constellation Signed Data:\n"));
+        assert_eq!(&buf[4..], to_bytes(&json!({"id": "test"}), true).unwrap());
+    }
+
+    #[test]
+    fn test_encode_data_update_matches_to_bytes() {
+        // Every remainder of the JSON length modulo 3, and a multi-MB payload
+        let payloads = (0..6)
+            .map(|n| json!({"id": "x".repeat(n)}))
+            .chain([json!({"payload": "y".repeat(3 * 1024 * 1024 + 1)})]);
+        for data in payloads {
+            assert_eq!(
+                encode_data_update(&data).unwrap(),
+                to_bytes(&data, true).unwrap()
+            );
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_base64_writer_handles_split_writes() {
+        use std::io::Write;
+
+        let input: Vec<u8> = (0..=255).collect();
+        for chunk in 1..8 {
+            let mut out = Vec::new();
+            let mut writer = stream::Base64Writer::new(&mut out);
+            for piece in input.chunks(chunk) {
+                writer.write_all(piece).unwrap();
+            }
+            writer.finish();
+            assert_eq!(
+                out,
+                base64::engine::general_purpose::STANDARD
+                    .encode(&input)
+                    .into_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn test_encode_data_update() {
+        let data = json!({"id": "test"});
+        let bytes = encode_data_update(&data).unwrap();
+        let s = String::from_utf8(bytes).unwrap();
+        assert!(s.starts_with("\x19Constellation Signed Data:\n"));
+    }
+}
@@ -2,8 +2,16 @@
 //!
 //! Provides deterministic JSON serialization according to RFC 8785.
 
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 use serde::Serialize;
-use serde_json_canonicalizer::to_vec as canonicalize_to_vec;
+#[cfg(feature = "std")]
+use serde_json_canonicalizer::{
+    to_vec as canonicalize_to_vec, to_writer as canonicalize_to_writer,
+};
+
+#[cfg(not(feature = "std"))]
+use super::jcs::{to_vec as canonicalize_to_vec, to_writer as canonicalize_to_writer};
 
 use crate::types::{Result, SdkError};
 
@@ -25,8 +33,7 @@ use crate::types::{Result, SdkError};
 /// assert_eq!(canonical, r#"{"a":1,"b":2}"#);
 /// ```
 pub fn canonicalize<T: Serialize>(data: &T) -> Result<String> {
-    let bytes =
-        canonicalize_to_vec(data).map_err(|e| SdkError::SerializationError(e.to_string()))?;
+    let bytes = canonicalize_to_vec(data)?;
     String::from_utf8(bytes).map_err(|e| SdkError::SerializationError(e.to_string()))
 }
 
@@ -38,7 +45,41 @@ pub fn canonicalize<T: Serialize>(data: &T) -> Result<String> {
 /// # Returns
 /// Canonical JSON as UTF-8 bytes
 pub fn canonicalize_bytes<T: Serialize>(data: &T) -> Result<Vec<u8>> {
-    canonicalize_to_vec(data).map_err(|e| SdkError::SerializationError(e.to_string()))
+    Ok(canonicalize_to_vec(data)?)
+}
+
+/// Append the RFC 8785 canonical JSON of data to a buffer
+///
+/// Loops over many payloads can reuse one buffer, clearing it between
+/// payloads, instead of allocating a fresh `Vec` each time.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `out` - Buffer the canonical JSON is appended to
+///
+/// # Example
+/// ```
+/// use constellation_sdk::canonicalize::canonicalize_into;
+/// use serde_json::json;
+///
+/// let mut buf = Vec::new();
+/// for i in 0..3 {
+///     buf.clear();
+///     canonicalize_into(&json!({"n": i}), &mut buf).unwrap();
+/// }
+/// assert_eq!(buf, br#"{"n":2}"#);
+/// ```
+pub fn canonicalize_into<T: Serialize>(data: &T, out: &mut Vec<u8>) -> Result<()> {
+    Ok(canonicalize_to_writer(data, &mut *out)?)
+}
+
+/// Write the canonical JSON of `data` to any writer
+#[cfg(feature = "std")]
+pub(crate) fn canonicalize_to_io<T: Serialize, W: std::io::Write>(
+    data: &T,
+    mut writer: W,
+) -> Result<()> {
+    Ok(canonicalize_to_writer(data, &mut writer)?)
 }
 
 #[cfg(test)]
@@ -84,6 +125,13 @@ mod tests {
         assert_eq!(canonical, "[3,1,2]");
     }
 
+    #[test]
+    fn test_canonicalize_into_appends() {
+        let mut buf = b"prefix:".to_vec();
+        canonicalize_into(&json!({"b": 2, "a": 1}), &mut buf).unwrap();
+        assert_eq!(buf, br#"prefix:{"a":1,"b":2}"#);
+    }
+
     #[test]
     fn test_canonicalize_bytes() {
         let data = json!({"id": "test"});
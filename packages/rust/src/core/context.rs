@@ -0,0 +1,73 @@
+//! Shared secp256k1 context
+//!
+//! Building a `Secp256k1` context precomputes tables for signing and
+//! verification, which costs more than the signature it is built for. Every
+//! K1 operation borrows one process-wide context instead.
+//!
+//! With `std` it is the library's global context, randomized against
+//! side-channel attacks on first use. `no_std` builds allocate one on first
+//! use and keep it for the life of the program; targets without pointer-sized
+//! atomics build a context per call, as before.
+
+use core::ops::Deref;
+use secp256k1::{All, Secp256k1};
+
+/// A borrowed or, on targets without atomics, owned context
+pub(crate) struct Context(Inner);
+
+#[cfg(any(feature = "std", target_has_atomic = "ptr"))]
+type Inner = &'static Secp256k1<All>;
+#[cfg(not(any(feature = "std", target_has_atomic = "ptr")))]
+type Inner = Secp256k1<All>;
+
+impl Deref for Context {
+    type Target = Secp256k1<All>;
+
+    fn deref(&self) -> &Secp256k1<All> {
+        #[allow(clippy::needless_borrow)]
+        &self.0
+    }
+}
+
+/// The shared context
+#[cfg(feature = "std")]
+pub(crate) fn secp() -> Context {
+    Context(secp256k1::SECP256K1)
+}
+
+/// The shared context, allocated on first use
+#[cfg(all(not(feature = "std"), target_has_atomic = "ptr"))]
+pub(crate) fn secp() -> Context {
+    use alloc::boxed::Box;
+    use core::ptr;
+    use core::sync::atomic::{AtomicPtr, Ordering};
+
+    static CONTEXT: AtomicPtr<Secp256k1<All>> = AtomicPtr::new(ptr::null_mut());
+
+    let mut context = CONTEXT.load(Ordering::Acquire);
+    if context.is_null() {
+        let new = Box::into_raw(Box::new(Secp256k1::new()));
+        context = match CONTEXT.compare_exchange(
+            ptr::null_mut(),
+            new,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => new,
+            Err(existing) => {
+                // Another thread installed one first
+                // SAFETY: `new` came from `Box::into_raw` and was never shared
+                drop(unsafe { Box::from_raw(new) });
+                existing
+            }
+        };
+    }
+    // SAFETY: the installed context is never freed or replaced
+    Context(unsafe { &*context })
+}
+
+/// A fresh context; this target cannot share one safely
+#[cfg(not(any(feature = "std", target_has_atomic = "ptr")))]
+pub(crate) fn secp() -> Context {
+    Context(Secp256k1::new())
+}
@@ -0,0 +1,26 @@
+//! Constant-time comparison
+//!
+//! Comparing a secret with `==` stops at the first differing byte, so the
+//! time taken tells an attacker who can measure it how long a prefix they
+//! guessed right. The comparisons here look at every byte whatever the
+//! input. Lengths are not secret and are compared directly.
+
+use subtle::ConstantTimeEq;
+
+/// Whether `a` and `b` are equal, in time that depends only on their lengths
+pub(crate) fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && bool::from(a.ct_eq(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compares_contents_and_lengths() {
+        assert!(ct_eq(b"", b""));
+        assert!(ct_eq(b"abc", b"abc"));
+        assert!(!ct_eq(b"abc", b"abd"));
+        assert!(!ct_eq(b"abc", b"ab"));
+    }
+}
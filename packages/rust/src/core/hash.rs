@@ -0,0 +1,413 @@
+//! Hashing Utilities
+//!
+//! SHA-256 and SHA-512 hashing functions for the Constellation protocol.
+
+use alloc::format;
+use alloc::string::ToString;
+use base64::Engine;
+use serde::Serialize;
+use sha2::{Digest, Sha256, Sha512};
+
+use super::binary::to_bytes_into;
+use super::pool::with_buffer;
+use crate::types::{Hash, Result, SdkError, CONSTELLATION_PREFIX, DOMAIN_PREFIX};
+
+/// Hash data using SHA-256
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `is_data_update` - Whether to encode as DataUpdate before hashing
+///
+/// # Returns
+/// Hash struct with value (hex) and bytes
+///
+/// # Example
+/// ```
+/// use constellation_sdk::hash::hash_data;
+/// use serde_json::json;
+///
+/// let data = json!({"id": "test"});
+/// let hash = hash_data(&data, false).unwrap();
+/// assert_eq!(hash.value.len(), 64); // 32 bytes = 64 hex chars
+/// ```
+pub fn hash_data<T: Serialize>(data: &T, is_data_update: bool) -> Result<Hash> {
+    with_buffer(|bytes| {
+        to_bytes_into(data, is_data_update, bytes)?;
+        Ok(hash_bytes(bytes))
+    })
+}
+
+/// Hash data under an application domain tag
+///
+/// Hashes `<prefix><tag length>\n<tag><canonical JSON>`. A signature over
+/// this hash only verifies under the same tag, so a payload signed for one
+/// metagraph application cannot be replayed to another, and the prefix
+/// keeps it from matching any regular or DataUpdate hash.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `domain` - Application-specific tag, e.g. `"com.example.voting/v1"`
+///
+/// # Errors
+///
+/// Returns `InvalidPayload` if `domain` is empty, or an error if the data
+/// cannot be serialized
+///
+/// # Example
+/// ```
+/// use constellation_sdk::hash::{hash_data, hash_with_domain};
+/// use serde_json::json;
+///
+/// let data = json!({"vote": 1});
+/// let voting = hash_with_domain(&data, "voting").unwrap();
+/// assert_ne!(voting, hash_with_domain(&data, "polling").unwrap());
+/// assert_ne!(voting, hash_data(&data, false).unwrap());
+/// ```
+pub fn hash_with_domain<T: Serialize>(data: &T, domain: &str) -> Result<Hash> {
+    if domain.is_empty() {
+        return Err(SdkError::InvalidPayload(
+            "domain tag must not be empty".to_string(),
+        ));
+    }
+    with_buffer(|bytes| {
+        bytes.extend_from_slice(DOMAIN_PREFIX.as_bytes());
+        bytes.extend_from_slice(domain.len().to_string().as_bytes());
+        bytes.push(b'\n');
+        bytes.extend_from_slice(domain.as_bytes());
+        to_bytes_into(data, false, bytes)?;
+        Ok(hash_bytes(bytes))
+    })
+}
+
+/// Hash raw bytes using SHA-256
+///
+/// # Arguments
+/// * `data` - Raw bytes to hash
+///
+/// # Returns
+/// Hash struct with value (hex) and bytes
+pub fn hash_bytes(data: &[u8]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    let hash_bytes = hasher.finalize().to_vec();
+    let hash_hex = hex::encode(&hash_bytes);
+
+    Hash {
+        value: hash_hex,
+        bytes: hash_bytes,
+    }
+}
+
+/// Compute the full signing digest for Constellation protocol
+///
+/// Protocol:
+/// 1. Serialize and hash with SHA-256
+/// 2. Convert hash to hex string
+/// 3. Treat hex string as UTF-8 bytes
+/// 4. SHA-512 hash
+/// 5. Truncate to 32 bytes
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `is_data_update` - Whether to encode as DataUpdate
+///
+/// # Returns
+/// 32-byte digest ready for signing
+pub fn compute_digest<T: Serialize>(data: &T, is_data_update: bool) -> Result<[u8; 32]> {
+    with_buffer(|bytes| {
+        to_bytes_into(data, is_data_update, bytes)?;
+        Ok(compute_digest_from_bytes(bytes))
+    })
+}
+
+/// Compute signing digest from raw bytes
+///
+/// # Arguments
+/// * `data` - Raw bytes to hash
+///
+/// # Returns
+/// 32-byte digest ready for signing
+pub fn compute_digest_from_bytes(data: &[u8]) -> [u8; 32] {
+    // Step 1: SHA-256
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    let sha256_hash = hasher.finalize();
+    let hash_hex = hex::encode(sha256_hash);
+
+    // Step 2-4: Treat hex as UTF-8 bytes, SHA-512, truncate
+    let hash_hex_bytes = hash_hex.as_bytes();
+    let mut sha512_hasher = Sha512::new();
+    sha512_hasher.update(hash_hex_bytes);
+    let sha512_hash = sha512_hasher.finalize();
+
+    // Step 5: Truncate to 32 bytes
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(&sha512_hash[..32]);
+    digest
+}
+
+/// Compute signing digest from a pre-computed SHA-256 hash hex string
+///
+/// # Arguments
+/// * `hash_hex` - 64-character hex string of SHA-256 hash
+///
+/// # Returns
+/// 32-byte digest ready for signing
+pub fn compute_digest_from_hash(hash_hex: &str) -> [u8; 32] {
+    // Treat hex as UTF-8 bytes
+    let hash_hex_bytes = hash_hex.as_bytes();
+
+    // SHA-512 hash
+    let mut sha512_hasher = Sha512::new();
+    sha512_hasher.update(hash_hex_bytes);
+    let sha512_hash = sha512_hasher.finalize();
+
+    // Truncate to 32 bytes
+    let mut digest = [0u8; 32];
+    digest.copy_from_slice(&sha512_hash[..32]);
+    digest
+}
+
+/// Incremental version of the signing digest pipeline
+///
+/// Computes what [`hash_bytes`] and [`compute_digest`] compute, but from
+/// chunks fed one at a time, so a payload read from a file or socket never
+/// has to be held in memory whole. Feed it the exact bytes that would be
+/// signed: canonical JSON for regular data, or the canonical JSON itself
+/// after [`DigestWriter::data_update`], which adds the DataUpdate prefix and
+/// base64 encoding on the fly.
+///
+/// With `std` it also implements [`std::io::Write`], so it works with
+/// [`std::io::copy`].
+///
+/// # Example
+/// ```
+/// use constellation_sdk::hash::{compute_digest, DigestWriter};
+/// use serde_json::json;
+///
+/// let data = json!({"id": "test", "value": 42});
+/// let canonical = br#"{"id":"test","value":42}"#;
+///
+/// let mut writer = DigestWriter::data_update(canonical.len());
+/// for chunk in canonical.chunks(5) {
+///     writer.update(chunk);
+/// }
+/// assert_eq!(writer.finalize().unwrap(), compute_digest(&data, true).unwrap());
+/// ```
+#[derive(Clone, Default)]
+pub struct DigestWriter {
+    sha256: Sha256,
+    data_update: Option<DataUpdateState>,
+}
+
+/// Base64 state for a DataUpdate being hashed
+#[derive(Clone)]
+struct DataUpdateState {
+    json_len: usize,
+    received: usize,
+    pending: [u8; 3],
+    pending_len: usize,
+}
+
+/// Input bytes base64-encoded per round; a multiple of 3
+const BASE64_BLOCK: usize = 768;
+
+impl DigestWriter {
+    /// Hash bytes exactly as given
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash canonical JSON of `json_len` bytes as a DataUpdate
+    ///
+    /// The length must be known up front because the DataUpdate prefix
+    /// states the length of the base64 encoding.
+    pub fn data_update(json_len: usize) -> Self {
+        let mut sha256 = Sha256::new();
+        sha256.update(CONSTELLATION_PREFIX.as_bytes());
+        // The length only overflows for inputs no machine can hold
+        let encoded_len = base64::encoded_len(json_len, true).unwrap_or(usize::MAX);
+        sha256.update(encoded_len.to_string().as_bytes());
+        sha256.update(b"\n");
+        Self {
+            sha256,
+            data_update: Some(DataUpdateState {
+                json_len,
+                received: 0,
+                pending: [0; 3],
+                pending_len: 0,
+            }),
+        }
+    }
+
+    /// Feed the next chunk
+    pub fn update(&mut self, chunk: &[u8]) {
+        let Some(state) = &mut self.data_update else {
+            self.sha256.update(chunk);
+            return;
+        };
+        state.received += chunk.len();
+        let mut input = chunk;
+        if state.pending_len > 0 {
+            let take = (3 - state.pending_len).min(input.len());
+            state.pending[state.pending_len..state.pending_len + take]
+                .copy_from_slice(&input[..take]);
+            state.pending_len += take;
+            input = &input[take..];
+            if state.pending_len < 3 {
+                return;
+            }
+            state.pending_len = 0;
+            let pending = state.pending;
+            hash_base64(&mut self.sha256, &pending);
+        }
+        let whole = input.len() - input.len() % 3;
+        for block in input[..whole].chunks(BASE64_BLOCK) {
+            hash_base64(&mut self.sha256, block);
+        }
+        let rest = &input[whole..];
+        state.pending[..rest.len()].copy_from_slice(rest);
+        state.pending_len = rest.len();
+    }
+
+    /// SHA-256 of everything fed so far, as [`hash_bytes`] would return it
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidPayload` if a DataUpdate received a different number
+    /// of bytes than announced
+    pub fn finalize_hash(mut self) -> Result<Hash> {
+        if let Some(state) = self.data_update.take() {
+            if state.received != state.json_len {
+                return Err(SdkError::InvalidPayload(format!(
+                    "DataUpdate announced {} bytes but received {}",
+                    state.json_len, state.received
+                )));
+            }
+            hash_base64(&mut self.sha256, &state.pending[..state.pending_len]);
+        }
+        let bytes = self.sha256.finalize().to_vec();
+        Ok(Hash {
+            value: hex::encode(&bytes),
+            bytes,
+        })
+    }
+
+    /// Signing digest of everything fed so far, as [`compute_digest`] would
+    /// return it
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidPayload` if a DataUpdate received a different number
+    /// of bytes than announced
+    pub fn finalize(self) -> Result<[u8; 32]> {
+        Ok(compute_digest_from_hash(&self.finalize_hash()?.value))
+    }
+}
+
+/// Feed the padded base64 encoding of `input` to `sha256`
+fn hash_base64(sha256: &mut Sha256, input: &[u8]) {
+    let mut encoded = [0u8; BASE64_BLOCK / 3 * 4];
+    // The buffer fits any block of at most BASE64_BLOCK bytes
+    if let Ok(len) = base64::engine::general_purpose::STANDARD.encode_slice(input, &mut encoded) {
+        sha256.update(&encoded[..len]);
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for DigestWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_hash_data() {
+        let data = json!({"id": "test", "value": 42});
+        let hash = hash_data(&data, false).unwrap();
+        assert_eq!(hash.value.len(), 64);
+        assert_eq!(hash.bytes.len(), 32);
+    }
+
+    #[test]
+    fn test_hash_bytes() {
+        let data = b"test data";
+        let hash = hash_bytes(data);
+        assert_eq!(hash.value.len(), 64);
+        assert_eq!(hash.bytes.len(), 32);
+    }
+
+    #[test]
+    fn test_compute_digest() {
+        let data = json!({"id": "test"});
+        let digest = compute_digest(&data, false).unwrap();
+        assert_eq!(digest.len(), 32);
+    }
+
+    #[test]
+    fn test_compute_digest_data_update() {
+        let data = json!({"id": "test"});
+        let regular_digest = compute_digest(&data, false).unwrap();
+        let update_digest = compute_digest(&data, true).unwrap();
+        // DataUpdate should produce different digest
+        assert_ne!(regular_digest, update_digest);
+    }
+
+    #[test]
+    fn test_digest_writer_matches_one_shot_digests() {
+        let payloads = (0..7)
+            .map(|n| json!({"id": "x".repeat(n)}))
+            .chain([json!({"payload": "y".repeat(5000)})]);
+        for data in payloads {
+            let canonical = crate::canonicalize::canonicalize_bytes(&data).unwrap();
+            for chunk in [1, 2, 7, 4096] {
+                let mut regular = DigestWriter::new();
+                let mut update = DigestWriter::data_update(canonical.len());
+                for piece in canonical.chunks(chunk) {
+                    regular.update(piece);
+                    update.update(piece);
+                }
+                assert_eq!(
+                    regular.clone().finalize_hash().unwrap(),
+                    hash_data(&data, false).unwrap()
+                );
+                assert_eq!(
+                    regular.finalize().unwrap(),
+                    compute_digest(&data, false).unwrap()
+                );
+                assert_eq!(
+                    update.finalize().unwrap(),
+                    compute_digest(&data, true).unwrap()
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_digest_writer_rejects_wrong_length() {
+        let mut writer = DigestWriter::data_update(10);
+        writer.update(b"{}");
+        assert!(matches!(
+            writer.finalize(),
+            Err(crate::types::SdkError::InvalidPayload(_))
+        ));
+    }
+
+    #[test]
+    fn test_deterministic_hashing() {
+        let data = json!({"id": "test", "value": 42});
+        let hash1 = hash_data(&data, false).unwrap();
+        let hash2 = hash_data(&data, false).unwrap();
+        assert_eq!(hash1.value, hash2.value);
+    }
+}
@@ -0,0 +1,144 @@
+//! RFC 8785 canonicalization without `std`
+//!
+//! `serde_json_canonicalizer` needs `std`, so `no_std` builds canonicalize
+//! through this encoder instead. It walks a [`serde_json::Value`], sorting
+//! object keys by their UTF-16 code units and writing floats the way
+//! ECMAScript's `Number.prototype.toString` does.
+
+use alloc::string::ToString;
+use alloc::vec::Vec;
+use serde::Serialize;
+use serde_json::{Number, Value};
+
+/// Canonicalize data to UTF-8 bytes according to RFC 8785
+pub(crate) fn to_vec<T: Serialize + ?Sized>(data: &T) -> serde_json::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    to_writer(data, &mut out)?;
+    Ok(out)
+}
+
+/// Append the canonical JSON of `data` to `out`
+pub(crate) fn to_writer<T: Serialize + ?Sized>(
+    data: &T,
+    out: &mut Vec<u8>,
+) -> serde_json::Result<()> {
+    let value = serde_json::to_value(data)?;
+    write_value(&value, out)
+}
+
+fn write_value(value: &Value, out: &mut Vec<u8>) -> serde_json::Result<()> {
+    match value {
+        Value::Null => out.extend_from_slice(b"null"),
+        Value::Bool(true) => out.extend_from_slice(b"true"),
+        Value::Bool(false) => out.extend_from_slice(b"false"),
+        Value::Number(number) => write_number(number, out),
+        // serde_json escapes exactly what RFC 8785 requires: `"`, `\`, and
+        // control characters, using the short forms where they exist
+        Value::String(string) => out.extend_from_slice(serde_json::to_string(string)?.as_bytes()),
+        Value::Array(items) => {
+            out.push(b'[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                write_value(item, out)?;
+            }
+            out.push(b']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push(b'{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(b',');
+                }
+                out.extend_from_slice(serde_json::to_string(key)?.as_bytes());
+                out.push(b':');
+                write_value(item, out)?;
+            }
+            out.push(b'}');
+        }
+    }
+    Ok(())
+}
+
+fn write_number(number: &Number, out: &mut Vec<u8>) {
+    if let Some(n) = number.as_u64() {
+        out.extend_from_slice(n.to_string().as_bytes());
+    } else if let Some(n) = number.as_i64() {
+        out.extend_from_slice(n.to_string().as_bytes());
+    } else if let Some(n) = number.as_f64() {
+        // A `Value` never holds NaN or an infinity
+        out.extend_from_slice(ryu_js::Buffer::new().format_finite(n).as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn canonical(json: &str) -> String {
+        let value: Value = serde_json::from_str(json).unwrap();
+        String::from_utf8(to_vec(&value).unwrap()).unwrap()
+    }
+
+    #[test]
+    #[allow(clippy::excessive_precision)]
+    fn test_rfc8785_example() {
+        // Built with `json!` so the floats are exact; serde_json's parser
+        // rounds some of them
+        let data = json!({
+            "numbers": [333333333.33333329, 1E30, 4.50, 2e-3, 0.000000000000000000000000001],
+            "string": "€$\u{000F}\nA'B\"\\\\\"/",
+            "literals": [null, true, false]
+        });
+        assert_eq!(
+            String::from_utf8(to_vec(&data).unwrap()).unwrap(),
+            r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e+30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+        );
+    }
+
+    #[test]
+    fn test_sorts_keys_by_utf16_code_units() {
+        let input = r#"{
+            "€": "Euro Sign",
+            "\r": "Carriage Return",
+            "דּ": "Hebrew Letter Dalet With Dagesh",
+            "1": "One",
+            "😀": "Emoji: Grinning Face",
+            "\u0080": "Control",
+            "ö": "Latin Small Letter O With Diaeresis"
+        }"#;
+        let out = canonical(input);
+        let order = [
+            "Carriage Return",
+            "One",
+            "Control",
+            "Latin Small Letter O With Diaeresis",
+            "Euro Sign",
+            "Emoji: Grinning Face",
+            "Hebrew Letter Dalet With Dagesh",
+        ];
+        let positions: Vec<usize> = order.iter().map(|v| out.find(v).unwrap()).collect();
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{out}");
+    }
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(
+            canonical("[-0.0, 1e21, 1e-7, 100, -5, 18446744073709551615]"),
+            "[0,1e+21,1e-7,100,-5,18446744073709551615]"
+        );
+    }
+
+    #[test]
+    fn test_matches_canonicalize_for_plain_objects() {
+        let data = json!({"b": [1, "x", null], "a": {"d": true, "c": -3}});
+        assert_eq!(
+            to_vec(&data).unwrap(),
+            crate::canonicalize::canonicalize_bytes(&data).unwrap()
+        );
+    }
+}
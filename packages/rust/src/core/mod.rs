@@ -0,0 +1,29 @@
+//! The crypto layer
+//!
+//! Hashing, RFC 8785 canonicalization, binary encoding, signing, and
+//! verification. These modules build with `no_std + alloc`, so firmware and
+//! secure-enclave builds can depend on the crate with
+//! `default-features = false` and still produce and check Constellation
+//! signatures. Each module is re-exported at the crate root under its
+//! original path (`constellation_sdk::sign`, `constellation_sdk::hash`, ...).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::core::{sign::sign_data_update, verify::verify_signature};
+//!
+//! let proof = sign_data_update(&reading, private_key)?;
+//! assert!(verify_signature(&reading, &proof, true)?);
+//! ```
+
+pub mod binary;
+pub mod canonicalize;
+pub(crate) mod context;
+pub(crate) mod ct;
+pub mod hash;
+mod pool;
+pub mod sign;
+pub mod verify;
+
+#[cfg(any(not(feature = "std"), test))]
+mod jcs;
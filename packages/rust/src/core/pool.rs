@@ -0,0 +1,102 @@
+//! Reusable encoding buffers
+//!
+//! Hashing a payload encodes it into a buffer that is only needed until the
+//! digest is computed. High-throughput submitters hash one DataUpdate after
+//! another, so each thread keeps a few of those buffers around and hands them
+//! out again instead of allocating, regrowing, and freeing a full-size buffer
+//! per payload. Buffers that grew past 1 MiB for an unusually large payload
+//! are freed rather than kept.
+//!
+//! `no_std` builds have no thread-local storage and allocate per call.
+
+use alloc::vec::Vec;
+
+/// Largest buffer kept for reuse
+#[cfg(feature = "std")]
+const MAX_RETAINED_CAPACITY: usize = 1024 * 1024;
+
+/// Buffers kept per thread; more are only needed by nested calls
+#[cfg(feature = "std")]
+const MAX_POOLED: usize = 4;
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static BUFFERS: core::cell::RefCell<Vec<Vec<u8>>> = const { core::cell::RefCell::new(Vec::new()) };
+}
+
+/// Run `f` with an empty buffer, returning the buffer to the pool afterwards
+pub(crate) fn with_buffer<R>(f: impl FnOnce(&mut Vec<u8>) -> R) -> R {
+    let mut buffer = take();
+    let result = f(&mut buffer);
+    give_back(buffer);
+    result
+}
+
+#[cfg(feature = "std")]
+fn take() -> Vec<u8> {
+    BUFFERS
+        .try_with(|buffers| buffers.borrow_mut().pop())
+        .ok()
+        .flatten()
+        .unwrap_or_default()
+}
+
+#[cfg(not(feature = "std"))]
+fn take() -> Vec<u8> {
+    Vec::new()
+}
+
+#[cfg(feature = "std")]
+fn give_back(mut buffer: Vec<u8>) {
+    if buffer.capacity() > MAX_RETAINED_CAPACITY {
+        return;
+    }
+    buffer.clear();
+    // Fails only while the thread is shutting down, when dropping is fine
+    let _ = BUFFERS.try_with(|buffers| {
+        let mut buffers = buffers.borrow_mut();
+        if buffers.len() < MAX_POOLED {
+            buffers.push(buffer);
+        }
+    });
+}
+
+#[cfg(not(feature = "std"))]
+fn give_back(_buffer: Vec<u8>) {}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_buffers_on_the_same_thread() {
+        let first = with_buffer(|buffer| {
+            buffer.extend_from_slice(&[1; 4096]);
+            buffer.as_ptr() as usize
+        });
+        let second = with_buffer(|buffer| {
+            assert!(buffer.is_empty());
+            assert!(buffer.capacity() >= 4096);
+            buffer.as_ptr() as usize
+        });
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn nested_calls_get_distinct_buffers() {
+        with_buffer(|outer| {
+            outer.push(1);
+            with_buffer(|inner| {
+                assert!(inner.is_empty());
+                inner.push(2);
+            });
+            assert_eq!(outer, &[1]);
+        });
+    }
+
+    #[test]
+    fn drops_oversized_buffers() {
+        with_buffer(|buffer| buffer.reserve(MAX_RETAINED_CAPACITY + 1));
+        with_buffer(|buffer| assert!(buffer.capacity() <= MAX_RETAINED_CAPACITY));
+    }
+}
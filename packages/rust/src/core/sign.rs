@@ -3,12 +3,13 @@
 //! ECDSA signing using secp256k1 curve.
 //! Implements the Constellation signature protocol.
 
-use secp256k1::{Message, Secp256k1, SecretKey};
+use alloc::string::String;
+use secp256k1::{Message, SecretKey};
 use serde::Serialize;
 
-use crate::binary::to_bytes;
-use crate::hash::{compute_digest_from_hash, hash_bytes};
-use crate::types::{Result, SdkError, SignatureProof};
+use super::context::secp;
+use super::hash::{compute_digest_from_hash, hash_data, hash_with_domain};
+use crate::types::{Result, SignatureProof};
 use crate::wallet::get_public_key_id;
 
 /// Sign data using the regular Constellation protocol (non-DataUpdate)
@@ -40,16 +41,21 @@ use crate::wallet::get_public_key_id;
 /// println!("ID: {}", proof.id);
 /// println!("Signature: {}", proof.signature);
 /// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(hash))
+)]
 pub fn sign<T: Serialize>(data: &T, private_key: &str) -> Result<SignatureProof> {
     // Serialize and hash
-    let bytes = to_bytes(data, false)?;
-    let hash = hash_bytes(&bytes);
+    let hash = hash_data(data, false)?;
+    record_span!("hash", hash.value.as_str());
 
     // Sign the hash
-    let signature = sign_hash(&hash.value, private_key)?;
+    let signature = sign_hash_as(&hash.value, private_key, "sign")?;
 
     // Get public key ID
     let id = get_public_key_id(private_key)?;
+    log_event!(Debug, "signed data", "hash" = hash.value, "signer" = id);
 
     Ok(SignatureProof { id, signature })
 }
@@ -62,16 +68,77 @@ pub fn sign<T: Serialize>(data: &T, private_key: &str) -> Result<SignatureProof>
 ///
 /// # Returns
 /// SignatureProof
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(hash))
+)]
 pub fn sign_data_update<T: Serialize>(data: &T, private_key: &str) -> Result<SignatureProof> {
     // Serialize with DataUpdate encoding and hash
-    let bytes = to_bytes(data, true)?;
-    let hash = hash_bytes(&bytes);
+    let hash = hash_data(data, true)?;
+    record_span!("hash", hash.value.as_str());
 
     // Sign the hash
-    let signature = sign_hash(&hash.value, private_key)?;
+    let signature = sign_hash_as(&hash.value, private_key, "sign_data_update")?;
 
     // Get public key ID
     let id = get_public_key_id(private_key)?;
+    log_event!(Debug, "signed data", "hash" = hash.value, "signer" = id);
+
+    Ok(SignatureProof { id, signature })
+}
+
+/// Sign data under an application domain tag
+///
+/// The signature covers [`hash_with_domain`], so it only verifies with
+/// [`verify_with_domain`](crate::verify::verify_with_domain) and the same
+/// tag, and never as a regular or DataUpdate signature.
+///
+/// # Arguments
+/// * `data` - Any serializable data
+/// * `domain` - Application-specific tag, e.g. `"com.example.voting/v1"`
+/// * `private_key` - Private key in hex format
+///
+/// # Errors
+///
+/// Returns `InvalidPayload` if `domain` is empty, or an error if the data
+/// cannot be serialized or the key is invalid
+///
+/// # Example
+/// ```
+/// use constellation_sdk::sign::sign_with_domain;
+/// use constellation_sdk::verify::verify_signature_with_domain;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let data = json!({"vote": 1});
+/// let proof = sign_with_domain(&data, "voting", &key_pair.private_key).unwrap();
+/// assert!(verify_signature_with_domain(&data, &proof, "voting").unwrap());
+/// assert!(!verify_signature_with_domain(&data, &proof, "polling").unwrap());
+/// ```
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(domain, hash))
+)]
+pub fn sign_with_domain<T: Serialize>(
+    data: &T,
+    domain: &str,
+    private_key: &str,
+) -> Result<SignatureProof> {
+    let hash = hash_with_domain(data, domain)?;
+    record_span!("domain", domain);
+    record_span!("hash", hash.value.as_str());
+
+    let signature = sign_hash_as(&hash.value, private_key, "sign_with_domain")?;
+
+    let id = get_public_key_id(private_key)?;
+    log_event!(
+        Debug,
+        "signed data",
+        "hash" = hash.value,
+        "signer" = id,
+        "domain" = domain,
+    );
 
     Ok(SignatureProof { id, signature })
 }
@@ -83,23 +150,48 @@ pub fn sign_data_update<T: Serialize>(data: &T, private_key: &str) -> Result<Sig
 /// * `private_key` - Private key in hex format
 ///
 /// # Returns
-/// DER-encoded signature in hex format
+/// DER-encoded signature in hex format, always strict DER with a low S, so
+/// it passes [`SignaturePolicy::STRICT`](crate::verify::SignaturePolicy::STRICT)
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all, fields(hash = %hash_hex)))]
 pub fn sign_hash(hash_hex: &str, private_key: &str) -> Result<String> {
-    let secp = Secp256k1::new();
+    sign_hash_as(hash_hex, private_key, "sign_hash")
+}
 
+/// [`sign_hash`], reporting the signature to the
+/// [signing audit hook](crate::signing_audit) as `operation`
+fn sign_hash_as(hash_hex: &str, private_key: &str, operation: &'static str) -> Result<String> {
     // Parse private key
     let private_key_bytes = hex::decode(private_key)?;
     let secret_key = SecretKey::from_slice(&private_key_bytes)?;
 
+    sign_hash_with_secret(hash_hex, &secret_key, operation)
+}
+
+/// [`sign_hash`] with a parsed key, reporting the signature to the
+/// [signing audit hook](crate::signing_audit) as `operation`
+pub(crate) fn sign_hash_with_secret(
+    hash_hex: &str,
+    secret_key: &SecretKey,
+    operation: &'static str,
+) -> Result<String> {
+    let secp = secp();
+
     // Compute signing digest
     let digest = compute_digest_from_hash(hash_hex);
 
     // Create message from digest
-    let message =
-        Message::from_digest_slice(&digest).map_err(|e| SdkError::CryptoError(e.to_string()))?;
+    let message = Message::from_digest_slice(&digest)?;
 
     // Sign with ECDSA
-    let signature = secp.sign_ecdsa(&message, &secret_key);
+    let signature = secp.sign_ecdsa(&message, secret_key);
+
+    audit_signing!(
+        operation,
+        hash_hex,
+        Ok(hex::encode(
+            &secp256k1::PublicKey::from_secret_key(&secp, secret_key).serialize_uncompressed()[1..]
+        ))
+    );
 
     // Return DER-encoded signature
     Ok(hex::encode(signature.serialize_der()))
@@ -0,0 +1,513 @@
+//! Signature Verification
+//!
+//! Verify ECDSA signatures using secp256k1 curve.
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use secp256k1::ecdsa::Signature;
+use secp256k1::{Message, PublicKey};
+use serde::Serialize;
+
+use super::context::secp;
+use super::hash::{compute_digest_from_hash, hash_data, hash_with_domain};
+use crate::types::{Hash, Result, SdkError, SignatureProof, Signed, VerificationResult};
+use crate::wallet::{normalize_public_key, normalize_public_key_to_id};
+
+/// Longest DER-encoded ECDSA signature, in hex characters
+pub const MAX_SIGNATURE_HEX_LEN: usize = 144;
+
+/// Longest public key accepted (uncompressed with `04` prefix), in hex characters
+const MAX_PUBLIC_KEY_HEX_LEN: usize = 130;
+
+/// Which signature encodings verification accepts
+///
+/// A signature `(r, s)` verifies just as well as `(r, n - s)`, and a lax DER
+/// parser accepts several byte strings for one signature, so either lets
+/// anyone change the bytes of a proof without the private key. Signing in
+/// this crate always produces strict DER with a low S whatever the policy;
+/// the policy only decides what is accepted from other signers.
+///
+/// The default, [`SignaturePolicy::COMPATIBLE`], is what verification has
+/// always done: high-S signatures are normalized before checking, since not
+/// every SDK on the network produces low S, and DER must be strict.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::verify::{verify_with_policy, SignaturePolicy};
+/// use constellation_sdk::signed_object::create_signed_object;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, false).unwrap();
+/// assert!(verify_with_policy(&signed, false, SignaturePolicy::STRICT).is_valid);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignaturePolicy {
+    /// Reject signatures with S above half the curve order instead of
+    /// normalizing them
+    pub reject_high_s: bool,
+    /// Reject DER encodings that are not canonical (BIP 66)
+    pub require_strict_der: bool,
+}
+
+impl SignaturePolicy {
+    /// Normalize high S and require strict DER
+    pub const COMPATIBLE: Self = Self {
+        reject_high_s: false,
+        require_strict_der: true,
+    };
+
+    /// Reject high S and require strict DER, as BIP 62/146 nodes do
+    pub const STRICT: Self = Self {
+        reject_high_s: true,
+        require_strict_der: true,
+    };
+
+    /// Parse a DER-encoded signature in hex, returning it in low-S form
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidSignature` if the signature is malformed, or is
+    /// high-S or lax DER and the policy rejects that
+    pub fn parse_signature(&self, signature: &str) -> Result<Signature> {
+        let bytes = decode_signature_hex(signature)?;
+        let parsed = if self.require_strict_der {
+            Signature::from_der(&bytes)
+        } else {
+            Signature::from_der_lax(&bytes)
+        }
+        .map_err(|e| SdkError::InvalidSignature(format!("malformed DER signature: {e}")))?;
+
+        let mut low_s = parsed;
+        low_s.normalize_s();
+        if self.reject_high_s && low_s != parsed {
+            return Err(SdkError::InvalidSignature(
+                "high-S signature rejected by policy".to_string(),
+            ));
+        }
+        Ok(low_s)
+    }
+}
+
+impl Default for SignaturePolicy {
+    fn default() -> Self {
+        Self::COMPATIBLE
+    }
+}
+
+/// Verify a signed object
+///
+/// # Arguments
+/// * `signed` - Signed object with value and proofs
+/// * `is_data_update` - Whether the value was signed as a DataUpdate
+///
+/// # Returns
+/// VerificationResult with valid/invalid proof lists. Nodes reject a
+/// submission in which a key signs twice, so every proof after a signer's
+/// first is invalid; see [`Signed::dedupe_proofs`].
+///
+/// # Example
+/// ```
+/// use constellation_sdk::verify::verify;
+/// use constellation_sdk::signed_object::create_signed_object;
+/// use constellation_sdk::wallet::generate_key_pair;
+/// use serde_json::json;
+///
+/// let key_pair = generate_key_pair();
+/// let signed = create_signed_object(&json!({"id": "test"}), &key_pair.private_key, false).unwrap();
+/// let result = verify(&signed, false);
+/// assert!(result.is_valid);
+/// ```
+pub fn verify<T: Serialize>(signed: &Signed<T>, is_data_update: bool) -> VerificationResult {
+    verify_with_policy(signed, is_data_update, SignaturePolicy::default())
+}
+
+/// Verify a signed object, accepting signatures as `policy` allows
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(is_data_update, proofs = signed.proofs.len(), hash, valid)
+))]
+pub fn verify_with_policy<T: Serialize>(
+    signed: &Signed<T>,
+    is_data_update: bool,
+    policy: SignaturePolicy,
+) -> VerificationResult {
+    record_span!("is_data_update", is_data_update);
+    // Compute the hash that should have been signed
+    verify_proofs(hash_data(&signed.value, is_data_update), signed, policy)
+}
+
+/// Verify a signed object whose proofs were made with
+/// [`sign_with_domain`](crate::sign::sign_with_domain) under `domain`
+///
+/// Proofs made under any other tag, or without one, are invalid.
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(domain, proofs = signed.proofs.len(), hash, valid)
+))]
+pub fn verify_with_domain<T: Serialize>(signed: &Signed<T>, domain: &str) -> VerificationResult {
+    record_span!("domain", domain);
+    verify_proofs(
+        hash_with_domain(&signed.value, domain),
+        signed,
+        SignaturePolicy::default(),
+    )
+}
+
+pub(crate) fn verify_proofs<T>(
+    hash: Result<Hash>,
+    signed: &Signed<T>,
+    policy: SignaturePolicy,
+) -> VerificationResult {
+    let hash = match hash {
+        Ok(hash) => hash,
+        Err(_) => {
+            return VerificationResult {
+                is_valid: false,
+                valid_proofs: vec![],
+                invalid_proofs: signed.proofs.clone(),
+            };
+        }
+    };
+    record_span!("hash", hash.value.as_str());
+
+    let mut valid_proofs = Vec::new();
+    let mut invalid_proofs = Vec::new();
+    let mut signers = BTreeSet::new();
+
+    for proof in &signed.proofs {
+        if !signers.insert(signer_key(&proof.id)) {
+            invalid_proofs.push(proof.clone());
+            continue;
+        }
+        match verify_hash_with_policy(&hash.value, &proof.signature, &proof.id, policy) {
+            Ok(true) => valid_proofs.push(proof.clone()),
+            Ok(false) | Err(_) => invalid_proofs.push(proof.clone()),
+        }
+    }
+
+    let is_valid = invalid_proofs.is_empty() && !valid_proofs.is_empty();
+    record_span!("valid", is_valid);
+    log_event!(
+        Debug,
+        "verified signed object",
+        "hash" = hash.value,
+        "valid" = is_valid,
+        "proofs" = signed.proofs.len(),
+    );
+
+    VerificationResult {
+        is_valid,
+        valid_proofs,
+        invalid_proofs,
+    }
+}
+
+/// A proof's signer id in one form, for telling whether two proofs are by
+/// the same key
+pub(crate) fn signer_key(id: &str) -> String {
+    normalize_public_key_to_id(&id.to_ascii_lowercase())
+}
+
+/// Verify a signature against a SHA-256 hash
+///
+/// Protocol:
+/// 1. Treat hash hex as UTF-8 bytes (NOT hex decode)
+/// 2. SHA-512 hash
+/// 3. Truncate to 32 bytes
+/// 4. Verify ECDSA signature
+///
+/// # Arguments
+/// * `hash_hex` - SHA-256 hash as 64-character hex string
+/// * `signature` - DER-encoded signature in hex format
+/// * `public_key_id` - Public key in hex (with or without 04 prefix)
+///
+/// # Returns
+/// true if signature is valid
+pub fn verify_hash(hash_hex: &str, signature: &str, public_key_id: &str) -> Result<bool> {
+    verify_hash_with_policy(
+        hash_hex,
+        signature,
+        public_key_id,
+        SignaturePolicy::default(),
+    )
+}
+
+/// Verify a signature against a SHA-256 hash, accepting signatures as
+/// `policy` allows
+///
+/// # Errors
+///
+/// Returns `InvalidSignature` if the policy rejects the signature's encoding
+#[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all, fields(hash = %hash_hex)))]
+pub fn verify_hash_with_policy(
+    hash_hex: &str,
+    signature: &str,
+    public_key_id: &str,
+    policy: SignaturePolicy,
+) -> Result<bool> {
+    let secp = secp();
+
+    // Normalize and parse public key
+    let public_key = parse_public_key(public_key_id)?;
+
+    // Parse signature, in low-S form unless the policy rejects high S
+    let sig = policy.parse_signature(signature)?;
+
+    // Compute signing digest
+    let digest = compute_digest_from_hash(hash_hex);
+
+    // Create message from digest
+    let message = Message::from_digest_slice(&digest)?;
+
+    // Verify signature
+    Ok(secp.verify_ecdsa(&message, &sig, &public_key).is_ok())
+}
+
+/// Parse a DER-encoded signature in hex
+///
+/// Safe to call on untrusted input: oversized input is rejected before it is
+/// decoded.
+///
+/// # Errors
+///
+/// Returns `InvalidSignature` saying whether the length, the hex, or the DER
+/// structure is wrong
+pub fn parse_signature(signature: &str) -> Result<Signature> {
+    let bytes = decode_signature_hex(signature)?;
+    Signature::from_der(&bytes)
+        .map_err(|e| SdkError::InvalidSignature(format!("malformed DER signature: {e}")))
+}
+
+fn decode_signature_hex(signature: &str) -> Result<Vec<u8>> {
+    if signature.len() > MAX_SIGNATURE_HEX_LEN {
+        return Err(SdkError::InvalidSignature(format!(
+            "signature is {} hex characters; DER signatures are at most {MAX_SIGNATURE_HEX_LEN}",
+            signature.len()
+        )));
+    }
+    hex::decode(signature)
+        .map_err(|e| SdkError::InvalidSignature(format!("signature is not hex: {e}")))
+}
+
+/// Parse a public key or public key ID in hex, rejecting oversized input
+/// before decoding it
+///
+/// Goes through the [public key cache](crate::key_cache) when one is enabled.
+pub(crate) fn parse_public_key(public_key_id: &str) -> Result<PublicKey> {
+    #[cfg(feature = "std")]
+    return crate::key_cache::get_or_parse(public_key_id, decode_public_key);
+    #[cfg(not(feature = "std"))]
+    decode_public_key(public_key_id)
+}
+
+fn decode_public_key(public_key_id: &str) -> Result<PublicKey> {
+    if public_key_id.len() > MAX_PUBLIC_KEY_HEX_LEN {
+        return Err(SdkError::InvalidPublicKey(format!(
+            "public key is {} hex characters; at most {MAX_PUBLIC_KEY_HEX_LEN} expected",
+            public_key_id.len()
+        )));
+    }
+    let bytes = hex::decode(normalize_public_key(public_key_id))
+        .map_err(|e| SdkError::InvalidPublicKey(format!("public key is not hex: {e}")))?;
+    PublicKey::from_slice(&bytes).map_err(|e| SdkError::InvalidPublicKey(e.to_string()))
+}
+
+/// Verify a single signature proof against data
+///
+/// # Arguments
+/// * `data` - The original data that was signed
+/// * `proof` - The signature proof to verify
+/// * `is_data_update` - Whether data was signed as DataUpdate
+///
+/// # Returns
+/// true if signature is valid
+pub fn verify_signature<T: Serialize>(
+    data: &T,
+    proof: &SignatureProof,
+    is_data_update: bool,
+) -> Result<bool> {
+    verify_signature_with_policy(data, proof, is_data_update, SignaturePolicy::default())
+}
+
+/// Verify a single signature proof against data, accepting signatures as
+/// `policy` allows
+pub fn verify_signature_with_policy<T: Serialize>(
+    data: &T,
+    proof: &SignatureProof,
+    is_data_update: bool,
+    policy: SignaturePolicy,
+) -> Result<bool> {
+    let hash = hash_data(data, is_data_update)?;
+    verify_hash_with_policy(&hash.value, &proof.signature, &proof.id, policy)
+}
+
+/// Verify a single proof made with
+/// [`sign_with_domain`](crate::sign::sign_with_domain) under `domain`
+///
+/// # Errors
+///
+/// Returns `InvalidPayload` if `domain` is empty, or an error if the data
+/// cannot be serialized or the proof is malformed
+pub fn verify_signature_with_domain<T: Serialize>(
+    data: &T,
+    proof: &SignatureProof,
+    domain: &str,
+) -> Result<bool> {
+    let hash = hash_with_domain(data, domain)?;
+    verify_hash(&hash.value, &proof.signature, &proof.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sign::{sign, sign_data_update};
+    use crate::wallet::generate_key_pair;
+    use serde_json::json;
+
+    #[test]
+    fn test_verify_signed_object() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test", "value": 42});
+        let proof = sign(&data, &key_pair.private_key).unwrap();
+
+        let signed = Signed {
+            value: data,
+            proofs: vec![proof],
+        };
+
+        let result = verify(&signed, false);
+        assert!(result.is_valid);
+        assert_eq!(result.valid_proofs.len(), 1);
+        assert!(result.invalid_proofs.is_empty());
+    }
+
+    #[test]
+    fn test_verify_data_update() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let proof = sign_data_update(&data, &key_pair.private_key).unwrap();
+
+        let signed = Signed {
+            value: data,
+            proofs: vec![proof],
+        };
+
+        let result = verify(&signed, true);
+        assert!(result.is_valid);
+    }
+
+    #[test]
+    fn test_verify_tampered_data() {
+        let key_pair = generate_key_pair();
+        let original_data = json!({"id": "test", "value": 42});
+        let proof = sign(&original_data, &key_pair.private_key).unwrap();
+
+        // Tamper with data
+        let tampered_data = json!({"id": "test", "value": 999});
+        let signed = Signed {
+            value: tampered_data,
+            proofs: vec![proof],
+        };
+
+        let result = verify(&signed, false);
+        assert!(!result.is_valid);
+        assert!(result.valid_proofs.is_empty());
+        assert_eq!(result.invalid_proofs.len(), 1);
+    }
+
+    #[test]
+    fn test_verify_hash() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let proof = sign(&data, &key_pair.private_key).unwrap();
+
+        let bytes = crate::binary::to_bytes(&data, false).unwrap();
+        let hash = crate::hash::hash_bytes(&bytes);
+
+        let is_valid = verify_hash(&hash.value, &proof.signature, &proof.id).unwrap();
+        assert!(is_valid);
+    }
+
+    #[test]
+    fn test_verify_signature_single() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let proof = sign(&data, &key_pair.private_key).unwrap();
+
+        let is_valid = verify_signature(&data, &proof, false).unwrap();
+        assert!(is_valid);
+    }
+
+    /// The same signature with S replaced by `n - S`
+    fn high_s(signature: &str) -> String {
+        let compact = parse_signature(signature).unwrap().serialize_compact();
+        let mut out = compact;
+        let mut borrow = 0i16;
+        for i in (0..32).rev() {
+            let diff =
+                secp256k1::constants::CURVE_ORDER[i] as i16 - compact[32 + i] as i16 - borrow;
+            borrow = (diff < 0) as i16;
+            out[32 + i] = diff.rem_euclid(256) as u8;
+        }
+        hex::encode(Signature::from_compact(&out).unwrap().serialize_der())
+    }
+
+    /// The same signature with a superfluous zero byte before S
+    fn padded_der(signature: &str) -> String {
+        let der = hex::decode(signature).unwrap();
+        let s_at = 4 + der[3] as usize;
+        let mut out = der[..s_at].to_vec();
+        out.extend([0x02, der[s_at + 1] + 1, 0x00]);
+        out.extend(&der[s_at + 2..]);
+        out[1] += 1;
+        hex::encode(out)
+    }
+
+    #[test]
+    fn test_signatures_are_low_s_strict_der() {
+        let key_pair = generate_key_pair();
+        for i in 0..32 {
+            let proof = sign(&json!({"n": i}), &key_pair.private_key).unwrap();
+            assert!(SignaturePolicy::STRICT
+                .parse_signature(&proof.signature)
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_policy_high_s() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let mut proof = sign(&data, &key_pair.private_key).unwrap();
+        proof.signature = high_s(&proof.signature);
+
+        assert!(verify_signature(&data, &proof, false).unwrap());
+        assert!(matches!(
+            verify_signature_with_policy(&data, &proof, false, SignaturePolicy::STRICT),
+            Err(SdkError::InvalidSignature(_))
+        ));
+    }
+
+    #[test]
+    fn test_policy_lax_der() {
+        let key_pair = generate_key_pair();
+        let data = json!({"id": "test"});
+        let mut proof = sign(&data, &key_pair.private_key).unwrap();
+        proof.signature = padded_der(&proof.signature);
+
+        assert!(verify_signature(&data, &proof, false).is_err());
+        let lax = SignaturePolicy {
+            require_strict_der: false,
+            ..SignaturePolicy::default()
+        };
+        assert!(verify_signature_with_policy(&data, &proof, false, lax).unwrap());
+    }
+}
@@ -3,15 +3,18 @@
 use num_bigint::BigUint;
 use rand::Rng;
 use regex::Regex;
-use secp256k1::{Message, Secp256k1, SecretKey};
+use secp256k1::{Message, SecretKey};
 use sha2::{Digest, Sha256, Sha512};
 
+use crate::core::context::secp;
 use crate::currency_types::{
     CurrencyTransaction, CurrencyTransactionValue, TransactionReference, TransferParams,
     TOKEN_DECIMALS,
 };
+use crate::signer::Signer;
 use crate::types::{Hash, Result, SdkError, SignatureProof, Signed, VerificationResult};
-use crate::wallet::get_address;
+use crate::verify::{parse_public_key, signer_key, SignaturePolicy};
+use crate::wallet::address_of;
 
 /// Minimum salt complexity (from dag4.js)
 const MIN_SALT: u64 = (1u64 << 53) - (1u64 << 48);
@@ -153,16 +156,26 @@ fn sign_hash_internal(hash_hex: &str, private_key_hex: &str) -> Result<String> {
     let digest = &sha512_hash[..32];
 
     // Sign with ECDSA
-    let secp = Secp256k1::new();
+    let secp = secp();
     let secret_key = SecretKey::from_slice(&hex::decode(private_key_hex)?)?;
     let message = Message::from_digest_slice(digest)?;
     let signature = secp.sign_ecdsa(&message, &secret_key);
+    audit_signing!(
+        "currency_transaction",
+        hash_hex,
+        crate::wallet::get_public_key_id(private_key_hex)
+    );
 
     Ok(hex::encode(signature.serialize_der()))
 }
 
 /// Verify a signature on a hash
-fn verify_hash_internal(public_key_hex: &str, hash_hex: &str, signature_hex: &str) -> bool {
+fn verify_hash_internal(
+    public_key_hex: &str,
+    hash_hex: &str,
+    signature_hex: &str,
+    policy: SignaturePolicy,
+) -> bool {
     // Hash hex as UTF-8 -> SHA-512 -> truncate 32 bytes
     let hash_utf8 = hash_hex.as_bytes();
     let mut sha512_hasher = Sha512::new();
@@ -171,36 +184,20 @@ fn verify_hash_internal(public_key_hex: &str, hash_hex: &str, signature_hex: &st
     let digest = &sha512_hash[..32];
 
     // Parse public key and signature
-    let public_key_bytes = match hex::decode(public_key_hex) {
-        Ok(bytes) => bytes,
-        Err(_) => return false,
-    };
-
-    let public_key = match secp256k1::PublicKey::from_slice(&public_key_bytes) {
-        Ok(pk) => pk,
-        Err(_) => return false,
-    };
-
-    let signature_bytes = match hex::decode(signature_hex) {
-        Ok(bytes) => bytes,
-        Err(_) => return false,
+    let Ok(public_key) = parse_public_key(public_key_hex) else {
+        return false;
     };
-
-    let mut signature = match secp256k1::ecdsa::Signature::from_der(&signature_bytes) {
-        Ok(sig) => sig,
-        Err(_) => return false,
+    // Low-S form, unless the policy rejects high-S signatures outright
+    let Ok(signature) = policy.parse_signature(signature_hex) else {
+        return false;
     };
 
-    // Normalize signature to low-S to accept high-S signatures (BIP 62 compatibility)
-    // This ensures we accept signatures from other SDKs that may not normalize to low-S
-    signature.normalize_s();
-
     let message = match Message::from_digest_slice(digest) {
         Ok(msg) => msg,
         Err(_) => return false,
     };
 
-    let secp = Secp256k1::new();
+    let secp = secp();
     secp.verify_ecdsa(&message, &signature, &public_key).is_ok()
 }
 
@@ -209,35 +206,108 @@ pub fn create_currency_transaction(
     params: TransferParams,
     private_key: &str,
     last_ref: TransactionReference,
+) -> Result<CurrencyTransaction> {
+    // Convert amounts to smallest units
+    let amount = token_to_units(params.amount);
+    let fee = token_to_units(params.fee);
+
+    build_currency_transaction(params.destination, amount, fee, private_key, last_ref)
+}
+
+/// Create a metagraph token transaction from amounts in smallest units
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(destination = %destination, amount = amount, fee = fee, parent_ordinal = last_ref.ordinal, hash)
+))]
+pub(crate) fn build_currency_transaction(
+    destination: String,
+    amount: i64,
+    fee: i64,
+    private_key: &str,
+    last_ref: TransactionReference,
 ) -> Result<CurrencyTransaction> {
     // Get source address from private key
     let secret_key = SecretKey::from_slice(&hex::decode(private_key)?)?;
-    let secp = Secp256k1::new();
+    let secp = secp();
     let public_key = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
     let public_key_hex = hex::encode(public_key.serialize_uncompressed());
-    let source = get_address(&public_key_hex);
+    let source = address_of(&public_key);
 
+    let mut tx = unsigned_transaction(source, destination, amount, fee, last_ref)?;
+    let hash_hex = transaction_hash_hex(&tx);
+    record_span!("hash", hash_hex.as_str());
+
+    // Sign
+    let signature = sign_hash_internal(&hash_hex, private_key)?;
+
+    // Create proof
+    let public_key_id = &public_key_hex[2..]; // Remove '04' prefix
+    let proof = SignatureProof {
+        id: public_key_id.to_string(),
+        signature,
+    };
+
+    // Add proof to transaction
+    tx.proofs.push(proof);
+    log_event!(
+        Info,
+        "created currency transaction",
+        "hash" = hash_hex,
+        "destination" = tx.value.destination,
+        "amount" = tx.value.amount,
+        "fee" = tx.value.fee,
+    );
+
+    Ok(tx)
+}
+
+/// Create a metagraph token transaction signed by a [`Signer`]
+///
+/// The counterpart of [`create_currency_transaction`] for keys that are not
+/// available as hex.
+pub fn create_currency_transaction_with_signer<S: Signer + ?Sized>(
+    params: TransferParams,
+    signer: &S,
+    last_ref: TransactionReference,
+) -> Result<CurrencyTransaction> {
+    let mut tx = unsigned_transaction(
+        signer.address(),
+        params.destination,
+        token_to_units(params.amount),
+        token_to_units(params.fee),
+        last_ref,
+    )?;
+    let hash_hex = transaction_hash_hex(&tx);
+    tx.proofs.push(signer.sign_hash_proof(&hash_hex)?);
+    Ok(tx)
+}
+
+/// Validate a transfer and build it without proofs
+fn unsigned_transaction(
+    source: String,
+    destination: String,
+    amount: i64,
+    fee: i64,
+    last_ref: TransactionReference,
+) -> Result<CurrencyTransaction> {
     // Validate addresses
     if !is_valid_dag_address(&source) {
         return Err(SdkError::InvalidAddress(
             "Invalid source address".to_string(),
         ));
     }
-    if !is_valid_dag_address(&params.destination) {
+    if !is_valid_dag_address(&destination) {
         return Err(SdkError::InvalidAddress(
             "Invalid destination address".to_string(),
         ));
     }
-    if source == params.destination {
+    if source == destination {
         return Err(SdkError::InvalidAddress(
             "Source and destination addresses cannot be the same".to_string(),
         ));
     }
 
-    // Convert amounts to smallest units
-    let amount = token_to_units(params.amount);
-    let fee = token_to_units(params.fee);
-
     // Validate amounts
     if amount < 1 {
         return Err(SdkError::InvalidAmount(
@@ -256,41 +326,31 @@ pub fn create_currency_transaction(
     // Create transaction value
     let tx_value = CurrencyTransactionValue {
         source,
-        destination: params.destination,
+        destination,
         amount,
         fee,
         parent: last_ref,
         salt,
     };
 
-    // Create signed transaction
-    let mut tx = Signed {
+    Ok(Signed {
         value: tx_value,
         proofs: vec![],
-    };
+    })
+}
 
-    // Encode and hash
-    let encoded = encode_transaction(&tx);
-    let serialized = kryo_serialize(&encoded, false);
+/// Hex SHA-256 of the Kryo-encoded transaction, the hash that gets signed
+fn transaction_hash_hex(transaction: &CurrencyTransaction) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(&serialized);
-    let hash_bytes = hasher.finalize();
-    let hash_hex = hex::encode(hash_bytes);
-
-    // Sign
-    let signature = sign_hash_internal(&hash_hex, private_key)?;
-
-    // Create proof
-    let public_key_id = &public_key_hex[2..]; // Remove '04' prefix
-    let proof = SignatureProof {
-        id: public_key_id.to_string(),
-        signature,
-    };
-
-    // Add proof to transaction
-    tx.proofs.push(proof);
+    hasher.update(transaction_bytes(transaction));
+    hex::encode(hasher.finalize())
+}
 
-    Ok(tx)
+/// Kryo-encoded bytes of a transaction, the input of its hash
+///
+/// Panics if the salt is not a decimal integer.
+pub(crate) fn transaction_bytes(transaction: &CurrencyTransaction) -> Vec<u8> {
+    kryo_serialize(&encode_transaction(transaction), false)
 }
 
 /// Create multiple metagraph token transactions (batch)
@@ -321,6 +381,10 @@ pub fn create_currency_transaction_batch(
 }
 
 /// Add a signature to an existing currency transaction (for multi-sig)
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip_all, fields(hash))
+)]
 pub fn sign_currency_transaction(
     transaction: &CurrencyTransaction,
     private_key: &str,
@@ -332,18 +396,24 @@ pub fn sign_currency_transaction(
     hasher.update(&serialized);
     let hash_bytes = hasher.finalize();
     let hash_hex = hex::encode(hash_bytes);
+    record_span!("hash", hash_hex.as_str());
 
     // Sign
     let signature = sign_hash_internal(&hash_hex, private_key)?;
 
     // Get public key
     let secret_key = SecretKey::from_slice(&hex::decode(private_key)?)?;
-    let secp = Secp256k1::new();
+    let secp = secp();
     let public_key = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
     let public_key_hex = hex::encode(public_key.serialize_uncompressed());
 
-    // Verify signature
-    if !verify_hash_internal(&public_key_hex, &hash_hex, &signature) {
+    // Verify signature, strictly: our own signatures are always low-S
+    if !verify_hash_internal(
+        &public_key_hex,
+        &hash_hex,
+        &signature,
+        SignaturePolicy::STRICT,
+    ) {
         return Err(SdkError::InvalidSignature("Sign-Verify failed".to_string()));
     }
 
@@ -357,6 +427,7 @@ pub fn sign_currency_transaction(
     // Create new signed transaction with updated proofs
     let mut new_proofs = transaction.proofs.clone();
     new_proofs.push(proof);
+    log_event!(Debug, "signed currency transaction", "hash" = hash_hex);
 
     Ok(Signed {
         value: transaction.value.clone(),
@@ -366,6 +437,20 @@ pub fn sign_currency_transaction(
 
 /// Verify all signatures on a currency transaction
 pub fn verify_currency_transaction(transaction: &CurrencyTransaction) -> VerificationResult {
+    verify_currency_transaction_with_policy(transaction, SignaturePolicy::default())
+}
+
+/// Verify all signatures on a currency transaction, accepting signatures as
+/// `policy` allows
+#[cfg_attr(feature = "tracing", tracing::instrument(
+    level = "debug",
+    skip_all,
+    fields(proofs = transaction.proofs.len(), hash, valid)
+))]
+pub fn verify_currency_transaction_with_policy(
+    transaction: &CurrencyTransaction,
+    policy: SignaturePolicy,
+) -> VerificationResult {
     // Encode and hash
     let encoded = encode_transaction(transaction);
     let serialized = kryo_serialize(&encoded, false);
@@ -373,14 +458,20 @@ pub fn verify_currency_transaction(transaction: &CurrencyTransaction) -> Verific
     hasher.update(&serialized);
     let hash_bytes = hasher.finalize();
     let hash_hex = hex::encode(hash_bytes);
+    record_span!("hash", hash_hex.as_str());
 
     let mut valid_proofs = Vec::new();
     let mut invalid_proofs = Vec::new();
 
-    // Verify each proof
+    // Verify each proof; a repeated signer is invalid, as in `verify`
+    let mut signers = std::collections::BTreeSet::new();
     for proof in &transaction.proofs {
+        if !signers.insert(signer_key(&proof.id)) {
+            invalid_proofs.push(proof.clone());
+            continue;
+        }
         let public_key = format!("04{}", proof.id); // Add back '04' prefix
-        let is_valid = verify_hash_internal(&public_key, &hash_hex, &proof.signature);
+        let is_valid = verify_hash_internal(&public_key, &hash_hex, &proof.signature, policy);
 
         if is_valid {
             valid_proofs.push(proof.clone());
@@ -389,8 +480,18 @@ pub fn verify_currency_transaction(transaction: &CurrencyTransaction) -> Verific
         }
     }
 
+    let is_valid = invalid_proofs.is_empty() && !valid_proofs.is_empty();
+    record_span!("valid", is_valid);
+    log_event!(
+        Debug,
+        "verified currency transaction",
+        "hash" = hash_hex,
+        "valid" = is_valid,
+        "proofs" = transaction.proofs.len(),
+    );
+
     VerificationResult {
-        is_valid: invalid_proofs.is_empty() && !valid_proofs.is_empty(),
+        is_valid,
         valid_proofs,
         invalid_proofs,
     }
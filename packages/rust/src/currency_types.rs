@@ -61,6 +61,7 @@ pub type CurrencyTransaction = Signed<CurrencyTransactionValue>;
 
 /// Parameters for creating a token transfer
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct TransferParams {
     /// Destination DAG address
     pub destination: String,
@@ -0,0 +1,74 @@
+//! Audit trail of daemon requests
+
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// One request handled by the daemon
+///
+/// Never contains a private key or passphrase.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// When the request was handled, in milliseconds since the Unix epoch
+    pub timestamp: u64,
+    /// Address of the client, for requests received over HTTP
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub peer: Option<String>,
+    /// JSON-RPC method, or `authenticate` for a rejected token
+    pub method: String,
+    /// Name of the key the request used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// Hash that was signed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    /// Whether the request succeeded
+    pub ok: bool,
+    /// Error message of a failed request
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Receives an [`AuditEntry`] for every request the daemon handles
+pub trait AuditSink: Send + Sync {
+    /// Record a handled request
+    fn record(&self, entry: &AuditEntry);
+}
+
+impl<F: Fn(&AuditEntry) + Send + Sync> AuditSink for F {
+    fn record(&self, entry: &AuditEntry) {
+        self(entry)
+    }
+}
+
+/// An [`AuditSink`] appending one JSON object per line to a file
+#[derive(Debug)]
+pub struct JsonLinesAudit {
+    file: Mutex<File>,
+}
+
+impl JsonLinesAudit {
+    /// Open a file for appending, creating it if needed
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of opening the file
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl AuditSink for JsonLinesAudit {
+    fn record(&self, entry: &AuditEntry) {
+        if let Ok(line) = serde_json::to_string(entry) {
+            let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+            let _ = writeln!(file, "{line}");
+        }
+    }
+}
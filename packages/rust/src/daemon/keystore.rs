@@ -0,0 +1,291 @@
+//! Passphrase-encrypted key files
+//!
+//! A [`Keystore`] is the Web3 Secret Storage (version 3) JSON that dag4.js
+//! and most wallets export: the private key encrypted with AES-128-CTR under a
+//! key derived from the passphrase with scrypt or PBKDF2, plus a Keccak-256
+//! MAC that detects a wrong passphrase. The daemon keeps keystores locked and
+//! only holds the decrypted key between `unlock` and `lock`; with the `memsec`
+//! feature it holds it as a [`ProtectedSigner`].
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+
+use crate::core::ct::ct_eq;
+#[cfg(feature = "memsec")]
+use crate::memsec::ProtectedSigner;
+use crate::signer::LocalSigner;
+use crate::types::{Result, SdkError};
+use crate::wallet::key_pair_from_private_key;
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// scrypt cost used by [`Keystore::encrypt`] (N = 2^13, r = 8, p = 1)
+const SCRYPT_LOG_N: u8 = 13;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+const DKLEN: usize = 32;
+
+/// Weakest KDF costs accepted when decrypting; a file with a lower cost
+/// would let a stolen copy be brute-forced cheaply
+const MIN_SCRYPT_N: u64 = 1 << 10;
+const MIN_PBKDF2_ITERATIONS: u32 = 1000;
+
+/// A version 3 keystore file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keystore {
+    /// Format version; always 3
+    pub version: u32,
+    /// Random UUID of the file
+    pub id: String,
+    /// DAG address of the key, if the exporter recorded it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Encrypted key and the parameters to decrypt it
+    #[serde(alias = "Crypto")]
+    pub crypto: KeystoreCrypto,
+}
+
+/// The `crypto` section of a [`Keystore`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    /// Cipher; only `aes-128-ctr` is supported
+    pub cipher: String,
+    /// Cipher parameters
+    pub cipherparams: CipherParams,
+    /// Encrypted private key in hex
+    pub ciphertext: String,
+    /// Key derivation function and its parameters
+    #[serde(flatten)]
+    pub kdf: Kdf,
+    /// Keccak-256 of the second half of the derived key and the ciphertext, in hex
+    pub mac: String,
+}
+
+/// AES-CTR parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CipherParams {
+    /// Initial counter block in hex
+    pub iv: String,
+}
+
+/// Key derivation function of a [`Keystore`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kdf", content = "kdfparams", rename_all = "lowercase")]
+pub enum Kdf {
+    /// scrypt with cost `n`, block size `r`, and parallelism `p`
+    Scrypt {
+        /// Derived key length in bytes
+        dklen: usize,
+        /// CPU/memory cost, a power of two
+        n: u64,
+        /// Block size
+        r: u32,
+        /// Parallelism
+        p: u32,
+        /// Salt in hex
+        salt: String,
+    },
+    /// PBKDF2 with HMAC-SHA256 and `c` iterations
+    Pbkdf2 {
+        /// Iterations
+        c: u32,
+        /// Derived key length in bytes
+        dklen: usize,
+        /// Pseudo-random function; only `hmac-sha256` is supported
+        prf: String,
+        /// Salt in hex
+        salt: String,
+    },
+}
+
+impl Keystore {
+    /// Encrypt a private key in hex under a passphrase, deriving with scrypt
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPrivateKey` error if the key is malformed
+    pub fn encrypt(private_key: &str, passphrase: &str) -> Result<Self> {
+        let key_pair = key_pair_from_private_key(private_key)?;
+        let mut rng = rand::thread_rng();
+        let mut salt = [0u8; 32];
+        let mut iv = [0u8; 16];
+        let mut id = [0u8; 16];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut iv);
+        rng.fill_bytes(&mut id);
+
+        let kdf = Kdf::Scrypt {
+            dklen: DKLEN,
+            n: 1 << SCRYPT_LOG_N,
+            r: SCRYPT_R,
+            p: SCRYPT_P,
+            salt: hex::encode(salt),
+        };
+        let derived = kdf.derive(passphrase)?;
+        let mut ciphertext = hex::decode(&key_pair.private_key)?;
+        Aes128Ctr::new(derived[..16].into(), (&iv).into()).apply_keystream(&mut ciphertext);
+
+        Ok(Self {
+            version: 3,
+            id: format_uuid(id),
+            address: Some(key_pair.address),
+            crypto: KeystoreCrypto {
+                cipher: "aes-128-ctr".to_string(),
+                cipherparams: CipherParams {
+                    iv: hex::encode(iv),
+                },
+                mac: hex::encode(mac(&derived, &ciphertext)),
+                ciphertext: hex::encode(ciphertext),
+                kdf,
+            },
+        })
+    }
+
+    /// Parse a keystore file
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SerializationError` if the JSON is malformed or the version
+    /// is not 3
+    pub fn from_json(json: &str) -> Result<Self> {
+        let keystore: Self = serde_json::from_str(json).map_err(SdkError::from)?;
+        if keystore.version != 3 {
+            return Err(SdkError::SerializationError(format!(
+                "unsupported keystore version {}",
+                keystore.version
+            )));
+        }
+        Ok(keystore)
+    }
+
+    /// Serialize the keystore to JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(SdkError::from)
+    }
+
+    /// Decrypt the key with a passphrase
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CryptoError` if the passphrase is wrong, the cipher or KDF
+    /// is unsupported, or the KDF cost is below scrypt N = 2^10 or 1000
+    /// PBKDF2 iterations
+    pub fn decrypt(&self, passphrase: &str) -> Result<LocalSigner> {
+        LocalSigner::new(&hex::encode(self.decrypt_key(passphrase)?))
+    }
+
+    /// Decrypt the key with a passphrase straight into a [`ProtectedSigner`]
+    ///
+    /// # Errors
+    ///
+    /// As [`decrypt`](Self::decrypt)
+    #[cfg(feature = "memsec")]
+    pub fn decrypt_protected(&self, passphrase: &str) -> Result<ProtectedSigner> {
+        let key = zeroize::Zeroizing::new(self.decrypt_key(passphrase)?);
+        ProtectedSigner::from_bytes(&key)
+    }
+
+    fn decrypt_key(&self, passphrase: &str) -> Result<Vec<u8>> {
+        let crypto = &self.crypto;
+        if crypto.cipher != "aes-128-ctr" {
+            return Err(SdkError::CryptoError(format!(
+                "unsupported keystore cipher {}",
+                crypto.cipher
+            )));
+        }
+        let derived = crypto.kdf.derive(passphrase)?;
+        let mut key = hex::decode(&crypto.ciphertext)?;
+        // Compared in constant time, and a malformed MAC fails the same way
+        // as a wrong one
+        let expected = hex::decode(&crypto.mac).unwrap_or_default();
+        if !ct_eq(&mac(&derived, &key), &expected) {
+            return Err(SdkError::CryptoError(
+                "wrong passphrase or corrupted keystore".to_string(),
+            ));
+        }
+        let iv: [u8; 16] = hex::decode(&crypto.cipherparams.iv)?
+            .try_into()
+            .map_err(|_| SdkError::CryptoError("keystore iv is not 16 bytes".to_string()))?;
+        Aes128Ctr::new(derived[..16].into(), (&iv).into()).apply_keystream(&mut key);
+        Ok(key)
+    }
+}
+
+impl Kdf {
+    fn derive(&self, passphrase: &str) -> Result<Vec<u8>> {
+        match self {
+            Kdf::Scrypt {
+                dklen,
+                n,
+                r,
+                p,
+                salt,
+            } => {
+                if !n.is_power_of_two() || *n < MIN_SCRYPT_N || *dklen < DKLEN {
+                    return Err(SdkError::CryptoError(format!(
+                        "unsupported scrypt parameters n={n} dklen={dklen}"
+                    )));
+                }
+                let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p, *dklen)
+                    .map_err(|e| SdkError::CryptoError(e.to_string()))?;
+                let mut derived = vec![0u8; *dklen];
+                scrypt::scrypt(
+                    passphrase.as_bytes(),
+                    &hex::decode(salt)?,
+                    &params,
+                    &mut derived,
+                )
+                .map_err(|e| SdkError::CryptoError(e.to_string()))?;
+                Ok(derived)
+            }
+            Kdf::Pbkdf2 {
+                c,
+                dklen,
+                prf,
+                salt,
+            } => {
+                if prf != "hmac-sha256" || *c < MIN_PBKDF2_ITERATIONS || *dklen < DKLEN {
+                    return Err(SdkError::CryptoError(format!(
+                        "unsupported pbkdf2 parameters prf={prf} c={c} dklen={dklen}"
+                    )));
+                }
+                let mut derived = vec![0u8; *dklen];
+                pbkdf2::pbkdf2_hmac::<sha2::Sha256>(
+                    passphrase.as_bytes(),
+                    &hex::decode(salt)?,
+                    *c,
+                    &mut derived,
+                );
+                Ok(derived)
+            }
+        }
+    }
+}
+
+fn mac(derived: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(&derived[16..32]);
+    hasher.update(ciphertext);
+    hasher.finalize().into()
+}
+
+fn format_uuid(mut bytes: [u8; 16]) -> String {
+    // Version 4, RFC 4122 variant
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex = hex::encode(bytes);
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
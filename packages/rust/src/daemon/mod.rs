@@ -0,0 +1,524 @@
+//! Local signing daemon
+//!
+//! A [`SigningDaemon`] holds named [`Signer`]s and signs on request over
+//! JSON-RPC, so other processes on a host can get signatures without ever
+//! reading a key file. Keys come from [`Keystore`] files that stay encrypted
+//! until a client unlocks them with the passphrase, or from any other
+//! [`Signer`]. Every request is passed to the configured [`AuditSink`]s.
+//!
+//! Requests from web pages are refused (see [`SigningDaemon::serve`]), and
+//! failed unlocks are throttled (see [`SigningDaemon::unlock`]).
+//!
+//! Requires the `daemon` feature.
+//!
+//! # Methods
+//!
+//! | Method       | Params                                   | Result |
+//! |--------------|------------------------------------------|--------|
+//! | `listKeys`   | none                                     | `[{name, unlocked, publicKeyId, address}]` |
+//! | `unlock`     | `{key, passphrase}`                      | `true` |
+//! | `lock`       | `{key}`                                  | `true` |
+//! | `signHash`   | `{key, hash}`                            | `{id, signature}` |
+//! | `sign`       | `{key, value, isDataUpdate?}`            | `{id, signature}` |
+//! | `signObject` | `{key, value, isDataUpdate?}`            | `{value, proofs}` |
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::daemon::{JsonLinesAudit, Keystore, SigningDaemon};
+//! use std::sync::Arc;
+//!
+//! let keystore = Keystore::from_json(&std::fs::read_to_string("validator.json")?)?;
+//! let daemon = SigningDaemon::new()
+//!     .with_keystore("validator", keystore)
+//!     .with_token(std::env::var("SIGNER_TOKEN")?)
+//!     .with_audit(JsonLinesAudit::open("/var/log/signer.jsonl")?);
+//!
+//! let listener = tokio::net::TcpListener::bind("127.0.0.1:9777").await?;
+//! Arc::new(daemon).serve(listener).await?;
+//! ```
+//!
+//! ```bash
+//! curl -H "Authorization: Bearer $SIGNER_TOKEN" -d \
+//!   '{"jsonrpc":"2.0","id":1,"method":"sign","params":{"key":"validator","value":{"id":1},"isDataUpdate":true}}' \
+//!   http://127.0.0.1:9777
+//! ```
+
+mod audit;
+mod keystore;
+mod server;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::core::ct::ct_eq;
+use crate::hash::hash_data;
+use crate::signer::Signer;
+use crate::types::{SdkError, Signed};
+
+// ─── Re-exports ─────────────────────────────────────────────────────────
+
+// Audit trail
+pub use audit::{AuditEntry, AuditSink, JsonLinesAudit};
+
+// Key files
+pub use keystore::{CipherParams, Kdf, Keystore, KeystoreCrypto};
+
+/// JSON-RPC error codes returned by the daemon
+pub mod error_codes {
+    /// The request is not valid JSON
+    pub const PARSE_ERROR: i64 = -32700;
+    /// The request is not a JSON-RPC 2.0 request object
+    pub const INVALID_REQUEST: i64 = -32600;
+    /// Unknown method
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    /// Missing or malformed params
+    pub const INVALID_PARAMS: i64 = -32602;
+    /// Unexpected failure inside the daemon
+    pub const INTERNAL_ERROR: i64 = -32603;
+    /// Signing or unlocking failed
+    pub const SIGNING_FAILED: i64 = -32000;
+    /// No key with that name
+    pub const UNKNOWN_KEY: i64 = -32001;
+    /// The key's keystore has not been unlocked
+    pub const KEY_LOCKED: i64 = -32002;
+}
+
+/// Unlock attempts allowed before failed ones are throttled
+const FREE_UNLOCK_ATTEMPTS: u32 = 3;
+/// Wait after the first throttled attempt, doubled with each further one
+const UNLOCK_BACKOFF: Duration = Duration::from_secs(1);
+/// Longest wait between throttled attempts
+const MAX_UNLOCK_BACKOFF: Duration = Duration::from_secs(300);
+
+/// A key the daemon can sign with
+struct KeySlot {
+    /// Encrypted key, for keys that can be locked
+    keystore: Option<Keystore>,
+    /// Signer, while the key is unlocked
+    signer: Option<Arc<dyn Signer>>,
+    /// Unlock attempts since the last successful one
+    unlock_attempts: u32,
+    /// When the next unlock attempt is allowed, once attempts are throttled
+    retry_after: Option<Instant>,
+}
+
+impl KeySlot {
+    fn new(keystore: Option<Keystore>, signer: Option<Arc<dyn Signer>>) -> Self {
+        Self {
+            keystore,
+            signer,
+            unlock_attempts: 0,
+            retry_after: None,
+        }
+    }
+
+    /// Hold off the next unlock attempt if too many have been made
+    fn throttle(&mut self, now: Instant) {
+        if self.unlock_attempts > FREE_UNLOCK_ATTEMPTS {
+            let doublings = (self.unlock_attempts - FREE_UNLOCK_ATTEMPTS - 1).min(16);
+            let backoff = (UNLOCK_BACKOFF * 2u32.pow(doublings)).min(MAX_UNLOCK_BACKOFF);
+            self.retry_after = Some(now + backoff);
+        }
+    }
+}
+
+/// Signs on behalf of its keys for local clients
+pub struct SigningDaemon {
+    keys: RwLock<BTreeMap<String, KeySlot>>,
+    audit: Vec<Arc<dyn AuditSink>>,
+    token: Option<String>,
+}
+
+impl Default for SigningDaemon {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for SigningDaemon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys: Vec<String> = self.read_keys().keys().cloned().collect();
+        f.debug_struct("SigningDaemon")
+            .field("keys", &keys)
+            .field("audit_sinks", &self.audit.len())
+            .field("token", &self.token.as_ref().map(|_| "<redacted>"))
+            .finish()
+    }
+}
+
+impl SigningDaemon {
+    /// Create a daemon without keys
+    pub fn new() -> Self {
+        Self {
+            keys: RwLock::new(BTreeMap::new()),
+            audit: Vec::new(),
+            token: None,
+        }
+    }
+
+    /// Add a signer that is always unlocked
+    pub fn with_signer(self, name: impl Into<String>, signer: impl Signer + 'static) -> Self {
+        self.with_shared_signer(name, Arc::new(signer))
+    }
+
+    /// Add a shared signer that is always unlocked
+    pub fn with_shared_signer(self, name: impl Into<String>, signer: Arc<dyn Signer>) -> Self {
+        self.write_keys()
+            .insert(name.into(), KeySlot::new(None, Some(signer)));
+        self
+    }
+
+    /// Add a keystore, locked until [`unlock`](Self::unlock) is called
+    pub fn with_keystore(self, name: impl Into<String>, keystore: Keystore) -> Self {
+        self.write_keys()
+            .insert(name.into(), KeySlot::new(Some(keystore), None));
+        self
+    }
+
+    /// Record every request to an audit sink
+    pub fn with_audit(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.audit.push(Arc::new(sink));
+        self
+    }
+
+    /// Require `Authorization: Bearer <token>` on HTTP requests
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Decrypt a keystore so its key can sign
+    ///
+    /// With the `memsec` feature the key is kept as a
+    /// [`ProtectedSigner`](crate::memsec::ProtectedSigner).
+    ///
+    /// After three failed attempts on a key, each further attempt must wait
+    /// a second, doubling up to five minutes, so a passphrase cannot be
+    /// guessed at the speed of the KDF. A successful unlock resets the count.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CryptoError` if the key is unknown, has no keystore, is
+    /// throttled, or the passphrase is wrong
+    pub fn unlock(&self, name: &str, passphrase: &str) -> Result<(), SdkError> {
+        let keystore = {
+            let mut keys = self.write_keys();
+            let slot = keys.get_mut(name).ok_or_else(|| unknown_key(name))?;
+            let keystore = slot
+                .keystore
+                .clone()
+                .ok_or_else(|| SdkError::CryptoError(format!("key {name} has no keystore")))?;
+            let now = Instant::now();
+            if let Some(retry_after) = slot.retry_after.filter(|at| *at > now) {
+                return Err(SdkError::CryptoError(format!(
+                    "too many failed attempts to unlock key {name}; retry in {}s",
+                    (retry_after - now).as_secs() + 1
+                )));
+            }
+            // Counted before deriving, so concurrent attempts are throttled too
+            slot.unlock_attempts += 1;
+            slot.throttle(now);
+            keystore
+        };
+        #[cfg(feature = "memsec")]
+        let decrypted = keystore.decrypt_protected(passphrase);
+        #[cfg(not(feature = "memsec"))]
+        let decrypted = keystore.decrypt(passphrase);
+        let mut keys = self.write_keys();
+        let Some(slot) = keys.get_mut(name) else {
+            return decrypted.map(|_| ());
+        };
+        match decrypted {
+            Ok(signer) => {
+                slot.signer = Some(Arc::new(signer));
+                slot.unlock_attempts = 0;
+                slot.retry_after = None;
+                Ok(())
+            }
+            Err(e) => {
+                // The wait starts once the KDF has finished
+                slot.throttle(Instant::now());
+                Err(e)
+            }
+        }
+    }
+
+    /// Drop the decrypted key of a keystore
+    ///
+    /// Signers added with [`with_signer`](Self::with_signer) cannot be locked.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `CryptoError` if the key is unknown or has no keystore
+    pub fn lock(&self, name: &str) -> Result<(), SdkError> {
+        let mut keys = self.write_keys();
+        let slot = keys.get_mut(name).ok_or_else(|| unknown_key(name))?;
+        if slot.keystore.is_none() {
+            return Err(SdkError::CryptoError(format!(
+                "key {name} has no keystore and cannot be locked"
+            )));
+        }
+        slot.signer = None;
+        Ok(())
+    }
+
+    /// Handle one JSON-RPC request and return the JSON-RPC response
+    ///
+    /// `peer` is recorded in the audit trail. Notifications (requests without
+    /// an `id`) are answered like other requests.
+    pub fn handle(&self, request: &str, peer: Option<SocketAddr>) -> String {
+        let mut entry = AuditEntry {
+            timestamp: now_millis(),
+            peer: peer.map(|p| p.to_string()),
+            method: String::new(),
+            key: None,
+            hash: None,
+            ok: false,
+            error: None,
+        };
+        let (id, result) = match serde_json::from_str::<Value>(request) {
+            Err(e) => (
+                Value::Null,
+                Err(RpcError::new(error_codes::PARSE_ERROR, e.to_string())),
+            ),
+            Ok(request) => {
+                let id = request.get("id").cloned().unwrap_or(Value::Null);
+                let result = serde_json::from_value::<RpcRequest>(request)
+                    .map_err(|e| RpcError::new(error_codes::INVALID_REQUEST, e.to_string()))
+                    .and_then(|request| {
+                        entry.method.clone_from(&request.method);
+                        self.dispatch(&request, &mut entry)
+                    });
+                (id, result)
+            }
+        };
+        entry.ok = result.is_ok();
+        entry.error = result.as_ref().err().map(|e| e.message.clone());
+        for sink in &self.audit {
+            sink.record(&entry);
+        }
+        let response = match result {
+            Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": {"code": e.code, "message": e.message},
+            }),
+        };
+        response.to_string()
+    }
+
+    fn dispatch(&self, request: &RpcRequest, entry: &mut AuditEntry) -> Result<Value, RpcError> {
+        if request.jsonrpc != "2.0" {
+            return Err(RpcError::new(
+                error_codes::INVALID_REQUEST,
+                "jsonrpc must be \"2.0\"",
+            ));
+        }
+        match request.method.as_str() {
+            "listKeys" => Ok(self.list_keys()),
+            "unlock" => {
+                let params: UnlockParams = params(request)?;
+                entry.key = Some(params.key.clone());
+                self.unlock(&params.key, &params.passphrase)
+                    .map_err(|e| self.key_error(&params.key, e))?;
+                Ok(Value::Bool(true))
+            }
+            "lock" => {
+                let params: KeyParams = params(request)?;
+                entry.key = Some(params.key.clone());
+                self.lock(&params.key)
+                    .map_err(|e| self.key_error(&params.key, e))?;
+                Ok(Value::Bool(true))
+            }
+            "signHash" => {
+                let params: SignHashParams = params(request)?;
+                entry.key = Some(params.key.clone());
+                entry.hash = Some(params.hash.clone());
+                let proof = self
+                    .signer(&params.key)?
+                    .sign_hash_proof(&params.hash)
+                    .map_err(signing_failed)?;
+                Ok(json!(proof))
+            }
+            "sign" | "signObject" => {
+                let params: SignParams = params(request)?;
+                entry.key = Some(params.key.clone());
+                let signer = self.signer(&params.key)?;
+                let hash =
+                    hash_data(&params.value, params.is_data_update).map_err(signing_failed)?;
+                entry.hash = Some(hash.value.clone());
+                let proof = signer
+                    .sign_hash_proof(&hash.value)
+                    .map_err(signing_failed)?;
+                if request.method == "sign" {
+                    Ok(json!(proof))
+                } else {
+                    Ok(json!(Signed {
+                        value: params.value,
+                        proofs: vec![proof],
+                    }))
+                }
+            }
+            other => Err(RpcError::new(
+                error_codes::METHOD_NOT_FOUND,
+                format!("unknown method {other}"),
+            )),
+        }
+    }
+
+    fn list_keys(&self) -> Value {
+        let keys = self.read_keys();
+        let list: Vec<Value> = keys
+            .iter()
+            .map(|(name, slot)| {
+                let public_key_id = slot.signer.as_ref().map(|s| s.public_key_id());
+                let address = slot
+                    .signer
+                    .as_ref()
+                    .map(|s| s.address())
+                    .or_else(|| slot.keystore.as_ref().and_then(|k| k.address.clone()));
+                json!({
+                    "name": name,
+                    "unlocked": slot.signer.is_some(),
+                    "publicKeyId": public_key_id,
+                    "address": address,
+                })
+            })
+            .collect();
+        Value::Array(list)
+    }
+
+    fn signer(&self, name: &str) -> Result<Arc<dyn Signer>, RpcError> {
+        let keys = self.read_keys();
+        let slot = keys.get(name).ok_or_else(|| {
+            RpcError::new(error_codes::UNKNOWN_KEY, unknown_key(name).to_string())
+        })?;
+        slot.signer
+            .clone()
+            .ok_or_else(|| RpcError::new(error_codes::KEY_LOCKED, format!("key {name} is locked")))
+    }
+
+    fn key_error(&self, name: &str, error: SdkError) -> RpcError {
+        if self.read_keys().contains_key(name) {
+            signing_failed(error)
+        } else {
+            RpcError::new(error_codes::UNKNOWN_KEY, error.to_string())
+        }
+    }
+
+    pub(super) fn authorized(&self, header: Option<&str>) -> bool {
+        match &self.token {
+            None => true,
+            Some(token) => header
+                .and_then(|h| h.strip_prefix("Bearer "))
+                .is_some_and(|given| ct_eq(given.as_bytes(), token.as_bytes())),
+        }
+    }
+
+    pub(super) fn audit_rejected(&self, peer: SocketAddr, reason: &str) {
+        let entry = AuditEntry {
+            timestamp: now_millis(),
+            peer: Some(peer.to_string()),
+            method: "authenticate".to_string(),
+            key: None,
+            hash: None,
+            ok: false,
+            error: Some(reason.to_string()),
+        };
+        for sink in &self.audit {
+            sink.record(&entry);
+        }
+    }
+
+    fn read_keys(&self) -> std::sync::RwLockReadGuard<'_, BTreeMap<String, KeySlot>> {
+        self.keys.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write_keys(&self) -> std::sync::RwLockWriteGuard<'_, BTreeMap<String, KeySlot>> {
+        self.keys.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    jsonrpc: String,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct KeyParams {
+    key: String,
+}
+
+#[derive(Deserialize)]
+struct UnlockParams {
+    key: String,
+    passphrase: String,
+}
+
+#[derive(Deserialize)]
+struct SignHashParams {
+    key: String,
+    hash: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SignParams {
+    key: String,
+    value: Value,
+    #[serde(default)]
+    is_data_update: bool,
+}
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+fn params<T: serde::de::DeserializeOwned>(request: &RpcRequest) -> Result<T, RpcError> {
+    serde_json::from_value(request.params.clone())
+        .map_err(|e| RpcError::new(error_codes::INVALID_PARAMS, e.to_string()))
+}
+
+fn signing_failed(error: SdkError) -> RpcError {
+    RpcError::new(error_codes::SIGNING_FAILED, error.to_string())
+}
+
+fn unknown_key(name: &str) -> SdkError {
+    SdkError::CryptoError(format!("no key named {name}"))
+}
+
+fn internal_error_reply() -> String {
+    json!({
+        "jsonrpc": "2.0",
+        "id": null,
+        "error": {"code": error_codes::INTERNAL_ERROR, "message": "internal error"},
+    })
+    .to_string()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
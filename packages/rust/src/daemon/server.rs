@@ -0,0 +1,117 @@
+//! HTTP transport of the signing daemon
+
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{AUTHORIZATION, CONTENT_TYPE, HOST, ORIGIN};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+use super::SigningDaemon;
+
+/// Largest request body accepted
+const MAX_BODY_BYTES: usize = 1 << 20;
+
+impl SigningDaemon {
+    /// Serve JSON-RPC over HTTP on a bound listener until it fails
+    ///
+    /// Requests are `POST`ed to any path with the JSON-RPC request as the
+    /// body. If a token is set, requests without `Authorization: Bearer
+    /// <token>` are answered with `401`. Bind the listener to a loopback
+    /// address; the daemon does not use TLS.
+    ///
+    /// Requests from web pages are answered with `403`, whether or not a
+    /// token is set: a request with an `Origin` header, or whose `Host` is
+    /// not `localhost` or a loopback address, as a page that rebinds its
+    /// own DNS name to 127.0.0.1 sends.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of accepting a connection
+    pub async fn serve(self: Arc<Self>, listener: TcpListener) -> std::io::Result<()> {
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            let daemon = self.clone();
+            tokio::spawn(async move {
+                let service = service_fn(move |request| {
+                    let daemon = daemon.clone();
+                    async move { Ok::<_, Infallible>(daemon.respond(request, peer).await) }
+                });
+                let _ = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await;
+            });
+        }
+    }
+
+    async fn respond(
+        self: Arc<Self>,
+        request: Request<Incoming>,
+        peer: SocketAddr,
+    ) -> Response<Full<Bytes>> {
+        if request.method() != Method::POST {
+            return plain(StatusCode::METHOD_NOT_ALLOWED, "use POST");
+        }
+        if request.headers().contains_key(ORIGIN) {
+            let reason = "cross-origin requests are refused";
+            self.audit_rejected(peer, reason);
+            return plain(StatusCode::FORBIDDEN, reason);
+        }
+        let host = request.headers().get(HOST).and_then(|v| v.to_str().ok());
+        if !host.is_some_and(is_loopback_host) {
+            let reason = "host is not a loopback address";
+            self.audit_rejected(peer, reason);
+            return plain(StatusCode::FORBIDDEN, reason);
+        }
+        if !self.authorized(
+            request
+                .headers()
+                .get(AUTHORIZATION)
+                .and_then(|v| v.to_str().ok()),
+        ) {
+            let reason = "missing or wrong bearer token";
+            self.audit_rejected(peer, reason);
+            return plain(StatusCode::UNAUTHORIZED, reason);
+        }
+        let body = match Limited::new(request.into_body(), MAX_BODY_BYTES)
+            .collect()
+            .await
+        {
+            Ok(body) => body.to_bytes(),
+            Err(_) => return plain(StatusCode::PAYLOAD_TOO_LARGE, "request too large"),
+        };
+        // Unlocking runs the KDF, so keep it off the reactor
+        let daemon = self.clone();
+        let reply = tokio::task::spawn_blocking(move || {
+            daemon.handle(&String::from_utf8_lossy(&body), Some(peer))
+        })
+        .await
+        .unwrap_or_else(|_| super::internal_error_reply());
+        Response::builder()
+            .header(CONTENT_TYPE, "application/json")
+            .body(Full::new(Bytes::from(reply)))
+            .expect("valid response")
+    }
+}
+
+fn plain(status: StatusCode, message: &'static str) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .body(Full::new(Bytes::from_static(message.as_bytes())))
+        .expect("valid response")
+}
+
+/// Whether a `Host` header names `localhost` or a loopback address
+fn is_loopback_host(host: &str) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(bracketed) => bracketed.split(']').next().unwrap_or_default(),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name.parse::<IpAddr>().is_ok_and(|ip| ip.is_loopback())
+}
@@ -0,0 +1,104 @@
+//! Typed data updates
+//!
+//! A metagraph's data application accepts a fixed set of update types, each
+//! posted to a known Data L1 route and signed over the bytes the
+//! application's `serializeUpdate` produces. Implementing [`DataUpdate`] for
+//! an update type records both once, so signing, verifying, and submitting
+//! it (`MetagraphClient::submit` with the `network` feature) need no
+//! `is_data_update` flag or path.
+//!
+//! The defaults match metakit's: updates are posted to `/data` and signed
+//! over the DataUpdate encoding of their canonical JSON, as
+//! [`sign_data_update`](crate::sign::sign_data_update) does. Override
+//! [`DataUpdate::serialize_update`] for an application that replaced its
+//! serializer.
+//!
+//! # Example
+//!
+//! ```
+//! use constellation_sdk::data_update::{sign_update, verify_update, DataUpdate};
+//! use constellation_sdk::LocalSigner;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Clone, Serialize)]
+//! struct CastVote {
+//!     poll: String,
+//!     option: u32,
+//! }
+//!
+//! impl DataUpdate for CastVote {}
+//!
+//! let signer = LocalSigner::generate();
+//! let vote = CastVote { poll: "p1".to_string(), option: 2 };
+//! let signed = sign_update(&signer, &vote).unwrap();
+//! assert!(verify_update(&signed).is_valid);
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::binary::to_bytes;
+use crate::hash::hash_bytes;
+use crate::signer::Signer;
+use crate::types::{Hash, Result, Signed, VerificationResult};
+use crate::verify::{verify_proofs, SignaturePolicy};
+
+/// An update type of a metagraph's data application
+pub trait DataUpdate: Serialize + Sized {
+    /// Data L1 route the update is posted to
+    const ENDPOINT: &'static str = "/data";
+
+    /// The bytes whose SHA-256 is signed
+    ///
+    /// Must produce what the data application's `serializeUpdate` does for
+    /// the same update. Defaults to the DataUpdate encoding of the canonical
+    /// JSON, as [`to_bytes`] with `is_data_update` set.
+    fn serialize_update(&self) -> Result<Vec<u8>> {
+        to_bytes(self, true)
+    }
+}
+
+/// An update tagged with its signer's sequence number
+///
+/// For data applications whose state machine applies a signer's updates
+/// strictly in order: the application accepts an update only if `nonce` is
+/// one past the last nonce it applied for the signer. Posted to the inner
+/// type's route and signed over the DataUpdate encoding of
+/// `{"nonce": ..., "update": ...}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Sequenced<U> {
+    /// Position of the update in the signer's sequence
+    pub nonce: u64,
+    /// The update
+    pub update: U,
+}
+
+impl<U: DataUpdate> DataUpdate for Sequenced<U> {
+    const ENDPOINT: &'static str = U::ENDPOINT;
+}
+
+/// The hash that is signed for `update`
+pub fn update_hash<U: DataUpdate>(update: &U) -> Result<Hash> {
+    Ok(hash_bytes(&update.serialize_update()?))
+}
+
+/// Sign a data update with a [`Signer`]
+pub fn sign_update<S, U>(signer: &S, update: &U) -> Result<Signed<U>>
+where
+    S: Signer + ?Sized,
+    U: DataUpdate + Clone,
+{
+    let proof = signer.sign_hash_proof(&update_hash(update)?.value)?;
+    Ok(Signed {
+        value: update.clone(),
+        proofs: vec![proof],
+    })
+}
+
+/// Verify a signed data update against its own serializer
+pub fn verify_update<U: DataUpdate>(signed: &Signed<U>) -> VerificationResult {
+    verify_proofs(
+        update_hash(&signed.value),
+        signed,
+        SignaturePolicy::default(),
+    )
+}
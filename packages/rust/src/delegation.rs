@@ -0,0 +1,222 @@
+//! Signing on behalf of another key
+//!
+//! Dapps that want session keys let a long-lived owner key authorize a
+//! short-lived delegate key once, then sign each update with the delegate.
+//! The owner signs a [`Delegation`] naming the delegate (and optionally an
+//! expiry); the delegate signs the payload; a [`Delegated`] carries both, and
+//! [`verify_delegated`] checks the whole chain and returns the owner the
+//! update acts for.
+//!
+//! The authorization is signed with [`DELEGATION_DOMAIN`] domain separation
+//! (see [`hash_with_domain`]), so an owner signature on a delegation can
+//! never pass as a signature on data, or the other way round.
+//!
+//! # Example
+//!
+//! ```
+//! use constellation_sdk::delegation::{sign_delegated, verify_delegated_at, Delegation};
+//! use constellation_sdk::{LocalSigner, Signer};
+//! use serde_json::json;
+//!
+//! let owner = LocalSigner::generate();
+//! let session = LocalSigner::generate();
+//!
+//! let authorization = Delegation::new(owner.public_key_id(), session.public_key_id())
+//!     .expires_at(1_800_000_000_000)
+//!     .sign(&owner)
+//!     .unwrap();
+//! let update = sign_delegated(&session, &authorization, json!({"move": "e4"}), true).unwrap();
+//!
+//! let acting_for = verify_delegated_at(&update, true, 1_700_000_000_000).unwrap();
+//! assert_eq!(acting_for, owner.public_key_id());
+//! ```
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::hash::{hash_data, hash_with_domain};
+use crate::signer::Signer;
+use crate::types::{Result, SdkError, SignatureProof, Signed};
+use crate::verify::{signer_key, verify_hash, verify_with_domain};
+
+/// Domain tag of the owner's signature on a [`Delegation`]
+pub const DELEGATION_DOMAIN: &str = "delegation";
+
+/// An owner key's permission for a delegate key to sign on its behalf
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Delegation {
+    /// Public key id of the owner
+    pub owner: String,
+    /// Public key id of the delegate
+    pub delegate: String,
+    /// Milliseconds since the Unix epoch after which the delegation lapses
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<u64>,
+}
+
+impl Delegation {
+    /// Let `delegate` sign for `owner`, without expiry
+    ///
+    /// Both keys are stored as lowercase ids without the `04` prefix.
+    pub fn new(owner: impl AsRef<str>, delegate: impl AsRef<str>) -> Self {
+        Self {
+            owner: signer_key(owner.as_ref()),
+            delegate: signer_key(delegate.as_ref()),
+            expires_at: None,
+        }
+    }
+
+    /// Lapse the delegation at `timestamp`, in milliseconds since the Unix
+    /// epoch
+    pub fn expires_at(mut self, timestamp: u64) -> Self {
+        self.expires_at = Some(timestamp);
+        self
+    }
+
+    /// Sign the delegation with the owner's key
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error if `owner` is not the key named as
+    /// the owner
+    pub fn sign<S: Signer + ?Sized>(self, owner: &S) -> Result<Signed<Delegation>> {
+        if signer_key(&owner.public_key_id()) != signer_key(&self.owner) {
+            return Err(SdkError::InvalidPayload(
+                "signer is not the delegation owner".to_string(),
+            ));
+        }
+        let hash = hash_with_domain(&self, DELEGATION_DOMAIN)?;
+        let proof = owner.sign_hash_proof(&hash.value)?;
+        Ok(Signed {
+            value: self,
+            proofs: vec![proof],
+        })
+    }
+}
+
+/// A value signed by a delegate, with the owner's authorization
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Delegated<T> {
+    /// The signed value
+    pub value: T,
+    /// The delegate's signature on `value`
+    pub proof: SignatureProof,
+    /// The owner's signed [`Delegation`] to the delegate
+    pub authorization: Signed<Delegation>,
+}
+
+/// Sign `value` with a delegate key, attaching its authorization
+///
+/// `is_data_update` selects the hashing as in [`sign`](crate::sign::sign)
+/// and [`sign_data_update`](crate::sign::sign_data_update).
+///
+/// # Errors
+///
+/// Returns an `InvalidPayload` error if `delegate` is not the key the
+/// authorization names
+pub fn sign_delegated<S, T>(
+    delegate: &S,
+    authorization: &Signed<Delegation>,
+    value: T,
+    is_data_update: bool,
+) -> Result<Delegated<T>>
+where
+    S: Signer + ?Sized,
+    T: Serialize,
+{
+    if signer_key(&delegate.public_key_id()) != signer_key(&authorization.value.delegate) {
+        return Err(SdkError::InvalidPayload(
+            "signer is not the authorized delegate".to_string(),
+        ));
+    }
+    let hash = hash_data(&value, is_data_update)?;
+    let proof = delegate.sign_hash_proof(&hash.value)?;
+    Ok(Delegated {
+        value,
+        proof,
+        authorization: authorization.clone(),
+    })
+}
+
+/// Why a [`Delegated`] value was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DelegationError {
+    /// The authorization is not validly signed by its owner
+    InvalidAuthorization,
+    /// The value was signed by a key other than the authorized delegate
+    DelegateMismatch,
+    /// The delegate's signature on the value does not verify
+    InvalidSignature,
+    /// The delegation lapsed at this time, in milliseconds since the Unix
+    /// epoch
+    Expired(u64),
+}
+
+impl fmt::Display for DelegationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DelegationError::InvalidAuthorization => {
+                write!(f, "delegation is not signed by its owner")
+            }
+            DelegationError::DelegateMismatch => {
+                write!(f, "value is not signed by the authorized delegate")
+            }
+            DelegationError::InvalidSignature => write!(f, "delegate signature does not verify"),
+            DelegationError::Expired(at) => write!(f, "delegation expired at {at}"),
+        }
+    }
+}
+
+impl std::error::Error for DelegationError {}
+
+/// Check a delegated value and return the owner's public key id
+///
+/// Checks that the owner signed the delegation, that it has not expired,
+/// and that the named delegate signed the value. Keys match regardless of
+/// case and `04` prefix, and the owner is returned in lowercase without the
+/// prefix.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn verify_delegated<T: Serialize>(
+    delegated: &Delegated<T>,
+    is_data_update: bool,
+) -> std::result::Result<String, DelegationError> {
+    verify_delegated_at(delegated, is_data_update, crate::timestamped::now_millis())
+}
+
+/// [`verify_delegated`] against a given time in milliseconds since the Unix
+/// epoch
+pub fn verify_delegated_at<T: Serialize>(
+    delegated: &Delegated<T>,
+    is_data_update: bool,
+    now_ms: u64,
+) -> std::result::Result<String, DelegationError> {
+    let authorization = &delegated.authorization;
+    let delegation = &authorization.value;
+    let owner = signer_key(&delegation.owner);
+    if !verify_with_domain(authorization, DELEGATION_DOMAIN).is_valid
+        || !authorization
+            .proofs
+            .iter()
+            .any(|proof| signer_key(&proof.id) == owner)
+    {
+        return Err(DelegationError::InvalidAuthorization);
+    }
+    if let Some(expires_at) = delegation.expires_at {
+        if now_ms >= expires_at {
+            return Err(DelegationError::Expired(expires_at));
+        }
+    }
+    if signer_key(&delegated.proof.id) != signer_key(&delegation.delegate) {
+        return Err(DelegationError::DelegateMismatch);
+    }
+    let valid = hash_data(&delegated.value, is_data_update)
+        .and_then(|hash| verify_hash(&hash.value, &delegated.proof.signature, &delegated.proof.id))
+        .unwrap_or(false);
+    if !valid {
+        return Err(DelegationError::InvalidSignature);
+    }
+    Ok(owner)
+}
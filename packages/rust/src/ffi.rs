@@ -0,0 +1,397 @@
+//! C ABI for linking from C, C++, and Go
+//!
+//! With the `ffi` feature the crate exports `extern "C"` functions for key
+//! generation, signing, verification, and DataUpdate encoding. The matching
+//! declarations are in `include/constellation_sdk.h`. Build the shared
+//! library with `cargo rustc --lib --release --features ffi --crate-type cdylib`.
+//!
+//! Conventions shared by every function:
+//!
+//! - Strings are NUL-terminated UTF-8; values travel as JSON and keys,
+//!   hashes, and signatures as hex.
+//! - The return value is a [`FfiStatus`]. Results are written through out
+//!   pointers, which are left untouched on failure.
+//! - Strings returned through out pointers are owned by the caller and must
+//!   be released with [`constellation_string_free`]; byte buffers with
+//!   [`constellation_bytes_free`].
+//! - After a failure, [`constellation_last_error`] returns the message of the
+//!   last error on the calling thread, and [`constellation_last_error_code`]
+//!   its [`SdkError::code`](crate::SdkError::code).
+//! - Panics are caught at the boundary and reported as
+//!   [`FfiStatus::Panic`].
+//!
+//! # Example
+//!
+//! ```c
+//! #include "constellation_sdk.h"
+//!
+//! char *private_key, *public_key, *address, *signed_json;
+//! constellation_generate_key_pair(&private_key, &public_key, &address);
+//! if (constellation_create_signed_object("{\"id\":1}", private_key, true, &signed_json) != 0) {
+//!     char *message = constellation_last_error();
+//!     fprintf(stderr, "%s\n", message);
+//!     constellation_string_free(message);
+//! }
+//! constellation_string_free(signed_json);
+//! ```
+
+use serde_json::Value;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+use crate::types::{SdkError, Signed};
+
+/// Status returned by every exported function
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FfiStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A pointer argument was null or a string was not valid UTF-8
+    InvalidArgument = 1,
+    /// The SDK reported an error; see [`constellation_last_error`]
+    Failed = 2,
+    /// The call panicked; see [`constellation_last_error`]
+    Panic = 3,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<(String, u32)>> = const { RefCell::new(None) };
+}
+
+/// Error raised inside an exported function, with its `SdkError` code or 0
+struct FfiError(FfiStatus, String, u32);
+
+impl<E: std::error::Error + 'static> From<E> for FfiError {
+    fn from(e: E) -> Self {
+        let code = (&e as &dyn std::error::Error)
+            .downcast_ref::<SdkError>()
+            .map_or(0, SdkError::code);
+        FfiError(FfiStatus::Failed, e.to_string(), code)
+    }
+}
+
+type FfiResult<T> = std::result::Result<T, FfiError>;
+
+/// Run an exported function body, recording its error for
+/// [`constellation_last_error`]
+fn guard(body: impl FnOnce() -> FfiResult<()>) -> FfiStatus {
+    let outcome = catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|panic| {
+        let message = panic
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| panic.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "panic".to_string());
+        Err(FfiError(FfiStatus::Panic, message, 0))
+    });
+    match outcome {
+        Ok(()) => FfiStatus::Ok,
+        Err(FfiError(status, message, code)) => {
+            LAST_ERROR.with(|last| *last.borrow_mut() = Some((message, code)));
+            status
+        }
+    }
+}
+
+unsafe fn arg<'a>(ptr: *const c_char, name: &str) -> FfiResult<&'a str> {
+    if ptr.is_null() {
+        return Err(invalid(format!("{name} is null")));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| invalid(format!("{name} is not valid UTF-8")))
+}
+
+fn out<T>(ptr: *mut T, name: &str) -> FfiResult<*mut T> {
+    if ptr.is_null() {
+        return Err(invalid(format!("{name} is null")));
+    }
+    Ok(ptr)
+}
+
+fn invalid(message: String) -> FfiError {
+    FfiError(FfiStatus::InvalidArgument, message, 0)
+}
+
+fn into_c_string(s: String) -> FfiResult<*mut c_char> {
+    Ok(CString::new(s)?.into_raw())
+}
+
+unsafe fn write_string(dest: *mut *mut c_char, s: String) -> FfiResult<()> {
+    *dest = into_c_string(s)?;
+    Ok(())
+}
+
+unsafe fn write_bytes(dest: *mut *mut u8, dest_len: *mut usize, bytes: Vec<u8>) {
+    let bytes = bytes.into_boxed_slice();
+    *dest_len = bytes.len();
+    *dest = Box::into_raw(bytes) as *mut u8;
+}
+
+/// Message of the last error on the calling thread, or null if none
+///
+/// The string is owned by the caller; release it with
+/// [`constellation_string_free`].
+#[no_mangle]
+pub extern "C" fn constellation_last_error() -> *mut c_char {
+    LAST_ERROR
+        .with(|last| last.borrow().clone())
+        .and_then(|(message, _)| into_c_string(message).ok())
+        .unwrap_or(ptr::null_mut())
+}
+
+/// [`SdkError::code`] of the last error on the calling thread
+///
+/// Returns 0 if there was no error or it did not come from the SDK, e.g. an
+/// invalid argument or a panic.
+#[no_mangle]
+pub extern "C" fn constellation_last_error_code() -> u32 {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(0, |(_, code)| *code))
+}
+
+/// Release a string returned by this library
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library that has not been
+/// released yet.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_string_free(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+/// Release a byte buffer returned by this library
+///
+/// # Safety
+///
+/// `bytes` and `len` must be null and 0, or a buffer and its length as
+/// returned by this library, not released yet.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_bytes_free(bytes: *mut u8, len: usize) {
+    if !bytes.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(bytes, len)));
+    }
+}
+
+/// Generate a random key pair
+///
+/// # Safety
+///
+/// The out pointers must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_generate_key_pair(
+    private_key: *mut *mut c_char,
+    public_key: *mut *mut c_char,
+    address: *mut *mut c_char,
+) -> FfiStatus {
+    guard(|| {
+        let (private_key, public_key, address) = (
+            out(private_key, "private_key")?,
+            out(public_key, "public_key")?,
+            out(address, "address")?,
+        );
+        let key_pair = crate::wallet::generate_key_pair();
+        write_string(private_key, key_pair.private_key)?;
+        write_string(public_key, key_pair.public_key)?;
+        write_string(address, key_pair.address)
+    })
+}
+
+/// Derive the public key and address of a private key in hex
+///
+/// # Safety
+///
+/// `private_key` must be a NUL-terminated string and the out pointers must be
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_key_pair_from_private_key(
+    private_key: *const c_char,
+    public_key: *mut *mut c_char,
+    address: *mut *mut c_char,
+) -> FfiStatus {
+    guard(|| {
+        let private_key = arg(private_key, "private_key")?;
+        let (public_key, address) = (out(public_key, "public_key")?, out(address, "address")?);
+        let key_pair = crate::wallet::key_pair_from_private_key(private_key)?;
+        write_string(public_key, key_pair.public_key)?;
+        write_string(address, key_pair.address)
+    })
+}
+
+/// Sign a JSON value and write the signed object as JSON
+///
+/// # Safety
+///
+/// The string arguments must be NUL-terminated and `signed_json` must be
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_create_signed_object(
+    value_json: *const c_char,
+    private_key: *const c_char,
+    is_data_update: bool,
+    signed_json: *mut *mut c_char,
+) -> FfiStatus {
+    guard(|| {
+        let value: Value = serde_json::from_str(arg(value_json, "value_json")?)?;
+        let private_key = arg(private_key, "private_key")?;
+        let signed_json = out(signed_json, "signed_json")?;
+        let signed =
+            crate::signed_object::create_signed_object(&value, private_key, is_data_update)?;
+        write_string(signed_json, serde_json::to_string(&signed)?)
+    })
+}
+
+/// Add a signature to a signed object given as JSON
+///
+/// # Safety
+///
+/// The string arguments must be NUL-terminated and `out_json` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_add_signature(
+    signed_json: *const c_char,
+    private_key: *const c_char,
+    is_data_update: bool,
+    out_json: *mut *mut c_char,
+) -> FfiStatus {
+    guard(|| {
+        let signed: Signed<Value> = serde_json::from_str(arg(signed_json, "signed_json")?)?;
+        let private_key = arg(private_key, "private_key")?;
+        let out_json = out(out_json, "out_json")?;
+        let signed = crate::signed_object::add_signature(signed, private_key, is_data_update)?;
+        write_string(out_json, serde_json::to_string(&signed)?)
+    })
+}
+
+/// Sign a SHA-256 hash in hex and write the DER signature in hex
+///
+/// # Safety
+///
+/// The string arguments must be NUL-terminated and `signature` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_sign_hash(
+    hash_hex: *const c_char,
+    private_key: *const c_char,
+    signature: *mut *mut c_char,
+) -> FfiStatus {
+    guard(|| {
+        let hash_hex = arg(hash_hex, "hash_hex")?;
+        let private_key = arg(private_key, "private_key")?;
+        let signature = out(signature, "signature")?;
+        write_string(signature, crate::sign::sign_hash(hash_hex, private_key)?)
+    })
+}
+
+/// Verify every proof of a signed object given as JSON
+///
+/// # Safety
+///
+/// `signed_json` must be NUL-terminated and `valid` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_verify(
+    signed_json: *const c_char,
+    is_data_update: bool,
+    valid: *mut bool,
+) -> FfiStatus {
+    guard(|| {
+        let signed: Signed<Value> = serde_json::from_str(arg(signed_json, "signed_json")?)?;
+        let valid = out(valid, "valid")?;
+        *valid = crate::verify::verify(&signed, is_data_update).is_valid;
+        Ok(())
+    })
+}
+
+/// Verify a signature over a SHA-256 hash in hex
+///
+/// # Safety
+///
+/// The string arguments must be NUL-terminated and `valid` must be valid for
+/// writes.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_verify_hash(
+    hash_hex: *const c_char,
+    signature: *const c_char,
+    public_key_id: *const c_char,
+    valid: *mut bool,
+) -> FfiStatus {
+    guard(|| {
+        let hash_hex = arg(hash_hex, "hash_hex")?;
+        let signature = arg(signature, "signature")?;
+        let public_key_id = arg(public_key_id, "public_key_id")?;
+        let valid = out(valid, "valid")?;
+        *valid = crate::verify::verify_hash(hash_hex, signature, public_key_id)?;
+        Ok(())
+    })
+}
+
+/// Write the bytes a JSON value is signed over, with the DataUpdate prefix
+/// if requested
+///
+/// # Safety
+///
+/// `value_json` must be NUL-terminated and `bytes` and `len` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_to_bytes(
+    value_json: *const c_char,
+    is_data_update: bool,
+    bytes: *mut *mut u8,
+    len: *mut usize,
+) -> FfiStatus {
+    guard(|| {
+        let value: Value = serde_json::from_str(arg(value_json, "value_json")?)?;
+        let (bytes, len) = (out(bytes, "bytes")?, out(len, "len")?);
+        write_bytes(bytes, len, crate::binary::to_bytes(&value, is_data_update)?);
+        Ok(())
+    })
+}
+
+/// Encode a JSON value as a DataUpdate
+///
+/// # Safety
+///
+/// `value_json` must be NUL-terminated and `bytes` and `len` must be valid
+/// for writes.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_encode_data_update(
+    value_json: *const c_char,
+    bytes: *mut *mut u8,
+    len: *mut usize,
+) -> FfiStatus {
+    guard(|| {
+        let value: Value = serde_json::from_str(arg(value_json, "value_json")?)?;
+        let (bytes, len) = (out(bytes, "bytes")?, out(len, "len")?);
+        write_bytes(bytes, len, crate::binary::encode_data_update(&value)?);
+        Ok(())
+    })
+}
+
+/// Write the SHA-256 of a byte buffer in hex
+///
+/// # Safety
+///
+/// `bytes` must point to `len` readable bytes (or be null with `len` 0) and
+/// `hash` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn constellation_hash_bytes(
+    bytes: *const u8,
+    len: usize,
+    hash: *mut *mut c_char,
+) -> FfiStatus {
+    guard(|| {
+        let data = if len == 0 {
+            &[][..]
+        } else if bytes.is_null() {
+            return Err(invalid("bytes is null".to_string()));
+        } else {
+            std::slice::from_raw_parts(bytes, len)
+        };
+        let hash = out(hash, "hash")?;
+        write_string(hash, crate::hash::hash_bytes(data).value)
+    })
+}
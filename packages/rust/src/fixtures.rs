@@ -0,0 +1,183 @@
+//! Compatibility fixtures for a metagraph's Scala test suite
+//!
+//! A Rust client and the metagraph's Scala validator must agree on every
+//! byte that is signed. When a field is renamed or a number changes shape on
+//! one side only, signatures stop verifying in production. A [`FixtureSet`]
+//! records, for sample values of the application's own types, the
+//! canonical JSON, the bytes that are signed, their SHA-256 hash, and a
+//! signature, in the same format as the SDK's [golden vectors](crate::vectors).
+//! Check the file into the Scala test suite and assert, for each fixture,
+//! that the Scala serializer produces `utf8_bytes_hex` from `data` and that
+//! the signature verifies against `public_key_hex`.
+//!
+//! Signatures are deterministic (RFC 6979), so the same values and key
+//! always produce the same file. [`FixtureSet::check`] compares a freshly
+//! generated set with the committed file, so drift on the Rust side fails
+//! the Rust tests too.
+//!
+//! The `metakit-fixtures` binary (feature `fixtures-cli`) does the same for
+//! values given as JSON.
+//!
+//! # Example
+//!
+//! ```
+//! use constellation_sdk::data_update::DataUpdate;
+//! use constellation_sdk::fixtures::FixtureSet;
+//! use constellation_sdk::LocalSigner;
+//! use serde::Serialize;
+//!
+//! #[derive(Serialize)]
+//! struct CastVote {
+//!     poll: String,
+//!     option: u32,
+//! }
+//!
+//! impl DataUpdate for CastVote {}
+//!
+//! let signer = LocalSigner::new(&"11".repeat(32)).unwrap();
+//! let mut fixtures = FixtureSet::new(&signer);
+//! fixtures
+//!     .data_update("cast-vote", &CastVote { poll: "p1".into(), option: 2 })
+//!     .unwrap();
+//! let json = fixtures.to_json().unwrap();
+//! // std::fs::write("modules/shared_data/src/test/resources/fixtures.json", json)?;
+//! # fixtures.check(&json).unwrap();
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use crate::canonicalize::canonicalize;
+use crate::data_update::DataUpdate;
+use crate::hash::hash_bytes;
+use crate::signer::Signer;
+use crate::types::{Result, SdkError};
+use crate::vectors::{TestVector, VectorKind};
+
+/// A named sample value with the expected output of every signing step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fixture {
+    /// Name the Scala test reports the fixture by
+    pub name: String,
+    /// The value and its canonical JSON, bytes, hash, and signature
+    #[serde(flatten)]
+    pub vector: TestVector,
+}
+
+/// Fixtures signed by one key
+pub struct FixtureSet<'a, S: Signer + ?Sized> {
+    signer: &'a S,
+    fixtures: Vec<Fixture>,
+}
+
+impl<'a, S: Signer + ?Sized> FixtureSet<'a, S> {
+    /// An empty set signed by `signer`
+    ///
+    /// Use a fixed test key, so that regenerating the set reproduces the
+    /// committed file.
+    pub fn new(signer: &'a S) -> Self {
+        Self {
+            signer,
+            fixtures: Vec::new(),
+        }
+    }
+
+    /// Add a value signed as regular data, over its canonical JSON
+    pub fn data<T: Serialize>(&mut self, name: &str, value: &T) -> Result<&mut Self> {
+        let bytes = canonicalize(value)?.into_bytes();
+        self.push(name, VectorKind::TestData, value, bytes)
+    }
+
+    /// Add a data update, signed over its
+    /// [`serialize_update`](DataUpdate::serialize_update) bytes
+    pub fn data_update<U: DataUpdate>(&mut self, name: &str, update: &U) -> Result<&mut Self> {
+        let bytes = update.serialize_update()?;
+        self.push(name, VectorKind::TestDataUpdate, update, bytes)
+    }
+
+    fn push<T: Serialize>(
+        &mut self,
+        name: &str,
+        kind: VectorKind,
+        value: &T,
+        bytes: Vec<u8>,
+    ) -> Result<&mut Self> {
+        if self.fixtures.iter().any(|fixture| fixture.name == name) {
+            return Err(SdkError::InvalidPayload(format!(
+                "duplicate fixture name {name}"
+            )));
+        }
+        let hash = hash_bytes(&bytes);
+        self.fixtures.push(Fixture {
+            name: name.to_string(),
+            vector: TestVector {
+                source: "rust".to_string(),
+                kind,
+                data: serde_json::to_value(value)?,
+                canonical_json: canonicalize(value)?,
+                utf8_bytes_hex: hex::encode(&bytes),
+                signature_hex: self.signer.sign_hash(&hash.value)?,
+                sha256_hash_hex: hash.value,
+                public_key_hex: format!("04{}", self.signer.public_key_id()),
+            },
+        });
+        Ok(self)
+    }
+
+    /// The fixtures, in the order they were added
+    pub fn fixtures(&self) -> &[Fixture] {
+        &self.fixtures
+    }
+
+    /// The fixtures as a pretty-printed JSON array
+    pub fn to_json(&self) -> Result<String> {
+        let mut json = serde_json::to_string_pretty(&self.fixtures)?;
+        json.push('\n');
+        Ok(json)
+    }
+
+    /// Check that a committed fixture file still matches this set
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SerializationError` naming the first fixture that is
+    /// missing from `committed` or whose output differs from it, and the
+    /// step that differs
+    pub fn check(&self, committed: &str) -> Result<()> {
+        let committed: Vec<Fixture> = serde_json::from_str(committed)?;
+        for fixture in &self.fixtures {
+            let name = &fixture.name;
+            let Some(expected) = committed.iter().find(|c| &c.name == name) else {
+                return Err(SdkError::SerializationError(format!(
+                    "fixture {name} is not in the committed file"
+                )));
+            };
+            let (expected, actual) = (&expected.vector, &fixture.vector);
+            let steps = [
+                ("data", expected.data.to_string(), actual.data.to_string()),
+                (
+                    "canonical JSON",
+                    expected.canonical_json.clone(),
+                    actual.canonical_json.clone(),
+                ),
+                (
+                    "bytes",
+                    expected.utf8_bytes_hex.clone(),
+                    actual.utf8_bytes_hex.clone(),
+                ),
+                (
+                    "signature",
+                    expected.signature_hex.clone(),
+                    actual.signature_hex.clone(),
+                ),
+            ];
+            for (step, expected, actual) in steps {
+                if !expected.eq_ignore_ascii_case(&actual) {
+                    return Err(SdkError::SerializationError(format!(
+                        "fixture {name}: {step} drifted, expected {expected}, got {actual}"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
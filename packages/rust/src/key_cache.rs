@@ -0,0 +1,190 @@
+//! Parsed public key cache for verification
+//!
+//! Every verification decodes the proof's hex public key id and checks that
+//! the point lies on the curve. Indexers verify thousands of proofs from a
+//! small validator set, so they parse the same handful of keys over and over.
+//! [`enable_public_key_cache`] keeps the most recently used keys parsed, and
+//! [`verify`](crate::verify::verify), [`verify_hash`](crate::verify::verify_hash),
+//! and the transaction verifiers look keys up there first.
+//!
+//! The cache is process-wide, since the verification functions are free
+//! functions, and off until enabled. Only keys that parsed successfully are
+//! stored.
+//!
+//! # Example
+//!
+//! ```rust
+//! use constellation_sdk::key_cache;
+//!
+//! // Room for a validator set plus some churn
+//! key_cache::enable_public_key_cache(256);
+//!
+//! // ... verify proofs ...
+//!
+//! if let Some(stats) = key_cache::public_key_cache_stats() {
+//!     println!("{} hits, {} misses", stats.hits, stats.misses);
+//! }
+//! # key_cache::disable_public_key_cache();
+//! ```
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use secp256k1::PublicKey;
+
+use crate::types::Result;
+
+/// Counters for the public key cache
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Lookups answered from the cache
+    pub hits: u64,
+    /// Lookups that had to parse the key
+    pub misses: u64,
+    /// Keys currently cached
+    pub len: usize,
+    /// Most keys kept at once
+    pub capacity: usize,
+}
+
+/// Least recently used map from public key id to parsed key
+///
+/// Eviction scans for the oldest entry, which is cheap at the sizes a
+/// validator set calls for and keeps hits to one hash lookup.
+struct Lru {
+    entries: HashMap<String, (PublicKey, u64)>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl Lru {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::with_capacity(capacity),
+            clock: 0,
+            stats: CacheStats {
+                capacity,
+                ..CacheStats::default()
+            },
+        }
+    }
+
+    fn get(&mut self, id: &str) -> Option<PublicKey> {
+        self.clock += 1;
+        let clock = self.clock;
+        match self.entries.get_mut(id) {
+            Some((key, last_used)) => {
+                *last_used = clock;
+                self.stats.hits += 1;
+                Some(*key)
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    fn insert(&mut self, id: &str, key: PublicKey) {
+        if self.entries.contains_key(id) {
+            // Another thread parsed it first
+            return;
+        }
+        if self.entries.len() >= self.stats.capacity {
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, last_used))| *last_used)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(id.to_string(), (key, self.clock));
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static CACHE: Mutex<Option<Lru>> = Mutex::new(None);
+
+/// Cache up to `capacity` parsed public keys, replacing any existing cache
+///
+/// A capacity of zero disables the cache.
+pub fn enable_public_key_cache(capacity: usize) {
+    if capacity == 0 {
+        disable_public_key_cache();
+        return;
+    }
+    *CACHE.lock().unwrap_or_else(|e| e.into_inner()) = Some(Lru::new(capacity));
+    ENABLED.store(true, Ordering::Release);
+}
+
+/// Drop the cache and parse every key again
+pub fn disable_public_key_cache() {
+    ENABLED.store(false, Ordering::Release);
+    *CACHE.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Counters for the cache, or `None` while it is disabled
+pub fn public_key_cache_stats() -> Option<CacheStats> {
+    CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_ref()
+        .map(|lru| CacheStats {
+            len: lru.entries.len(),
+            ..lru.stats
+        })
+}
+
+/// Look `id` up in the cache, parsing and caching it on a miss
+///
+/// Calls `parse` directly while the cache is disabled.
+pub(crate) fn get_or_parse(
+    id: &str,
+    parse: impl FnOnce(&str) -> Result<PublicKey>,
+) -> Result<PublicKey> {
+    if !ENABLED.load(Ordering::Acquire) {
+        return parse(id);
+    }
+    if let Some(key) = CACHE
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .as_mut()
+        .and_then(|lru| lru.get(id))
+    {
+        return Ok(key);
+    }
+    // Parse outside the lock so other verifiers are not held up
+    let key = parse(id)?;
+    if let Some(lru) = CACHE.lock().unwrap_or_else(|e| e.into_inner()).as_mut() {
+        lru.insert(id, key);
+    }
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::parse_public_key;
+    use crate::wallet::generate_key_pair;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let ids: Vec<_> = (0..3).map(|_| generate_key_pair().public_key).collect();
+        let keys: Vec<_> = ids.iter().map(|id| parse_public_key(id).unwrap()).collect();
+        let mut lru = Lru::new(2);
+
+        lru.insert(&ids[0], keys[0]);
+        lru.insert(&ids[1], keys[1]);
+        assert_eq!(lru.get(&ids[0]), Some(keys[0]));
+        lru.insert(&ids[2], keys[2]);
+
+        assert_eq!(lru.entries.len(), 2);
+        assert_eq!(lru.get(&ids[1]), None);
+        assert_eq!(lru.get(&ids[0]), Some(keys[0]));
+        assert_eq!(lru.get(&ids[2]), Some(keys[2]));
+        assert_eq!((lru.stats.hits, lru.stats.misses), (3, 1));
+    }
+}
@@ -9,6 +9,8 @@
 //! - **Cross-language compatibility** — interoperable with TypeScript, Python, Go implementations
 //! - **Multi-signature support** — create and verify objects signed by multiple parties
 //! - **Optional secp256r1 (P-256)** — TPM-native curve, behind the `r1` cargo feature
+//! - **Optional `tracing` spans** — network calls and sign/verify paths, behind the `tracing` feature
+//! - **`no_std` crypto core** — hashing, canonicalization, signing, and verification build with only `alloc`
 //!
 //! # Quick Start
 //!
@@ -43,18 +45,102 @@
 //! let kp = generate_key_pair();
 //! let sig = sign_hash(&"00".repeat(32), &kp.private_key)?;
 //! ```
+//!
+//! # `no_std`
+//!
+//! With `default-features = false` the crate is `no_std + alloc` and contains
+//! [`core`](crate::core), [`types`], and [`wallet`] (without key generation),
+//! enough to sign and verify on firmware or in a secure enclave. Everything
+//! else needs the default `std` feature.
+//!
+//! ```toml
+//! [dependencies]
+//! constellation-metagraph-sdk = { version = "0.2", default-features = false }
+//! ```
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+/// Record a field on the current tracing span (no-op without the `tracing` feature)
+macro_rules! record_span {
+    ($field:literal, $value:expr) => {
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record($field, $value);
+    };
+}
+
+/// Pass an event to the installed [`log_hook`] (no-op without the `std` feature)
+macro_rules! log_event {
+    ($level:ident, $message:literal $(, $field:literal = $value:expr)* $(,)?) => {{
+        #[cfg(feature = "std")]
+        if $crate::log_hook::is_enabled() {
+            $crate::log_hook::emit(
+                $crate::log_hook::LogLevel::$level,
+                module_path!(),
+                $message,
+                &[$(($field, &$value as &dyn core::fmt::Display)),*],
+            );
+        }
+    }};
+}
+
+/// Pass a signature to the installed [`signing_audit`] hook (no-op without
+/// the `std` feature)
+///
+/// `$signer` evaluates to the signer's public key id and is only evaluated
+/// while a hook is installed.
+macro_rules! audit_signing {
+    ($operation:expr, $hash:expr, $signer:expr) => {{
+        #[cfg(feature = "std")]
+        $crate::signing_audit::record($operation, $hash, || $signer);
+        #[cfg(not(feature = "std"))]
+        let _ = ($operation, $hash);
+    }};
+}
+
+pub mod core;
+pub mod types;
+pub mod vectors;
+pub mod wallet;
+
+// The crypto modules keep their original paths.
+pub use self::core::{binary, canonicalize, hash, sign, verify};
 
-pub mod binary;
-pub mod canonicalize;
+#[cfg(feature = "std")]
 pub mod codec;
+#[cfg(feature = "std")]
 pub mod currency_transaction;
+#[cfg(feature = "std")]
 pub mod currency_types;
-pub mod hash;
-pub mod sign;
+#[cfg(feature = "std")]
+pub mod data_update;
+#[cfg(feature = "std")]
+pub mod delegation;
+#[cfg(feature = "std")]
+pub mod fixtures;
+#[cfg(feature = "std")]
+pub mod key_cache;
+#[cfg(feature = "std")]
+pub mod log_hook;
+#[cfg(feature = "std")]
+pub mod merkle;
+#[cfg(feature = "std")]
+pub mod multisig;
+#[cfg(feature = "std")]
+pub mod offline;
+#[cfg(feature = "std")]
+pub mod preflight;
+#[cfg(feature = "std")]
+pub mod psct;
+#[cfg(feature = "std")]
 pub mod signed_object;
-pub mod types;
-pub mod verify;
-pub mod wallet;
+#[cfg(feature = "std")]
+pub mod signer;
+#[cfg(feature = "std")]
+pub mod signing_audit;
+#[cfg(feature = "std")]
+pub mod timestamped;
 
 #[cfg(feature = "r1")]
 pub mod r1;
@@ -62,33 +148,85 @@ pub mod r1;
 #[cfg(feature = "network")]
 pub mod network;
 
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+
+#[cfg(feature = "napi")]
+pub mod node;
+
+#[cfg(feature = "ffi")]
+pub mod ffi;
+
+#[cfg(feature = "python")]
+pub mod python;
+
+#[cfg(all(feature = "daemon", not(target_arch = "wasm32")))]
+pub mod daemon;
+
+#[cfg(feature = "memsec")]
+pub mod memsec;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
+
 // ─── Crate-root re-exports ──────────────────────────────────────────────
 
 // Common types
 pub use types::{
-    Hash, KeyPair, Result, SdkError, SignatureProof, Signed, SigningOptions, SigningScheme,
-    VerificationResult, ALGORITHM, ALGORITHM_R1, CONSTELLATION_PREFIX,
+    ErrorCategory, Hash, KeyPair, Result, SdkError, SignatureProof, Signed, SigningOptions,
+    SigningScheme, VerificationResult, ALGORITHM, ALGORITHM_R1, CONSTELLATION_PREFIX,
+    DOMAIN_PREFIX,
 };
 
 // secp256k1 (K1) — always present
-pub use binary::{encode_data_update, to_bytes};
-pub use canonicalize::{canonicalize, canonicalize_bytes};
-pub use codec::decode_data_update;
-pub use hash::{compute_digest, hash_bytes, hash_data};
-pub use sign::{sign, sign_data_update, sign_hash};
-pub use signed_object::{add_signature, batch_sign, create_signed_object};
-pub use verify::{verify, verify_hash, verify_signature};
+pub use binary::{encode_data_update, to_bytes, to_bytes_into};
+pub use canonicalize::{canonicalize, canonicalize_bytes, canonicalize_into};
+pub use hash::{compute_digest, hash_bytes, hash_data, hash_with_domain, DigestWriter};
+pub use sign::{sign, sign_data_update, sign_hash, sign_with_domain};
+pub use verify::{
+    verify, verify_hash, verify_hash_with_policy, verify_signature, verify_signature_with_domain,
+    verify_signature_with_policy, verify_with_domain, verify_with_policy, SignaturePolicy,
+};
+#[allow(deprecated)]
+pub use wallet::get_address;
 pub use wallet::{
-    generate_key_pair, get_address, get_public_key_hex, get_public_key_id, is_valid_private_key,
-    is_valid_public_key, key_pair_from_private_key,
+    get_public_key_hex, get_public_key_id, is_valid_private_key, is_valid_public_key,
+    key_pair_from_private_key, try_get_address,
+};
+
+// secp256k1 (K1) — `std` only
+#[cfg(feature = "std")]
+pub use codec::decode_data_update;
+#[cfg(feature = "std")]
+pub use multisig::SigningEnvelope;
+#[cfg(feature = "std")]
+pub use offline::{import_signature, import_signatures, PayloadKind, UnsignedPayload};
+#[cfg(feature = "std")]
+pub use preflight::{validate_data_update, ValidationIssue, ValidationReport, ValidationRules};
+#[cfg(feature = "std")]
+pub use psct::{PartiallySignedTransaction, SignerHint};
+#[cfg(feature = "std")]
+pub use signed_object::{
+    add_signature, batch_sign, batch_sign_owned, create_signed_object, create_signed_object_owned,
 };
+#[cfg(feature = "std")]
+pub use signer::{sign_object, sign_with, LocalSigner, Signer};
+#[cfg(feature = "std")]
+pub use wallet::{generate_key_pair, generate_key_pair_with_rng};
 
 // Currency transactions (K1-only API).
+#[cfg(feature = "std")]
 pub use currency_transaction::{
-    create_currency_transaction, create_currency_transaction_batch, encode_currency_transaction,
+    create_currency_transaction, create_currency_transaction_batch,
+    create_currency_transaction_with_signer, encode_currency_transaction,
     get_transaction_reference, hash_currency_transaction, is_valid_dag_address,
     sign_currency_transaction, token_to_units, units_to_token, verify_currency_transaction,
+    verify_currency_transaction_with_policy,
 };
+#[cfg(feature = "std")]
 pub use currency_types::{
     CurrencyTransaction, CurrencyTransactionValue, TransactionReference, TransferParams,
     TOKEN_DECIMALS,
@@ -0,0 +1,164 @@
+//! Log callback for embedders with their own logging stack
+//!
+//! The `tracing` feature suits applications already built on `tracing`.
+//! Everyone else can install a [`LogHook`] with [`set_log_hook`] and receive
+//! the same milestones as structured [`LogEvent`]s: signatures created and
+//! verified, transactions built, and HTTP requests sent, retried, or failed.
+//!
+//! The hook is process-wide, since the signing functions are free functions.
+//! While none is installed, emitting an event costs one atomic load and
+//! nothing is formatted.
+//!
+//! # Example
+//!
+//! ```rust
+//! use constellation_sdk::log_hook::{self, LogEvent, LogLevel};
+//!
+//! log_hook::set_log_hook(|event: &LogEvent<'_>| {
+//!     if event.level >= LogLevel::Info {
+//!         eprintln!("[{}] {} {}", event.target, event.message, event);
+//!     }
+//! });
+//! # log_hook::clear_log_hook();
+//! ```
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// Severity of a [`LogEvent`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    /// Per-signature and per-hash detail
+    Trace,
+    /// Routine operations such as a completed request
+    Debug,
+    /// Notable operations such as a submitted transaction
+    Info,
+    /// Failures the SDK recovers from, such as a retried request
+    Warn,
+    /// Failures returned to the caller
+    Error,
+}
+
+impl fmt::Display for LogLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LogLevel::Trace => "TRACE",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Info => "INFO",
+            LogLevel::Warn => "WARN",
+            LogLevel::Error => "ERROR",
+        })
+    }
+}
+
+/// A structured event emitted by the SDK
+///
+/// Field values are formatted only when read, so a hook that filters on
+/// `level` or `target` first pays nothing for events it drops. `Display`
+/// prints the fields as `name=value` pairs.
+pub struct LogEvent<'a> {
+    /// Severity
+    pub level: LogLevel,
+    /// Module that emitted the event, e.g. `constellation_sdk::network::client`
+    pub target: &'static str,
+    /// Fixed description of what happened, e.g. `"request failed"`
+    pub message: &'static str,
+    /// Named values describing this occurrence
+    pub fields: &'a [(&'static str, &'a dyn fmt::Display)],
+}
+
+impl LogEvent<'_> {
+    /// Formatted value of the field called `name`
+    pub fn field(&self, name: &str) -> Option<String> {
+        self.fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.to_string())
+    }
+}
+
+impl fmt::Display for LogEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (name, value)) in self.fields.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}={}", name, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for LogEvent<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LogEvent")
+            .field("level", &self.level)
+            .field("target", &self.target)
+            .field("message", &self.message)
+            .field("fields", &self.to_string())
+            .finish()
+    }
+}
+
+/// Receiver of SDK log events
+///
+/// Called synchronously on the thread that emitted the event, so it should
+/// hand events off rather than block. Implemented for closures taking a
+/// `&LogEvent`.
+pub trait LogHook: Send + Sync {
+    /// Handle one event
+    fn log(&self, event: &LogEvent<'_>);
+}
+
+impl<F> LogHook for F
+where
+    F: Fn(&LogEvent<'_>) + Send + Sync,
+{
+    fn log(&self, event: &LogEvent<'_>) {
+        self(event)
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static HOOK: RwLock<Option<Arc<dyn LogHook>>> = RwLock::new(None);
+
+/// Install `hook`, replacing any previous one
+pub fn set_log_hook(hook: impl LogHook + 'static) {
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(hook));
+    ENABLED.store(true, Ordering::Release);
+}
+
+/// Remove the installed hook, if any
+pub fn clear_log_hook() {
+    ENABLED.store(false, Ordering::Release);
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Whether a hook is installed
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+/// Pass an event to the installed hook
+///
+/// Used through the crate's `log_event!` macro, which skips building the
+/// event when no hook is installed.
+pub(crate) fn emit(
+    level: LogLevel,
+    target: &'static str,
+    message: &'static str,
+    fields: &[(&'static str, &dyn fmt::Display)],
+) {
+    // Clone the hook out so it can replace itself without deadlocking
+    let hook = HOOK.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(hook) = hook {
+        hook.log(&LogEvent {
+            level,
+            target,
+            message,
+            fields,
+        });
+    }
+}
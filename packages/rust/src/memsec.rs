@@ -0,0 +1,294 @@
+//! Hot keys kept encrypted in locked memory
+//!
+//! A [`LocalSigner`](crate::signer::LocalSigner) holds its private key as a
+//! hex string for as long as it lives, so a core dump, a swapped-out page, or
+//! a stray read of the heap can leak it. A [`ProtectedSigner`] is meant for
+//! long-running services that keep a key unlocked: it keeps the key
+//! encrypted and decrypts it only for the duration of each signature.
+//!
+//! The key is encrypted with AES-256-CTR under the SHA-256 of a 16 KiB random
+//! pre-key, so recovering it from memory takes every byte of the pre-key, not
+//! just 32. The pre-key and the ciphertext live in their own pages, which are
+//! locked so they are never swapped (`mlock`), excluded from core dumps on
+//! Linux (`MADV_DONTDUMP`), and zeroed when the signer is dropped. The
+//! decrypted key is zeroed as soon as the signature is made.
+//!
+//! Locking is best effort: it is not available on every platform and fails
+//! once the process exceeds `RLIMIT_MEMLOCK`. The key is still encrypted
+//! either way; [`ProtectedSigner::is_locked`] reports whether the pages were
+//! locked.
+//!
+//! # Example
+//!
+//! ```
+//! use constellation_sdk::memsec::ProtectedSigner;
+//! use constellation_sdk::signer::{sign_object, Signer};
+//! use constellation_sdk::verify::verify;
+//! use serde_json::json;
+//!
+//! let signer = ProtectedSigner::generate();
+//! let signed = sign_object(&signer, &json!({"id": "test"}), false).unwrap();
+//! assert!(verify(&signed, false).is_valid);
+//! ```
+
+use std::alloc::{self, Layout};
+use std::fmt;
+use std::ptr::NonNull;
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use secp256k1::SecretKey;
+use sha2::{Digest, Sha256};
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::core::sign::sign_hash_with_secret;
+use crate::signer::Signer;
+use crate::types::{Result, SdkError};
+use crate::wallet::{normalize_public_key_to_id, try_get_address};
+
+type Aes256Ctr = ctr::Ctr128BE<aes::Aes256>;
+
+const PREKEY_LEN: usize = 16 * 1024;
+const IV_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+/// A [`Signer`] whose private key stays encrypted in locked memory except
+/// while it signs
+///
+/// Signatures are identical to those of a
+/// [`LocalSigner`](crate::signer::LocalSigner) with the same key, and are
+/// reported to the [signing audit hook](crate::signing_audit) as
+/// `protected_signer`.
+pub struct ProtectedSigner {
+    public_key_id: String,
+    address: String,
+    /// Pre-key, then IV, then the encrypted private key
+    sealed: LockedPages,
+}
+
+impl ProtectedSigner {
+    /// Protect a private key given in hex
+    ///
+    /// The caller's copy of the key is not touched; zero it once this
+    /// returns.
+    pub fn new(private_key: &str) -> Result<Self> {
+        let key = Zeroizing::new(hex::decode(private_key)?);
+        Self::from_bytes(&key)
+    }
+
+    /// Protect a freshly generated key
+    pub fn generate() -> Self {
+        let key = Zeroizing::new(SecretKey::new(&mut OsRng).secret_bytes());
+        // A key straight from `SecretKey` is always valid
+        Self::from_bytes(&*key).expect("generated key is valid")
+    }
+
+    /// Protect a 32-byte private key
+    pub(crate) fn from_bytes(key: &[u8]) -> Result<Self> {
+        let mut secret_key = SecretKey::from_slice(key)?;
+        let public_key = secp256k1::PublicKey::from_secret_key(secp256k1::SECP256K1, &secret_key);
+        secret_key.non_secure_erase();
+        let public_key_hex = hex::encode(public_key.serialize_uncompressed());
+
+        let mut sealed = LockedPages::new(PREKEY_LEN + IV_LEN + KEY_LEN);
+        let buffer = sealed.as_mut_slice();
+        OsRng.fill_bytes(&mut buffer[..PREKEY_LEN + IV_LEN]);
+        buffer[PREKEY_LEN + IV_LEN..].copy_from_slice(key);
+        apply_keystream(buffer);
+
+        Ok(Self {
+            public_key_id: normalize_public_key_to_id(&public_key_hex),
+            address: try_get_address(&public_key_hex)?,
+            sealed,
+        })
+    }
+
+    /// Whether the operating system locked the key's pages in memory
+    pub fn is_locked(&self) -> bool {
+        self.sealed.locked
+    }
+}
+
+impl Signer for ProtectedSigner {
+    fn public_key_id(&self) -> String {
+        self.public_key_id.clone()
+    }
+
+    fn sign_hash(&self, hash_hex: &str) -> Result<String> {
+        let mut key = Zeroizing::new([0u8; KEY_LEN]);
+        let sealed = self.sealed.as_slice();
+        key.copy_from_slice(&sealed[PREKEY_LEN + IV_LEN..]);
+        let mut cipher = cipher(
+            &sealed[..PREKEY_LEN],
+            &sealed[PREKEY_LEN..PREKEY_LEN + IV_LEN],
+        );
+        cipher.apply_keystream(&mut *key);
+
+        let mut secret_key = SecretKey::from_slice(&*key)
+            .map_err(|_| SdkError::CryptoError("protected key is corrupted".to_string()))?;
+        let signature = sign_hash_with_secret(hash_hex, &secret_key, "protected_signer");
+        secret_key.non_secure_erase();
+        signature
+    }
+
+    fn address(&self) -> String {
+        self.address.clone()
+    }
+}
+
+impl fmt::Debug for ProtectedSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProtectedSigner")
+            .field("address", &self.address)
+            .field("locked", &self.sealed.locked)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Encrypt or decrypt the key at the end of a sealed buffer in place
+fn apply_keystream(buffer: &mut [u8]) {
+    let (header, key) = buffer.split_at_mut(PREKEY_LEN + IV_LEN);
+    cipher(&header[..PREKEY_LEN], &header[PREKEY_LEN..]).apply_keystream(key);
+}
+
+/// AES-256-CTR keyed with the SHA-256 of `prekey`
+///
+/// The cipher zeroes its round keys when dropped.
+fn cipher(prekey: &[u8], iv: &[u8]) -> Aes256Ctr {
+    let mut aes_key = Sha256::digest(prekey);
+    let cipher = Aes256Ctr::new(&aes_key, iv.into());
+    aes_key[..].zeroize();
+    cipher
+}
+
+/// Page-aligned heap memory, locked while it lives and zeroed on drop
+///
+/// Whole pages are allocated so that unlocking never unlocks memory that
+/// belongs to another allocation.
+struct LockedPages {
+    ptr: NonNull<u8>,
+    len: usize,
+    layout: Layout,
+    locked: bool,
+}
+
+// The pages are owned and only mutated through `&mut self`
+unsafe impl Send for LockedPages {}
+unsafe impl Sync for LockedPages {}
+
+impl LockedPages {
+    fn new(len: usize) -> Self {
+        let page = page_size();
+        let size = (len + page - 1) / page * page;
+        let layout = Layout::from_size_align(size, page).expect("page-aligned layout");
+        // SAFETY: `layout` has a non-zero size
+        let ptr = unsafe { alloc::alloc_zeroed(layout) };
+        let ptr = NonNull::new(ptr).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        // SAFETY: `ptr` points to `layout.size()` bytes owned by this value
+        let locked = unsafe { lock(ptr.as_ptr(), layout.size()) };
+        Self {
+            ptr,
+            len,
+            layout,
+            locked,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        // SAFETY: `ptr` is valid for `len` initialized bytes
+        unsafe { std::slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        // SAFETY: `ptr` is valid for `len` initialized bytes, borrowed uniquely
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for LockedPages {
+    fn drop(&mut self) {
+        // SAFETY: `ptr` is valid for `layout.size()` bytes
+        let pages =
+            unsafe { std::slice::from_raw_parts_mut(self.ptr.as_ptr(), self.layout.size()) };
+        pages.zeroize();
+        // SAFETY: the pages were allocated with `layout` and are not used again
+        unsafe {
+            if self.locked {
+                unlock(self.ptr.as_ptr(), self.layout.size());
+            }
+            alloc::dealloc(self.ptr.as_ptr(), self.layout);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn page_size() -> usize {
+    // SAFETY: `sysconf` has no preconditions
+    match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+        size if size > 0 => size as usize,
+        _ => 4096,
+    }
+}
+
+#[cfg(not(unix))]
+fn page_size() -> usize {
+    4096
+}
+
+/// Lock `len` bytes at `ptr` into memory and keep them out of core dumps
+#[cfg(unix)]
+unsafe fn lock(ptr: *mut u8, len: usize) -> bool {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    libc::madvise(ptr.cast(), len, libc::MADV_DONTDUMP);
+    libc::mlock(ptr.cast(), len) == 0
+}
+
+#[cfg(not(unix))]
+unsafe fn lock(_ptr: *mut u8, _len: usize) -> bool {
+    false
+}
+
+#[cfg(unix)]
+unsafe fn unlock(ptr: *mut u8, len: usize) {
+    libc::munlock(ptr.cast(), len);
+}
+
+#[cfg(not(unix))]
+unsafe fn unlock(_ptr: *mut u8, _len: usize) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::LocalSigner;
+    use crate::wallet::generate_key_pair;
+
+    #[test]
+    fn keeps_the_key_encrypted_and_signs_like_a_local_signer() {
+        let key_pair = generate_key_pair();
+        let protected = ProtectedSigner::new(&key_pair.private_key).unwrap();
+        let local = LocalSigner::new(&key_pair.private_key).unwrap();
+
+        let plaintext = hex::decode(&key_pair.private_key).unwrap();
+        assert_ne!(
+            &protected.sealed.as_slice()[PREKEY_LEN + IV_LEN..],
+            &plaintext[..]
+        );
+        assert_eq!(protected.address(), key_pair.address);
+        assert_eq!(protected.public_key_id(), local.public_key_id());
+
+        let hash = "a".repeat(64);
+        assert_eq!(
+            protected.sign_hash(&hash).unwrap(),
+            local.sign_hash(&hash).unwrap()
+        );
+    }
+
+    #[test]
+    fn allocates_whole_pages() {
+        let pages = LockedPages::new(10);
+        assert_eq!(pages.as_slice().len(), 10);
+        assert_eq!(pages.ptr.as_ptr() as usize % page_size(), 0);
+        assert_eq!(pages.layout.size() % page_size(), 0);
+    }
+}
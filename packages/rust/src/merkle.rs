@@ -0,0 +1,191 @@
+//! Merkle trees over update hashes
+//!
+//! An application that accepts many updates can commit to all of them with
+//! one hash: it builds a [`MerkleTree`] over the updates' hashes, puts the
+//! [`root`](MerkleTree::root) on chain, and serves each update's
+//! [`MerkleProof`] off chain. Anyone holding an update, its proof, and the
+//! root can check the update was included without seeing the others.
+//!
+//! The tree follows RFC 6962 (Certificate Transparency): leaves and interior
+//! nodes are hashed with different prefixes, so a leaf can never be passed
+//! off as a node, and an odd node at the end of a level is carried up
+//! unpaired rather than duplicated.
+//!
+//! ```text
+//! leaf = SHA-256(0x00 || hash bytes)
+//! node = SHA-256(0x01 || left || right)
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use constellation_sdk::hash::hash_bytes;
+//! use constellation_sdk::merkle::MerkleTree;
+//!
+//! let hashes: Vec<_> = (0u8..5).map(|i| hash_bytes(&[i])).collect();
+//! let tree = MerkleTree::new(&hashes);
+//!
+//! let proof = tree.proof(3).unwrap();
+//! assert!(proof.verify(&hashes[3], &tree.root()));
+//! assert!(!proof.verify(&hashes[2], &tree.root()));
+//! ```
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::data_update::{update_hash, DataUpdate};
+use crate::types::{Hash, Result};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// A Merkle tree over a list of hashes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MerkleTree {
+    /// Each level of the tree, from the leaves up to the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTree {
+    /// Build a tree whose leaves are `hashes`, in order
+    pub fn new(hashes: &[Hash]) -> Self {
+        let leaves = hashes.iter().map(|hash| leaf(&hash.bytes)).collect();
+        let mut levels: Vec<Vec<[u8; 32]>> = vec![leaves];
+        while levels[levels.len() - 1].len() > 1 {
+            let next = levels[levels.len() - 1]
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => node(left, right),
+                    [single] => *single,
+                    _ => unreachable!("chunks of two"),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self { levels }
+    }
+
+    /// Build a tree over the [`update_hash`] of each update
+    pub fn from_updates<U: DataUpdate>(updates: &[U]) -> Result<Self> {
+        let hashes = updates
+            .iter()
+            .map(update_hash)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(&hashes))
+    }
+
+    /// Number of leaves
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Whether the tree has no leaves
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    /// The root hash
+    ///
+    /// The root of an empty tree is the SHA-256 of no bytes.
+    pub fn root(&self) -> Hash {
+        match self.levels[self.levels.len() - 1].first() {
+            Some(root) => to_hash(root),
+            None => to_hash(&Sha256::digest([]).into()),
+        }
+    }
+
+    /// Proof that the leaf at `index` is in the tree, or `None` if there is
+    /// no such leaf
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.len() {
+            return None;
+        }
+        let mut siblings = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            if let Some(sibling) = level.get(position ^ 1) {
+                siblings.push(hex::encode(sibling));
+            }
+            position /= 2;
+        }
+        Some(MerkleProof {
+            index,
+            leaf_count: self.len(),
+            siblings,
+        })
+    }
+}
+
+/// Proof that a hash is a leaf of a [`MerkleTree`] with a given root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MerkleProof {
+    /// Position of the leaf
+    pub index: usize,
+    /// Number of leaves in the tree
+    pub leaf_count: usize,
+    /// Sibling node hashes in hex, from the leaf's level up
+    pub siblings: Vec<String>,
+}
+
+impl MerkleProof {
+    /// Whether `hash` is the leaf at [`index`](Self::index) of a tree with
+    /// root `root`
+    pub fn verify(&self, hash: &Hash, root: &Hash) -> bool {
+        self.compute_root(hash)
+            .is_some_and(|computed| computed.ct_eq(root))
+    }
+
+    /// The root of the tree this proof describes, if `hash` is its leaf
+    ///
+    /// Returns `None` if the proof is malformed for its leaf count.
+    pub fn compute_root(&self, hash: &Hash) -> Option<Hash> {
+        if self.index >= self.leaf_count {
+            return None;
+        }
+        // RFC 9162, section 2.1.3.2
+        let mut position = self.index;
+        let mut last = self.leaf_count - 1;
+        let mut current = leaf(&hash.bytes);
+        for sibling in &self.siblings {
+            if last == 0 {
+                return None;
+            }
+            let sibling: [u8; 32] = hex::decode(sibling).ok()?.try_into().ok()?;
+            if position % 2 == 1 || position == last {
+                current = node(&sibling, &current);
+                while position % 2 == 0 && position != 0 {
+                    position /= 2;
+                    last /= 2;
+                }
+            } else {
+                current = node(&current, &sibling);
+            }
+            position /= 2;
+            last /= 2;
+        }
+        (last == 0).then(|| to_hash(&current))
+    }
+}
+
+fn leaf(bytes: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(bytes);
+    hasher.finalize().into()
+}
+
+fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+fn to_hash(bytes: &[u8; 32]) -> Hash {
+    Hash {
+        value: hex::encode(bytes),
+        bytes: bytes.to_vec(),
+    }
+}
@@ -0,0 +1,238 @@
+//! Kotlin and Swift bindings through uniffi
+//!
+//! With the `uniffi` feature the wallet functions, JSON signing and
+//! verification, and the high-level [`Account`] are exported through
+//! [uniffi](https://mozilla.github.io/uniffi-rs/), so Android and iOS wallets
+//! can link this crate instead of re-implementing the signing protocol.
+//! Signed objects cross the boundary as JSON strings; [`KeyPair`],
+//! [`TransferParams`], [`Balance`], and [`Receipt`] become records, and every
+//! failure is raised as a [`MobileError`].
+//!
+//! Build the library, then generate the sources with the bundled
+//! `uniffi-bindgen` binary:
+//!
+//! ```bash
+//! cargo rustc --lib --release --features uniffi --crate-type cdylib
+//! cargo run --features uniffi-cli --bin uniffi-bindgen -- generate \
+//!     --library target/release/libconstellation_sdk.so --language kotlin --out-dir out
+//! ```
+//!
+//! # Example
+//!
+//! ```kotlin
+//! val account = MobileAccount(privateKey, Endpoints(network = "mainnet", l0Url = null,
+//!     currencyL0Url = null, l1Url = null, dataL1Url = null, blockExplorerUrl = null))
+//! val balance = account.balance()
+//! val receipt = account.send(TransferParams(destination = "DAG...", amount = 1.5, fee = 0.0))
+//! ```
+
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::currency_transaction::is_valid_dag_address as dag_address_is_valid;
+use crate::currency_types::TransferParams;
+use crate::network::{Account, Balance, NetworkConfig, NetworkError, Receipt};
+use crate::types::{KeyPair, SdkError, Signed};
+
+/// Error raised to Kotlin and Swift, carrying the SDK's message
+#[derive(Debug, thiserror::Error, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum MobileError {
+    /// Key, signing, or serialization failure
+    #[error(transparent)]
+    Sdk(#[from] SdkError),
+    /// Request or configuration failure
+    #[error(transparent)]
+    Network(#[from] NetworkError),
+}
+
+/// Result of the exported functions
+pub type MobileResult<T> = std::result::Result<T, MobileError>;
+
+impl From<serde_json::Error> for MobileError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Sdk(e.into())
+    }
+}
+
+/// Generate a random key pair
+#[uniffi::export]
+pub fn generate_key_pair() -> KeyPair {
+    crate::wallet::generate_key_pair()
+}
+
+/// Derive the key pair of a private key in hex
+#[uniffi::export]
+pub fn key_pair_from_private_key(private_key: String) -> MobileResult<KeyPair> {
+    Ok(crate::wallet::key_pair_from_private_key(&private_key)?)
+}
+
+/// DAG address of a public key in hex
+#[uniffi::export]
+pub fn get_address(public_key: String) -> MobileResult<String> {
+    Ok(crate::wallet::try_get_address(&public_key)?)
+}
+
+/// Whether a string is a well-formed private key in hex
+#[uniffi::export]
+pub fn is_valid_private_key(private_key: String) -> bool {
+    crate::wallet::is_valid_private_key(&private_key)
+}
+
+/// Whether a string is a well-formed DAG address
+#[uniffi::export]
+pub fn is_valid_dag_address(address: String) -> bool {
+    dag_address_is_valid(&address)
+}
+
+/// Sign a JSON value and return the signed object as JSON
+#[uniffi::export]
+pub fn create_signed_object(
+    value_json: String,
+    private_key: String,
+    is_data_update: bool,
+) -> MobileResult<String> {
+    let value: Value = serde_json::from_str(&value_json)?;
+    let signed = crate::signed_object::create_signed_object(&value, &private_key, is_data_update)?;
+    Ok(serde_json::to_string(&signed)?)
+}
+
+/// Add a signature to a signed object given as JSON
+#[uniffi::export]
+pub fn add_signature(
+    signed_json: String,
+    private_key: String,
+    is_data_update: bool,
+) -> MobileResult<String> {
+    let signed: Signed<Value> = serde_json::from_str(&signed_json)?;
+    let signed = crate::signed_object::add_signature(signed, &private_key, is_data_update)?;
+    Ok(serde_json::to_string(&signed)?)
+}
+
+/// Verify every proof of a signed object given as JSON
+#[uniffi::export]
+pub fn verify(signed_json: String, is_data_update: bool) -> MobileResult<bool> {
+    let signed: Signed<Value> = serde_json::from_str(&signed_json)?;
+    Ok(crate::verify::verify(&signed, is_data_update).is_valid)
+}
+
+/// Sign a SHA-256 hash in hex, returning the DER signature in hex
+#[uniffi::export]
+pub fn sign_hash(hash_hex: String, private_key: String) -> MobileResult<String> {
+    Ok(crate::sign::sign_hash(&hash_hex, &private_key)?)
+}
+
+/// Verify a signature over a SHA-256 hash in hex
+#[uniffi::export]
+pub fn verify_hash(
+    hash_hex: String,
+    signature: String,
+    public_key_id: String,
+) -> MobileResult<bool> {
+    Ok(crate::verify::verify_hash(
+        &hash_hex,
+        &signature,
+        &public_key_id,
+    )?)
+}
+
+/// Endpoints an account connects to
+///
+/// `network` names a public network preset (`mainnet`, `testnet`, or
+/// `integrationnet`); the URLs add to or override it.
+#[derive(Debug, Clone, Default, uniffi::Record)]
+pub struct Endpoints {
+    /// Public network preset
+    pub network: Option<String>,
+    /// Global L0 URL
+    pub l0_url: Option<String>,
+    /// Metagraph L0 URL
+    pub currency_l0_url: Option<String>,
+    /// Currency L1 URL
+    pub l1_url: Option<String>,
+    /// Data L1 URL
+    pub data_l1_url: Option<String>,
+    /// Block explorer URL
+    pub block_explorer_url: Option<String>,
+}
+
+impl Endpoints {
+    fn to_config(&self) -> MobileResult<NetworkConfig> {
+        let mut config = match self.network.as_deref() {
+            None => NetworkConfig::default(),
+            Some("mainnet") => NetworkConfig::mainnet(),
+            Some("testnet") => NetworkConfig::testnet(),
+            Some("integrationnet") => NetworkConfig::integrationnet(),
+            Some(other) => {
+                return Err(NetworkError::ConfigError(format!("Unknown network {}", other)).into())
+            }
+        };
+        let urls = [
+            (&self.l0_url, &mut config.l0_url),
+            (&self.currency_l0_url, &mut config.currency_l0_url),
+            (&self.l1_url, &mut config.l1_url),
+            (&self.data_l1_url, &mut config.data_l1_url),
+            (&self.block_explorer_url, &mut config.block_explorer_url),
+        ];
+        for (url, field) in urls {
+            if url.is_some() {
+                field.clone_from(url);
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// An [`Account`] exported to Kotlin and Swift
+#[derive(Debug, uniffi::Object)]
+pub struct MobileAccount {
+    inner: Account,
+}
+
+#[uniffi::export(async_runtime = "tokio")]
+impl MobileAccount {
+    /// Create an account for a private key in hex
+    #[uniffi::constructor]
+    pub fn new(private_key: String, endpoints: Endpoints) -> MobileResult<Arc<Self>> {
+        let inner =
+            Account::from_private_key(&private_key)?.with_config(&endpoints.to_config()?)?;
+        Ok(Arc::new(Self { inner }))
+    }
+
+    /// DAG address of the account
+    pub fn address(&self) -> String {
+        self.inner.address()
+    }
+
+    /// Public key ID of the account
+    pub fn public_key_id(&self) -> String {
+        self.inner.public_key_id()
+    }
+
+    /// Token balance on the Metagraph L0, or DAG balance on the Global L0
+    pub async fn balance(&self) -> MobileResult<Balance> {
+        Ok(self.inner.balance().await?)
+    }
+
+    /// Send tokens, chaining on the cached last reference
+    pub async fn send(&self, params: TransferParams) -> MobileResult<Receipt> {
+        Ok(self.inner.send(params).await?)
+    }
+
+    /// Sign a JSON value as a DataUpdate and return the signed object as JSON
+    pub fn sign_data(&self, data_json: String) -> MobileResult<String> {
+        let data: Value = serde_json::from_str(&data_json)?;
+        Ok(serde_json::to_string(&self.inner.sign_data(&data)?)?)
+    }
+
+    /// Sign a JSON value as a DataUpdate and submit it to the Data L1
+    pub async fn submit_data(&self, data_json: String) -> MobileResult<Receipt> {
+        let data: Value = serde_json::from_str(&data_json)?;
+        Ok(self.inner.submit_data(&data).await?)
+    }
+
+    /// Drop the cached last reference so the next send fetches it again
+    pub async fn reset_reference(&self) {
+        self.inner.reset_reference().await
+    }
+}
@@ -0,0 +1,299 @@
+//! Multi-party signing across machines
+//!
+//! A [`SigningEnvelope`] is passed between the participants of a multi-sig
+//! until enough of them have signed. It carries the [`UnsignedPayload`] being
+//! signed, the public key IDs allowed to sign it, how many proofs are needed,
+//! and the proofs collected so far. Each participant signs their own copy
+//! and the copies are merged in any order; once the threshold is reached,
+//! [`finalize`](SigningEnvelope::finalize) produces the signed object.
+//!
+//! Every proof is checked against the payload hash and the signer set when it
+//! enters an envelope, so a file received from another participant can be
+//! merged without trusting its sender.
+//!
+//! # Example
+//!
+//! ```
+//! use constellation_sdk::multisig::SigningEnvelope;
+//! use constellation_sdk::offline::UnsignedPayload;
+//! use constellation_sdk::signer::{LocalSigner, Signer};
+//! use constellation_sdk::verify::verify;
+//! use serde_json::{json, Value};
+//!
+//! let alice = LocalSigner::generate();
+//! let bob = LocalSigner::generate();
+//! let carol = LocalSigner::generate();
+//!
+//! let payload = UnsignedPayload::data(&json!({"action": "release"}), true).unwrap();
+//! let ids = [alice.public_key_id(), bob.public_key_id(), carol.public_key_id()];
+//! let envelope = SigningEnvelope::new(payload, ids, 2).unwrap();
+//! let exported = envelope.to_json().unwrap();
+//!
+//! // Each participant signs the file they received
+//! let mut from_alice = SigningEnvelope::from_json(&exported).unwrap();
+//! from_alice.sign(&alice).unwrap();
+//! let mut from_carol = SigningEnvelope::from_json(&exported).unwrap();
+//! from_carol.sign(&carol).unwrap();
+//!
+//! // The coordinator merges them
+//! let mut envelope = from_alice;
+//! envelope.merge(&from_carol).unwrap();
+//! assert!(envelope.is_complete());
+//!
+//! let signed = envelope.finalize::<Value>().unwrap();
+//! assert!(verify(&signed, true).is_valid);
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use crate::offline::{import_signatures, UnsignedPayload};
+use crate::signer::Signer;
+use crate::types::{Result, SdkError, SignatureProof, Signed};
+use crate::verify::{signer_key, verify_hash};
+
+/// Version of the [`SigningEnvelope`] format written by this SDK
+pub const ENVELOPE_FORMAT_VERSION: u32 = 1;
+
+/// A payload travelling between multi-sig participants, with the proofs
+/// collected so far
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningEnvelope {
+    /// Format version
+    pub version: u32,
+    /// What is being signed
+    pub payload: UnsignedPayload,
+    /// Public key IDs allowed to sign, without the 04 prefix
+    pub signers: BTreeSet<String>,
+    /// Number of proofs needed
+    pub threshold: usize,
+    /// Proofs collected so far, at most one per signer
+    #[serde(default)]
+    pub proofs: Vec<SignatureProof>,
+}
+
+impl SigningEnvelope {
+    /// Start collecting proofs for a payload
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error if the payload fails its check, no
+    /// signers are given, or the threshold is zero or above the number of
+    /// signers
+    pub fn new(
+        payload: UnsignedPayload,
+        signers: impl IntoIterator<Item = impl AsRef<str>>,
+        threshold: usize,
+    ) -> Result<Self> {
+        let envelope = Self {
+            version: ENVELOPE_FORMAT_VERSION,
+            payload,
+            signers: signers
+                .into_iter()
+                .map(|id| signer_key(id.as_ref()))
+                .collect(),
+            threshold,
+            proofs: Vec::new(),
+        };
+        envelope.validate()?;
+        Ok(envelope)
+    }
+
+    /// Serialize the envelope to JSON for passing on
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(SdkError::from)
+    }
+
+    /// Parse a received envelope and [`validate`](Self::validate) it
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SerializationError` if the JSON is malformed, or the error
+    /// of [`validate`](Self::validate)
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut envelope: Self = serde_json::from_str(json).map_err(SdkError::from)?;
+        envelope.signers = envelope.signers.iter().map(|id| signer_key(id)).collect();
+        envelope.validate()?;
+        Ok(envelope)
+    }
+
+    /// Check the version, the payload, the threshold, and every proof
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error if the envelope is malformed or a
+    /// proof comes from an unknown or repeated signer, and an
+    /// `InvalidSignature` error if a proof does not sign the payload hash
+    pub fn validate(&self) -> Result<()> {
+        if self.version != ENVELOPE_FORMAT_VERSION {
+            return Err(SdkError::InvalidPayload(format!(
+                "unsupported envelope version {}",
+                self.version
+            )));
+        }
+        self.payload.check()?;
+        if self.threshold == 0 || self.threshold > self.signers.len() {
+            return Err(SdkError::InvalidPayload(format!(
+                "threshold {} is not between 1 and the {} signers",
+                self.threshold,
+                self.signers.len()
+            )));
+        }
+        let mut seen = BTreeSet::new();
+        for proof in &self.proofs {
+            self.check_proof(proof)?;
+            if !seen.insert(signer_key(&proof.id)) {
+                return Err(SdkError::InvalidPayload(format!(
+                    "more than one proof by {}",
+                    proof.id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sign the payload and add the proof
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error if the signer is not one of the
+    /// envelope's signers, or an error if signing fails
+    pub fn sign<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<&SignatureProof> {
+        if !self.signers.contains(&signer_key(&signer.public_key_id())) {
+            return Err(not_a_signer(&signer.public_key_id()));
+        }
+        let proof = self.payload.sign(signer)?;
+        self.add_proof(proof)
+    }
+
+    /// Add a proof produced elsewhere
+    ///
+    /// A proof from a signer who already signed replaces the earlier one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error if the proof's signer is not one of
+    /// the envelope's signers, and an `InvalidSignature` error if it does not
+    /// sign the payload hash
+    pub fn add_proof(&mut self, proof: SignatureProof) -> Result<&SignatureProof> {
+        self.check_proof(&proof)?;
+        let id = signer_key(&proof.id);
+        let index = match self.proofs.iter().position(|p| signer_key(&p.id) == id) {
+            Some(index) => {
+                self.proofs[index] = proof;
+                index
+            }
+            None => {
+                self.proofs.push(proof);
+                self.proofs.len() - 1
+            }
+        };
+        Ok(&self.proofs[index])
+    }
+
+    /// Add the proofs of another participant's copy of this envelope
+    ///
+    /// Returns the number of proofs that were new.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error if `other` is for a different
+    /// payload, signer set, or threshold, or the error of
+    /// [`add_proof`](Self::add_proof) for the first invalid proof. Nothing is
+    /// added on error.
+    pub fn merge(&mut self, other: &SigningEnvelope) -> Result<usize> {
+        if other.payload.hash != self.payload.hash
+            || other.payload.kind != self.payload.kind
+            || other.signers != self.signers
+            || other.threshold != self.threshold
+        {
+            return Err(SdkError::InvalidPayload(
+                "envelopes are for different signing requests".to_string(),
+            ));
+        }
+        for proof in &other.proofs {
+            self.check_proof(proof)?;
+        }
+        let mut signed = self.signed_by();
+        let mut added = 0;
+        for proof in &other.proofs {
+            if signed.insert(signer_key(&proof.id)) {
+                self.proofs.push(proof.clone());
+                added += 1;
+            }
+        }
+        Ok(added)
+    }
+
+    /// Public key IDs that have signed
+    pub fn signed_by(&self) -> BTreeSet<String> {
+        self.proofs.iter().map(|p| signer_key(&p.id)).collect()
+    }
+
+    /// Public key IDs that have not signed yet
+    pub fn missing_signers(&self) -> BTreeSet<String> {
+        self.signers
+            .difference(&self.signed_by())
+            .cloned()
+            .collect()
+    }
+
+    /// Whether enough distinct signers have valid proofs in the envelope
+    pub fn is_complete(&self) -> bool {
+        self.valid_signers().len() >= self.threshold
+    }
+
+    /// Public key IDs of the envelope's signers with a proof that verifies
+    fn valid_signers(&self) -> BTreeSet<String> {
+        self.proofs
+            .iter()
+            .filter(|proof| self.check_proof(proof).is_ok())
+            .map(|proof| signer_key(&proof.id))
+            .collect()
+    }
+
+    /// Produce the signed object once the threshold is reached
+    ///
+    /// `T` is the value type, as for [`import_signature`](crate::offline::import_signature).
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`validate`](Self::validate), which refuses
+    /// more than one proof by a signer, an `InvalidPayload` error if fewer
+    /// distinct signers than the threshold have signed, or the error of
+    /// [`import_signatures`](crate::offline::import_signatures)
+    pub fn finalize<T: DeserializeOwned>(&self) -> Result<Signed<T>> {
+        self.validate()?;
+        let signed = self.valid_signers().len();
+        if signed < self.threshold {
+            return Err(SdkError::InvalidPayload(format!(
+                "{signed} of {} required signers signed",
+                self.threshold
+            )));
+        }
+        import_signatures(&self.payload, self.proofs.iter().cloned())
+    }
+
+    fn check_proof(&self, proof: &SignatureProof) -> Result<()> {
+        if !self.signers.contains(&signer_key(&proof.id)) {
+            return Err(not_a_signer(&proof.id));
+        }
+        if !verify_hash(&self.payload.hash, &proof.signature, &proof.id).unwrap_or(false) {
+            return Err(SdkError::InvalidSignature(format!(
+                "proof by {} does not sign {}",
+                proof.id, self.payload.hash
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn not_a_signer(id: &str) -> SdkError {
+    SdkError::InvalidPayload(format!("{id} is not one of the envelope's signers"))
+}
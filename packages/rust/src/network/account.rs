@@ -0,0 +1,311 @@
+//! High-level account bundling a signer with its network clients
+//!
+//! An [`Account`] holds a [`Signer`], the clients of one deployment, and the
+//! account's last transaction reference, so application code can check a
+//! balance, send tokens, and submit data updates without threading keys,
+//! layers, and parent references through every call.
+//!
+//! Transfers are chained on a locally cached last reference, fetched on the
+//! first send and dropped again whenever a submission fails. The reference
+//! is held by a [`ReferenceManager`], so concurrent sends from one account
+//! are submitted one after another and never share a parent.
+//!
+//! A [`SpendPolicy`] set with [`Account::with_spend_policy`] is checked
+//! before each transfer is signed.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{Account, NetworkConfig};
+//! use constellation_sdk::{LocalSigner, TransferParams};
+//!
+//! let account = Account::new(LocalSigner::new(&private_key)?)
+//!     .with_config(&NetworkConfig::mainnet())?;
+//!
+//! println!("{} holds {}", account.address(), account.balance().await?.amount);
+//! let receipt = account
+//!     .send(TransferParams {
+//!         destination: "DAG...".to_string(),
+//!         amount: 10.0,
+//!         fee: 0.0,
+//!     })
+//!     .await?;
+//! ```
+
+use serde::Serialize;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::block_explorer::BlockExplorerClient;
+use super::config::NetworkConfig;
+use super::global_l0_client::GlobalL0Client;
+use super::metagraph_client::MetagraphClient;
+use super::receipt::Receipt;
+use super::reference_manager::ReferenceManager;
+use super::spend_policy::{SpendLedger, SpendPolicy};
+use super::types::{Balance, ErrorKind, NetworkError, NetworkResult};
+use crate::currency_types::{TransactionReference, TransferParams};
+use crate::signer::{sign_object, LocalSigner, Signer};
+use crate::types::Signed;
+
+/// A signer together with the clients it transacts through
+pub struct Account {
+    signer: Arc<dyn Signer>,
+    global_l0: Option<GlobalL0Client>,
+    l0: Option<MetagraphClient>,
+    l1: Option<ReferenceManager>,
+    data_l1: Option<MetagraphClient>,
+    block_explorer: Option<BlockExplorerClient>,
+    spend_ledger: Option<Mutex<SpendLedger>>,
+}
+
+impl Account {
+    /// Create an account without clients
+    pub fn new(signer: impl Signer + 'static) -> Self {
+        Self::with_shared_signer(Arc::new(signer))
+    }
+
+    /// Create an account for a private key in hex
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the private key is invalid
+    pub fn from_private_key(private_key: &str) -> NetworkResult<Self> {
+        Ok(Self::new(LocalSigner::new(private_key)?))
+    }
+
+    /// Create an account from a signer shared with other code
+    pub fn with_shared_signer(signer: Arc<dyn Signer>) -> Self {
+        Self {
+            signer,
+            global_l0: None,
+            l0: None,
+            l1: None,
+            data_l1: None,
+            block_explorer: None,
+            spend_ledger: None,
+        }
+    }
+
+    /// Add a client for every endpoint set in `config`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configured client cannot be created
+    pub fn with_config(mut self, config: &NetworkConfig) -> NetworkResult<Self> {
+        if config.l0_url.is_some() {
+            self.global_l0 = Some(config.global_l0_client()?);
+        }
+        if config.currency_l0_url.is_some() {
+            self.l0 = Some(config.currency_l0_client()?);
+        }
+        if config.l1_url.is_some() || !config.l1_urls.is_empty() {
+            self.l1 = Some(ReferenceManager::unchecked(config.currency_l1_client()?));
+        }
+        if config.data_l1_url.is_some() || !config.data_l1_urls.is_empty() {
+            self.data_l1 = Some(config.data_l1_client()?);
+        }
+        if config.block_explorer_url.is_some() {
+            self.block_explorer = Some(config.block_explorer_client()?);
+        }
+        Ok(self)
+    }
+
+    /// Use a Global L0 client for DAG balances
+    pub fn with_global_l0(mut self, client: GlobalL0Client) -> Self {
+        self.global_l0 = Some(client);
+        self
+    }
+
+    /// Use a Metagraph L0 client for token balances
+    pub fn with_l0(mut self, client: MetagraphClient) -> Self {
+        self.l0 = Some(client);
+        self
+    }
+
+    /// Use a Currency L1 client for transfers
+    pub fn with_l1(mut self, client: MetagraphClient) -> Self {
+        self.l1 = Some(ReferenceManager::unchecked(client));
+        self
+    }
+
+    /// Use a Data L1 client for data submissions
+    pub fn with_data_l1(mut self, client: MetagraphClient) -> Self {
+        self.data_l1 = Some(client);
+        self
+    }
+
+    /// Use a block explorer client for confirmed transactions
+    pub fn with_block_explorer(mut self, client: BlockExplorerClient) -> Self {
+        self.block_explorer = Some(client);
+        self
+    }
+
+    /// Check every transfer against `policy` before signing it
+    ///
+    /// Transfers that break a rule fail with `PolicyViolation`. Sends made
+    /// before the policy was set do not count toward its daily cap.
+    pub fn with_spend_policy(mut self, policy: SpendPolicy) -> Self {
+        self.spend_ledger = Some(Mutex::new(SpendLedger::new(policy)));
+        self
+    }
+
+    /// The account's signer
+    pub fn signer(&self) -> &dyn Signer {
+        self.signer.as_ref()
+    }
+
+    /// DAG address of the account
+    pub fn address(&self) -> String {
+        self.signer.address()
+    }
+
+    /// Public key ID of the account
+    pub fn public_key_id(&self) -> String {
+        self.signer.public_key_id()
+    }
+
+    /// Get the account's balance
+    ///
+    /// Reads the metagraph token balance from the Metagraph L0 client if one
+    /// is set, otherwise the DAG balance from the Global L0 client.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if neither L0 client is set, or an error if
+    /// the request fails
+    pub async fn balance(&self) -> NetworkResult<Balance> {
+        let address = self.address();
+        match (&self.l0, &self.global_l0) {
+            (Some(l0), _) => l0.get_balance(&address).await,
+            (None, Some(global_l0)) => global_l0.get_balance(&address).await,
+            (None, None) => Err(missing("an L0")),
+        }
+    }
+
+    /// Get the reference the next transfer will chain from
+    ///
+    /// Returns the cached reference, fetching it from the Currency L1 node
+    /// the first time. Waits for a send in progress to finish first.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if no Currency L1 client is set, or an error if
+    /// the request fails
+    pub async fn last_reference(&self) -> NetworkResult<TransactionReference> {
+        let lease = self.references()?.lease(&self.address()).await?;
+        let reference = lease.reference().clone();
+        lease.release();
+        Ok(reference)
+    }
+
+    /// Drop the cached last reference so the next transfer fetches it again
+    ///
+    /// Waits for a send in progress to finish first.
+    pub async fn reset_reference(&self) {
+        if let Some(references) = &self.l1 {
+            references.invalidate(&self.address()).await;
+        }
+    }
+
+    /// Build, sign, and submit a transfer
+    ///
+    /// With a [spend policy](Self::with_spend_policy), the transfer counts
+    /// toward the daily cap from the moment it passes the check. A failed
+    /// submission stops counting unless it timed out or lost the connection,
+    /// since the node may have accepted it anyway.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `PolicyViolation` if the spend policy refuses the transfer,
+    /// a `ConfigError` if no Currency L1 client is set, an error if the
+    /// transfer parameters are invalid, or an error if a request fails
+    pub async fn send(&self, params: TransferParams) -> NetworkResult<Receipt> {
+        let references = self.references()?;
+        let reservation = match self.ledger() {
+            Some(mut ledger) => Some(ledger.reserve(&params)?),
+            None => None,
+        };
+        let result = references.submit(params, self.signer.as_ref()).await;
+        if let (Err(e), Some(id)) = (&result, reservation) {
+            if !matches!(e.kind(), ErrorKind::Timeout | ErrorKind::Connection) {
+                if let Some(mut ledger) = self.ledger() {
+                    ledger.release(id);
+                }
+            }
+        }
+        result
+    }
+
+    /// Sign a value as a data update
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be encoded or signed
+    pub fn sign_data<T: Serialize + Clone>(&self, data: &T) -> NetworkResult<Signed<T>> {
+        Ok(sign_object(self.signer.as_ref(), data, true)?)
+    }
+
+    /// Sign a value as a data update and submit it to the Data L1 node
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if no Data L1 client is set, or an error if
+    /// signing or the request fails
+    pub async fn submit_data<T: Serialize + Clone>(&self, data: &T) -> NetworkResult<Receipt> {
+        let data_l1 = self.data_l1.as_ref().ok_or_else(|| missing("a Data L1"))?;
+        let response = data_l1.post_data(&self.sign_data(data)?).await?;
+        Receipt::for_data_update(data, response.hash)
+    }
+
+    /// Sign a data update, pay the fee the Data L1 node quotes, and submit both
+    ///
+    /// See [`MetagraphClient::submit_data_with_fee`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if no Data L1 client is set, or an error if
+    /// signing or a request fails
+    pub async fn submit_data_with_fee<T: Serialize + Clone>(
+        &self,
+        data: &T,
+    ) -> NetworkResult<Receipt> {
+        let data_l1 = self.data_l1.as_ref().ok_or_else(|| missing("a Data L1"))?;
+        data_l1
+            .submit_data_with_fee(self.signer.as_ref(), data)
+            .await
+    }
+
+    fn ledger(&self) -> Option<MutexGuard<'_, SpendLedger>> {
+        let ledger = self.spend_ledger.as_ref()?;
+        Some(ledger.lock().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    fn references(&self) -> NetworkResult<&ReferenceManager> {
+        self.l1.as_ref().ok_or_else(|| missing("a Currency L1"))
+    }
+
+    pub(super) fn block_explorer(&self) -> NetworkResult<&BlockExplorerClient> {
+        self.block_explorer
+            .as_ref()
+            .ok_or_else(|| missing("a block explorer"))
+    }
+}
+
+fn missing(client: &str) -> NetworkError {
+    NetworkError::ConfigError(format!("Account has no {} client", client))
+}
+
+impl fmt::Debug for Account {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Account")
+            .field("address", &self.address())
+            .field("global_l0", &self.global_l0.is_some())
+            .field("l0", &self.l0.is_some())
+            .field("l1", &self.l1.is_some())
+            .field("data_l1", &self.data_l1.is_some())
+            .field("block_explorer", &self.block_explorer.is_some())
+            .field("spend_policy", &self.spend_ledger.is_some())
+            .finish()
+    }
+}
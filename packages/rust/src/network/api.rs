@@ -0,0 +1,138 @@
+//! Client traits for Currency L1 and Data L1 operations
+//!
+//! Code that submits transactions or data can be written against
+//! [`CurrencyL1Api`] / [`DataL1Api`] instead of [`MetagraphClient`], so the
+//! real client can be swapped for [`MockL1Client`](super::MockL1Client) in
+//! unit tests.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{CurrencyL1Api, NetworkResult};
+//!
+//! async fn submit(cl1: &impl CurrencyL1Api, tx: &CurrencyTransaction) -> NetworkResult<String> {
+//!     Ok(cl1.post_transaction(tx).await?.hash)
+//! }
+//! ```
+
+use async_trait::async_trait;
+use serde::Serialize;
+
+use super::confirmation::{self, ConfirmationOutcome, PollOptions};
+use super::metagraph_client::MetagraphClient;
+use super::types::{
+    EstimateFeeResponse, NetworkResult, PendingTransaction, PostDataResponse,
+    PostTransactionResponse,
+};
+use crate::currency_types::{CurrencyTransaction, TransactionReference};
+use crate::types::Signed;
+
+/// Currency L1 operations used to build and submit token transactions
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait CurrencyL1Api: Send + Sync {
+    /// Get the last accepted transaction reference for an address
+    async fn get_last_reference(&self, address: &str) -> NetworkResult<TransactionReference>;
+
+    /// Submit a signed currency transaction
+    async fn post_transaction(
+        &self,
+        transaction: &CurrencyTransaction,
+    ) -> NetworkResult<PostTransactionResponse>;
+
+    /// Get a pending transaction by hash, or `None` once it left the pool
+    async fn get_pending_transaction(
+        &self,
+        hash: &str,
+    ) -> NetworkResult<Option<PendingTransaction>>;
+
+    /// Get every transaction from an address still in the pending pool,
+    /// ordered by parent ordinal
+    async fn get_pending_transactions(
+        &self,
+        address: &str,
+    ) -> NetworkResult<Vec<PendingTransaction>>;
+
+    /// Poll until a submitted transaction leaves the pending pool
+    ///
+    /// See [`MetagraphClient::wait_for_confirmation`].
+    async fn wait_for_confirmation(
+        &self,
+        transaction: &CurrencyTransaction,
+        options: &PollOptions,
+    ) -> NetworkResult<ConfirmationOutcome> {
+        confirmation::wait_for_confirmation(self, transaction, options).await
+    }
+}
+
+/// Data L1 operations used to submit signed data updates
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait DataL1Api: Send + Sync {
+    /// Estimate the fee for submitting data
+    async fn estimate_fee<T: Serialize + Sync>(
+        &self,
+        data: &Signed<T>,
+    ) -> NetworkResult<EstimateFeeResponse>;
+
+    /// Submit signed data
+    async fn post_data<T: Serialize + Sync>(
+        &self,
+        data: &Signed<T>,
+    ) -> NetworkResult<PostDataResponse>;
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl CurrencyL1Api for MetagraphClient {
+    async fn get_last_reference(&self, address: &str) -> NetworkResult<TransactionReference> {
+        MetagraphClient::get_last_reference(self, address).await
+    }
+
+    async fn post_transaction(
+        &self,
+        transaction: &CurrencyTransaction,
+    ) -> NetworkResult<PostTransactionResponse> {
+        MetagraphClient::post_transaction(self, transaction).await
+    }
+
+    async fn get_pending_transaction(
+        &self,
+        hash: &str,
+    ) -> NetworkResult<Option<PendingTransaction>> {
+        MetagraphClient::get_pending_transaction(self, hash).await
+    }
+
+    async fn get_pending_transactions(
+        &self,
+        address: &str,
+    ) -> NetworkResult<Vec<PendingTransaction>> {
+        MetagraphClient::get_pending_transactions(self, address).await
+    }
+
+    async fn wait_for_confirmation(
+        &self,
+        transaction: &CurrencyTransaction,
+        options: &PollOptions,
+    ) -> NetworkResult<ConfirmationOutcome> {
+        MetagraphClient::wait_for_confirmation(self, transaction, options).await
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl DataL1Api for MetagraphClient {
+    async fn estimate_fee<T: Serialize + Sync>(
+        &self,
+        data: &Signed<T>,
+    ) -> NetworkResult<EstimateFeeResponse> {
+        MetagraphClient::estimate_fee(self, data).await
+    }
+
+    async fn post_data<T: Serialize + Sync>(
+        &self,
+        data: &Signed<T>,
+    ) -> NetworkResult<PostDataResponse> {
+        MetagraphClient::post_data(self, data).await
+    }
+}
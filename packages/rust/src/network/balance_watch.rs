@@ -0,0 +1,145 @@
+//! Streams of balance changes for one address
+//!
+//! [`MetagraphClient::watch_balance`] and [`GlobalL0Client::watch_balance`]
+//! poll an address's balance and yield a [`BalanceChange`] whenever it moves,
+//! tagged with the snapshot ordinal the new balance was read at. Merchant
+//! integrations use it for payment-received notifications without running a
+//! full snapshot indexer.
+//!
+//! Balances only change with snapshots and nodes only serve the latest one,
+//! so changes from several snapshots between two polls arrive as one event.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use futures_util::StreamExt;
+//! use std::time::Duration;
+//!
+//! let changes = ml0.watch_balance("DAG...", Duration::from_secs(5))?;
+//! futures_util::pin_mut!(changes);
+//!
+//! while let Some(change) = changes.next().await {
+//!     let change = change?;
+//!     if change.is_incoming() {
+//!         println!("received {} at snapshot {}", change.delta(), change.ordinal);
+//!     }
+//! }
+//! ```
+
+use futures_util::stream::{self, Stream};
+use std::future::Future;
+use std::time::Duration;
+
+use super::global_l0_client::GlobalL0Client;
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::rt;
+use super::types::{Balance, NetworkResult};
+
+/// A change of an address's balance
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceChange {
+    /// Watched address
+    pub address: String,
+    /// Balance before the change, in smallest units (1e-8)
+    pub previous: i64,
+    /// Balance after the change, in smallest units (1e-8)
+    pub balance: i64,
+    /// Snapshot ordinal the new balance was read at
+    pub ordinal: i64,
+}
+
+impl BalanceChange {
+    /// Signed change in smallest units
+    pub fn delta(&self) -> i64 {
+        self.balance - self.previous
+    }
+
+    /// Whether the balance went up
+    pub fn is_incoming(&self) -> bool {
+        self.balance > self.previous
+    }
+}
+
+impl MetagraphClient {
+    /// Stream changes of an address's token balance
+    ///
+    /// The first poll only records the current balance; a change is emitted
+    /// whenever a later poll reads a different one. A failed poll yields an
+    /// error item and the stream keeps polling. The stream never ends on its
+    /// own; drop it to stop.
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub fn watch_balance(
+        &self,
+        address: &str,
+        poll_interval: Duration,
+    ) -> NetworkResult<impl Stream<Item = NetworkResult<BalanceChange>> + '_> {
+        self.assert_layer(&[LayerType::ML0], "watch_balance")?;
+        let address = address.to_string();
+        Ok(watch(address.clone(), poll_interval, move || {
+            let address = address.clone();
+            async move { self.get_balance(&address).await }
+        }))
+    }
+}
+
+impl GlobalL0Client {
+    /// Stream changes of an address's DAG balance
+    ///
+    /// The first poll only records the current balance; a change is emitted
+    /// whenever a later poll reads a different one. A failed poll yields an
+    /// error item and the stream keeps polling. The stream never ends on its
+    /// own; drop it to stop.
+    pub fn watch_balance(
+        &self,
+        address: &str,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = NetworkResult<BalanceChange>> + '_ {
+        let address = address.to_string();
+        watch(address.clone(), poll_interval, move || {
+            let address = address.clone();
+            async move { self.get_balance(&address).await }
+        })
+    }
+}
+
+fn watch<'a, F, Fut>(
+    address: String,
+    poll_interval: Duration,
+    fetch: F,
+) -> impl Stream<Item = NetworkResult<BalanceChange>> + 'a
+where
+    F: Fn() -> Fut + 'a,
+    Fut: Future<Output = NetworkResult<Balance>> + 'a,
+{
+    let state = (fetch, None::<i64>, false);
+    stream::unfold(state, move |(fetch, mut last, mut polled)| {
+        let address = address.clone();
+        async move {
+            loop {
+                if polled {
+                    rt::sleep(poll_interval).await;
+                }
+                polled = true;
+                let balance = match fetch().await {
+                    Ok(balance) => balance,
+                    Err(e) => return Some((Err(e), (fetch, last, polled))),
+                };
+                let previous = last.replace(balance.amount);
+                if let Some(previous) = previous.filter(|p| *p != balance.amount) {
+                    let change = BalanceChange {
+                        address,
+                        previous,
+                        balance: balance.amount,
+                        ordinal: balance.ordinal,
+                    };
+                    return Some((Ok(change), (fetch, last, polled)));
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,140 @@
+//! Batch submission of data updates and batch reference lookups
+//!
+//! Data L1 nodes accept one update per request and have no batch route, so
+//! [`MetagraphClient::post_data_batch`] sends the updates concurrently, with a
+//! bound on how many requests are in flight, and reports each one separately.
+//! A rejected update does not stop the others.
+//!
+//! Likewise there is no route for the last references of several addresses,
+//! so [`MetagraphClient::get_last_references`] fetches them concurrently for
+//! payout engines that would otherwise look them up one by one.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let results = dl1.post_data_batch(&updates).await?;
+//! for (update, result) in updates.iter().zip(&results) {
+//!     match result {
+//!         Ok(response) => println!("accepted {}", response.hash),
+//!         Err(e) => println!("rejected: {}", e),
+//!     }
+//! }
+//!
+//! let references = cl1.get_last_references(&recipients).await?;
+//! ```
+
+use futures_util::stream::{self, StreamExt};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::types::{NetworkResult, PostDataResponse, RequestOptions};
+use crate::currency_types::TransactionReference;
+use crate::types::Signed;
+
+/// Requests in flight at once unless the caller asks otherwise
+pub const DEFAULT_BATCH_CONCURRENCY: usize = 4;
+
+impl MetagraphClient {
+    /// Submit several signed data updates, returning one result per update
+    ///
+    /// Results are in the same order as `data`. Up to
+    /// [`DEFAULT_BATCH_CONCURRENCY`] requests run at once.
+    ///
+    /// Available on: DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer; failures of
+    /// individual updates are returned in the result list
+    pub async fn post_data_batch<T: Serialize + Sync>(
+        &self,
+        data: &[Signed<T>],
+    ) -> NetworkResult<Vec<NetworkResult<PostDataResponse>>> {
+        self.post_data_batch_with_options(
+            data,
+            DEFAULT_BATCH_CONCURRENCY,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`post_data_batch`](Self::post_data_batch) with a concurrency bound and
+    /// per-request options applied to every update
+    ///
+    /// A `concurrency` of 0 is treated as 1.
+    ///
+    /// Available on: DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer; failures of
+    /// individual updates are returned in the result list
+    pub async fn post_data_batch_with_options<T: Serialize + Sync>(
+        &self,
+        data: &[Signed<T>],
+        concurrency: usize,
+        options: &RequestOptions,
+    ) -> NetworkResult<Vec<NetworkResult<PostDataResponse>>> {
+        self.assert_layer(&[LayerType::DL1], "post_data_batch")?;
+        Ok(stream::iter(data)
+            .map(|update| self.post_data_with_options(update, options))
+            .buffered(concurrency.max(1))
+            .collect()
+            .await)
+    }
+
+    /// Get the last accepted transaction reference of several addresses
+    ///
+    /// Returns one result per distinct address, keyed by address. Up to
+    /// [`DEFAULT_BATCH_CONCURRENCY`] requests run at once.
+    ///
+    /// Available on: CL1, ML0 (if currency enabled)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer; failed lookups are
+    /// returned in the map
+    pub async fn get_last_references<S: AsRef<str>>(
+        &self,
+        addresses: &[S],
+    ) -> NetworkResult<HashMap<String, NetworkResult<TransactionReference>>> {
+        self.get_last_references_with_options(
+            addresses,
+            DEFAULT_BATCH_CONCURRENCY,
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// [`get_last_references`](Self::get_last_references) with a concurrency
+    /// bound and per-request options applied to every lookup
+    ///
+    /// A `concurrency` of 0 is treated as 1.
+    ///
+    /// Available on: CL1, ML0 (if currency enabled)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer; failed lookups are
+    /// returned in the map
+    pub async fn get_last_references_with_options<S: AsRef<str>>(
+        &self,
+        addresses: &[S],
+        concurrency: usize,
+        options: &RequestOptions,
+    ) -> NetworkResult<HashMap<String, NetworkResult<TransactionReference>>> {
+        self.assert_layer(&[LayerType::CL1, LayerType::ML0], "get_last_references")?;
+        let mut unique: Vec<&str> = addresses.iter().map(AsRef::as_ref).collect();
+        unique.sort_unstable();
+        unique.dedup();
+        Ok(stream::iter(unique)
+            .map(|address| async move {
+                let reference = self.get_last_reference_with_options(address, options).await;
+                (address.to_string(), reference)
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await)
+    }
+}
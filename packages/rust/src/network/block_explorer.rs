@@ -0,0 +1,239 @@
+//! Client for the block explorer API
+//!
+//! L0 and L1 nodes only serve current state. The block explorer indexes every
+//! snapshot and serves the history of an address, one page at a time.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{BlockExplorerClient, PageRequest};
+//! use futures_util::StreamExt;
+//!
+//! let be = BlockExplorerClient::new("https://be-mainnet.constellationnetwork.io")?;
+//! let page = be.get_transactions_by_address("DAG...", &PageRequest::default()).await?;
+//!
+//! // Or walk the whole history
+//! let history = be.transactions_by_address("DAG...", 100);
+//! futures_util::pin_mut!(history);
+//! while let Some(tx) = history.next().await {
+//!     println!("{}", tx?.hash);
+//! }
+//! ```
+
+use futures_util::stream::{self, Stream};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+use super::client::HttpClient;
+use super::types::{NetworkResult, RequestOptions};
+use crate::currency_types::TransactionReference;
+
+/// Which page of a paginated listing to fetch
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PageRequest {
+    /// Maximum number of items (the explorer's default when unset)
+    pub limit: Option<u32>,
+    /// Continuation token from the previous [`Page`]
+    pub next: Option<String>,
+}
+
+/// One page of a paginated listing
+#[derive(Debug, Clone)]
+pub struct Page<T> {
+    /// Items on this page, newest first
+    pub data: Vec<T>,
+    /// Token for the following page, `None` on the last page
+    pub next: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Request for the page after this one, if there is one
+    pub fn next_request(&self, limit: Option<u32>) -> Option<PageRequest> {
+        self.next.as_ref().map(|next| PageRequest {
+            limit,
+            next: Some(next.clone()),
+        })
+    }
+}
+
+/// A currency transaction as indexed by the block explorer
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExplorerTransaction {
+    /// Transaction hash
+    pub hash: String,
+    /// Ordinal of the transaction in the source's chain
+    pub ordinal: i64,
+    /// Source DAG address
+    pub source: String,
+    /// Destination DAG address
+    pub destination: String,
+    /// Amount in smallest units (1e-8)
+    pub amount: i64,
+    /// Fee in smallest units (1e-8)
+    pub fee: i64,
+    /// Reference to the parent transaction
+    pub parent: TransactionReference,
+    /// Hash of the block that included the transaction
+    #[serde(default)]
+    pub block_hash: Option<String>,
+    /// Hash of the snapshot that included the transaction
+    #[serde(default)]
+    pub snapshot_hash: Option<String>,
+    /// Ordinal of the snapshot that included the transaction
+    #[serde(default)]
+    pub snapshot_ordinal: Option<i64>,
+    /// When the including snapshot was created
+    #[serde(default)]
+    pub timestamp: Option<String>,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Envelope of single-item responses
+#[derive(Deserialize)]
+pub(super) struct DataResponse<T> {
+    pub(super) data: T,
+}
+
+#[derive(Deserialize)]
+struct PageResponse<T> {
+    data: Vec<T>,
+    #[serde(default)]
+    meta: Option<PageMeta>,
+}
+
+#[derive(Deserialize)]
+struct PageMeta {
+    #[serde(default)]
+    next: Option<String>,
+}
+
+/// Client for a block explorer instance
+///
+/// Serves DAG history by default; [`for_metagraph`](Self::for_metagraph)
+/// switches to the history of a metagraph token.
+pub struct BlockExplorerClient {
+    client: HttpClient,
+    metagraph_id: Option<String>,
+}
+
+impl BlockExplorerClient {
+    /// Create a new BlockExplorerClient
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be initialized
+    pub fn new(base_url: impl Into<String>) -> NetworkResult<Self> {
+        Ok(Self::with_http_client(HttpClient::new(base_url, None)?))
+    }
+
+    /// Create a BlockExplorerClient on top of a pre-built [`HttpClient`]
+    pub fn with_http_client(client: HttpClient) -> Self {
+        Self {
+            client,
+            metagraph_id: None,
+        }
+    }
+
+    /// Query the history of a metagraph token instead of DAG
+    pub fn for_metagraph(mut self, metagraph_id: impl Into<String>) -> Self {
+        self.metagraph_id = Some(metagraph_id.into());
+        self
+    }
+
+    /// Metagraph whose token is queried, `None` for DAG
+    pub fn metagraph_id(&self) -> Option<&str> {
+        self.metagraph_id.as_deref()
+    }
+
+    pub(super) fn http_client(&self) -> &HttpClient {
+        &self.client
+    }
+
+    /// Get a transaction by hash, `None` if the explorer has not indexed it
+    pub async fn get_transaction(&self, hash: &str) -> NetworkResult<Option<ExplorerTransaction>> {
+        let path = match &self.metagraph_id {
+            Some(id) => format!("/currency/{}/transactions/{}", id, hash),
+            None => format!("/transactions/{}", hash),
+        };
+        match self.client.get::<DataResponse<_>>(&path).await {
+            Ok(response) => Ok(Some(response.data)),
+            Err(e) if e.status_code() == Some(404) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Get one page of the transactions sent or received by an address
+    ///
+    /// An address the explorer has no history for gets an empty last page.
+    pub async fn get_transactions_by_address(
+        &self,
+        address: &str,
+        page: &PageRequest,
+    ) -> NetworkResult<Page<ExplorerTransaction>> {
+        let path = match &self.metagraph_id {
+            Some(id) => format!("/currency/{}/addresses/{}/transactions", id, address),
+            None => format!("/addresses/{}/transactions", address),
+        };
+        match self.get_page(&path, page).await {
+            Err(e) if e.status_code() == Some(404) => Ok(Page {
+                data: Vec::new(),
+                next: None,
+            }),
+            result => result,
+        }
+    }
+
+    /// Stream every transaction of an address, fetching `page_size` at a time
+    ///
+    /// Pages are fetched lazily as the stream is consumed. A failed request
+    /// yields the error and ends the stream.
+    pub fn transactions_by_address<'a>(
+        &'a self,
+        address: &'a str,
+        page_size: u32,
+    ) -> impl Stream<Item = NetworkResult<ExplorerTransaction>> + 'a {
+        let first = PageRequest {
+            limit: Some(page_size),
+            next: None,
+        };
+        let state = (Some(first), VecDeque::new());
+        stream::unfold(state, move |(mut request, mut buffer)| async move {
+            loop {
+                if let Some(tx) = buffer.pop_front() {
+                    return Some((Ok(tx), (request, buffer)));
+                }
+                let page = match self
+                    .get_transactions_by_address(address, &request.take()?)
+                    .await
+                {
+                    Ok(page) => page,
+                    Err(e) => return Some((Err(e), (None, buffer))),
+                };
+                request = page.next_request(Some(page_size));
+                buffer.extend(page.data);
+            }
+        })
+    }
+
+    async fn get_page<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        page: &PageRequest,
+    ) -> NetworkResult<Page<T>> {
+        let mut options = RequestOptions::default();
+        if let Some(limit) = page.limit {
+            options.query.push(("limit".to_string(), limit.to_string()));
+        }
+        if let Some(next) = &page.next {
+            options.query.push(("next".to_string(), next.clone()));
+        }
+        let response: PageResponse<T> = self.client.get_with_options(path, &options).await?;
+        Ok(Page {
+            data: response.data,
+            next: response.meta.and_then(|meta| meta.next),
+        })
+    }
+}
@@ -0,0 +1,455 @@
+//! Blocking (synchronous) clients
+//!
+//! Wrappers around the async clients for CLI tools and codebases without an
+//! async runtime. Each client drives its requests on a private single-threaded
+//! runtime, so failover, retries, and interceptors behave exactly as in the
+//! async clients.
+//!
+//! Requires the `blocking` feature. The methods must not be called from
+//! within an async runtime; use the async clients there instead.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::blocking::MetagraphClient;
+//! use constellation_sdk::network::LayerType;
+//!
+//! let cl1 = MetagraphClient::new("http://localhost:9300", LayerType::CL1)?;
+//! let last_ref = cl1.get_last_reference("DAG...")?;
+//! let hash = cl1.transfer(params, &private_key)?;
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::future::Future;
+use tokio::runtime::{Builder, Runtime};
+
+use super::metagraph_client::ClusterInfo;
+use super::types::{
+    Balance, CirculatingSupply, EstimateFeeResponse, GlobalSnapshot, NetworkError, NetworkResult,
+    PendingTransaction, PostDataResponse, PostTransactionResponse, RequestOptions, SnapshotHeader,
+    TotalSupply,
+};
+use super::{CalculatedState, LayerType, NodeInfo, NodeMetrics, Receipt, ValidatorSet};
+use crate::currency_types::{CurrencyTransaction, TransactionReference, TransferParams};
+use crate::data_update::DataUpdate;
+use crate::signer::Signer;
+use crate::types::Signed;
+
+fn runtime() -> NetworkResult<Runtime> {
+    Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(|e| NetworkError::ConfigError(format!("failed to start runtime: {}", e)))
+}
+
+/// Blocking counterpart of [`super::MetagraphClient`]
+pub struct MetagraphClient {
+    inner: super::MetagraphClient,
+    runtime: Runtime,
+}
+
+impl MetagraphClient {
+    /// Create a new blocking MetagraphClient
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client or runtime cannot be initialized
+    pub fn new(base_url: impl Into<String>, layer: LayerType) -> NetworkResult<Self> {
+        Self::from_async(super::MetagraphClient::new(base_url, layer)?)
+    }
+
+    /// Wrap an async client, e.g. one built by [`NetworkConfig`](super::NetworkConfig)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the runtime cannot be started
+    pub fn from_async(inner: super::MetagraphClient) -> NetworkResult<Self> {
+        Ok(Self {
+            inner,
+            runtime: runtime()?,
+        })
+    }
+
+    /// Get the wrapped async client
+    pub fn inner(&self) -> &super::MetagraphClient {
+        &self.inner
+    }
+
+    /// Get the layer type of this client
+    pub fn layer(&self) -> LayerType {
+        self.inner.layer()
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// Check the health/availability of the node
+    pub fn check_health(&self) -> bool {
+        self.block_on(self.inner.check_health())
+    }
+
+    /// Get cluster information
+    pub fn get_cluster_info(&self) -> NetworkResult<ClusterInfo> {
+        self.block_on(self.inner.get_cluster_info())
+    }
+
+    /// Get the node's ID, state, version, and session
+    pub fn node_info(&self) -> NetworkResult<NodeInfo> {
+        self.block_on(self.inner.node_info())
+    }
+
+    /// Scrape the node's Prometheus metrics
+    pub fn get_metrics(&self) -> NetworkResult<NodeMetrics> {
+        self.block_on(self.inner.get_metrics())
+    }
+
+    /// Get the last accepted transaction reference for an address
+    ///
+    /// Available on: CL1, ML0 (if currency enabled)
+    pub fn get_last_reference(&self, address: &str) -> NetworkResult<TransactionReference> {
+        self.block_on(self.inner.get_last_reference(address))
+    }
+
+    /// Get the token balance of an address
+    ///
+    /// Available on: ML0
+    pub fn get_balance(&self, address: &str) -> NetworkResult<Balance> {
+        self.block_on(self.inner.get_balance(address))
+    }
+
+    /// Get the total supply of the metagraph token
+    ///
+    /// Available on: ML0
+    pub fn get_total_supply(&self) -> NetworkResult<TotalSupply> {
+        self.block_on(self.inner.get_total_supply())
+    }
+
+    /// Get the circulating supply of the metagraph token
+    ///
+    /// Available on: ML0
+    pub fn get_circulating_supply(&self) -> NetworkResult<CirculatingSupply> {
+        self.block_on(self.inner.get_circulating_supply())
+    }
+
+    /// Get the header of the latest currency snapshot
+    ///
+    /// Available on: ML0
+    pub fn get_latest_snapshot_header(&self) -> NetworkResult<SnapshotHeader> {
+        self.block_on(self.inner.get_latest_snapshot_header())
+    }
+
+    /// Get the header of a currency snapshot by ordinal
+    ///
+    /// Available on: ML0
+    pub fn get_snapshot_header(&self, ordinal: i64) -> NetworkResult<SnapshotHeader> {
+        self.block_on(self.inner.get_snapshot_header(ordinal))
+    }
+
+    /// Get the ordinal of the latest currency snapshot
+    ///
+    /// Available on: ML0
+    pub fn get_latest_snapshot_ordinal(&self) -> NetworkResult<i64> {
+        self.block_on(self.inner.get_latest_snapshot_ordinal())
+    }
+
+    /// Submit a signed currency transaction
+    ///
+    /// Available on: CL1
+    pub fn post_transaction(
+        &self,
+        transaction: &CurrencyTransaction,
+    ) -> NetworkResult<PostTransactionResponse> {
+        self.block_on(self.inner.post_transaction(transaction))
+    }
+
+    /// Verify a transaction locally, then submit it
+    ///
+    /// Available on: CL1
+    pub fn post_transaction_checked(
+        &self,
+        transaction: &CurrencyTransaction,
+    ) -> NetworkResult<PostTransactionResponse> {
+        self.block_on(self.inner.post_transaction_checked(transaction))
+    }
+
+    /// Get a pending transaction by hash
+    ///
+    /// Available on: CL1
+    pub fn get_pending_transaction(&self, hash: &str) -> NetworkResult<Option<PendingTransaction>> {
+        self.block_on(self.inner.get_pending_transaction(hash))
+    }
+
+    /// Get every transaction from an address still in the pending pool
+    ///
+    /// Available on: CL1
+    pub fn get_pending_transactions(
+        &self,
+        address: &str,
+    ) -> NetworkResult<Vec<PendingTransaction>> {
+        self.block_on(self.inner.get_pending_transactions(address))
+    }
+
+    /// Fetch the last reference, sign, and submit a transfer
+    ///
+    /// Available on: CL1
+    pub fn transfer(&self, params: TransferParams, private_key: &str) -> NetworkResult<String> {
+        self.block_on(self.inner.transfer(params, private_key))
+    }
+
+    /// Estimate the fee for submitting data
+    ///
+    /// Available on: DL1
+    pub fn estimate_fee<T: Serialize>(
+        &self,
+        data: &Signed<T>,
+    ) -> NetworkResult<EstimateFeeResponse> {
+        self.block_on(self.inner.estimate_fee(data))
+    }
+
+    /// Submit signed data to the Data L1 node
+    ///
+    /// Available on: DL1
+    pub fn post_data<T: Serialize>(&self, data: &Signed<T>) -> NetworkResult<PostDataResponse> {
+        self.block_on(self.inner.post_data(data))
+    }
+
+    /// Check whether the node would accept a data update, without submitting it
+    ///
+    /// Available on: DL1
+    pub fn validate_data<T: Serialize>(&self, data: &Signed<T>) -> NetworkResult<()> {
+        self.block_on(self.inner.validate_data(data))
+    }
+
+    /// Sign a data update, pay the fee the node quotes for it, and submit both
+    ///
+    /// Available on: DL1
+    pub fn submit_data_with_fee<S, T>(&self, signer: &S, update: &T) -> NetworkResult<Receipt>
+    where
+        S: Signer + ?Sized,
+        T: Serialize + Clone,
+    {
+        self.block_on(self.inner.submit_data_with_fee(signer, update))
+    }
+
+    /// Sign a typed data update and post it to its type's route
+    ///
+    /// Available on: DL1
+    pub fn submit<S, U>(&self, signer: &S, update: &U) -> NetworkResult<Receipt>
+    where
+        S: Signer + ?Sized,
+        U: DataUpdate + Clone,
+    {
+        self.block_on(self.inner.submit(signer, update))
+    }
+
+    /// Verify the proofs of a data update locally, then submit it
+    ///
+    /// Available on: DL1
+    pub fn post_data_checked<T: Serialize>(
+        &self,
+        data: &Signed<T>,
+    ) -> NetworkResult<PostDataResponse> {
+        self.block_on(self.inner.post_data_checked(data))
+    }
+
+    /// Submit several signed data updates, returning one result per update
+    ///
+    /// Available on: DL1
+    pub fn post_data_batch<T: Serialize + Sync>(
+        &self,
+        data: &[Signed<T>],
+    ) -> NetworkResult<Vec<NetworkResult<PostDataResponse>>> {
+        self.block_on(self.inner.post_data_batch(data))
+    }
+
+    /// Query a route exposed by the metagraph's data application
+    ///
+    /// Available on: ML0, DL1
+    pub fn get_data_application<T: DeserializeOwned>(&self, path: &str) -> NetworkResult<T> {
+        self.block_on(self.inner.get_data_application(path))
+    }
+
+    /// Get the latest calculated state, deserialized into `S`
+    ///
+    /// Available on: ML0, DL1
+    pub fn get_calculated_state<S: DeserializeOwned>(&self) -> NetworkResult<S> {
+        self.block_on(self.inner.get_calculated_state())
+    }
+
+    /// Get the latest calculated state with its ordinal and snapshot hash
+    ///
+    /// Available on: ML0
+    pub fn get_calculated_state_with_proof<S: DeserializeOwned>(
+        &self,
+    ) -> NetworkResult<CalculatedState<S>> {
+        self.block_on(self.inner.get_calculated_state_with_proof())
+    }
+
+    /// GET a custom data-application route
+    ///
+    /// Available on: ML0, DL1
+    pub fn get_custom<T: DeserializeOwned>(&self, path: &str) -> NetworkResult<T> {
+        self.block_on(self.inner.get_custom(path))
+    }
+
+    /// GET a custom data-application route with per-request options
+    ///
+    /// Available on: ML0, DL1
+    pub fn get_custom_with_options<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        options: &RequestOptions,
+    ) -> NetworkResult<T> {
+        self.block_on(self.inner.get_custom_with_options(path, options))
+    }
+
+    /// POST a JSON body to a custom data-application route
+    ///
+    /// Available on: ML0, DL1
+    pub fn post_custom<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> NetworkResult<T> {
+        self.block_on(self.inner.post_custom(path, body))
+    }
+
+    /// [`post_custom`](Self::post_custom) with per-request options
+    ///
+    /// Available on: ML0, DL1
+    pub fn post_custom_with_options<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+        options: &RequestOptions,
+    ) -> NetworkResult<T> {
+        self.block_on(self.inner.post_custom_with_options(path, body, options))
+    }
+
+    /// Make a raw GET request to the node
+    pub fn get<T: DeserializeOwned>(&self, path: &str) -> NetworkResult<T> {
+        self.block_on(self.inner.get(path))
+    }
+
+    /// Make a raw POST request to the node
+    pub fn post<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> NetworkResult<T> {
+        self.block_on(self.inner.post(path, body))
+    }
+}
+
+/// Blocking counterpart of [`super::GlobalL0Client`]
+pub struct GlobalL0Client {
+    inner: super::GlobalL0Client,
+    runtime: Runtime,
+}
+
+impl GlobalL0Client {
+    /// Create a new blocking GlobalL0Client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client or runtime cannot be initialized
+    pub fn new(base_url: impl Into<String>) -> NetworkResult<Self> {
+        Self::from_async(super::GlobalL0Client::new(base_url)?)
+    }
+
+    /// Wrap an async client, e.g. one built by [`NetworkConfig`](super::NetworkConfig)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the runtime cannot be started
+    pub fn from_async(inner: super::GlobalL0Client) -> NetworkResult<Self> {
+        Ok(Self {
+            inner,
+            runtime: runtime()?,
+        })
+    }
+
+    /// Get the wrapped async client
+    pub fn inner(&self) -> &super::GlobalL0Client {
+        &self.inner
+    }
+
+    /// Check the health/availability of the node
+    pub fn check_health(&self) -> bool {
+        self.runtime.block_on(self.inner.check_health())
+    }
+
+    /// Get cluster information
+    pub fn get_cluster_info(&self) -> NetworkResult<ClusterInfo> {
+        self.runtime.block_on(self.inner.get_cluster_info())
+    }
+
+    /// Get the node's ID, state, version, and session
+    pub fn node_info(&self) -> NetworkResult<NodeInfo> {
+        self.runtime.block_on(self.inner.node_info())
+    }
+
+    /// Scrape the node's Prometheus metrics
+    pub fn get_metrics(&self) -> NetworkResult<NodeMetrics> {
+        self.runtime.block_on(self.inner.get_metrics())
+    }
+
+    /// Get the latest global snapshot
+    pub fn get_latest_snapshot(&self) -> NetworkResult<Signed<GlobalSnapshot>> {
+        self.runtime.block_on(self.inner.get_latest_snapshot())
+    }
+
+    /// Get the latest global snapshot, checking it was signed by `validators`
+    pub fn get_latest_verified_snapshot(
+        &self,
+        validators: &ValidatorSet,
+    ) -> NetworkResult<Signed<GlobalSnapshot>> {
+        self.runtime
+            .block_on(self.inner.get_latest_verified_snapshot(validators))
+    }
+
+    /// Get a global snapshot by ordinal, checking it was signed by `validators`
+    pub fn get_verified_snapshot(
+        &self,
+        ordinal: i64,
+        validators: &ValidatorSet,
+    ) -> NetworkResult<Signed<GlobalSnapshot>> {
+        self.runtime
+            .block_on(self.inner.get_verified_snapshot(ordinal, validators))
+    }
+
+    /// Get the ordinal of the latest global snapshot
+    pub fn get_latest_snapshot_ordinal(&self) -> NetworkResult<i64> {
+        self.runtime
+            .block_on(self.inner.get_latest_snapshot_ordinal())
+    }
+
+    /// Get the header of the latest global snapshot
+    pub fn get_latest_snapshot_header(&self) -> NetworkResult<SnapshotHeader> {
+        self.runtime
+            .block_on(self.inner.get_latest_snapshot_header())
+    }
+
+    /// Get the header of a global snapshot by ordinal
+    pub fn get_snapshot_header(&self, ordinal: i64) -> NetworkResult<SnapshotHeader> {
+        self.runtime
+            .block_on(self.inner.get_snapshot_header(ordinal))
+    }
+
+    /// Get the DAG balance of an address
+    pub fn get_balance(&self, address: &str) -> NetworkResult<Balance> {
+        self.runtime.block_on(self.inner.get_balance(address))
+    }
+
+    /// Get the total DAG supply
+    pub fn get_total_supply(&self) -> NetworkResult<TotalSupply> {
+        self.runtime.block_on(self.inner.get_total_supply())
+    }
+
+    /// Get the circulating DAG supply
+    pub fn get_circulating_supply(&self) -> NetworkResult<CirculatingSupply> {
+        self.runtime.block_on(self.inner.get_circulating_supply())
+    }
+}
@@ -0,0 +1,139 @@
+//! Size-limited response body reading
+//!
+//! A misbehaving or malicious endpoint can answer with an arbitrarily large
+//! body. Bodies are read chunk by chunk and abandoned as soon as they exceed
+//! the client's limit, instead of being buffered whole first.
+//!
+//! [`decode_json_streaming`] goes further for large documents such as
+//! snapshots: on native targets the JSON is parsed on a blocking thread while
+//! it downloads, so the raw body is never held in memory next to the parsed
+//! value. On wasm32 it falls back to a buffered, size-limited parse.
+
+use futures_util::StreamExt;
+use reqwest::Response;
+use serde::de::DeserializeOwned;
+
+use super::types::{NetworkError, NetworkResult};
+
+/// Error bodies are only kept for diagnostics, so they are cut off here
+pub(super) const MAX_ERROR_BODY: usize = 64 * 1024;
+
+/// Read a whole body, failing once it exceeds `limit` bytes
+pub(super) async fn read_body(response: Response, limit: usize) -> NetworkResult<Vec<u8>> {
+    check_length(&response, limit)?;
+    let mut body = Vec::new();
+    let mut chunks = response.bytes_stream();
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(|e| NetworkError::SerializationError(e.to_string()))?;
+        if body.len() + chunk.len() > limit {
+            return Err(NetworkError::ResponseTooLarge { limit });
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Read at most `limit` bytes of a body as text, dropping the rest
+pub(super) async fn read_truncated(response: Response, limit: usize) -> String {
+    let mut body = Vec::new();
+    let mut chunks = response.bytes_stream();
+    while let Some(Ok(chunk)) = chunks.next().await {
+        let room = limit - body.len();
+        body.extend_from_slice(&chunk[..chunk.len().min(room)]);
+        if body.len() == limit {
+            break;
+        }
+    }
+    String::from_utf8_lossy(&body).into_owned()
+}
+
+/// Read a body as text, failing once it exceeds `limit` bytes
+pub(super) async fn read_text(response: Response, limit: usize) -> NetworkResult<String> {
+    let body = read_body(response, limit).await?;
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+/// Read and parse a JSON body, failing once it exceeds `limit` bytes
+pub(super) async fn decode_json<T: DeserializeOwned>(
+    response: Response,
+    limit: usize,
+) -> NetworkResult<T> {
+    let body = read_body(response, limit).await?;
+    serde_json::from_slice(&body).map_err(|e| NetworkError::SerializationError(e.to_string()))
+}
+
+/// Parse a JSON body while it downloads, failing once it exceeds `limit` bytes
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) async fn decode_json_streaming<T: DeserializeOwned + Send + 'static>(
+    response: Response,
+    limit: usize,
+) -> NetworkResult<T> {
+    check_length(&response, limit)?;
+    // A few chunks of slack keep the download going while the parser works
+    let (sender, receiver) = tokio::sync::mpsc::channel(8);
+    let parser = tokio::task::spawn_blocking(move || {
+        serde_json::from_reader::<_, T>(ChunkReader {
+            receiver,
+            chunk: bytes::Bytes::new(),
+        })
+    });
+
+    let mut received = 0;
+    let mut chunks = response.bytes_stream();
+    // Returning early drops the sender, which ends the parser's input
+    while let Some(chunk) = chunks.next().await {
+        let chunk = chunk.map_err(|e| NetworkError::SerializationError(e.to_string()))?;
+        received += chunk.len();
+        if received > limit {
+            return Err(NetworkError::ResponseTooLarge { limit });
+        }
+        if sender.send(chunk).await.is_err() {
+            // The parser gave up early; its error is reported below
+            break;
+        }
+    }
+    drop(sender);
+
+    parser
+        .await
+        .map_err(|e| NetworkError::SerializationError(e.to_string()))?
+        .map_err(|e| NetworkError::SerializationError(e.to_string()))
+}
+
+/// Parse a JSON body, failing once it exceeds `limit` bytes
+#[cfg(target_arch = "wasm32")]
+pub(super) async fn decode_json_streaming<T: DeserializeOwned>(
+    response: Response,
+    limit: usize,
+) -> NetworkResult<T> {
+    decode_json(response, limit).await
+}
+
+fn check_length(response: &Response, limit: usize) -> NetworkResult<()> {
+    match response.content_length() {
+        Some(length) if length > limit as u64 => Err(NetworkError::ResponseTooLarge { limit }),
+        _ => Ok(()),
+    }
+}
+
+/// Blocking reader over the chunks of a body arriving on a channel
+#[cfg(not(target_arch = "wasm32"))]
+struct ChunkReader {
+    receiver: tokio::sync::mpsc::Receiver<bytes::Bytes>,
+    chunk: bytes::Bytes,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl std::io::Read for ChunkReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.chunk.is_empty() {
+            match self.receiver.blocking_recv() {
+                Some(chunk) => self.chunk = chunk,
+                None => return Ok(0),
+            }
+        }
+        let n = buf.len().min(self.chunk.len());
+        buf[..n].copy_from_slice(&self.chunk.split_to(n));
+        Ok(n)
+    }
+}
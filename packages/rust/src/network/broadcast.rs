@@ -0,0 +1,169 @@
+//! Multi-node transaction broadcast
+//!
+//! A transaction reaches consensus sooner, and survives a node dropping it,
+//! when every Currency L1 node has it in its pool rather than only the one
+//! node a normal submission is routed to. [`MetagraphClient::broadcast_transaction`]
+//! posts it to all configured nodes at once and returns as soon as a quorum
+//! has acknowledged it. Nodes that gossiped the transaction before our copy
+//! arrived answer that it already exists, which counts as an acknowledgement.
+//!
+//! Requests still in flight when the quorum is reached are dropped; the
+//! transaction has usually reached those nodes already, and gossip covers the
+//! rest.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let config = NetworkConfig::new()
+//!     .l1_urls(vec!["http://node1:9300".into(), "http://node2:9300".into(), "http://node3:9300".into()]);
+//! let cl1 = config.currency_l1_client()?;
+//!
+//! // Returns once 2 of the 3 nodes have the transaction
+//! let outcome = cl1.broadcast_transaction(&signed_tx).await?;
+//! println!("{} accepted by {:?}", outcome.hash, outcome.acknowledged);
+//! ```
+
+use futures_util::stream::{FuturesUnordered, StreamExt};
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::node_error;
+use super::types::{NetworkError, NetworkResult, PostTransactionResponse, RequestOptions};
+use crate::currency_transaction::hash_currency_transaction;
+use crate::currency_types::CurrencyTransaction;
+
+/// Result of broadcasting a transaction to several nodes
+#[derive(Debug)]
+pub struct BroadcastOutcome {
+    /// Hash of the transaction
+    pub hash: String,
+    /// Base URLs of the nodes that accepted the transaction or already had it
+    pub acknowledged: Vec<String>,
+    /// Base URLs and errors of the nodes that answered before the quorum was reached and refused
+    pub failed: Vec<(String, NetworkError)>,
+}
+
+impl MetagraphClient {
+    /// Submit a transaction to every configured node, returning once a
+    /// majority has acknowledged it
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer, or
+    /// `QuorumNotReached` with each node's error if too few nodes acknowledged
+    pub async fn broadcast_transaction(
+        &self,
+        transaction: &CurrencyTransaction,
+    ) -> NetworkResult<BroadcastOutcome> {
+        let majority = self.base_urls().len() / 2 + 1;
+        self.broadcast_transaction_with_options(transaction, majority, &RequestOptions::default())
+            .await
+    }
+
+    /// [`broadcast_transaction`](Self::broadcast_transaction) with the number
+    /// of acknowledgements to wait for and per-request options applied to
+    /// every node
+    ///
+    /// `quorum` is clamped to between 1 and the number of configured nodes.
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer, or
+    /// `QuorumNotReached` with each node's error if too few nodes acknowledged
+    pub async fn broadcast_transaction_with_options(
+        &self,
+        transaction: &CurrencyTransaction,
+        quorum: usize,
+        options: &RequestOptions,
+    ) -> NetworkResult<BroadcastOutcome> {
+        self.assert_layer(&[LayerType::CL1], "broadcast_transaction")?;
+        let nodes = self.base_urls();
+        let required = quorum.clamp(1, nodes.len());
+
+        let mut pending: FuturesUnordered<_> = nodes
+            .into_iter()
+            .map(|base_url| async move {
+                let result = self
+                    .client
+                    .post_to_node::<PostTransactionResponse, _>(
+                        base_url,
+                        "/transactions",
+                        transaction,
+                        options,
+                    )
+                    .await;
+                (base_url.to_string(), result)
+            })
+            .collect();
+
+        let mut outcome = BroadcastOutcome {
+            hash: hash_currency_transaction(transaction).value,
+            acknowledged: Vec::new(),
+            failed: Vec::new(),
+        };
+        while let Some((base_url, result)) = pending.next().await {
+            match result {
+                Ok(_) => outcome.acknowledged.push(base_url),
+                Err(e) if is_already_known(&e) => outcome.acknowledged.push(base_url),
+                Err(e) => outcome.failed.push((base_url, e)),
+            }
+            if outcome.acknowledged.len() >= required {
+                if let Some(cache) = &self.response_cache {
+                    cache.invalidate_transaction(transaction);
+                }
+                log_event!(
+                    Info,
+                    "broadcast transaction",
+                    "tx_hash" = outcome.hash,
+                    "acknowledged" = outcome.acknowledged.len(),
+                    "required" = required,
+                );
+                return Ok(outcome);
+            }
+        }
+
+        Err(NetworkError::QuorumNotReached {
+            acknowledged: outcome.acknowledged.len(),
+            required,
+            errors: outcome
+                .failed
+                .iter()
+                .map(|(base_url, e)| format!("{}: {}", base_url, e))
+                .collect(),
+        })
+    }
+}
+
+/// Whether a node refused the transaction only because it already has it
+fn is_already_known(error: &NetworkError) -> bool {
+    if error.status_code() == Some(409) {
+        return true;
+    }
+    let body = match error {
+        NetworkError::HttpError {
+            status_code: Some(400..=499),
+            response: Some(body),
+            ..
+        }
+        | NetworkError::Node { response: body, .. } => body,
+        _ => return false,
+    };
+    node_error::reasons(body).iter().any(|reason| {
+        let key: String = reason
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        [
+            "alreadyexist",
+            "alreadyknown",
+            "alreadyaccepted",
+            "duplicate",
+        ]
+        .iter()
+        .any(|pattern| key.contains(pattern))
+    })
+}
@@ -0,0 +1,98 @@
+//! Typed queries of a data application's calculated state
+//!
+//! Data applications built from the metagraph templates serve their current
+//! calculated state at `/data-application/calculated-state/latest` as
+//! `{"ordinal": ..., "calculatedState": ...}`. [`MetagraphClient::get_calculated_state`]
+//! deserializes the state into the application's own type, and
+//! [`MetagraphClient::get_calculated_state_with_proof`] also returns the
+//! `calculatedStateProof` hash committed in the currency snapshot of the same
+//! ordinal.
+//!
+//! # Example
+//!
+//! ```ignore
+//! #[derive(Deserialize)]
+//! struct VotingState {
+//!     polls: BTreeMap<String, Poll>,
+//! }
+//!
+//! let state: VotingState = ml0.get_calculated_state().await?;
+//!
+//! let proven = ml0.get_calculated_state_with_proof::<VotingState>().await?;
+//! println!("state at {} has hash {:?}", proven.ordinal, proven.hash);
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::types::NetworkResult;
+
+/// Route serving the latest calculated state
+const LATEST_PATH: &str = "/data-application/calculated-state/latest";
+
+/// A calculated state and where it was taken
+#[derive(Debug, Clone, PartialEq)]
+pub struct CalculatedState<S> {
+    /// Ordinal of the snapshot the state was calculated at
+    pub ordinal: i64,
+    /// The application's state
+    pub state: S,
+    /// `calculatedStateProof` of the snapshot at `ordinal`, if it has one
+    pub hash: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CalculatedStateResponse<S> {
+    ordinal: i64,
+    calculated_state: S,
+}
+
+impl MetagraphClient {
+    /// Get the latest calculated state, deserialized into `S`
+    ///
+    /// Available on: ML0, DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer, or a
+    /// `SerializationError` if the state does not match `S`
+    pub async fn get_calculated_state<S: DeserializeOwned>(&self) -> NetworkResult<S> {
+        self.assert_layer(&[LayerType::ML0, LayerType::DL1], "get_calculated_state")?;
+        let response: CalculatedStateResponse<S> = self.get(LATEST_PATH).await?;
+        Ok(response.calculated_state)
+    }
+
+    /// Get the latest calculated state with its ordinal and the state hash
+    /// committed in the snapshot at that ordinal
+    ///
+    /// The hash is computed by the data application, so it is returned as
+    /// published rather than re-derived from the state.
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer, or a
+    /// `SerializationError` if the state does not match `S`
+    pub async fn get_calculated_state_with_proof<S: DeserializeOwned>(
+        &self,
+    ) -> NetworkResult<CalculatedState<S>> {
+        self.assert_layer(&[LayerType::ML0], "get_calculated_state_with_proof")?;
+        let response: CalculatedStateResponse<S> = self.get(LATEST_PATH).await?;
+        let snapshot = self.get_snapshot(response.ordinal).await?;
+        let hash = snapshot
+            .value
+            .data_application
+            .as_ref()
+            .and_then(|part| part.get("calculatedStateProof"))
+            .and_then(|proof| proof.as_str())
+            .map(str::to_string);
+        Ok(CalculatedState {
+            ordinal: response.ordinal,
+            state: response.calculated_state,
+            hash,
+        })
+    }
+}
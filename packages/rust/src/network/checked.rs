@@ -0,0 +1,134 @@
+//! Local checks before a submission leaves the process
+//!
+//! [`MetagraphClient::post_transaction_checked`] and
+//! [`MetagraphClient::post_data_checked`] verify every proof and the fields a
+//! node would reject before making any network call, and report all problems
+//! at once as [`NetworkError::InvalidSubmission`]. Proofs are checked under
+//! the client's [`SignaturePolicy`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! match cl1.post_transaction_checked(&tx).await {
+//!     Err(NetworkError::InvalidSubmission(problems)) => {
+//!         for problem in problems {
+//!             eprintln!("not sent: {}", problem);
+//!         }
+//!     }
+//!     result => println!("{:?}", result?),
+//! }
+//! ```
+
+use serde::Serialize;
+
+use super::metagraph_client::MetagraphClient;
+use super::types::{NetworkError, NetworkResult, PostDataResponse, PostTransactionResponse};
+use crate::currency_transaction::{is_valid_dag_address, verify_currency_transaction_with_policy};
+use crate::currency_types::CurrencyTransaction;
+use crate::types::{Signed, VerificationResult};
+use crate::verify::{verify_with_policy, SignaturePolicy};
+use crate::wallet::try_get_address;
+
+/// Problems a node would reject a currency transaction for
+fn transaction_problems(transaction: &CurrencyTransaction, policy: SignaturePolicy) -> Vec<String> {
+    let tx = &transaction.value;
+    let mut problems = Vec::new();
+
+    if !is_valid_dag_address(&tx.source) {
+        problems.push(format!("invalid source address {:?}", tx.source));
+    }
+    if !is_valid_dag_address(&tx.destination) {
+        problems.push(format!("invalid destination address {:?}", tx.destination));
+    }
+    if tx.source == tx.destination {
+        problems.push("source and destination are the same address".to_string());
+    }
+    if tx.amount <= 0 {
+        problems.push(format!("amount must be positive, got {}", tx.amount));
+    }
+    if tx.fee < 0 {
+        problems.push(format!("fee must not be negative, got {}", tx.fee));
+    }
+    if tx.parent.ordinal < 0 {
+        problems.push(format!(
+            "parent ordinal must not be negative, got {}",
+            tx.parent.ordinal
+        ));
+    }
+    if tx.parent.hash.len() != 64 || !tx.parent.hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        problems.push(format!("invalid parent hash {:?}", tx.parent.hash));
+    }
+
+    proof_problems(
+        &verify_currency_transaction_with_policy(transaction, policy),
+        &mut problems,
+    );
+    if !transaction
+        .proofs
+        .iter()
+        .any(|proof| try_get_address(&proof.id).is_ok_and(|address| address == tx.source))
+    {
+        problems.push(format!("no proof is signed by the source {}", tx.source));
+    }
+    problems
+}
+
+/// Problems with the proofs of a signed object
+fn proof_problems(result: &VerificationResult, problems: &mut Vec<String>) {
+    if result.valid_proofs.is_empty() && result.invalid_proofs.is_empty() {
+        problems.push("no proofs".to_string());
+    }
+    for proof in &result.invalid_proofs {
+        problems.push(format!("invalid signature from {}", proof.id));
+    }
+}
+
+fn check(problems: Vec<String>) -> NetworkResult<()> {
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(NetworkError::InvalidSubmission(problems))
+    }
+}
+
+impl MetagraphClient {
+    /// Verify a transaction locally, then submit it
+    ///
+    /// Checks the addresses, amount, fee, and parent reference, that every
+    /// proof verifies, and that the source signed it.
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidSubmission` listing every problem found, without
+    /// sending anything; otherwise as [`post_transaction`](Self::post_transaction)
+    pub async fn post_transaction_checked(
+        &self,
+        transaction: &CurrencyTransaction,
+    ) -> NetworkResult<PostTransactionResponse> {
+        check(transaction_problems(transaction, self.signature_policy()))?;
+        self.post_transaction(transaction).await
+    }
+
+    /// Verify the proofs of a data update locally, then submit it
+    ///
+    /// Available on: DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidSubmission` listing every problem found, without
+    /// sending anything; otherwise as [`post_data`](Self::post_data)
+    pub async fn post_data_checked<T: Serialize>(
+        &self,
+        data: &Signed<T>,
+    ) -> NetworkResult<PostDataResponse> {
+        let mut problems = Vec::new();
+        proof_problems(
+            &verify_with_policy(data, true, self.signature_policy()),
+            &mut problems,
+        );
+        check(problems)?;
+        self.post_data(data).await
+    }
+}
@@ -0,0 +1,183 @@
+//! Circuit breaker for node endpoints
+//!
+//! After `failure_threshold` consecutive failures a [`CircuitBreaker`] opens
+//! and requests to that node are short-circuited instead of waiting for
+//! another timeout. Once `reset_timeout` has passed, a single probe request is
+//! let through: success closes the circuit, failure opens it again.
+//!
+//! [`HttpClient`](super::HttpClient) keeps one breaker per node. Breakers are
+//! disabled unless configured through
+//! [`HttpClient::with_circuit_breaker`](super::HttpClient::with_circuit_breaker)
+//! or [`NetworkConfig::circuit_breaker`](super::NetworkConfig::circuit_breaker).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{CircuitBreakerConfig, NetworkConfig};
+//! use std::time::Duration;
+//!
+//! let config = NetworkConfig {
+//!     l1_urls: vec!["http://node-1:9300".to_string(), "http://node-2:9300".to_string()],
+//!     circuit_breaker: Some(CircuitBreakerConfig {
+//!         failure_threshold: 3,
+//!         reset_timeout: Duration::from_secs(10),
+//!     }),
+//!     ..Default::default()
+//! };
+//! ```
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::rt::Instant;
+
+/// Configuration for a [`CircuitBreaker`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+    /// Time the circuit stays open before a probe request is allowed
+    pub reset_timeout: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            reset_timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl CircuitBreakerConfig {
+    /// A configuration whose circuit never opens
+    pub fn disabled() -> Self {
+        Self {
+            failure_threshold: u32::MAX,
+            ..Self::default()
+        }
+    }
+}
+
+/// Observable state of a [`CircuitBreaker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests are rejected until the reset timeout elapses
+    Open,
+    /// The next request (or the one in flight) probes for recovery
+    HalfOpen,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Closed,
+    Open { since: Instant },
+    HalfOpen { probe_started: Instant },
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: State,
+    consecutive_failures: u32,
+}
+
+/// Tracks consecutive failures of one endpoint and short-circuits it when open
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    /// Create a closed circuit breaker
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(Inner {
+                state: State::Closed,
+                consecutive_failures: 0,
+            }),
+        }
+    }
+
+    /// Get the configuration of this breaker
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    /// Get the current state
+    pub fn state(&self) -> CircuitState {
+        let inner = self.lock();
+        match inner.state {
+            State::Closed => CircuitState::Closed,
+            State::Open { since } if since.elapsed() < self.config.reset_timeout => {
+                CircuitState::Open
+            }
+            State::Open { .. } | State::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Number of failures recorded since the last success
+    pub fn consecutive_failures(&self) -> u32 {
+        self.lock().consecutive_failures
+    }
+
+    /// Whether a request may be sent now
+    ///
+    /// While half-open only one probe is allowed per `reset_timeout`, so
+    /// concurrent callers keep being short-circuited until the probe reports
+    /// back. A probe that never reports (e.g. a dropped future) is replaced
+    /// once the timeout passes again.
+    pub fn allow_request(&self) -> bool {
+        let mut inner = self.lock();
+        match inner.state {
+            State::Closed => true,
+            State::Open { since: started }
+            | State::HalfOpen {
+                probe_started: started,
+            } => {
+                if started.elapsed() >= self.config.reset_timeout {
+                    inner.state = State::HalfOpen {
+                        probe_started: Instant::now(),
+                    };
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Record a successful request, closing the circuit
+    pub fn record_success(&self) {
+        let mut inner = self.lock();
+        inner.state = State::Closed;
+        inner.consecutive_failures = 0;
+    }
+
+    /// Record a failed request, opening the circuit once the threshold is hit
+    pub fn record_failure(&self) {
+        let mut inner = self.lock();
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        match inner.state {
+            State::Closed if inner.consecutive_failures >= self.config.failure_threshold => {
+                inner.state = State::Open {
+                    since: Instant::now(),
+                };
+            }
+            State::HalfOpen { .. } => {
+                inner.state = State::Open {
+                    since: Instant::now(),
+                };
+            }
+            State::Closed | State::Open { .. } => {}
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        // The state stays consistent even if a holder panicked
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -1,58 +1,345 @@
 //! Base HTTP client for network operations
 
-use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use reqwest::{Client, RequestBuilder, Response};
 use serde::{de::DeserializeOwned, Serialize};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 
-use super::types::{NetworkError, NetworkResult};
+use super::body::{self, MAX_ERROR_BODY};
+use super::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use super::connection::ConnectionOptions;
+use super::idempotency::IDEMPOTENCY_HEADER;
+use super::interceptor::{Interceptor, RequestContext};
+use super::node_error::NodeError;
+use super::rate_limiter::{RateLimitConfig, RateLimiter};
+use super::retry::RetryPolicy;
+use super::rt::{self, Instant};
+use super::tls::TlsConfig;
+use super::types::{ErrorKind, NetworkError, NetworkResult, RequestOptions};
 
 const DEFAULT_TIMEOUT: u64 = 30;
+/// Large enough for mainnet global snapshots, small enough not to OOM
+const DEFAULT_MAX_RESPONSE_SIZE: usize = 256 * 1024 * 1024;
+const HEALTH_PATH: &str = "/cluster/info";
+
+struct Node {
+    base_url: String,
+    breaker: CircuitBreaker,
+}
 
 /// Simple HTTP client using reqwest
+///
+/// A client can front several equivalent nodes. Requests are spread
+/// round-robin over the nodes currently considered healthy and fail over to
+/// the next node when one stops responding. An optional per-node circuit
+/// breaker stops sending requests to a node that keeps failing.
 pub struct HttpClient {
     client: Client,
-    base_url: String,
+    timeout: Duration,
+    connection: ConnectionOptions,
+    tls: Option<TlsConfig>,
+    nodes: Vec<Node>,
+    next_node: AtomicUsize,
+    retry_policy: RetryPolicy,
+    default_headers: HeaderMap,
+    interceptors: Vec<Arc<dyn Interceptor>>,
+    rate_limiter: Option<Arc<RateLimiter>>,
+    max_response_size: usize,
 }
 
 impl HttpClient {
     /// Create a new HTTP client
     pub fn new(base_url: impl Into<String>, timeout: Option<u64>) -> NetworkResult<Self> {
-        let url = base_url.into();
-        let base_url = url.trim_end_matches('/').to_string();
-        if base_url.is_empty() {
+        Self::with_nodes(vec![base_url.into()], timeout)
+    }
+
+    /// Create an HTTP client that balances requests over several nodes
+    ///
+    /// GET requests fail over to the next node on timeouts, connection
+    /// errors, 429, and 5xx responses. POST requests only fail over when the
+    /// connection could not be established, since a node that timed out or
+    /// answered 5xx may already have accepted the submission.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no URL is given or any URL is empty
+    pub fn with_nodes(base_urls: Vec<String>, timeout: Option<u64>) -> NetworkResult<Self> {
+        let nodes = base_urls
+            .into_iter()
+            .map(|url| {
+                let base_url = url.trim_end_matches('/').to_string();
+                if base_url.is_empty() {
+                    return Err(NetworkError::ConfigError(
+                        "base_url is required".to_string(),
+                    ));
+                }
+                Ok(Node {
+                    base_url,
+                    breaker: CircuitBreaker::new(CircuitBreakerConfig::disabled()),
+                })
+            })
+            .collect::<NetworkResult<Vec<_>>>()?;
+        if nodes.is_empty() {
             return Err(NetworkError::ConfigError(
                 "base_url is required".to_string(),
             ));
         }
 
-        let timeout_secs = timeout.unwrap_or(DEFAULT_TIMEOUT);
-        let client = Client::builder()
-            .timeout(Duration::from_secs(timeout_secs))
-            .build()
-            .map_err(|e| NetworkError::http(e.to_string(), None, None))?;
+        let timeout = Duration::from_secs(timeout.unwrap_or(DEFAULT_TIMEOUT));
+        let connection = ConnectionOptions::default();
+        let client = build_client(timeout, &connection, None)?;
+
+        Ok(Self {
+            client,
+            timeout,
+            connection,
+            tls: None,
+            nodes,
+            next_node: AtomicUsize::new(0),
+            retry_policy: RetryPolicy::default(),
+            default_headers: HeaderMap::new(),
+            interceptors: Vec::new(),
+            rate_limiter: None,
+            max_response_size: DEFAULT_MAX_RESPONSE_SIZE,
+        })
+    }
+
+    /// Base URLs of all configured nodes
+    pub fn base_urls(&self) -> Vec<&str> {
+        self.nodes.iter().map(|n| n.base_url.as_str()).collect()
+    }
+
+    /// Base URLs of the nodes currently considered healthy
+    ///
+    /// A node is healthy when its last request or probe succeeded.
+    pub fn healthy_urls(&self) -> Vec<&str> {
+        self.nodes
+            .iter()
+            .filter(|n| n.breaker.consecutive_failures() == 0)
+            .map(|n| n.base_url.as_str())
+            .collect()
+    }
+
+    /// Get the circuit breaker of the node with the given base URL
+    pub fn circuit_breaker(&self, base_url: &str) -> Option<&CircuitBreaker> {
+        let base_url = base_url.trim_end_matches('/');
+        self.nodes
+            .iter()
+            .find(|n| n.base_url == base_url)
+            .map(|n| &n.breaker)
+    }
+
+    /// Add headers sent with every request, including health probes
+    ///
+    /// Per-request headers from [`RequestOptions`] take precedence.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if a header name or value is invalid
+    pub fn with_default_headers(
+        mut self,
+        headers: &std::collections::HashMap<String, String>,
+    ) -> NetworkResult<Self> {
+        self.default_headers.extend(header_map(headers)?);
+        Ok(self)
+    }
+
+    /// Send `Authorization: Bearer <token>` with every request
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the token is not a valid header value
+    pub fn with_bearer_token(mut self, token: &str) -> NetworkResult<Self> {
+        let mut value = HeaderValue::from_str(&format!("Bearer {}", token))
+            .map_err(|_| NetworkError::ConfigError("invalid bearer token".to_string()))?;
+        value.set_sensitive(true);
+        self.default_headers.insert(AUTHORIZATION, value);
+        Ok(self)
+    }
+
+    /// Use custom root CAs and/or a client certificate for HTTPS
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if a certificate or the identity cannot be parsed
+    pub fn with_tls(mut self, tls: &TlsConfig) -> NetworkResult<Self> {
+        self.client = build_client(self.timeout, &self.connection, Some(tls))?;
+        self.tls = Some(tls.clone());
+        Ok(self)
+    }
+
+    /// Tune connection pooling, keepalive, and connect/read timeouts
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the HTTP client cannot be rebuilt
+    pub fn with_connection_options(mut self, connection: ConnectionOptions) -> NetworkResult<Self> {
+        self.client = build_client(self.timeout, &connection, self.tls.as_ref())?;
+        self.connection = connection;
+        Ok(self)
+    }
+
+    /// Add an interceptor, invoked after the ones already registered
+    pub fn with_interceptor(self, interceptor: impl Interceptor + 'static) -> Self {
+        self.with_shared_interceptor(Arc::new(interceptor))
+    }
+
+    /// Add an interceptor shared with other clients
+    pub fn with_shared_interceptor(mut self, interceptor: Arc<dyn Interceptor>) -> Self {
+        self.interceptors.push(interceptor);
+        self
+    }
+
+    /// Enable a circuit breaker on every node
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        for node in &mut self.nodes {
+            node.breaker = CircuitBreaker::new(config.clone());
+        }
+        self
+    }
 
-        Ok(Self { client, base_url })
+    /// Limit how fast this client sends requests
+    pub fn with_rate_limit(self, config: RateLimitConfig) -> Self {
+        self.with_shared_rate_limiter(Arc::new(RateLimiter::new(config)))
+    }
+
+    /// Limit this client with a rate limiter shared with other clients
+    pub fn with_shared_rate_limiter(mut self, limiter: Arc<RateLimiter>) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// Fail requests whose response body is larger than `bytes`
+    ///
+    /// Bodies are read in chunks and abandoned with
+    /// [`NetworkError::ResponseTooLarge`] once they pass the limit, so a
+    /// misbehaving endpoint cannot exhaust memory. The default is 256 MiB.
+    pub fn with_max_response_size(mut self, bytes: usize) -> Self {
+        self.max_response_size = bytes;
+        self
+    }
+
+    /// Largest response body accepted, in bytes
+    pub fn max_response_size(&self) -> usize {
+        self.max_response_size
+    }
+
+    /// Probe every node and update its health, returning the healthy count
+    ///
+    /// Nodes with failed requests are tried last, so long-running callers
+    /// should run this periodically to move recovered nodes back into
+    /// rotation. Probes bypass open circuit breakers and close them on
+    /// success.
+    pub async fn check_nodes(&self) -> usize {
+        let mut healthy = 0;
+        for node in &self.nodes {
+            let url = format!("{}{}", node.base_url, HEALTH_PATH);
+            let response = self
+                .send(
+                    self.client
+                        .get(&url)
+                        .headers(self.default_headers.clone())
+                        .timeout(self.timeout),
+                )
+                .await;
+            let ok = match response {
+                Ok(response) => {
+                    body::decode_json::<serde_json::Value>(response, self.max_response_size)
+                        .await
+                        .is_ok()
+                }
+                Err(_) => false,
+            };
+            if ok {
+                node.breaker.record_success();
+                healthy += 1;
+            } else {
+                node.breaker.record_failure();
+            }
+        }
+        healthy
+    }
+
+    /// Replace the retry policy (GET requests retry by default)
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Get the retry policy in use
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
     }
 
     /// Make a GET request
     pub async fn get<T: DeserializeOwned>(&self, path: &str) -> NetworkResult<T> {
-        let url = format!("{}{}", self.base_url, path);
+        self.get_with_options(path, &RequestOptions::default())
+            .await
+    }
 
+    /// Make a GET request with per-request options
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if a header name or value is invalid
+    pub async fn get_with_options<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        options: &RequestOptions,
+    ) -> NetworkResult<T> {
         let response = self
-            .client
-            .get(&url)
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .map_err(|e| {
-                if e.is_timeout() {
-                    NetworkError::Timeout
-                } else {
-                    NetworkError::http(e.to_string(), None, None)
-                }
-            })?;
+            .execute(
+                path,
+                |url| self.client.get(url).header("Accept", "application/json"),
+                true,
+                options,
+            )
+            .await?;
+        body::decode_json(response, self.max_response_size).await
+    }
 
-        self.handle_response(response).await
+    /// Make a GET request for a large JSON document, parsing it as it
+    /// downloads
+    ///
+    /// On native targets the body is parsed on a blocking thread while it
+    /// arrives, so it is never buffered whole; use this for snapshots and
+    /// other multi-megabyte responses. Requires a tokio runtime.
+    ///
+    /// # Errors
+    ///
+    /// Returns `ResponseTooLarge` if the body passes
+    /// [`max_response_size`](Self::max_response_size), or an error if the
+    /// request fails or the body is not valid JSON for `T`
+    pub async fn get_streaming<T: DeserializeOwned + Send + 'static>(
+        &self,
+        path: &str,
+        options: &RequestOptions,
+    ) -> NetworkResult<T> {
+        let response = self
+            .execute(
+                path,
+                |url| self.client.get(url).header("Accept", "application/json"),
+                true,
+                options,
+            )
+            .await?;
+        body::decode_json_streaming(response, self.max_response_size).await
+    }
+
+    /// Make a GET request for a plain-text resource, such as a Prometheus
+    /// metrics page
+    pub async fn get_text(&self, path: &str) -> NetworkResult<String> {
+        let response = self
+            .execute(
+                path,
+                |url| self.client.get(url).header("Accept", "text/plain"),
+                true,
+                &RequestOptions::default(),
+            )
+            .await?;
+        body::read_text(response, self.max_response_size).await
     }
 
     /// Make a POST request
@@ -61,36 +348,314 @@ impl HttpClient {
         path: &str,
         body: &B,
     ) -> NetworkResult<T> {
-        let url = format!("{}{}", self.base_url, path);
+        self.post_with_options(path, body, &RequestOptions::default())
+            .await
+    }
+
+    /// Make a POST request with per-request options
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if a header name or value is invalid
+    pub async fn post_with_options<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+        options: &RequestOptions,
+    ) -> NetworkResult<T> {
+        let response = self
+            .execute(
+                path,
+                |url| {
+                    self.client
+                        .post(url)
+                        .header("Accept", "application/json")
+                        .header("Content-Type", "application/json")
+                        .json(body)
+                },
+                false,
+                options,
+            )
+            .await?;
+        body::decode_json(response, self.max_response_size).await
+    }
 
+    /// Make a POST request to one configured node, without failing over
+    ///
+    /// Lets a caller submit the same request to every node, as
+    /// [`MetagraphClient::broadcast_transaction`](super::MetagraphClient::broadcast_transaction)
+    /// does. The node's circuit breaker and the retry policy still apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if `base_url` is not one of this client's nodes
+    /// or a header name or value is invalid
+    pub async fn post_to_node<T: DeserializeOwned, B: Serialize>(
+        &self,
+        base_url: &str,
+        path: &str,
+        body: &B,
+        options: &RequestOptions,
+    ) -> NetworkResult<T> {
+        let base_url = base_url.trim_end_matches('/');
+        let index = self
+            .nodes
+            .iter()
+            .position(|node| node.base_url == base_url)
+            .ok_or_else(|| NetworkError::ConfigError(format!("unknown node {}", base_url)))?;
         let response = self
-            .client
-            .post(&url)
-            .header("Accept", "application/json")
-            .header("Content-Type", "application/json")
-            .json(body)
-            .send()
+            .execute_on(
+                Some(index),
+                path,
+                |url| {
+                    self.client
+                        .post(url)
+                        .header("Accept", "application/json")
+                        .header("Content-Type", "application/json")
+                        .json(body)
+                },
+                false,
+                options,
+            )
+            .await?;
+        body::decode_json(response, self.max_response_size).await
+    }
+
+    /// Make a POST request and return the response body as text, for
+    /// endpoints that answer with an empty or non-JSON body
+    pub async fn post_text<B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+        options: &RequestOptions,
+    ) -> NetworkResult<String> {
+        let response = self
+            .execute(
+                path,
+                |url| {
+                    self.client
+                        .post(url)
+                        .header("Content-Type", "application/json")
+                        .json(body)
+                },
+                false,
+                options,
+            )
+            .await?;
+        body::read_text(response, self.max_response_size).await
+    }
+
+    async fn execute(
+        &self,
+        path: &str,
+        build: impl Fn(&str) -> RequestBuilder,
+        idempotent: bool,
+        options: &RequestOptions,
+    ) -> NetworkResult<Response> {
+        self.execute_on(None, path, build, idempotent, options)
             .await
-            .map_err(|e| {
-                if e.is_timeout() {
+    }
+
+    /// Send a request to the node at index `only`, or to any node with failover
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(path = %path)))]
+    async fn execute_on(
+        &self,
+        only: Option<usize>,
+        path: &str,
+        build: impl Fn(&str) -> RequestBuilder,
+        idempotent: bool,
+        options: &RequestOptions,
+    ) -> NetworkResult<Response> {
+        let headers = header_map(&options.headers)?;
+        let build = |url: &str| {
+            let mut request = build(url)
+                .headers(self.default_headers.clone())
+                .headers(headers.clone());
+            if let Some(key) = &options.idempotency_key {
+                request = request.header(IDEMPOTENCY_HEADER, key);
+            }
+            if !options.query.is_empty() {
+                request = request.query(&options.query);
+            }
+            let timeout = options.timeout.map(Duration::from_secs);
+            // fetch has no client-wide timeout, so wasm builds set it per request
+            #[cfg(target_arch = "wasm32")]
+            let timeout = timeout.or(Some(self.timeout));
+            if let Some(timeout) = timeout {
+                request = request.timeout(timeout);
+            }
+            request
+        };
+
+        let policy = options.retry.as_ref().unwrap_or(&self.retry_policy);
+        let retryable = idempotent || policy.retry_posts;
+        let mut attempt = 1;
+        loop {
+            match self
+                .send_with_failover(only, path, &build, idempotent)
+                .await
+            {
+                Err(e) if retryable && attempt < policy.max_attempts && policy.should_retry(&e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(attempt, error = %e, "request failed, retrying");
+                    log_event!(
+                        Warn,
+                        "request failed, retrying",
+                        "path" = path,
+                        "attempt" = attempt,
+                        "error" = e,
+                    );
+                    rt::sleep(policy.backoff(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn send_with_failover(
+        &self,
+        only: Option<usize>,
+        path: &str,
+        build: &impl Fn(&str) -> RequestBuilder,
+        idempotent: bool,
+    ) -> NetworkResult<Response> {
+        let mut last_error = None;
+        let order = match only {
+            Some(index) => vec![index],
+            None => self.node_order(),
+        };
+        for index in order {
+            let node = &self.nodes[index];
+            if !node.breaker.allow_request() {
+                last_error.get_or_insert(NetworkError::CircuitOpen(node.base_url.clone()));
+                continue;
+            }
+            let url = format!("{}{}", node.base_url, path);
+            if let Some(limiter) = &self.rate_limiter {
+                limiter.acquire().await;
+            }
+            match self.send(build(&url)).await {
+                Ok(value) => {
+                    node.breaker.record_success();
+                    return Ok(value);
+                }
+                Err(e) => {
+                    let node_failure = is_node_failure(&e);
+                    if node_failure {
+                        node.breaker.record_failure();
+                    } else {
+                        node.breaker.record_success();
+                    }
+                    let fail_over = if idempotent {
+                        node_failure
+                    } else {
+                        is_connection_failure(&e)
+                    };
+                    if !fail_over {
+                        return Err(e);
+                    }
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| NetworkError::ConfigError("no nodes configured".to_string())))
+    }
+
+    /// Node indices to try for one request, starting from the next
+    /// round-robin position with the fewest consecutive failures first
+    fn node_order(&self) -> Vec<usize> {
+        let count = self.nodes.len();
+        let start = self.next_node.fetch_add(1, Ordering::Relaxed) % count;
+        let mut order: Vec<usize> = (0..count).map(|i| (start + i) % count).collect();
+        order.sort_by_key(|&i| self.nodes[i].breaker.consecutive_failures());
+        order
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(method, url, status, latency_ms))
+    )]
+    async fn send(&self, request: RequestBuilder) -> NetworkResult<Response> {
+        let mut request = request
+            .build()
+            .map_err(|e| NetworkError::ConfigError(format!("invalid request: {}", e)))?;
+        for interceptor in &self.interceptors {
+            interceptor.before_send(&mut request)?;
+        }
+        record_span!("method", request.method().as_str());
+        record_span!("url", request.url().as_str());
+
+        let method = request.method().clone();
+        let url = request.url().clone();
+        let started = Instant::now();
+        let response = match self.client.execute(request).await {
+            Ok(response) => response,
+            Err(e) => {
+                let error = if e.is_timeout() {
                     NetworkError::Timeout
                 } else {
                     NetworkError::http(e.to_string(), None, None)
+                };
+                let context = RequestContext {
+                    method: &method,
+                    url: &url,
+                    elapsed: started.elapsed(),
+                };
+                for interceptor in &self.interceptors {
+                    interceptor.on_error(&context, &error);
                 }
-            })?;
+                record_span!("latency_ms", started.elapsed().as_millis() as u64);
+                #[cfg(feature = "tracing")]
+                tracing::debug!(error = %error, "request failed");
+                log_event!(
+                    Debug,
+                    "request failed",
+                    "method" = method,
+                    "url" = url,
+                    "error" = error,
+                );
+                return Err(error);
+            }
+        };
+        let context = RequestContext {
+            method: &method,
+            url: &url,
+            elapsed: started.elapsed(),
+        };
+        for interceptor in &self.interceptors {
+            interceptor.after_receive(&context, &response);
+        }
+        record_span!("status", response.status().as_u16());
+        record_span!("latency_ms", started.elapsed().as_millis() as u64);
+        log_event!(
+            Debug,
+            "request completed",
+            "method" = method,
+            "url" = url,
+            "status" = response.status().as_u16(),
+            "latency_ms" = started.elapsed().as_millis(),
+        );
 
         self.handle_response(response).await
     }
 
-    async fn handle_response<T: DeserializeOwned>(
-        &self,
-        response: reqwest::Response,
-    ) -> NetworkResult<T> {
+    async fn handle_response(&self, response: Response) -> NetworkResult<Response> {
         let status = response.status();
         let status_code = status.as_u16();
 
         if !status.is_success() {
-            let body = response.text().await.unwrap_or_default();
+            let body = body::read_truncated(response, MAX_ERROR_BODY).await;
+            if status.is_client_error() && status_code != 429 {
+                if let Some(error) = NodeError::parse(&body) {
+                    return Err(NetworkError::Node {
+                        status_code,
+                        error,
+                        response: body,
+                    });
+                }
+            }
             return Err(NetworkError::http(
                 format!(
                     "HTTP {}: {}",
@@ -102,9 +667,57 @@ impl HttpClient {
             ));
         }
 
-        response
-            .json()
-            .await
-            .map_err(|e| NetworkError::SerializationError(e.to_string()))
+        Ok(response)
+    }
+}
+
+/// Whether an error indicates the node itself is unavailable or overloaded
+fn is_node_failure(error: &NetworkError) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::Timeout
+            | ErrorKind::Connection
+            | ErrorKind::RateLimited
+            | ErrorKind::ServerError
+    )
+}
+
+/// Whether an error happened before the request reached the node
+fn is_connection_failure(error: &NetworkError) -> bool {
+    error.kind() == ErrorKind::Connection
+}
+
+fn build_client(
+    timeout: Duration,
+    connection: &ConnectionOptions,
+    tls: Option<&TlsConfig>,
+) -> NetworkResult<Client> {
+    #[cfg(not(target_arch = "wasm32"))]
+    let mut builder = connection.apply(Client::builder().timeout(timeout));
+    // In the browser, timeouts are set per request and fetch owns the connections
+    #[cfg(target_arch = "wasm32")]
+    let mut builder = {
+        let _ = (timeout, connection);
+        Client::builder()
+    };
+    if let Some(tls) = tls {
+        builder = tls.apply(builder)?;
+    }
+    builder
+        .build()
+        .map_err(|e| NetworkError::ConfigError(format!("failed to build HTTP client: {}", e)))
+}
+
+fn header_map(headers: &std::collections::HashMap<String, String>) -> NetworkResult<HeaderMap> {
+    let mut map = HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers {
+        let name = HeaderName::from_bytes(name.as_bytes()).map_err(|e| {
+            NetworkError::ConfigError(format!("invalid header name {}: {}", name, e))
+        })?;
+        let value = HeaderValue::from_str(value).map_err(|e| {
+            NetworkError::ConfigError(format!("invalid value for header {}: {}", name, e))
+        })?;
+        map.insert(name, value);
     }
+    Ok(map)
 }
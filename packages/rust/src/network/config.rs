@@ -0,0 +1,317 @@
+//! Network endpoint configuration
+//!
+//! [`NetworkConfig`] collects the node URLs of one deployment (Global L0 plus a
+//! metagraph's L0/L1 layers) and builds the matching clients.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::NetworkConfig;
+//!
+//! let config = NetworkConfig {
+//!     l0_url: Some("http://localhost:9000".to_string()),
+//!     currency_l0_url: Some("http://localhost:9200".to_string()),
+//!     l1_url: Some("http://localhost:9300".to_string()),
+//!     data_l1_url: Some("http://localhost:9400".to_string()),
+//!     ..Default::default()
+//! };
+//!
+//! let gl0 = config.global_l0_client()?;
+//! let ml0 = config.currency_l0_client()?;
+//! let cl1 = config.currency_l1_client()?;
+//! let dl1 = config.data_l1_client()?;
+//! ```
+//!
+//! L1 layers usually run several nodes. Listing them in `l1_urls` /
+//! `data_l1_urls` makes the client spread requests over every node and fail
+//! over when one becomes unavailable:
+//!
+//! ```ignore
+//! let config = NetworkConfig {
+//!     l1_urls: vec![
+//!         "http://node-1:9300".to_string(),
+//!         "http://node-2:9300".to_string(),
+//!     ],
+//!     ..Default::default()
+//! };
+//! let cl1 = config.currency_l1_client()?;
+//! ```
+//!
+//! The public networks have presets, which fill in the Global L0, DAG L1, and
+//! block explorer load balancers. [`NetworkConfig::for_metagraph`] switches a
+//! preset over to a metagraph's token; the metagraph's own L0/L1 URLs are
+//! then added as usual:
+//!
+//! ```ignore
+//! let dag = NetworkConfig::mainnet().currency_l1_client()?;
+//!
+//! let config = NetworkConfig {
+//!     currency_l0_url: Some("http://my-metagraph:9200".to_string()),
+//!     l1_url: Some("http://my-metagraph:9300".to_string()),
+//!     ..NetworkConfig::mainnet().for_metagraph("DAG...")
+//! };
+//! ```
+//!
+//! Deployments behind an authenticated gateway can attach credentials to every
+//! request with `bearer_token` or `default_headers`; endpoints with private or
+//! mutual TLS are configured through [`TlsConfig`].
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use super::block_explorer::BlockExplorerClient;
+use super::circuit_breaker::CircuitBreakerConfig;
+use super::client::HttpClient;
+use super::connection::ConnectionOptions;
+use super::global_l0_client::GlobalL0Client;
+use super::interceptor::Interceptor;
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::rate_limiter::RateLimitConfig;
+use super::retry::RetryPolicy;
+use super::tls::TlsConfig;
+use super::types::{NetworkError, NetworkResult};
+
+/// Node endpoints for a Constellation deployment
+#[derive(Clone, Default)]
+pub struct NetworkConfig {
+    /// Global L0 endpoint
+    pub l0_url: Option<String>,
+    /// Metagraph (currency) L0 endpoint
+    pub currency_l0_url: Option<String>,
+    /// Currency L1 endpoint
+    pub l1_url: Option<String>,
+    /// Data L1 endpoint
+    pub data_l1_url: Option<String>,
+    /// Block explorer endpoint for transaction history
+    pub block_explorer_url: Option<String>,
+    /// Metagraph whose token the block explorer client queries (`None` for DAG)
+    pub metagraph_id: Option<String>,
+    /// Additional Currency L1 nodes to balance and fail over across
+    pub l1_urls: Vec<String>,
+    /// Additional Data L1 nodes to balance and fail over across
+    pub data_l1_urls: Vec<String>,
+    /// Request timeout in seconds (default: 30)
+    pub timeout: Option<u64>,
+    /// Connection pool, keepalive, and connect/read timeout settings
+    pub connection: ConnectionOptions,
+    /// Retry policy for every client (default: [`RetryPolicy::default`])
+    pub retry: Option<RetryPolicy>,
+    /// Per-node circuit breaker for every client (default: disabled)
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    /// Request rate limit, applied to each client separately (default: none)
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Headers sent with every request, e.g. gateway or Cloudflare Access credentials
+    pub default_headers: HashMap<String, String>,
+    /// Token sent as `Authorization: Bearer <token>` with every request
+    pub bearer_token: Option<String>,
+    /// Custom root CAs and client certificate for HTTPS endpoints
+    pub tls: Option<TlsConfig>,
+    /// Interceptors installed on every client, in order
+    pub interceptors: Vec<Arc<dyn Interceptor>>,
+}
+
+impl fmt::Debug for NetworkConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Header values and tokens are credentials; only show header names
+        f.debug_struct("NetworkConfig")
+            .field("l0_url", &self.l0_url)
+            .field("currency_l0_url", &self.currency_l0_url)
+            .field("l1_url", &self.l1_url)
+            .field("data_l1_url", &self.data_l1_url)
+            .field("block_explorer_url", &self.block_explorer_url)
+            .field("metagraph_id", &self.metagraph_id)
+            .field("l1_urls", &self.l1_urls)
+            .field("data_l1_urls", &self.data_l1_urls)
+            .field("timeout", &self.timeout)
+            .field("connection", &self.connection)
+            .field("retry", &self.retry)
+            .field("circuit_breaker", &self.circuit_breaker)
+            .field("rate_limit", &self.rate_limit)
+            .field("default_headers", &self.default_headers.keys())
+            .field(
+                "bearer_token",
+                &self.bearer_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("tls", &self.tls)
+            .field("interceptors", &self.interceptors.len())
+            .finish()
+    }
+}
+
+/// Public load balancers of a hosted network
+struct Preset {
+    l0: &'static str,
+    l1: &'static str,
+    block_explorer: &'static str,
+}
+
+const MAINNET: Preset = Preset {
+    l0: "https://l0-lb-mainnet.constellationnetwork.io",
+    l1: "https://l1-lb-mainnet.constellationnetwork.io",
+    block_explorer: "https://be-mainnet.constellationnetwork.io",
+};
+
+const TESTNET: Preset = Preset {
+    l0: "https://l0-lb-testnet.constellationnetwork.io",
+    l1: "https://l1-lb-testnet.constellationnetwork.io",
+    block_explorer: "https://be-testnet.constellationnetwork.io",
+};
+
+const INTEGRATIONNET: Preset = Preset {
+    l0: "https://l0-lb-integrationnet.constellationnetwork.io",
+    l1: "https://l1-lb-integrationnet.constellationnetwork.io",
+    block_explorer: "https://be-integrationnet.constellationnetwork.io",
+};
+
+impl From<&Preset> for NetworkConfig {
+    fn from(preset: &Preset) -> Self {
+        Self {
+            l0_url: Some(preset.l0.to_string()),
+            l1_url: Some(preset.l1.to_string()),
+            block_explorer_url: Some(preset.block_explorer.to_string()),
+            ..Default::default()
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Public endpoints of MainNet: Global L0, DAG L1, and block explorer
+    pub fn mainnet() -> Self {
+        Self::from(&MAINNET)
+    }
+
+    /// Public endpoints of TestNet: Global L0, DAG L1, and block explorer
+    pub fn testnet() -> Self {
+        Self::from(&TESTNET)
+    }
+
+    /// Public endpoints of IntegrationNet: Global L0, DAG L1, and block explorer
+    pub fn integrationnet() -> Self {
+        Self::from(&INTEGRATIONNET)
+    }
+
+    /// Point the configuration at a metagraph's token instead of DAG
+    ///
+    /// Sets `metagraph_id`, so the block explorer client serves the token's
+    /// history, and clears `l1_url` / `l1_urls`, since the DAG L1 nodes of a
+    /// preset do not process metagraph transactions. The metagraph's L0 and
+    /// L1 URLs still have to be set.
+    pub fn for_metagraph(mut self, metagraph_id: impl Into<String>) -> Self {
+        self.metagraph_id = Some(metagraph_id.into());
+        self.l1_url = None;
+        self.l1_urls.clear();
+        self
+    }
+
+    /// Create a client for the Global L0 endpoint
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `l0_url` is not set
+    pub fn global_l0_client(&self) -> NetworkResult<GlobalL0Client> {
+        let url = required(&self.l0_url, "l0_url")?;
+        Ok(GlobalL0Client::with_http_client(
+            self.http_client(vec![url.to_string()])?,
+        ))
+    }
+
+    /// Create a client for the metagraph L0 endpoint
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `currency_l0_url` is not set
+    pub fn currency_l0_client(&self) -> NetworkResult<MetagraphClient> {
+        self.metagraph_client(
+            &self.currency_l0_url,
+            &[],
+            "currency_l0_url",
+            LayerType::ML0,
+        )
+    }
+
+    /// Create a client for the Currency L1 nodes (`l1_url` and `l1_urls`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if neither `l1_url` nor `l1_urls` is set
+    pub fn currency_l1_client(&self) -> NetworkResult<MetagraphClient> {
+        self.metagraph_client(&self.l1_url, &self.l1_urls, "l1_url", LayerType::CL1)
+    }
+
+    /// Create a client for the Data L1 nodes (`data_l1_url` and `data_l1_urls`)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if neither `data_l1_url` nor `data_l1_urls` is set
+    pub fn data_l1_client(&self) -> NetworkResult<MetagraphClient> {
+        self.metagraph_client(
+            &self.data_l1_url,
+            &self.data_l1_urls,
+            "data_l1_url",
+            LayerType::DL1,
+        )
+    }
+
+    /// Create a client for the block explorer
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `block_explorer_url` is not set
+    pub fn block_explorer_client(&self) -> NetworkResult<BlockExplorerClient> {
+        let url = required(&self.block_explorer_url, "block_explorer_url")?;
+        let client =
+            BlockExplorerClient::with_http_client(self.http_client(vec![url.to_string()])?);
+        Ok(match &self.metagraph_id {
+            Some(id) => client.for_metagraph(id.clone()),
+            None => client,
+        })
+    }
+
+    fn metagraph_client(
+        &self,
+        url: &Option<String>,
+        extra_urls: &[String],
+        field: &str,
+        layer: LayerType,
+    ) -> NetworkResult<MetagraphClient> {
+        let urls: Vec<String> = url.iter().chain(extra_urls).cloned().collect();
+        if urls.is_empty() {
+            return Err(NetworkError::ConfigError(format!("{} is required", field)));
+        }
+        Ok(MetagraphClient::with_http_client(
+            self.http_client(urls)?,
+            layer,
+        ))
+    }
+
+    fn http_client(&self, urls: Vec<String>) -> NetworkResult<HttpClient> {
+        let mut client = HttpClient::with_nodes(urls, self.timeout)?
+            .with_connection_options(self.connection.clone())?;
+        if let Some(tls) = &self.tls {
+            client = client.with_tls(tls)?;
+        }
+        if let Some(policy) = &self.retry {
+            client = client.with_retry_policy(policy.clone());
+        }
+        if let Some(breaker) = &self.circuit_breaker {
+            client = client.with_circuit_breaker(breaker.clone());
+        }
+        if let Some(rate_limit) = &self.rate_limit {
+            client = client.with_rate_limit(rate_limit.clone());
+        }
+        client = client.with_default_headers(&self.default_headers)?;
+        if let Some(token) = &self.bearer_token {
+            client = client.with_bearer_token(token)?;
+        }
+        for interceptor in &self.interceptors {
+            client = client.with_shared_interceptor(Arc::clone(interceptor));
+        }
+        Ok(client)
+    }
+}
+
+fn required<'a>(url: &'a Option<String>, field: &str) -> NetworkResult<&'a str> {
+    url.as_deref()
+        .ok_or_else(|| NetworkError::ConfigError(format!("{} is required", field)))
+}
@@ -0,0 +1,243 @@
+//! Waiting for a submitted transaction to settle
+//!
+//! [`MetagraphClient::wait_for_confirmation`] polls a Currency L1 node until a
+//! transaction leaves the pending pool, then compares the source address's
+//! last reference with the transaction's parent to tell whether it was
+//! accepted, replaced, or dropped.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{ConfirmationOutcome, PollOptions};
+//! use std::time::Duration;
+//!
+//! cl1.post_transaction(&tx).await?;
+//! let outcome = cl1
+//!     .wait_for_confirmation(
+//!         &tx,
+//!         &PollOptions {
+//!             timeout: Some(Duration::from_secs(60)),
+//!             ..Default::default()
+//!         },
+//!     )
+//!     .await?;
+//! assert_eq!(outcome, ConfirmationOutcome::Confirmed);
+//! ```
+
+use std::future::Future;
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::api::CurrencyL1Api;
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::rt::{self, Instant};
+use super::types::{NetworkResult, TransactionStatus};
+use crate::currency_transaction::hash_currency_transaction;
+use crate::currency_types::{CurrencyTransaction, TransactionReference};
+
+/// Polling schedule for [`MetagraphClient::wait_for_confirmation`]
+#[derive(Debug, Clone)]
+pub struct PollOptions {
+    /// Delay before the second poll
+    pub interval: Duration,
+    /// Upper bound for the delay between polls
+    pub max_interval: Duration,
+    /// Factor applied to the delay after every poll
+    pub multiplier: f64,
+    /// Give up after this long (`None` waits indefinitely)
+    pub timeout: Option<Duration>,
+    /// Stop waiting once this flag is set
+    pub cancel: Option<Arc<AtomicBool>>,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(10),
+            multiplier: 1.5,
+            timeout: Some(Duration::from_secs(120)),
+            cancel: None,
+        }
+    }
+}
+
+/// How waiting for a transaction ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmationOutcome {
+    /// Left the pending pool and became the source's last reference
+    Confirmed,
+    /// Left the pending pool and a different transaction took its ordinal
+    Conflicted {
+        /// The source's last reference when the conflict was detected
+        last_reference: TransactionReference,
+    },
+    /// Left the pending pool without being accepted; safe to rebroadcast
+    Dropped,
+    /// Left the pending pool and the source chain has since moved more than
+    /// one ordinal past its parent, so L1 alone cannot tell whether it was
+    /// accepted
+    Superseded {
+        /// The source's last reference when the transaction was last checked
+        last_reference: TransactionReference,
+    },
+    /// Still pending when the timeout elapsed
+    TimedOut {
+        /// Pending status observed by the last poll
+        last_status: TransactionStatus,
+    },
+    /// The cancel flag was set before the transaction settled
+    Cancelled,
+}
+
+impl ConfirmationOutcome {
+    /// Whether the transaction is known to be accepted
+    pub fn is_confirmed(&self) -> bool {
+        matches!(self, ConfirmationOutcome::Confirmed)
+    }
+}
+
+impl MetagraphClient {
+    /// Poll until a submitted transaction leaves the pending pool
+    ///
+    /// Polls with exponential backoff as described by `options`. Once the
+    /// transaction is no longer pending, the source's last reference decides
+    /// the outcome, so a transaction accepted before the first poll is still
+    /// reported as confirmed. Request errors end the wait and are returned
+    /// as-is.
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer or a request fails
+    pub async fn wait_for_confirmation(
+        &self,
+        transaction: &CurrencyTransaction,
+        options: &PollOptions,
+    ) -> NetworkResult<ConfirmationOutcome> {
+        self.assert_layer(&[LayerType::CL1], "wait_for_confirmation")?;
+        wait_for_confirmation(self, transaction, options).await
+    }
+}
+
+pub(super) async fn wait_for_confirmation<C: CurrencyL1Api + ?Sized>(
+    client: &C,
+    transaction: &CurrencyTransaction,
+    options: &PollOptions,
+) -> NetworkResult<ConfirmationOutcome> {
+    let hash = hash_currency_transaction(transaction).value;
+    let pending = poll_until(options, || {
+        let hash = hash.as_str();
+        async move {
+            Ok(match client.get_pending_transaction(hash).await? {
+                Some(pending) => ControlFlow::Continue(pending.status),
+                None => ControlFlow::Break(()),
+            })
+        }
+    })
+    .await?;
+    match pending {
+        Polled::Done(()) => {}
+        Polled::TimedOut(last_status) => return Ok(ConfirmationOutcome::TimedOut { last_status }),
+        Polled::Cancelled => return Ok(ConfirmationOutcome::Cancelled),
+    }
+
+    let last_reference = client.get_last_reference(&transaction.value.source).await?;
+
+    Ok(
+        match settle(&hash, &transaction.value.parent, &last_reference) {
+            Settlement::Confirmed => ConfirmationOutcome::Confirmed,
+            Settlement::Dropped => ConfirmationOutcome::Dropped,
+            Settlement::Conflicted => ConfirmationOutcome::Conflicted { last_reference },
+            Settlement::Superseded => ConfirmationOutcome::Superseded { last_reference },
+        },
+    )
+}
+
+/// How a [`poll_until`] ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Polled<T, P> {
+    /// The check finished with this value
+    Done(T),
+    /// The timeout elapsed; the last check was still waiting on this
+    TimedOut(P),
+    /// The cancel flag was set
+    Cancelled,
+}
+
+/// Run `check` on the schedule in `options` until it breaks
+///
+/// `check` returns `Continue` with what it observed while still waiting, or
+/// `Break` once done. Its errors end the poll and are returned as-is.
+pub(super) async fn poll_until<T, P, F, Fut>(
+    options: &PollOptions,
+    mut check: F,
+) -> NetworkResult<Polled<T, P>>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = NetworkResult<ControlFlow<T, P>>>,
+{
+    let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+    let mut interval = options.interval;
+    loop {
+        let cancelled = options
+            .cancel
+            .as_ref()
+            .is_some_and(|flag| flag.load(Ordering::Relaxed));
+        if cancelled {
+            return Ok(Polled::Cancelled);
+        }
+
+        let pending = match check().await? {
+            ControlFlow::Break(done) => return Ok(Polled::Done(done)),
+            ControlFlow::Continue(pending) => pending,
+        };
+
+        let mut delay = interval;
+        if let Some(deadline) = deadline {
+            let now = Instant::now();
+            if now >= deadline {
+                return Ok(Polled::TimedOut(pending));
+            }
+            delay = delay.min(deadline - now);
+        }
+        rt::sleep(delay).await;
+        interval = interval
+            .mul_f64(options.multiplier.max(1.0))
+            .min(options.max_interval);
+    }
+}
+
+/// How a transaction that left the pending pool settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Settlement {
+    /// It is the source's last reference
+    Confirmed,
+    /// Its parent ordinal is still unused
+    Dropped,
+    /// A different transaction took its ordinal
+    Conflicted,
+    /// The chain moved more than one ordinal past its parent
+    Superseded,
+}
+
+/// Judge a transaction that left the pending pool by its source's last
+/// reference
+pub(super) fn settle(
+    hash: &str,
+    parent: &TransactionReference,
+    last_reference: &TransactionReference,
+) -> Settlement {
+    if last_reference.hash == hash {
+        Settlement::Confirmed
+    } else if last_reference.ordinal <= parent.ordinal {
+        Settlement::Dropped
+    } else if last_reference.ordinal == parent.ordinal + 1 {
+        Settlement::Conflicted
+    } else {
+        Settlement::Superseded
+    }
+}
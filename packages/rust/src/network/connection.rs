@@ -0,0 +1,78 @@
+//! Connection pool and socket tuning
+//!
+//! reqwest's defaults suit occasional requests. High-throughput submitters
+//! talking to a handful of L1 nodes benefit from keeping more idle connections
+//! per host, TCP keepalive, and a connect timeout that is shorter than the
+//! overall request timeout so dead nodes are detected quickly.
+//!
+//! Responses are requested with gzip or brotli `Accept-Encoding` and
+//! decompressed transparently, which shrinks multi-megabyte snapshots and
+//! transaction lists on slow links. Set `disable_compression` for nodes behind
+//! proxies that mangle compressed bodies.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{ConnectionOptions, NetworkConfig};
+//! use std::time::Duration;
+//!
+//! let config = NetworkConfig {
+//!     l1_url: Some("http://localhost:9300".to_string()),
+//!     connection: ConnectionOptions {
+//!         connect_timeout: Some(Duration::from_secs(2)),
+//!         pool_max_idle_per_host: Some(64),
+//!         tcp_keepalive: Some(Duration::from_secs(30)),
+//!         ..Default::default()
+//!     },
+//!     ..Default::default()
+//! };
+//! ```
+
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::ClientBuilder;
+use std::time::Duration;
+
+/// Socket and connection-pool settings for the HTTP client
+///
+/// Unset fields keep reqwest's defaults. Ignored on wasm32, where the
+/// browser's `fetch` manages connections.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConnectionOptions {
+    /// Timeout for establishing a connection, separate from the request timeout
+    pub connect_timeout: Option<Duration>,
+    /// Timeout for each read from an established connection
+    pub read_timeout: Option<Duration>,
+    /// Maximum idle connections kept per host
+    pub pool_max_idle_per_host: Option<usize>,
+    /// How long idle connections are kept in the pool
+    pub pool_idle_timeout: Option<Duration>,
+    /// Interval of TCP keepalive probes on open connections
+    pub tcp_keepalive: Option<Duration>,
+    /// Do not ask for gzip or brotli compressed responses
+    pub disable_compression: bool,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl ConnectionOptions {
+    pub(super) fn apply(&self, mut builder: ClientBuilder) -> ClientBuilder {
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+        if self.disable_compression {
+            builder = builder.no_gzip().no_brotli();
+        }
+        builder
+    }
+}
@@ -0,0 +1,138 @@
+//! Metagraph L0 (currency L0) operations
+//!
+//! Currency snapshots, token supply, and data-application routes served by a
+//! metagraph's L0 layer. These are available on a [`MetagraphClient`] created
+//! for [`LayerType::ML0`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{MetagraphClient, LayerType};
+//!
+//! let ml0 = MetagraphClient::new("http://localhost:9200", LayerType::ML0)?;
+//! let snapshot = ml0.get_latest_snapshot().await?;
+//! let supply = ml0.get_total_supply().await?;
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::types::{
+    CirculatingSupply, CurrencySnapshot, NetworkResult, RequestOptions, SnapshotHeader, TotalSupply,
+};
+use crate::types::Signed;
+
+#[derive(Deserialize)]
+struct OrdinalResponse {
+    value: i64,
+}
+
+impl MetagraphClient {
+    /// Get the latest currency snapshot
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_latest_snapshot(&self) -> NetworkResult<Signed<CurrencySnapshot>> {
+        self.assert_layer(&[LayerType::ML0], "get_latest_snapshot")?;
+        self.http_client()
+            .get_streaming("/snapshots/latest", &RequestOptions::default())
+            .await
+    }
+
+    /// Get a currency snapshot by ordinal
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_snapshot(&self, ordinal: i64) -> NetworkResult<Signed<CurrencySnapshot>> {
+        self.assert_layer(&[LayerType::ML0], "get_snapshot")?;
+        self.http_client()
+            .get_streaming(
+                &format!("/snapshots/{}", ordinal),
+                &RequestOptions::default(),
+            )
+            .await
+    }
+
+    /// Get the header of the latest currency snapshot
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_latest_snapshot_header(&self) -> NetworkResult<SnapshotHeader> {
+        Ok(self.get_latest_snapshot().await?.value.header())
+    }
+
+    /// Get the header of a currency snapshot by ordinal
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_snapshot_header(&self, ordinal: i64) -> NetworkResult<SnapshotHeader> {
+        Ok(self.get_snapshot(ordinal).await?.value.header())
+    }
+
+    /// Get the ordinal of the latest currency snapshot
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_latest_snapshot_ordinal(&self) -> NetworkResult<i64> {
+        self.assert_layer(&[LayerType::ML0], "get_latest_snapshot_ordinal")?;
+        let response: OrdinalResponse = self.get("/snapshots/latest/ordinal").await?;
+        Ok(response.value)
+    }
+
+    /// Get the total supply of the metagraph token
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_total_supply(&self) -> NetworkResult<TotalSupply> {
+        self.assert_layer(&[LayerType::ML0], "get_total_supply")?;
+        self.get("/currency/total-supply").await
+    }
+
+    /// Get the circulating supply of the metagraph token
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_circulating_supply(&self) -> NetworkResult<CirculatingSupply> {
+        self.assert_layer(&[LayerType::ML0], "get_circulating_supply")?;
+        self.get("/currency/circulated-supply").await
+    }
+
+    /// Query a route exposed by the metagraph's data application
+    ///
+    /// `path` is relative to `/data-application`, e.g. `/state` or
+    /// `/calculated-state/latest`, and the response is deserialized into `T`.
+    /// See [`get_custom_with_options`](Self::get_custom_with_options) for
+    /// query parameters and [`post_custom`](Self::post_custom) for POST routes.
+    ///
+    /// Available on: ML0, DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_data_application<T: DeserializeOwned>(&self, path: &str) -> NetworkResult<T> {
+        self.assert_layer(&[LayerType::ML0, LayerType::DL1], "get_data_application")?;
+        self.get(&format!("/data-application{}", path)).await
+    }
+}
@@ -0,0 +1,110 @@
+//! Custom routes of a metagraph's data application
+//!
+//! Data applications register their own GET and POST routes under
+//! `/data-application` on the Metagraph L0 and Data L1 layers. These methods
+//! reach them through the same client, with its failover, retries, and
+//! interceptors, so app-specific endpoints need no second HTTP client.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{LayerType, MetagraphClient, RequestOptions};
+//!
+//! let dl1 = MetagraphClient::new("http://localhost:9400", LayerType::DL1)?;
+//! let polls: Vec<Poll> = dl1.get_custom("/polls").await?;
+//!
+//! let options = RequestOptions {
+//!     query: vec![("status".to_string(), "open".to_string())],
+//!     ..Default::default()
+//! };
+//! let open: Vec<Poll> = dl1.get_custom_with_options("/polls", &options).await?;
+//! let vote: VoteReceipt = dl1.post_custom("/polls/42/votes", &ballot).await?;
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::types::{NetworkResult, RequestOptions};
+
+/// Prefix of every data-application route
+const DATA_APPLICATION_PREFIX: &str = "/data-application";
+
+/// Full path of a data-application route, with or without a leading slash
+fn route(path: &str) -> String {
+    format!(
+        "{}/{}",
+        DATA_APPLICATION_PREFIX,
+        path.trim_start_matches('/')
+    )
+}
+
+impl MetagraphClient {
+    /// GET a custom data-application route
+    ///
+    /// `path` is relative to `/data-application`, e.g. `/polls`.
+    ///
+    /// Available on: ML0, DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_custom<T: DeserializeOwned>(&self, path: &str) -> NetworkResult<T> {
+        self.get_custom_with_options(path, &RequestOptions::default())
+            .await
+    }
+
+    /// [`get_custom`](Self::get_custom) with query parameters and other
+    /// per-request options
+    ///
+    /// Available on: ML0, DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_custom_with_options<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        options: &RequestOptions,
+    ) -> NetworkResult<T> {
+        self.assert_layer(&[LayerType::ML0, LayerType::DL1], "get_custom")?;
+        self.get_with_options(&route(path), options).await
+    }
+
+    /// POST a JSON body to a custom data-application route
+    ///
+    /// `path` is relative to `/data-application`. Like every POST, the
+    /// request is not retried unless the retry policy allows it.
+    ///
+    /// Available on: ML0, DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn post_custom<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> NetworkResult<T> {
+        self.post_custom_with_options(path, body, &RequestOptions::default())
+            .await
+    }
+
+    /// [`post_custom`](Self::post_custom) with query parameters and other
+    /// per-request options
+    ///
+    /// Available on: ML0, DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn post_custom_with_options<T: DeserializeOwned, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+        options: &RequestOptions,
+    ) -> NetworkResult<T> {
+        self.assert_layer(&[LayerType::ML0, LayerType::DL1], "post_custom")?;
+        self.post_with_options(&route(path), body, options).await
+    }
+}
@@ -0,0 +1,146 @@
+//! Data submissions that pay a fee
+//!
+//! Metagraphs can charge for data updates. The Data L1 node quotes the fee
+//! and the address to pay through `/data/estimate-fee`, and expects the
+//! update to arrive together with a signed [`FeeTransaction`] paying it.
+//! [`MetagraphClient::submit_data_with_fee`] runs the whole exchange:
+//! sign the update, get the quote, build and sign the fee transaction, and
+//! submit both.
+//!
+//! The fee transaction refers to the update by the hash its proof signs.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::LocalSigner;
+//!
+//! let signer = LocalSigner::new(&private_key)?;
+//! let receipt = dl1.submit_data_with_fee(&signer, &update).await?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::receipt::Receipt;
+use super::types::{
+    EstimateFeeResponse, NetworkError, NetworkResult, PostDataResponse, RequestOptions,
+};
+use crate::currency_transaction::is_valid_dag_address;
+use crate::hash::hash_data;
+use crate::signer::{sign_object, Signer};
+use crate::types::Signed;
+
+/// Payment of a data update's fee
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeTransaction {
+    /// Paying DAG address
+    pub source: String,
+    /// Fee address quoted by the node
+    pub destination: String,
+    /// Fee in smallest units (1e-8)
+    pub amount: i64,
+    /// Hash of the data update the fee pays for
+    pub data_update_ref: String,
+}
+
+/// Body of a data submission carrying a fee
+#[derive(Serialize)]
+struct DataWithFee<'a, T> {
+    data: &'a Signed<T>,
+    fee: &'a Signed<FeeTransaction>,
+}
+
+/// Sign the payment of a quoted fee for a signed data update
+///
+/// # Errors
+///
+/// Returns an `InvalidSubmission` error if the quoted fee address is not a
+/// valid DAG address, or an error if the update cannot be hashed or signing
+/// fails
+pub fn sign_fee_transaction<S, T>(
+    signer: &S,
+    data: &Signed<T>,
+    quote: &EstimateFeeResponse,
+) -> NetworkResult<Signed<FeeTransaction>>
+where
+    S: Signer + ?Sized,
+    T: Serialize,
+{
+    if !is_valid_dag_address(&quote.address) {
+        return Err(NetworkError::InvalidSubmission(vec![format!(
+            "node quoted an invalid fee address: {}",
+            quote.address
+        )]));
+    }
+    let fee = FeeTransaction {
+        source: signer.address(),
+        destination: quote.address.clone(),
+        amount: quote.fee,
+        data_update_ref: hash_data(&data.value, true)?.value,
+    };
+    Ok(sign_object(signer, &fee, false)?)
+}
+
+impl MetagraphClient {
+    /// Submit a signed data update together with its fee payment
+    ///
+    /// Goes through the same idempotency cache as
+    /// [`post_data`](Self::post_data), keyed by the update's hash, so a
+    /// retried submission does not pay twice.
+    ///
+    /// Available on: DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn post_data_with_fee<T: Serialize>(
+        &self,
+        data: &Signed<T>,
+        fee: &Signed<FeeTransaction>,
+    ) -> NetworkResult<PostDataResponse> {
+        self.assert_layer(&[LayerType::DL1], "post_data_with_fee")?;
+        self.post_data_to(
+            "/data",
+            &DataWithFee { data, fee },
+            || hash_data(&data.value, true),
+            &RequestOptions::default(),
+        )
+        .await
+    }
+
+    /// Sign a data update, pay the fee the node quotes for it, and submit both
+    ///
+    /// If the quoted fee is zero the update is submitted on its own. Returns
+    /// the update's receipt.
+    ///
+    /// Available on: DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidSubmission` error if the quoted fee is negative or
+    /// its address is invalid, or an error if called on an unsupported layer, if signing fails, or if
+    /// a request fails
+    pub async fn submit_data_with_fee<S, T>(&self, signer: &S, update: &T) -> NetworkResult<Receipt>
+    where
+        S: Signer + ?Sized,
+        T: Serialize + Clone,
+    {
+        self.assert_layer(&[LayerType::DL1], "submit_data_with_fee")?;
+        let data = sign_object(signer, update, true)?;
+        let quote = self.estimate_fee(&data).await?;
+        if quote.fee < 0 {
+            return Err(NetworkError::InvalidSubmission(vec![format!(
+                "node quoted a negative fee: {}",
+                quote.fee
+            )]));
+        }
+        let response = if quote.fee == 0 {
+            self.post_data(&data).await?
+        } else {
+            let fee = sign_fee_transaction(signer, &data, &quote)?;
+            self.post_data_with_fee(&data, &fee).await?
+        };
+        Receipt::for_data_update(update, response.hash)
+    }
+}
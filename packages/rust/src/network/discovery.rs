@@ -0,0 +1,134 @@
+//! Peer discovery from `/cluster/info`
+//!
+//! Every node lists the peers of its cluster. [`MetagraphClient::discover_peers`]
+//! and [`GlobalL0Client::discover_peers`] return them as [`PeerInfo`], and
+//! [`NetworkConfig::discover_peers`] adds the ready L1 peers to the node pools
+//! so a single seed URL per layer is enough for failover.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::NetworkConfig;
+//!
+//! let config = NetworkConfig {
+//!     l1_url: Some("http://seed-node:9300".to_string()),
+//!     ..Default::default()
+//! }
+//! .discover_peers()
+//! .await?;
+//! let cl1 = config.currency_l1_client()?; // balances over every ready peer
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::config::NetworkConfig;
+use super::global_l0_client::GlobalL0Client;
+use super::metagraph_client::MetagraphClient;
+use super::node_info::NodeState;
+use super::types::NetworkResult;
+
+/// A cluster member as reported by `/cluster/info`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerInfo {
+    /// Peer ID (the node's public key, hex)
+    pub id: String,
+    /// IP address or host name
+    pub ip: String,
+    /// Port of the public HTTP API
+    pub public_port: u16,
+    /// Port of the peer-to-peer API
+    #[serde(default)]
+    pub p2p_port: Option<u16>,
+    /// Node state, e.g. `Ready`, `Observing`, or `Leaving`
+    pub state: NodeState,
+    /// Session token of the peer's current cluster session
+    #[serde(default)]
+    pub session: Option<String>,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl PeerInfo {
+    /// Whether the peer is ready to serve requests
+    pub fn is_ready(&self) -> bool {
+        self.state == NodeState::Ready
+    }
+
+    /// Base URL of the peer's public API
+    pub fn base_url(&self) -> String {
+        format!("http://{}:{}", self.ip, self.public_port)
+    }
+}
+
+/// `/cluster/info` is a bare list on current nodes; older ones wrap it
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ClusterPeers {
+    List(Vec<PeerInfo>),
+    Wrapped { peers: Vec<PeerInfo> },
+}
+
+impl From<ClusterPeers> for Vec<PeerInfo> {
+    fn from(peers: ClusterPeers) -> Self {
+        match peers {
+            ClusterPeers::List(peers) | ClusterPeers::Wrapped { peers } => peers,
+        }
+    }
+}
+
+impl MetagraphClient {
+    /// List the peers of this node's cluster
+    pub async fn discover_peers(&self) -> NetworkResult<Vec<PeerInfo>> {
+        let peers: ClusterPeers = self.get("/cluster/info").await?;
+        Ok(peers.into())
+    }
+}
+
+impl GlobalL0Client {
+    /// List the peers of this node's cluster
+    pub async fn discover_peers(&self) -> NetworkResult<Vec<PeerInfo>> {
+        let peers: ClusterPeers = self.client.get("/cluster/info").await?;
+        Ok(peers.into())
+    }
+}
+
+impl NetworkConfig {
+    /// Add the ready peers of the configured L1 clusters to the node pools
+    ///
+    /// Asks the Currency L1 and Data L1 nodes already configured for their
+    /// cluster members and appends every ready peer not yet listed to
+    /// `l1_urls` / `data_l1_urls`. Layers without a configured URL are skipped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configured cluster cannot be queried
+    pub async fn discover_peers(mut self) -> NetworkResult<Self> {
+        if self.l1_url.is_some() || !self.l1_urls.is_empty() {
+            let peers = self.currency_l1_client()?.discover_peers().await?;
+            extend_ready(&mut self.l1_urls, &self.l1_url, peers);
+        }
+        if self.data_l1_url.is_some() || !self.data_l1_urls.is_empty() {
+            let peers = self.data_l1_client()?.discover_peers().await?;
+            extend_ready(&mut self.data_l1_urls, &self.data_l1_url, peers);
+        }
+        Ok(self)
+    }
+}
+
+fn extend_ready(urls: &mut Vec<String>, seed: &Option<String>, peers: Vec<PeerInfo>) {
+    let normalize = |url: &str| url.trim_end_matches('/').to_string();
+    for url in peers
+        .iter()
+        .filter(|p| p.is_ready())
+        .map(PeerInfo::base_url)
+    {
+        let known = seed.as_deref().map(normalize).as_deref() == Some(url.as_str())
+            || urls.iter().any(|existing| normalize(existing) == url);
+        if !known {
+            urls.push(url);
+        }
+    }
+}
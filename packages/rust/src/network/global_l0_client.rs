@@ -0,0 +1,113 @@
+//! Client for Global L0 nodes
+//!
+//! The Global L0 is the hypergraph layer that produces global snapshots and
+//! holds DAG balances. It is not a metagraph layer, so it has its own client
+//! rather than a [`LayerType`](super::LayerType).
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::GlobalL0Client;
+//!
+//! let gl0 = GlobalL0Client::new("http://localhost:9000")?;
+//! let ordinal = gl0.get_latest_snapshot_ordinal().await?;
+//! let balance = gl0.get_balance("DAG...").await?;
+//! ```
+
+use serde::Deserialize;
+
+use super::client::HttpClient;
+use super::metagraph_client::ClusterInfo;
+use super::types::{
+    Balance, CirculatingSupply, GlobalSnapshot, NetworkResult, RequestOptions, SnapshotHeader,
+    TotalSupply,
+};
+use crate::types::Signed;
+
+#[derive(Deserialize)]
+struct OrdinalResponse {
+    value: i64,
+}
+
+/// Client for interacting with a Global L0 node
+pub struct GlobalL0Client {
+    pub(super) client: HttpClient,
+}
+
+impl GlobalL0Client {
+    /// Create a new GlobalL0Client
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the HTTP client cannot be initialized
+    pub fn new(base_url: impl Into<String>) -> NetworkResult<Self> {
+        let client = HttpClient::new(base_url, None)?;
+        Ok(Self { client })
+    }
+
+    /// Create a GlobalL0Client on top of a pre-built [`HttpClient`]
+    pub fn with_http_client(client: HttpClient) -> Self {
+        Self { client }
+    }
+
+    /// Check the health/availability of the node
+    pub async fn check_health(&self) -> bool {
+        self.client
+            .get::<serde_json::Value>("/cluster/info")
+            .await
+            .is_ok()
+    }
+
+    /// Get cluster information
+    pub async fn get_cluster_info(&self) -> NetworkResult<ClusterInfo> {
+        self.client.get("/cluster/info").await
+    }
+
+    /// Get the latest global snapshot
+    pub async fn get_latest_snapshot(&self) -> NetworkResult<Signed<GlobalSnapshot>> {
+        self.client
+            .get_streaming("/global-snapshots/latest", &RequestOptions::default())
+            .await
+    }
+
+    /// Get a global snapshot by ordinal
+    pub async fn get_snapshot(&self, ordinal: i64) -> NetworkResult<Signed<GlobalSnapshot>> {
+        self.client
+            .get_streaming(
+                &format!("/global-snapshots/{}", ordinal),
+                &RequestOptions::default(),
+            )
+            .await
+    }
+
+    /// Get the header of the latest global snapshot
+    pub async fn get_latest_snapshot_header(&self) -> NetworkResult<SnapshotHeader> {
+        Ok(self.get_latest_snapshot().await?.value.header())
+    }
+
+    /// Get the header of a global snapshot by ordinal
+    pub async fn get_snapshot_header(&self, ordinal: i64) -> NetworkResult<SnapshotHeader> {
+        Ok(self.get_snapshot(ordinal).await?.value.header())
+    }
+
+    /// Get the ordinal of the latest global snapshot
+    pub async fn get_latest_snapshot_ordinal(&self) -> NetworkResult<i64> {
+        let response: OrdinalResponse = self.client.get("/global-snapshots/latest/ordinal").await?;
+        Ok(response.value)
+    }
+
+    /// Get the DAG balance of an address
+    pub async fn get_balance(&self, address: &str) -> NetworkResult<Balance> {
+        self.client.get(&format!("/dag/{}/balance", address)).await
+    }
+
+    /// Get the total DAG supply
+    pub async fn get_total_supply(&self) -> NetworkResult<TotalSupply> {
+        self.client.get("/dag/total-supply").await
+    }
+
+    /// Get the circulating DAG supply
+    pub async fn get_circulating_supply(&self) -> NetworkResult<CirculatingSupply> {
+        self.client.get("/dag/circulated-supply").await
+    }
+}
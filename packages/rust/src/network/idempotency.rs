@@ -0,0 +1,138 @@
+//! Idempotent submissions
+//!
+//! A submission that times out may still have reached the node. Sending it
+//! again from a retry loop is harmless when the node recognises the
+//! duplicate, but callers can't always tell an ambiguous failure from a
+//! rejection. Two mechanisms keep a submission from being applied twice:
+//!
+//! - [`RequestOptions::idempotency_key`](super::RequestOptions::idempotency_key)
+//!   is sent as an `Idempotency-Key` header on every attempt, for gateways and
+//!   proxies in front of the nodes that deduplicate on it.
+//! - An [`IdempotencyCache`] installed with
+//!   [`MetagraphClient::with_idempotency_cache`] remembers what was submitted,
+//!   keyed by transaction or data hash. An accepted submission is never sent
+//!   again; its original response is returned instead. After a timeout the
+//!   key is "in doubt", and the next attempt of a transaction first looks it
+//!   up in the node's pending pool.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::IdempotencyCache;
+//! use std::sync::Arc;
+//!
+//! let cache = Arc::new(IdempotencyCache::new());
+//! let cl1 = MetagraphClient::new("http://localhost:9300", LayerType::CL1)?
+//!     .with_idempotency_cache(cache.clone());
+//!
+//! // Safe to call again after a timeout: resolves to the same hash
+//! let response = cl1.post_transaction(&tx).await?;
+//! ```
+
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard};
+
+use super::types::{NetworkError, RequestOptions};
+
+/// HTTP header carrying the idempotency key
+pub(super) const IDEMPOTENCY_HEADER: &str = "Idempotency-Key";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Entry {
+    /// The node accepted the submission and answered with this hash
+    Accepted(String),
+    /// The last attempt failed without telling whether the node got it
+    InDoubt,
+}
+
+/// Submissions made through a client, keyed by idempotency key
+///
+/// Entries are kept until [`forget`](Self::forget) or [`clear`](Self::clear);
+/// long-running services should clear keys once the submission is confirmed.
+#[derive(Debug, Default)]
+pub struct IdempotencyCache {
+    entries: Mutex<HashMap<String, Entry>>,
+}
+
+impl IdempotencyCache {
+    /// Create an empty cache
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash the node answered with for an accepted submission
+    pub fn accepted(&self, key: &str) -> Option<String> {
+        match self.entries().get(key) {
+            Some(Entry::Accepted(hash)) => Some(hash.clone()),
+            _ => None,
+        }
+    }
+
+    /// Whether the last attempt with `key` failed ambiguously
+    pub fn is_in_doubt(&self, key: &str) -> bool {
+        self.entries().get(key) == Some(&Entry::InDoubt)
+    }
+
+    /// Drop the entry for `key`, allowing it to be submitted again
+    pub fn forget(&self, key: &str) {
+        self.entries().remove(key);
+    }
+
+    /// Drop all entries
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    /// Number of keys tracked
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Whether no keys are tracked
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Record the outcome of a submission attempt
+    ///
+    /// Rejections clear the key so a corrected resubmission is not blocked.
+    pub(super) fn record(&self, key: &str, result: Result<&str, &NetworkError>) {
+        let mut entries = self.entries();
+        match result {
+            Ok(hash) => {
+                entries.insert(key.to_string(), Entry::Accepted(hash.to_string()));
+            }
+            Err(e) if is_ambiguous(e) => {
+                entries.insert(key.to_string(), Entry::InDoubt);
+            }
+            Err(_) => {
+                entries.remove(key);
+            }
+        }
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Whether a failed request may still have been processed by the node
+fn is_ambiguous(error: &NetworkError) -> bool {
+    matches!(
+        error,
+        NetworkError::Timeout
+            | NetworkError::HttpError {
+                status_code: None,
+                ..
+            }
+    )
+}
+
+/// Options carrying `key` as the idempotency key, unless the caller set one
+pub(super) fn with_key(options: &RequestOptions, key: &str) -> RequestOptions {
+    let mut options = options.clone();
+    options
+        .idempotency_key
+        .get_or_insert_with(|| key.to_string());
+    options
+}
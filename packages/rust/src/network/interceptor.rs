@@ -0,0 +1,62 @@
+//! Request/response middleware hooks
+//!
+//! An [`Interceptor`] observes or rewrites every request an
+//! [`HttpClient`](super::HttpClient) sends, including retries, failover
+//! attempts, and health probes. Typical uses are logging, request signing,
+//! header rewriting, and metrics.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{reqwest, HttpClient, Interceptor, RequestContext};
+//!
+//! struct Logger;
+//!
+//! impl Interceptor for Logger {
+//!     fn after_receive(&self, context: &RequestContext<'_>, response: &reqwest::Response) {
+//!         println!("{} {} -> {} in {:?}", context.method, context.url, response.status(), context.elapsed);
+//!     }
+//! }
+//!
+//! let client = HttpClient::new("http://localhost:9300", None)?.with_interceptor(Logger);
+//! ```
+
+use std::time::Duration;
+
+use super::types::{NetworkError, NetworkResult};
+
+/// Details of a completed attempt passed to [`Interceptor`] hooks
+#[derive(Debug, Clone, Copy)]
+pub struct RequestContext<'a> {
+    /// HTTP method of the request
+    pub method: &'a reqwest::Method,
+    /// Full request URL
+    pub url: &'a reqwest::Url,
+    /// Time from sending the request to receiving the response (or error)
+    pub elapsed: Duration,
+}
+
+/// Hooks invoked around every HTTP request
+///
+/// All methods have no-op defaults, so implementations only override the
+/// hooks they need.
+pub trait Interceptor: Send + Sync {
+    /// Called before each attempt is sent
+    ///
+    /// The request may be modified in place (e.g. to add a signature header).
+    /// Returning an error aborts the request with that error.
+    fn before_send(&self, request: &mut reqwest::Request) -> NetworkResult<()> {
+        let _ = request;
+        Ok(())
+    }
+
+    /// Called when a response arrives, before its body is read
+    fn after_receive(&self, context: &RequestContext<'_>, response: &reqwest::Response) {
+        let _ = (context, response);
+    }
+
+    /// Called when an attempt fails without receiving a response
+    fn on_error(&self, context: &RequestContext<'_>, error: &NetworkError) {
+        let _ = (context, error);
+    }
+}
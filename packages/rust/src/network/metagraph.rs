@@ -0,0 +1,228 @@
+//! Facade over the clients of one metagraph
+//!
+//! A [`Metagraph`] bundles the metagraph ID with its Metagraph L0, Currency
+//! L1, and Data L1 clients and exposes the operations apps run against a
+//! metagraph, so they deal with one object instead of three clients and the
+//! layer each call belongs to.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{Metagraph, NetworkConfig};
+//! use constellation_sdk::{LocalSigner, TransferParams};
+//!
+//! let metagraph = Metagraph::from_config("DAG...", &config)?;
+//! let signer = LocalSigner::new(&private_key)?;
+//!
+//! let balance = metagraph.token_balance(&signer.address()).await?;
+//! metagraph.submit_update(&signer, &update).await?;
+//! let receipt = metagraph
+//!     .token_transfer(
+//!         &signer,
+//!         TransferParams {
+//!             destination: "DAG...".to_string(),
+//!             amount: 10.0,
+//!             fee: 0.0,
+//!         },
+//!     )
+//!     .await?;
+//! ```
+
+use serde::Serialize;
+use std::fmt;
+
+use super::config::NetworkConfig;
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::receipt::Receipt;
+use super::reference_manager::ReferenceManager;
+use super::types::{Balance, NetworkError, NetworkResult};
+use crate::currency_types::TransferParams;
+use crate::data_update::DataUpdate;
+use crate::signer::{sign_object, Signer};
+
+/// One metagraph's clients and the operations apps run against it
+pub struct Metagraph {
+    metagraph_id: String,
+    l0: Option<MetagraphClient>,
+    l1: Option<ReferenceManager>,
+    data_l1: Option<MetagraphClient>,
+}
+
+impl Metagraph {
+    /// Create a metagraph handle without clients
+    pub fn new(metagraph_id: impl Into<String>) -> Self {
+        Self {
+            metagraph_id: metagraph_id.into(),
+            l0: None,
+            l1: None,
+            data_l1: None,
+        }
+    }
+
+    /// Create a metagraph handle with a client for every metagraph endpoint
+    /// set in `config`
+    ///
+    /// The Global L0 and block explorer endpoints of `config` are not used.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configured client cannot be created
+    pub fn from_config(
+        metagraph_id: impl Into<String>,
+        config: &NetworkConfig,
+    ) -> NetworkResult<Self> {
+        let mut metagraph = Self::new(metagraph_id);
+        if config.currency_l0_url.is_some() {
+            metagraph.l0 = Some(config.currency_l0_client()?);
+        }
+        if config.l1_url.is_some() || !config.l1_urls.is_empty() {
+            metagraph.l1 = Some(ReferenceManager::unchecked(config.currency_l1_client()?));
+        }
+        if config.data_l1_url.is_some() || !config.data_l1_urls.is_empty() {
+            metagraph.data_l1 = Some(config.data_l1_client()?);
+        }
+        Ok(metagraph)
+    }
+
+    /// Use a Metagraph L0 client
+    pub fn with_l0(mut self, client: MetagraphClient) -> Self {
+        self.l0 = Some(client);
+        self
+    }
+
+    /// Use a Currency L1 client
+    pub fn with_l1(mut self, client: MetagraphClient) -> Self {
+        self.l1 = Some(ReferenceManager::unchecked(client));
+        self
+    }
+
+    /// Use a Data L1 client
+    pub fn with_data_l1(mut self, client: MetagraphClient) -> Self {
+        self.data_l1 = Some(client);
+        self
+    }
+
+    /// The metagraph ID
+    pub fn metagraph_id(&self) -> &str {
+        &self.metagraph_id
+    }
+
+    /// The Metagraph L0 client
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the metagraph has no Metagraph L0 client
+    pub fn l0(&self) -> NetworkResult<&MetagraphClient> {
+        self.client(self.l0.as_ref(), LayerType::ML0)
+    }
+
+    /// The Currency L1 client
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the metagraph has no Currency L1 client
+    pub fn l1(&self) -> NetworkResult<&MetagraphClient> {
+        Ok(self.references()?.client())
+    }
+
+    /// The Data L1 client
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the metagraph has no Data L1 client
+    pub fn data_l1(&self) -> NetworkResult<&MetagraphClient> {
+        self.client(self.data_l1.as_ref(), LayerType::DL1)
+    }
+
+    /// Get an address's token balance
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if there is no Metagraph L0 client, or an
+    /// error if the request fails
+    pub async fn token_balance(&self, address: &str) -> NetworkResult<Balance> {
+        self.l0()?.get_balance(address).await
+    }
+
+    /// Sign a data update and submit it to the Data L1
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if there is no Data L1 client, or an error if
+    /// signing or the request fails
+    pub async fn submit_update<S, T>(&self, signer: &S, update: &T) -> NetworkResult<Receipt>
+    where
+        S: Signer + ?Sized,
+        T: Serialize + Clone,
+    {
+        let data_l1 = self.data_l1()?;
+        let response = data_l1
+            .post_data(&sign_object(signer, update, true)?)
+            .await?;
+        Receipt::for_data_update(update, response.hash)
+    }
+
+    /// Sign a typed data update and submit it to its route on the Data L1
+    ///
+    /// See [`MetagraphClient::submit`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if there is no Data L1 client, or an error if
+    /// signing or the request fails
+    pub async fn submit<S, U>(&self, signer: &S, update: &U) -> NetworkResult<Receipt>
+    where
+        S: Signer + ?Sized,
+        U: DataUpdate + Clone,
+    {
+        self.data_l1()?.submit(signer, update).await
+    }
+
+    /// Build, sign, and submit a token transfer from the signer's address
+    ///
+    /// Parent references are held by a [`ReferenceManager`], so concurrent
+    /// transfers from one address chain one after another instead of
+    /// reusing a parent.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if there is no Currency L1 client, or an error
+    /// if the transfer parameters are invalid or a request fails
+    pub async fn token_transfer<S: Signer + ?Sized>(
+        &self,
+        signer: &S,
+        params: TransferParams,
+    ) -> NetworkResult<Receipt> {
+        self.references()?.submit(params, signer).await
+    }
+
+    fn references(&self) -> NetworkResult<&ReferenceManager> {
+        self.l1.as_ref().ok_or_else(|| self.missing(LayerType::CL1))
+    }
+
+    fn client<'a>(
+        &self,
+        client: Option<&'a MetagraphClient>,
+        layer: LayerType,
+    ) -> NetworkResult<&'a MetagraphClient> {
+        client.ok_or_else(|| self.missing(layer))
+    }
+
+    fn missing(&self, layer: LayerType) -> NetworkError {
+        NetworkError::ConfigError(format!(
+            "Metagraph {} has no {:?} client",
+            self.metagraph_id, layer
+        ))
+    }
+}
+
+impl fmt::Debug for Metagraph {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Metagraph")
+            .field("metagraph_id", &self.metagraph_id)
+            .field("l0", &self.l0.is_some())
+            .field("l1", &self.l1.is_some())
+            .field("data_l1", &self.data_l1.is_some())
+            .finish()
+    }
+}
@@ -19,14 +19,21 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use super::circuit_breaker::CircuitBreaker;
 use super::client::HttpClient;
+use super::idempotency::{self, IdempotencyCache};
+use super::response_cache::{CacheKey, CachedEndpoint, ResponseCache};
 use super::types::{
-    EstimateFeeResponse, NetworkError, NetworkResult, PendingTransaction, PostDataResponse,
-    PostTransactionResponse,
+    Balance, EstimateFeeResponse, NetworkError, NetworkResult, PendingTransaction,
+    PostDataResponse, PostTransactionResponse, RequestOptions,
 };
+use crate::currency_transaction::hash_currency_transaction;
 use crate::currency_types::{CurrencyTransaction, TransactionReference};
-use crate::types::Signed;
+use crate::hash::hash_data;
+use crate::types::{Hash, Result, Signed};
+use crate::verify::SignaturePolicy;
 
 /// Supported L1 layer types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -101,8 +108,11 @@ pub struct MetagraphClientConfig {
 /// let result = dl1.post_data(&signed_data).await?;
 /// ```
 pub struct MetagraphClient {
-    client: HttpClient,
+    pub(super) client: HttpClient,
     layer: LayerType,
+    idempotency: Option<Arc<IdempotencyCache>>,
+    pub(super) response_cache: Option<Arc<ResponseCache>>,
+    signature_policy: SignaturePolicy,
 }
 
 impl MetagraphClient {
@@ -118,16 +128,66 @@ impl MetagraphClient {
     /// Returns an error if the HTTP client cannot be initialized
     pub fn new(base_url: impl Into<String>, layer: LayerType) -> NetworkResult<Self> {
         let client = HttpClient::new(base_url, None)?;
-        Ok(Self { client, layer })
+        Ok(Self::with_http_client(client, layer))
     }
 
     /// Create a new MetagraphClient with full configuration
     pub fn with_config(config: MetagraphClientConfig) -> NetworkResult<Self> {
         let client = HttpClient::new(config.base_url, config.timeout)?;
-        Ok(Self {
+        Ok(Self::with_http_client(client, config.layer))
+    }
+
+    /// Create a MetagraphClient on top of a pre-built [`HttpClient`]
+    ///
+    /// Use this to share transport settings such as interceptors, retry
+    /// policies, or TLS configuration that [`MetagraphClientConfig`] does not
+    /// cover.
+    pub fn with_http_client(client: HttpClient, layer: LayerType) -> Self {
+        Self {
             client,
-            layer: config.layer,
-        })
+            layer,
+            idempotency: None,
+            response_cache: None,
+            signature_policy: SignaturePolicy::default(),
+        }
+    }
+
+    /// Serve last references, balances, and cluster info from `cache` while
+    /// they are fresh
+    ///
+    /// A successful `post_transaction` drops the cached entries of the
+    /// transaction's source and destination. The cache can be shared
+    /// between clients of the same layer.
+    pub fn with_response_cache(mut self, cache: Arc<ResponseCache>) -> Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Track submissions in `cache` so none is sent twice
+    ///
+    /// `post_transaction` and `post_data` key each submission by its hash
+    /// (or the request's idempotency key) and send that key as the
+    /// `Idempotency-Key` header. The cache can be shared between clients.
+    pub fn with_idempotency_cache(mut self, cache: Arc<IdempotencyCache>) -> Self {
+        self.idempotency = Some(cache);
+        self
+    }
+
+    /// Verify proofs under `policy` in the `_checked` submission methods and
+    /// [`validate_data`](Self::validate_data)
+    ///
+    /// Defaults to [`SignaturePolicy::COMPATIBLE`]. Use
+    /// [`SignaturePolicy::STRICT`] to refuse high-S signatures before they
+    /// are sent.
+    pub fn with_signature_policy(mut self, policy: SignaturePolicy) -> Self {
+        self.signature_policy = policy;
+        self
+    }
+
+    /// Signature policy used by the `_checked` submission methods and
+    /// [`validate_data`](Self::validate_data)
+    pub fn signature_policy(&self) -> SignaturePolicy {
+        self.signature_policy
     }
 
     /// Get the layer type of this client
@@ -135,6 +195,26 @@ impl MetagraphClient {
         self.layer
     }
 
+    /// Base URLs of the nodes this client balances over
+    pub fn base_urls(&self) -> Vec<&str> {
+        self.client.base_urls()
+    }
+
+    /// Base URLs of the nodes currently considered healthy
+    pub fn healthy_urls(&self) -> Vec<&str> {
+        self.client.healthy_urls()
+    }
+
+    /// Get the circuit breaker of the node with the given base URL
+    pub fn circuit_breaker(&self, base_url: &str) -> Option<&CircuitBreaker> {
+        self.client.circuit_breaker(base_url)
+    }
+
+    /// Probe every node and update its health, returning the healthy count
+    pub async fn check_nodes(&self) -> usize {
+        self.client.check_nodes().await
+    }
+
     // ============================================
     // Common operations (all layers)
     // ============================================
@@ -149,7 +229,11 @@ impl MetagraphClient {
 
     /// Get cluster information
     pub async fn get_cluster_info(&self) -> NetworkResult<ClusterInfo> {
-        self.client.get("/cluster/info").await
+        self.cached(
+            CachedEndpoint::ClusterInfo,
+            self.client.get("/cluster/info"),
+        )
+        .await
     }
 
     // ============================================
@@ -167,12 +251,50 @@ impl MetagraphClient {
     ///
     /// Returns an error if called on an unsupported layer
     pub async fn get_last_reference(&self, address: &str) -> NetworkResult<TransactionReference> {
-        self.assert_layer(&[LayerType::CL1, LayerType::ML0], "get_last_reference")?;
-        self.client
-            .get(&format!("/transactions/last-reference/{}", address))
+        self.get_last_reference_with_options(address, &RequestOptions::default())
             .await
     }
 
+    /// [`get_last_reference`](Self::get_last_reference) with per-request options
+    ///
+    /// Available on: CL1, ML0 (if currency enabled)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(layer = %self.layer, address = %address)))]
+    pub async fn get_last_reference_with_options(
+        &self,
+        address: &str,
+        options: &RequestOptions,
+    ) -> NetworkResult<TransactionReference> {
+        self.assert_layer(&[LayerType::CL1, LayerType::ML0], "get_last_reference")?;
+        self.cached(
+            CachedEndpoint::LastReference(address.to_string()),
+            self.client.get_with_options(
+                &format!("/transactions/last-reference/{}", address),
+                options,
+            ),
+        )
+        .await
+    }
+
+    /// Get the token balance of an address
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_balance(&self, address: &str) -> NetworkResult<Balance> {
+        self.assert_layer(&[LayerType::ML0], "get_balance")?;
+        self.cached(
+            CachedEndpoint::Balance(address.to_string()),
+            self.client.get(&format!("/currency/{}/balance", address)),
+        )
+        .await
+    }
+
     /// Submit a signed currency transaction
     ///
     /// Available on: CL1
@@ -183,9 +305,74 @@ impl MetagraphClient {
     pub async fn post_transaction(
         &self,
         transaction: &CurrencyTransaction,
+    ) -> NetworkResult<PostTransactionResponse> {
+        self.post_transaction_with_options(transaction, &RequestOptions::default())
+            .await
+    }
+
+    /// [`post_transaction`](Self::post_transaction) with per-request options
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(layer = %self.layer, tx_hash)))]
+    pub async fn post_transaction_with_options(
+        &self,
+        transaction: &CurrencyTransaction,
+        options: &RequestOptions,
     ) -> NetworkResult<PostTransactionResponse> {
         self.assert_layer(&[LayerType::CL1], "post_transaction")?;
-        self.client.post("/transactions", transaction).await
+        let response = self.send_transaction(transaction, options).await?;
+        if let Some(cache) = &self.response_cache {
+            cache.invalidate_transaction(transaction);
+        }
+        record_span!("tx_hash", response.hash.as_str());
+        log_event!(
+            Info,
+            "submitted transaction",
+            "layer" = self.layer,
+            "tx_hash" = response.hash
+        );
+        Ok(response)
+    }
+
+    /// Submit a transaction once per idempotency key when a cache is installed
+    async fn send_transaction(
+        &self,
+        transaction: &CurrencyTransaction,
+        options: &RequestOptions,
+    ) -> NetworkResult<PostTransactionResponse> {
+        let Some(cache) = &self.idempotency else {
+            return self
+                .client
+                .post_with_options("/transactions", transaction, options)
+                .await;
+        };
+
+        let tx_hash = hash_currency_transaction(transaction).value;
+        let options = idempotency::with_key(options, &tx_hash);
+        let key = options.idempotency_key.as_deref().unwrap_or(&tx_hash);
+        if let Some(hash) = cache.accepted(key) {
+            return Ok(PostTransactionResponse {
+                hash,
+                extra: HashMap::new(),
+            });
+        }
+        if cache.is_in_doubt(key) && self.get_pending_transaction(&tx_hash).await?.is_some() {
+            cache.record(key, Ok(&tx_hash));
+            return Ok(PostTransactionResponse {
+                hash: tx_hash,
+                extra: HashMap::new(),
+            });
+        }
+        let result: NetworkResult<PostTransactionResponse> = self
+            .client
+            .post_with_options("/transactions", transaction, &options)
+            .await;
+        cache.record(key, result.as_ref().map(|response| response.hash.as_str()));
+        result
     }
 
     /// Get a pending transaction by hash
@@ -198,9 +385,31 @@ impl MetagraphClient {
     pub async fn get_pending_transaction(
         &self,
         hash: &str,
+    ) -> NetworkResult<Option<PendingTransaction>> {
+        self.get_pending_transaction_with_options(hash, &RequestOptions::default())
+            .await
+    }
+
+    /// [`get_pending_transaction`](Self::get_pending_transaction) with
+    /// per-request options
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(layer = %self.layer, tx_hash = %hash)))]
+    pub async fn get_pending_transaction_with_options(
+        &self,
+        hash: &str,
+        options: &RequestOptions,
     ) -> NetworkResult<Option<PendingTransaction>> {
         self.assert_layer(&[LayerType::CL1], "get_pending_transaction")?;
-        match self.client.get(&format!("/transactions/{}", hash)).await {
+        match self
+            .client
+            .get_with_options(&format!("/transactions/{}", hash), options)
+            .await
+        {
             Ok(tx) => Ok(Some(tx)),
             Err(NetworkError::HttpError {
                 status_code: Some(404),
@@ -210,6 +419,56 @@ impl MetagraphClient {
         }
     }
 
+    /// Get every transaction from an address still in the pending pool
+    ///
+    /// Lists both `Waiting` and `InProgress` transactions, ordered by parent
+    /// ordinal, so a process sharing a wallet with others can chain its next
+    /// transaction after the ones already submitted instead of reusing an
+    /// ordinal. An address with nothing pending gets an empty list.
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_pending_transactions(
+        &self,
+        address: &str,
+    ) -> NetworkResult<Vec<PendingTransaction>> {
+        self.get_pending_transactions_with_options(address, &RequestOptions::default())
+            .await
+    }
+
+    /// [`get_pending_transactions`](Self::get_pending_transactions) with
+    /// per-request options
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_pending_transactions_with_options(
+        &self,
+        address: &str,
+        options: &RequestOptions,
+    ) -> NetworkResult<Vec<PendingTransaction>> {
+        self.assert_layer(&[LayerType::CL1], "get_pending_transactions")?;
+        let mut pending: Vec<PendingTransaction> = match self
+            .client
+            .get_with_options(&format!("/transactions/pending/{}", address), options)
+            .await
+        {
+            Ok(pending) => pending,
+            Err(NetworkError::HttpError {
+                status_code: Some(404),
+                ..
+            }) => Vec::new(),
+            Err(e) => return Err(e),
+        };
+        pending.sort_by_key(|tx| tx.transaction.value.parent.ordinal);
+        Ok(pending)
+    }
+
     // ============================================
     // Data operations (DL1)
     // ============================================
@@ -224,9 +483,28 @@ impl MetagraphClient {
     pub async fn estimate_fee<T: Serialize>(
         &self,
         data: &Signed<T>,
+    ) -> NetworkResult<EstimateFeeResponse> {
+        self.estimate_fee_with_options(data, &RequestOptions::default())
+            .await
+    }
+
+    /// [`estimate_fee`](Self::estimate_fee) with per-request options
+    ///
+    /// Available on: DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(layer = %self.layer)))]
+    pub async fn estimate_fee_with_options<T: Serialize>(
+        &self,
+        data: &Signed<T>,
+        options: &RequestOptions,
     ) -> NetworkResult<EstimateFeeResponse> {
         self.assert_layer(&[LayerType::DL1], "estimate_fee")?;
-        self.client.post("/data/estimate-fee", data).await
+        self.client
+            .post_with_options("/data/estimate-fee", data, options)
+            .await
     }
 
     /// Submit signed data to the Data L1 node
@@ -239,9 +517,76 @@ impl MetagraphClient {
     pub async fn post_data<T: Serialize>(
         &self,
         data: &Signed<T>,
+    ) -> NetworkResult<PostDataResponse> {
+        self.post_data_with_options(data, &RequestOptions::default())
+            .await
+    }
+
+    /// [`post_data`](Self::post_data) with per-request options
+    ///
+    /// Available on: DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip_all, fields(layer = %self.layer, hash)))]
+    pub async fn post_data_with_options<T: Serialize>(
+        &self,
+        data: &Signed<T>,
+        options: &RequestOptions,
+    ) -> NetworkResult<PostDataResponse> {
+        self.post_data_to("/data", data, || hash_data(&data.value, true), options)
+            .await
+    }
+
+    /// Post a body carrying signed data to a Data L1 route
+    ///
+    /// `data_hash` is the hash the data was signed over, used as the
+    /// idempotency key.
+    pub(super) async fn post_data_to<B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+        data_hash: impl FnOnce() -> Result<Hash>,
+        options: &RequestOptions,
     ) -> NetworkResult<PostDataResponse> {
         self.assert_layer(&[LayerType::DL1], "post_data")?;
-        self.client.post("/data", data).await
+        let Some(cache) = &self.idempotency else {
+            let response: PostDataResponse =
+                self.client.post_with_options(path, body, options).await?;
+            record_span!("hash", response.hash.as_str());
+            log_event!(
+                Info,
+                "submitted data",
+                "layer" = self.layer,
+                "hash" = response.hash
+            );
+            return Ok(response);
+        };
+
+        let data_hash = data_hash()
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))?
+            .value;
+        let options = idempotency::with_key(options, &data_hash);
+        let key = options.idempotency_key.as_deref().unwrap_or(&data_hash);
+        if let Some(hash) = cache.accepted(key) {
+            return Ok(PostDataResponse {
+                hash,
+                extra: HashMap::new(),
+            });
+        }
+        let result: NetworkResult<PostDataResponse> =
+            self.client.post_with_options(path, body, &options).await;
+        cache.record(key, result.as_ref().map(|response| response.hash.as_str()));
+        let response = result?;
+        record_span!("hash", response.hash.as_str());
+        log_event!(
+            Info,
+            "submitted data",
+            "layer" = self.layer,
+            "hash" = response.hash
+        );
+        Ok(response)
     }
 
     // ============================================
@@ -253,6 +598,11 @@ impl MetagraphClient {
         self.client.get(path).await
     }
 
+    /// Make a raw GET request for a plain-text resource
+    pub async fn get_text(&self, path: &str) -> NetworkResult<String> {
+        self.client.get_text(path).await
+    }
+
     /// Make a raw POST request to the node
     pub async fn post<T: for<'de> Deserialize<'de>, B: Serialize>(
         &self,
@@ -262,11 +612,53 @@ impl MetagraphClient {
         self.client.post(path, body).await
     }
 
+    /// Make a raw GET request with per-request options
+    pub async fn get_with_options<T: for<'de> Deserialize<'de>>(
+        &self,
+        path: &str,
+        options: &RequestOptions,
+    ) -> NetworkResult<T> {
+        self.client.get_with_options(path, options).await
+    }
+
+    /// Make a raw POST request with per-request options
+    pub async fn post_with_options<T: for<'de> Deserialize<'de>, B: Serialize>(
+        &self,
+        path: &str,
+        body: &B,
+        options: &RequestOptions,
+    ) -> NetworkResult<T> {
+        self.client.post_with_options(path, body, options).await
+    }
+
     // ============================================
     // Helpers
     // ============================================
 
-    fn assert_layer(&self, allowed: &[LayerType], method: &str) -> NetworkResult<()> {
+    /// Answer from the response cache if it holds a fresh `key`, otherwise
+    /// run `fetch` and cache its result
+    async fn cached<T: Clone + Send + Sync + 'static>(
+        &self,
+        endpoint: CachedEndpoint,
+        fetch: impl std::future::Future<Output = NetworkResult<T>>,
+    ) -> NetworkResult<T> {
+        let Some(cache) = &self.response_cache else {
+            return fetch.await;
+        };
+        let key = CacheKey::new(self, endpoint);
+        if let Some(value) = cache.get(&key) {
+            return Ok(value);
+        }
+        let value = fetch.await?;
+        cache.insert(key, value.clone());
+        Ok(value)
+    }
+
+    pub(super) fn http_client(&self) -> &HttpClient {
+        &self.client
+    }
+
+    pub(super) fn assert_layer(&self, allowed: &[LayerType], method: &str) -> NetworkResult<()> {
         if !allowed.contains(&self.layer) {
             let allowed_str: Vec<&str> = allowed.iter().map(|l| l.as_str()).collect();
             return Err(NetworkError::ConfigError(format!(
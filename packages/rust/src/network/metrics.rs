@@ -0,0 +1,315 @@
+//! Client metrics in Prometheus format
+//!
+//! [`MetricsRegistry`] is an [`Interceptor`] that counts requests and errors
+//! and records latencies per node and endpoint. A [`PendingTracker`] can also
+//! report how long transactions took to confirm. Long-running submitters can
+//! serve [`MetricsRegistry::render_prometheus`] from their metrics endpoint.
+//!
+//! Endpoint labels are the request path with addresses, hashes, and ordinals
+//! replaced by `:param`, so label cardinality stays bounded.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{MetricsRegistry, NetworkConfig, PendingTracker};
+//! use std::sync::Arc;
+//!
+//! let metrics = Arc::new(MetricsRegistry::new());
+//! let config = NetworkConfig {
+//!     l1_url: Some("http://localhost:9300".to_string()),
+//!     interceptors: vec![metrics.clone()],
+//!     ..Default::default()
+//! };
+//! let mut tracker = PendingTracker::new().with_metrics(metrics.clone());
+//!
+//! // Later, from the metrics HTTP handler:
+//! let body = metrics.render_prometheus();
+//! ```
+//!
+//! [`PendingTracker`]: super::PendingTracker
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use super::interceptor::{Interceptor, RequestContext};
+use super::types::NetworkError;
+
+/// Upper bounds (seconds) of the request latency histogram buckets
+const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+/// Upper bounds (seconds) of the confirmation time histogram buckets
+const CONFIRMATION_BUCKETS: &[f64] = &[1.0, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0, 600.0, 1800.0];
+
+/// Cumulative histogram of durations
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Bucket upper bounds in seconds
+    pub bounds: &'static [f64],
+    /// Number of observations at or below each bound
+    pub counts: Vec<u64>,
+    /// Total number of observations
+    pub count: u64,
+    /// Sum of all observations in seconds
+    pub sum: f64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            counts: vec![0; bounds.len()],
+            count: 0,
+            sum: 0.0,
+        }
+    }
+
+    fn observe(&mut self, value: Duration) {
+        let seconds = value.as_secs_f64();
+        for (bound, count) in self.bounds.iter().zip(self.counts.iter_mut()) {
+            if seconds <= *bound {
+                *count += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+
+    /// Mean of all observations, if any
+    pub fn mean(&self) -> Option<Duration> {
+        (self.count > 0).then(|| Duration::from_secs_f64(self.sum / self.count as f64))
+    }
+}
+
+/// Metrics for one node, HTTP method, and endpoint
+#[derive(Debug, Clone, PartialEq)]
+pub struct EndpointMetrics {
+    /// Node base address (`host:port`)
+    pub node: String,
+    /// HTTP method
+    pub method: String,
+    /// Normalized request path
+    pub endpoint: String,
+    /// Number of attempts sent
+    pub requests: u64,
+    /// Attempts that failed without a response or returned a non-2xx status
+    pub errors: u64,
+    /// Latency of attempts that received a response
+    pub latency: Histogram,
+}
+
+impl EndpointMetrics {
+    /// Fraction of attempts that failed (0.0 when nothing was sent)
+    pub fn error_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.errors as f64 / self.requests as f64
+        }
+    }
+}
+
+type EndpointKey = (String, String, String);
+
+#[derive(Debug)]
+struct Inner {
+    endpoints: BTreeMap<EndpointKey, EndpointMetrics>,
+    confirmations: Histogram,
+}
+
+/// Registry of client metrics, installed as an interceptor
+#[derive(Debug)]
+pub struct MetricsRegistry {
+    inner: Mutex<Inner>,
+}
+
+impl Default for MetricsRegistry {
+    fn default() -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                endpoints: BTreeMap::new(),
+                confirmations: Histogram::new(CONFIRMATION_BUCKETS),
+            }),
+        }
+    }
+}
+
+impl MetricsRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record how long a transaction took from submission to confirmation
+    pub fn record_confirmation(&self, duration: Duration) {
+        self.lock().confirmations.observe(duration);
+    }
+
+    /// Snapshot of per-endpoint metrics, ordered by node, method, and endpoint
+    pub fn endpoints(&self) -> Vec<EndpointMetrics> {
+        self.lock().endpoints.values().cloned().collect()
+    }
+
+    /// Snapshot of the confirmation time histogram
+    pub fn confirmations(&self) -> Histogram {
+        self.lock().confirmations.clone()
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn render_prometheus(&self) -> String {
+        let inner = self.lock();
+        let mut out = String::new();
+
+        out.push_str("# HELP metakit_requests_total HTTP requests sent to nodes\n");
+        out.push_str("# TYPE metakit_requests_total counter\n");
+        for m in inner.endpoints.values() {
+            let _ = writeln!(
+                out,
+                "metakit_requests_total{{{}}} {}",
+                labels(m),
+                m.requests
+            );
+        }
+
+        out.push_str("# HELP metakit_request_errors_total Requests that failed or returned a non-2xx status\n");
+        out.push_str("# TYPE metakit_request_errors_total counter\n");
+        for m in inner.endpoints.values() {
+            let _ = writeln!(
+                out,
+                "metakit_request_errors_total{{{}}} {}",
+                labels(m),
+                m.errors
+            );
+        }
+
+        out.push_str("# HELP metakit_request_duration_seconds Latency of requests that received a response\n");
+        out.push_str("# TYPE metakit_request_duration_seconds histogram\n");
+        for m in inner.endpoints.values() {
+            write_histogram(
+                &mut out,
+                "metakit_request_duration_seconds",
+                &labels(m),
+                &m.latency,
+            );
+        }
+
+        out.push_str(
+            "# HELP metakit_confirmation_duration_seconds Time from submission to confirmation\n",
+        );
+        out.push_str("# TYPE metakit_confirmation_duration_seconds histogram\n");
+        write_histogram(
+            &mut out,
+            "metakit_confirmation_duration_seconds",
+            "",
+            &inner.confirmations,
+        );
+
+        out
+    }
+
+    fn record(&self, context: &RequestContext<'_>, latency: Option<Duration>, error: bool) {
+        let url = context.url;
+        let method = context.method.as_str();
+        let node = match (url.host_str(), url.port_or_known_default()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => String::new(),
+        };
+        let endpoint = normalize_path(url.path());
+        let key = (node.clone(), method.to_string(), endpoint.clone());
+
+        let mut inner = self.lock();
+        let entry = inner
+            .endpoints
+            .entry(key)
+            .or_insert_with(|| EndpointMetrics {
+                node,
+                method: method.to_string(),
+                endpoint,
+                requests: 0,
+                errors: 0,
+                latency: Histogram::new(LATENCY_BUCKETS),
+            });
+        entry.requests += 1;
+        if error {
+            entry.errors += 1;
+        }
+        if let Some(latency) = latency {
+            entry.latency.observe(latency);
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Interceptor for MetricsRegistry {
+    fn after_receive(&self, context: &RequestContext<'_>, response: &reqwest::Response) {
+        let error = !response.status().is_success();
+        self.record(context, Some(context.elapsed), error);
+    }
+
+    fn on_error(&self, context: &RequestContext<'_>, _error: &NetworkError) {
+        self.record(context, None, true);
+    }
+}
+
+/// Replace path segments that identify addresses, hashes, or ordinals
+fn normalize_path(path: &str) -> String {
+    path.split('/')
+        .map(|segment| {
+            let is_ordinal = !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit());
+            let is_hash = segment.len() == 64 && segment.bytes().all(|b| b.is_ascii_hexdigit());
+            let is_address = segment.len() == 40 && segment.starts_with("DAG");
+            if is_ordinal || is_hash || is_address {
+                ":param"
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn labels(m: &EndpointMetrics) -> String {
+    format!(
+        "node=\"{}\",method=\"{}\",endpoint=\"{}\"",
+        escape(&m.node),
+        m.method,
+        escape(&m.endpoint)
+    )
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn write_histogram(out: &mut String, name: &str, labels: &str, histogram: &Histogram) {
+    let sep = if labels.is_empty() { "" } else { "," };
+    for (bound, count) in histogram.bounds.iter().zip(&histogram.counts) {
+        let _ = writeln!(
+            out,
+            "{}_bucket{{{}{}le=\"{}\"}} {}",
+            name, labels, sep, bound, count
+        );
+    }
+    let _ = writeln!(
+        out,
+        "{}_bucket{{{}{}le=\"+Inf\"}} {}",
+        name, labels, sep, histogram.count
+    );
+    let braces = if labels.is_empty() {
+        String::new()
+    } else {
+        format!("{{{}}}", labels)
+    };
+    let _ = writeln!(out, "{}_sum{} {}", name, braces, histogram.sum);
+    let _ = writeln!(out, "{}_count{} {}", name, braces, histogram.count);
+}
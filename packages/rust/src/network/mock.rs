@@ -0,0 +1,220 @@
+//! In-memory L1 client for unit tests
+//!
+//! [`MockL1Client`] implements [`CurrencyL1Api`] and [`DataL1Api`] without a
+//! node. It chains last references the way a Currency L1 does, keeps submitted
+//! transactions in a pending pool until they are confirmed, and records every
+//! submission for assertions.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{CurrencyL1Api, MockL1Client};
+//!
+//! let mock = MockL1Client::new();
+//! let hash = submit(&mock, &tx).await?.hash;
+//!
+//! assert_eq!(mock.transactions().len(), 1);
+//! assert!(mock.get_pending_transaction(&hash).await?.is_some());
+//!
+//! mock.confirm(&hash);
+//! assert!(mock.get_pending_transaction(&hash).await?.is_none());
+//! ```
+
+use async_trait::async_trait;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+
+use super::api::{CurrencyL1Api, DataL1Api};
+use super::types::{
+    EstimateFeeResponse, NetworkError, NetworkResult, PendingTransaction, PostDataResponse,
+    PostTransactionResponse, TransactionStatus,
+};
+use crate::currency_transaction::hash_currency_transaction;
+use crate::currency_types::{CurrencyTransaction, TransactionReference};
+use crate::hash::hash_data;
+use crate::types::Signed;
+
+/// Hash of the reference an address without transactions chains from
+const GENESIS_REFERENCE_HASH: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Default)]
+struct MockState {
+    last_references: HashMap<String, TransactionReference>,
+    pending: HashMap<String, PendingTransaction>,
+    transactions: Vec<CurrencyTransaction>,
+    data: Vec<serde_json::Value>,
+    fee: EstimateFeeResponse,
+    errors: VecDeque<NetworkError>,
+}
+
+/// In-memory stand-in for a Currency L1 / Data L1 node
+#[derive(Default)]
+pub struct MockL1Client {
+    state: Mutex<MockState>,
+}
+
+impl MockL1Client {
+    /// Create an empty mock; every address starts at the genesis reference
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the last reference returned for an address
+    pub fn set_last_reference(&self, address: impl Into<String>, reference: TransactionReference) {
+        self.state()
+            .last_references
+            .insert(address.into(), reference);
+    }
+
+    /// Set the response of [`DataL1Api::estimate_fee`]
+    pub fn set_fee(&self, fee: i64, address: impl Into<String>) {
+        self.state().fee = EstimateFeeResponse {
+            fee,
+            address: address.into(),
+            ..Default::default()
+        };
+    }
+
+    /// Fail the next call with `error`
+    ///
+    /// Queued errors are returned in order, one per call, before the mock
+    /// resumes answering normally.
+    pub fn push_error(&self, error: NetworkError) {
+        self.state().errors.push_back(error);
+    }
+
+    /// Set the status of a pending transaction, returning `false` if unknown
+    pub fn set_status(&self, hash: &str, status: TransactionStatus) -> bool {
+        match self.state().pending.get_mut(hash) {
+            Some(pending) => {
+                pending.status = status;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove a transaction from the pending pool, as a node does on acceptance
+    pub fn confirm(&self, hash: &str) -> bool {
+        self.state().pending.remove(hash).is_some()
+    }
+
+    /// Transactions submitted so far, in order
+    pub fn transactions(&self) -> Vec<CurrencyTransaction> {
+        self.state().transactions.clone()
+    }
+
+    /// Data submitted so far, serialized to JSON, in order
+    pub fn data(&self) -> Vec<serde_json::Value> {
+        self.state().data.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn next_call(&self) -> NetworkResult<MutexGuard<'_, MockState>> {
+        let mut state = self.state();
+        match state.errors.pop_front() {
+            Some(error) => Err(error),
+            None => Ok(state),
+        }
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl CurrencyL1Api for MockL1Client {
+    async fn get_last_reference(&self, address: &str) -> NetworkResult<TransactionReference> {
+        let state = self.next_call()?;
+        Ok(state
+            .last_references
+            .get(address)
+            .cloned()
+            .unwrap_or_else(|| TransactionReference {
+                hash: GENESIS_REFERENCE_HASH.to_string(),
+                ordinal: 0,
+            }))
+    }
+
+    async fn post_transaction(
+        &self,
+        transaction: &CurrencyTransaction,
+    ) -> NetworkResult<PostTransactionResponse> {
+        let mut state = self.next_call()?;
+        let hash = hash_currency_transaction(transaction).value;
+        state.last_references.insert(
+            transaction.value.source.clone(),
+            TransactionReference {
+                hash: hash.clone(),
+                ordinal: transaction.value.parent.ordinal + 1,
+            },
+        );
+        state.pending.insert(
+            hash.clone(),
+            PendingTransaction {
+                hash: hash.clone(),
+                status: TransactionStatus::Waiting,
+                transaction: transaction.clone(),
+                extra: HashMap::new(),
+            },
+        );
+        state.transactions.push(transaction.clone());
+        Ok(PostTransactionResponse {
+            hash,
+            extra: HashMap::new(),
+        })
+    }
+
+    async fn get_pending_transaction(
+        &self,
+        hash: &str,
+    ) -> NetworkResult<Option<PendingTransaction>> {
+        let state = self.next_call()?;
+        Ok(state.pending.get(hash).cloned())
+    }
+
+    async fn get_pending_transactions(
+        &self,
+        address: &str,
+    ) -> NetworkResult<Vec<PendingTransaction>> {
+        let state = self.next_call()?;
+        let mut pending: Vec<PendingTransaction> = state
+            .pending
+            .values()
+            .filter(|tx| tx.transaction.value.source == address)
+            .cloned()
+            .collect();
+        pending.sort_by_key(|tx| tx.transaction.value.parent.ordinal);
+        Ok(pending)
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl DataL1Api for MockL1Client {
+    async fn estimate_fee<T: Serialize + Sync>(
+        &self,
+        _data: &Signed<T>,
+    ) -> NetworkResult<EstimateFeeResponse> {
+        let state = self.next_call()?;
+        Ok(state.fee.clone())
+    }
+
+    async fn post_data<T: Serialize + Sync>(
+        &self,
+        data: &Signed<T>,
+    ) -> NetworkResult<PostDataResponse> {
+        let mut state = self.next_call()?;
+        let json = serde_json::to_value(data)
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))?;
+        let hash = hash_data(&data.value, true)?.value;
+        state.data.push(json);
+        Ok(PostDataResponse {
+            hash,
+            extra: HashMap::new(),
+        })
+    }
+}
@@ -7,6 +7,9 @@
 //! - **CL1** (Currency L1): Currency transactions
 //! - **DL1** (Data L1): Data/update submissions
 //!
+//! Global L0 (hypergraph) queries are served by the separate [`GlobalL0Client`],
+//! and [`NetworkConfig`] builds all of these clients from one set of endpoints.
+//!
 //! # Features
 //!
 //! This module requires the `network` feature to be enabled:
@@ -16,6 +19,15 @@
 //! constellation-metagraph-sdk = { version = "0.1", features = ["network"] }
 //! ```
 //!
+//! Synchronous wrappers for non-async code live in the `blocking` module (requires the
+//! `blocking` feature). The `snapshot` module decodes full snapshot bodies into blocks,
+//! transactions, and balances. The `store` module (requires the `store` feature) keeps
+//! a local, offline transaction history per address.
+//!
+//! The `network` feature also builds for `wasm32-unknown-unknown`, where requests go
+//! through the browser's `fetch` and no tokio runtime is needed. There, `ConnectionOptions`
+//! is ignored, a `TlsConfig` is rejected, and `blocking` is unavailable.
+//!
 //! # Example
 //!
 //! ```ignore
@@ -26,6 +38,9 @@
 //! let last_ref = cl1.get_last_reference("DAG...").await?;
 //! cl1.post_transaction(&signed_tx).await?;
 //!
+//! // Or fetch the reference, sign, and submit in one call
+//! let hash = cl1.transfer(transfer_params, &private_key).await?;
+//!
 //! // Data L1 client
 //! let dl1 = create_metagraph_client("http://localhost:9400", LayerType::DL1)?;
 //! let fee = dl1.estimate_fee(&signed_data).await?;
@@ -36,20 +51,150 @@
 //! let info = ml0.get_cluster_info().await?;
 //! ```
 
+mod account;
+mod api;
+mod balance_watch;
+mod batch;
+mod block_explorer;
+#[cfg(all(feature = "blocking", not(target_arch = "wasm32")))]
+pub mod blocking;
+mod body;
+mod broadcast;
+mod calculated_state;
+mod checked;
+mod circuit_breaker;
 mod client;
+mod config;
+mod confirmation;
+mod connection;
+mod currency_l0;
+mod custom_routes;
+mod data_fee;
+mod discovery;
+mod global_l0_client;
+mod idempotency;
+mod interceptor;
+mod metagraph;
 mod metagraph_client;
+mod metrics;
+mod mock;
+mod node_error;
+mod node_info;
+mod node_metrics;
+mod pending_tracker;
+mod rate_limiter;
+mod receipt;
+mod reference_manager;
+mod registry;
+mod resolver;
+mod response_cache;
+mod retry;
+mod rt;
+mod sequenced;
+pub mod snapshot;
+mod snapshot_fees;
+mod snapshot_verification;
+mod spend_policy;
+#[cfg(all(feature = "store", not(target_arch = "wasm32")))]
+pub mod store;
+mod subscription;
+#[cfg(all(feature = "testkit", not(target_arch = "wasm32")))]
+pub mod testkit;
+mod tls;
+mod transaction_monitor;
+mod transaction_watch;
+mod transfer;
+mod typed_update;
 mod types;
+mod validation;
 
 // Generic metagraph client
 pub use metagraph_client::{
     create_metagraph_client, ClusterInfo, LayerType, MetagraphClient, MetagraphClientConfig,
 };
 
+// Client traits and an in-memory implementation for tests
+pub use api::{CurrencyL1Api, DataL1Api};
+pub use mock::MockL1Client;
+
+// Data submissions that pay a fee
+pub use data_fee::{sign_fee_transaction, FeeTransaction};
+
+// Deployment configuration
+pub use batch::DEFAULT_BATCH_CONCURRENCY;
+pub use calculated_state::CalculatedState;
+pub use config::NetworkConfig;
+pub use discovery::PeerInfo;
+pub use node_info::{NodeInfo, NodeState};
+pub use node_metrics::{MetricSample, NodeMetrics};
+
+// Global L0 client
+pub use global_l0_client::GlobalL0Client;
+
+// Ordered data updates
+pub use sequenced::{SequenceOutcome, SequenceStop, SequencedUpdates};
+
+// Snapshot fee budgeting
+pub use snapshot_fees::{SnapshotFeeProjection, SnapshotFeeSchedule, SnapshotUsage};
+
+// Snapshot verification
+pub use snapshot_verification::{verify_snapshot, SnapshotVerification, ValidatorSet};
+
+// Idempotent submissions
+pub use idempotency::IdempotencyCache;
+
+// Response caching
+pub use response_cache::{CacheConfig, ResponseCache};
+
+// Block explorer history
+pub use block_explorer::{BlockExplorerClient, ExplorerTransaction, Page, PageRequest};
+pub use resolver::MetagraphEndpoints;
+
+// One metagraph behind a single handle, or several side by side
+pub use metagraph::Metagraph;
+pub use registry::MetagraphRegistry;
+
+// Client metrics
+pub use metrics::{EndpointMetrics, Histogram, MetricsRegistry};
+
+// Waiting for submitted transactions to settle
+pub use confirmation::{ConfirmationOutcome, PollOptions};
+
+// Polling-based event streams
+pub use balance_watch::BalanceChange;
+pub use subscription::{SubscriptionEvent, SubscriptionOptions};
+
+// Local tracking of submitted transactions
+pub use pending_tracker::{PendingTracker, TrackedStatus, TrackedTransaction};
+pub use reference_manager::{ReferenceLease, ReferenceManager};
+pub use transaction_monitor::{
+    MonitorEvent, MonitorStore, TransactionMonitor, DEFAULT_MONITOR_INTERVAL,
+};
+
+// High-level transfers
+pub use broadcast::BroadcastOutcome;
+pub use transfer::{ResubmitOutcome, SweepOutcome, TransferOutcome};
+
+// Accounts
+pub use account::Account;
+pub use receipt::{Receipt, ReceiptKind};
+pub use spend_policy::{PolicyViolation, SpendPolicy};
+
 // HTTP client (for custom implementations)
+pub use circuit_breaker::{CircuitBreaker, CircuitBreakerConfig, CircuitState};
 pub use client::HttpClient;
+pub use connection::ConnectionOptions;
+pub use interceptor::{Interceptor, RequestContext};
+pub use rate_limiter::{RateLimitConfig, RateLimiter};
+// Re-exported so interceptors can name the request/response types
+pub use reqwest;
+pub use retry::RetryPolicy;
+pub use tls::TlsConfig;
 
 // Types and errors
+pub use node_error::NodeError;
 pub use types::{
-    EstimateFeeResponse, NetworkError, PendingTransaction, PostDataResponse,
-    PostTransactionResponse, RequestOptions, TransactionStatus,
+    Balance, CirculatingSupply, CurrencySnapshot, ErrorKind, EstimateFeeResponse, GlobalSnapshot,
+    NetworkError, NetworkResult, PendingTransaction, PostDataResponse, PostTransactionResponse,
+    RequestOptions, SnapshotHeader, TotalSupply, TransactionStatus,
 };
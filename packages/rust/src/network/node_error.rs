@@ -0,0 +1,123 @@
+//! Typed errors reported by nodes
+//!
+//! Currency and Data L1 nodes reject submissions with a 4xx response whose
+//! JSON body names the validation that failed, e.g.
+//! `{"errors": [{"message": "InsufficientBalance(...)"}]}`. [`NodeError`]
+//! turns the failures applications commonly react to into variants, so a
+//! wallet can tell a low balance from a stale parent reference without
+//! matching on the body text. `HttpClient` returns them as
+//! [`NetworkError::Node`](super::NetworkError::Node).
+
+use serde_json::Value;
+use thiserror::Error;
+
+/// A recognized validation failure reported by a node
+///
+/// Each variant carries the node's message.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum NodeError {
+    /// The source address cannot cover the amount and fee
+    #[error("Insufficient balance: {0}")]
+    InsufficientBalance(String),
+
+    /// The source address sent too many fee-less transactions recently
+    #[error("Transaction limited: {0}")]
+    TransactionLimited(String),
+
+    /// The parent reference is not the address's last accepted transaction
+    #[error("Parent ordinal mismatch: {0}")]
+    ParentOrdinalMismatch(String),
+
+    /// A proof is invalid or not by the source address's owner
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+
+    /// The fee is below what the node requires
+    #[error("Fee too low: {0}")]
+    FeeTooLow(String),
+}
+
+impl NodeError {
+    /// The first recognized failure in a node's error body
+    ///
+    /// Returns `None` if the body is not JSON or names no known failure.
+    pub fn parse(body: &str) -> Option<Self> {
+        serde_json::from_str::<Value>(body.trim()).ok()?;
+        reasons(body)
+            .into_iter()
+            .find_map(|reason| Self::classify(&reason))
+    }
+
+    /// Recognize a single reason reported by a node
+    pub fn classify(reason: &str) -> Option<Self> {
+        let key: String = reason
+            .chars()
+            .filter(char::is_ascii_alphanumeric)
+            .map(|c| c.to_ascii_lowercase())
+            .collect();
+        let has = |patterns: &[&str]| patterns.iter().any(|p| key.contains(p));
+        let message = reason.to_string();
+        if has(&["insufficientbalance"]) {
+            Some(Self::InsufficientBalance(message))
+        } else if has(&["transactionlimited"]) {
+            Some(Self::TransactionLimited(message))
+        } else if has(&["parentordinal", "nomatchingparent", "parenthash"]) {
+            Some(Self::ParentOrdinalMismatch(message))
+        } else if has(&[
+            "invalidsigned",
+            "invalidsignature",
+            "notsignedbysourceaddressowner",
+        ]) {
+            Some(Self::InvalidSignature(message))
+        } else if has(&["feetoolow", "insufficientfee", "feebelowminimum"]) {
+            Some(Self::FeeTooLow(message))
+        } else {
+            None
+        }
+    }
+
+    /// The node's message
+    pub fn message(&self) -> &str {
+        match self {
+            Self::InsufficientBalance(message)
+            | Self::TransactionLimited(message)
+            | Self::ParentOrdinalMismatch(message)
+            | Self::InvalidSignature(message)
+            | Self::FeeTooLow(message) => message,
+        }
+    }
+}
+
+/// Reasons in an error body
+///
+/// Understands `{"errors": [...]}` lists of strings or of objects with a
+/// `message`, `reason`, or `error` field, a single object with one of those
+/// fields, a JSON string, and plain text.
+pub(super) fn reasons(body: &str) -> Vec<String> {
+    let body = body.trim();
+    let Ok(json) = serde_json::from_str::<Value>(body) else {
+        return if body.is_empty() {
+            Vec::new()
+        } else {
+            vec![body.to_string()]
+        };
+    };
+    match &json {
+        Value::Object(object) => match object.get("errors") {
+            Some(Value::Array(errors)) => errors.iter().filter_map(reason).collect(),
+            _ => reason(&json).into_iter().collect(),
+        },
+        Value::Array(errors) => errors.iter().filter_map(reason).collect(),
+        _ => reason(&json).into_iter().collect(),
+    }
+}
+
+fn reason(value: &Value) -> Option<String> {
+    match value {
+        Value::String(message) => Some(message.clone()),
+        Value::Object(object) => ["message", "reason", "error"]
+            .iter()
+            .find_map(|field| object.get(*field).and_then(reason)),
+        _ => None,
+    }
+}
@@ -0,0 +1,162 @@
+//! Typed node state from `/node/info`
+//!
+//! [`check_health`](super::MetagraphClient::check_health) only tells whether a
+//! node answers. [`MetagraphClient::node_info`] and
+//! [`GlobalL0Client::node_info`] also report what the node is doing, so callers
+//! can, for example, hold off submissions until it is [`NodeState::Ready`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::NodeState;
+//!
+//! let info = cl1.node_info().await?;
+//! if info.state != NodeState::Ready {
+//!     println!("node {} is {}", info.id, info.state);
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+use super::global_l0_client::GlobalL0Client;
+use super::metagraph_client::MetagraphClient;
+use super::types::NetworkResult;
+
+/// Lifecycle state of a node
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum NodeState {
+    /// Started, not yet joining
+    Initial,
+    /// Ready to join a cluster
+    ReadyToJoin,
+    /// Loading the genesis snapshot
+    LoadingGenesis,
+    /// Genesis loaded
+    GenesisReady,
+    /// Starting a cluster session
+    StartingSession,
+    /// Cluster session started
+    SessionStarted,
+    /// Waiting to download snapshots
+    WaitingForDownload,
+    /// Downloading snapshots
+    DownloadInProgress,
+    /// Joined and catching up; not serving consensus yet
+    Observing,
+    /// Fully joined and serving requests
+    Ready,
+    /// Leaving the cluster
+    Leaving,
+    /// Left the cluster
+    Offline,
+    /// A state this SDK does not know
+    Unknown(String),
+}
+
+impl NodeState {
+    /// Name of the state as reported by nodes
+    pub fn as_str(&self) -> &str {
+        match self {
+            NodeState::Initial => "Initial",
+            NodeState::ReadyToJoin => "ReadyToJoin",
+            NodeState::LoadingGenesis => "LoadingGenesis",
+            NodeState::GenesisReady => "GenesisReady",
+            NodeState::StartingSession => "StartingSession",
+            NodeState::SessionStarted => "SessionStarted",
+            NodeState::WaitingForDownload => "WaitingForDownload",
+            NodeState::DownloadInProgress => "DownloadInProgress",
+            NodeState::Observing => "Observing",
+            NodeState::Ready => "Ready",
+            NodeState::Leaving => "Leaving",
+            NodeState::Offline => "Offline",
+            NodeState::Unknown(state) => state,
+        }
+    }
+}
+
+impl From<String> for NodeState {
+    fn from(state: String) -> Self {
+        match state.as_str() {
+            "Initial" => NodeState::Initial,
+            "ReadyToJoin" => NodeState::ReadyToJoin,
+            "LoadingGenesis" => NodeState::LoadingGenesis,
+            "GenesisReady" => NodeState::GenesisReady,
+            "StartingSession" => NodeState::StartingSession,
+            "SessionStarted" => NodeState::SessionStarted,
+            "WaitingForDownload" => NodeState::WaitingForDownload,
+            "DownloadInProgress" => NodeState::DownloadInProgress,
+            "Observing" => NodeState::Observing,
+            "Ready" => NodeState::Ready,
+            "Leaving" => NodeState::Leaving,
+            "Offline" => NodeState::Offline,
+            _ => NodeState::Unknown(state),
+        }
+    }
+}
+
+impl From<NodeState> for String {
+    fn from(state: NodeState) -> Self {
+        state.as_str().to_string()
+    }
+}
+
+impl fmt::Display for NodeState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A node's own view of itself, from `/node/info`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeInfo {
+    /// Node ID (the node's public key, hex)
+    pub id: String,
+    /// Current lifecycle state
+    pub state: NodeState,
+    /// Software version
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Token of the node's current session
+    #[serde(default)]
+    pub session: Option<String>,
+    /// Token of the cluster session the node joined
+    #[serde(default)]
+    pub cluster_session: Option<String>,
+    /// Host the node advertises
+    #[serde(default)]
+    pub host: Option<String>,
+    /// Port of the public HTTP API
+    #[serde(default)]
+    pub public_port: Option<u16>,
+    /// Port of the peer-to-peer API
+    #[serde(default)]
+    pub p2p_port: Option<u16>,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl NodeInfo {
+    /// Whether the node is ready to serve requests
+    pub fn is_ready(&self) -> bool {
+        self.state == NodeState::Ready
+    }
+}
+
+impl MetagraphClient {
+    /// Get the node's ID, state, version, and session
+    pub async fn node_info(&self) -> NetworkResult<NodeInfo> {
+        self.get("/node/info").await
+    }
+}
+
+impl GlobalL0Client {
+    /// Get the node's ID, state, version, and session
+    pub async fn node_info(&self) -> NetworkResult<NodeInfo> {
+        self.client.get("/node/info").await
+    }
+}
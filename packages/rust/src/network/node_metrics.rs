@@ -0,0 +1,224 @@
+//! Node metrics scraped from the Prometheus endpoint
+//!
+//! Nodes publish their own metrics at `/metrics` in the Prometheus text
+//! format. [`MetagraphClient::get_metrics`] and [`GlobalL0Client::get_metrics`]
+//! fetch and parse that page into [`NodeMetrics`], which keeps every sample
+//! and picks out the series operator dashboards usually need.
+//!
+//! Throughput is a counter, so it takes two scrapes:
+//!
+//! ```ignore
+//! let before = cl1.get_metrics().await?;
+//! tokio::time::sleep(Duration::from_secs(10)).await;
+//! let after = cl1.get_metrics().await?;
+//!
+//! println!("tps: {:?}", after.tps_since(&before));
+//! println!("lag: {:?}", after.snapshot_lag(gl0.get_latest_snapshot_ordinal().await?));
+//! println!("heap: {:?}", after.memory_used_bytes);
+//! ```
+
+use std::collections::BTreeMap;
+
+use super::global_l0_client::GlobalL0Client;
+use super::metagraph_client::MetagraphClient;
+use super::rt::Instant;
+use super::types::{NetworkError, NetworkResult};
+
+/// Counter of transactions the node has accepted
+const TRANSACTIONS_SERIES: &str = "dag_transactions_accepted_total";
+
+/// Gauges of the latest snapshot ordinal, global first
+const SNAPSHOT_ORDINAL_SERIES: &[&str] = &[
+    "dag_global_snapshot_ordinal",
+    "dag_currency_snapshot_ordinal",
+];
+
+/// JVM memory gauges, summed over the heap pools
+const MEMORY_USED_SERIES: &str = "jvm_memory_used_bytes";
+const MEMORY_MAX_SERIES: &str = "jvm_memory_max_bytes";
+
+/// One sample of a Prometheus series
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSample {
+    /// Metric name
+    pub name: String,
+    /// Labels, e.g. `area="heap"`
+    pub labels: BTreeMap<String, String>,
+    /// Sample value
+    pub value: f64,
+}
+
+/// Metrics published by a node
+#[derive(Debug, Clone)]
+pub struct NodeMetrics {
+    /// Total transactions accepted since the node started
+    pub transactions_accepted: Option<f64>,
+    /// Ordinal of the latest snapshot the node knows
+    pub snapshot_ordinal: Option<i64>,
+    /// JVM heap in use, in bytes
+    pub memory_used_bytes: Option<f64>,
+    /// JVM heap limit, in bytes
+    pub memory_max_bytes: Option<f64>,
+    /// Every sample on the page
+    pub samples: Vec<MetricSample>,
+    /// When the page was parsed
+    pub scraped_at: Instant,
+}
+
+impl NodeMetrics {
+    /// Parse a page in the Prometheus text format
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SerializationError` naming the first malformed line
+    pub fn parse(text: &str) -> NetworkResult<Self> {
+        let samples = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .map(|(index, line)| {
+                parse_sample(line).ok_or_else(|| {
+                    NetworkError::SerializationError(format!(
+                        "invalid metrics line {}: {}",
+                        index + 1,
+                        line
+                    ))
+                })
+            })
+            .collect::<NetworkResult<Vec<_>>>()?;
+
+        let heap = |name: &str| {
+            let mut pools = samples
+                .iter()
+                .filter(|s| {
+                    s.name == name && s.labels.get("area").map(String::as_str) == Some("heap")
+                })
+                .peekable();
+            pools.peek()?;
+            Some(pools.map(|s| s.value).sum())
+        };
+
+        Ok(Self {
+            transactions_accepted: sum(&samples, TRANSACTIONS_SERIES),
+            snapshot_ordinal: SNAPSHOT_ORDINAL_SERIES
+                .iter()
+                .find_map(|name| sum(&samples, name))
+                .map(|ordinal| ordinal as i64),
+            memory_used_bytes: heap(MEMORY_USED_SERIES),
+            memory_max_bytes: heap(MEMORY_MAX_SERIES),
+            scraped_at: Instant::now(),
+            samples,
+        })
+    }
+
+    /// Value of a series, summed over all its labels
+    pub fn value(&self, name: &str) -> Option<f64> {
+        sum(&self.samples, name)
+    }
+
+    /// Transactions per second between an earlier scrape and this one
+    ///
+    /// Returns `None` if either scrape lacks the transaction counter, or if
+    /// the counter went backwards because the node restarted.
+    pub fn tps_since(&self, earlier: &NodeMetrics) -> Option<f64> {
+        let accepted = self.transactions_accepted? - earlier.transactions_accepted?;
+        let elapsed = self
+            .scraped_at
+            .checked_duration_since(earlier.scraped_at)?
+            .as_secs_f64();
+        (accepted >= 0.0 && elapsed > 0.0).then(|| accepted / elapsed)
+    }
+
+    /// How many snapshots the node is behind `latest_ordinal`
+    pub fn snapshot_lag(&self, latest_ordinal: i64) -> Option<i64> {
+        self.snapshot_ordinal
+            .map(|ordinal| latest_ordinal.saturating_sub(ordinal).max(0))
+    }
+}
+
+fn sum(samples: &[MetricSample], name: &str) -> Option<f64> {
+    let mut matching = samples.iter().filter(|s| s.name == name).peekable();
+    matching.peek()?;
+    Some(matching.map(|s| s.value).sum())
+}
+
+/// Parse `name{label="value",...} value [timestamp]`
+fn parse_sample(line: &str) -> Option<MetricSample> {
+    let name_end = line
+        .find(|c: char| c == '{' || c.is_whitespace())
+        .unwrap_or(line.len());
+    let name = &line[..name_end];
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut rest = &line[name_end..];
+    let mut labels = BTreeMap::new();
+    if let Some(body) = rest.strip_prefix('{') {
+        let (parsed, after) = parse_labels(body)?;
+        labels = parsed;
+        rest = after;
+    }
+
+    let value = match rest.split_whitespace().next()? {
+        "+Inf" => f64::INFINITY,
+        "-Inf" => f64::NEG_INFINITY,
+        value => value.parse().ok()?,
+    };
+    Some(MetricSample {
+        name: name.to_string(),
+        labels,
+        value,
+    })
+}
+
+/// Parse labels up to the closing brace, returning the text after it
+fn parse_labels(mut body: &str) -> Option<(BTreeMap<String, String>, &str)> {
+    let mut labels = BTreeMap::new();
+    loop {
+        body = body.trim_start_matches([' ', ',']);
+        if let Some(rest) = body.strip_prefix('}') {
+            return Some((labels, rest));
+        }
+        let (key, rest) = body.split_once('=')?;
+        let mut chars = rest.strip_prefix('"')?.char_indices();
+        let mut value = String::new();
+        let end = loop {
+            match chars.next()? {
+                (i, '"') => break i,
+                (_, '\\') => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    c => value.push(c),
+                },
+                (_, c) => value.push(c),
+            }
+        };
+        labels.insert(key.trim().to_string(), value);
+        body = &rest[end + 2..];
+    }
+}
+
+impl MetagraphClient {
+    /// Scrape the node's Prometheus metrics
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the page cannot be fetched or is not in the
+    /// Prometheus text format
+    pub async fn get_metrics(&self) -> NetworkResult<NodeMetrics> {
+        NodeMetrics::parse(&self.get_text("/metrics").await?)
+    }
+}
+
+impl GlobalL0Client {
+    /// Scrape the node's Prometheus metrics
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the page cannot be fetched or is not in the
+    /// Prometheus text format
+    pub async fn get_metrics(&self) -> NetworkResult<NodeMetrics> {
+        NodeMetrics::parse(&self.client.get_text("/metrics").await?)
+    }
+}
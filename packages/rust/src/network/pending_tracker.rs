@@ -0,0 +1,276 @@
+//! Local tracking of submitted currency transactions
+//!
+//! Records the hash and parent reference of every transaction submitted from
+//! this process and reconciles them against a Currency L1 node, so callers can
+//! tell which transactions are still in flight, which landed, and which need
+//! to be rebroadcast or rebuilt.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{MetagraphClient, LayerType, PendingTracker};
+//!
+//! let cl1 = MetagraphClient::new("http://localhost:9300", LayerType::CL1)?;
+//! let mut tracker = PendingTracker::new();
+//!
+//! cl1.post_transaction(&tx).await?;
+//! let hash = tracker.track(&tx);
+//!
+//! tracker.reconcile(&cl1).await?;
+//! println!("{:?}", tracker.status(&hash));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use super::confirmation::{settle, Settlement};
+use super::metagraph_client::MetagraphClient;
+use super::metrics::MetricsRegistry;
+use super::rt::SystemTime;
+use super::types::{NetworkResult, TransactionStatus};
+use crate::currency_transaction::hash_currency_transaction;
+use crate::currency_types::{CurrencyTransaction, TransactionReference};
+
+/// State of a locally submitted transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TrackedStatus {
+    /// Recorded locally but not yet reconciled against a node
+    Submitted,
+    /// Present in the L1 pending pool with the given status
+    Pending(TransactionStatus),
+    /// Left the pending pool and is part of the source's accepted chain
+    Confirmed,
+    /// Left the pending pool and its parent ordinal was consumed by a
+    /// transaction this tracker cannot link back to it
+    Conflicted,
+    /// Left the pending pool without being accepted; safe to rebroadcast
+    Dropped,
+    /// Left the pending pool and the source chain has since moved more than
+    /// one ordinal past its parent, so L1 alone cannot tell whether it was
+    /// accepted
+    Superseded,
+}
+
+impl TrackedStatus {
+    /// Whether no further state change is expected
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            TrackedStatus::Confirmed
+                | TrackedStatus::Conflicted
+                | TrackedStatus::Dropped
+                | TrackedStatus::Superseded
+        )
+    }
+}
+
+impl fmt::Display for TrackedStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrackedStatus::Submitted => write!(f, "Submitted"),
+            TrackedStatus::Pending(status) => write!(f, "Pending({status})"),
+            TrackedStatus::Confirmed => write!(f, "Confirmed"),
+            TrackedStatus::Conflicted => write!(f, "Conflicted"),
+            TrackedStatus::Dropped => write!(f, "Dropped"),
+            TrackedStatus::Superseded => write!(f, "Superseded"),
+        }
+    }
+}
+
+/// A transaction recorded by [`PendingTracker`]
+#[derive(Debug, Clone)]
+pub struct TrackedTransaction {
+    /// Transaction hash
+    pub hash: String,
+    /// Source DAG address
+    pub source: String,
+    /// Parent reference the transaction was built on
+    pub parent: TransactionReference,
+    /// Last known state
+    pub status: TrackedStatus,
+    /// When the transaction was recorded
+    pub submitted_at: SystemTime,
+}
+
+/// Tracks locally submitted transactions until they settle
+#[derive(Debug, Default)]
+pub struct PendingTracker {
+    entries: HashMap<String, TrackedTransaction>,
+    metrics: Option<Arc<MetricsRegistry>>,
+}
+
+impl PendingTracker {
+    /// Create an empty tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Report confirmation times of tracked transactions to a metrics registry
+    pub fn with_metrics(mut self, metrics: Arc<MetricsRegistry>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    /// Record a submitted transaction and return its hash
+    pub fn track(&mut self, transaction: &CurrencyTransaction) -> String {
+        let hash = hash_currency_transaction(transaction).value;
+        self.track_hash(
+            hash.clone(),
+            transaction.value.source.clone(),
+            transaction.value.parent.clone(),
+        );
+        hash
+    }
+
+    /// Record a submitted transaction by hash, source, and parent reference
+    pub fn track_hash(
+        &mut self,
+        hash: impl Into<String>,
+        source: impl Into<String>,
+        parent: TransactionReference,
+    ) {
+        let hash = hash.into();
+        self.entries.insert(
+            hash.clone(),
+            TrackedTransaction {
+                hash,
+                source: source.into(),
+                parent,
+                status: TrackedStatus::Submitted,
+                submitted_at: SystemTime::now(),
+            },
+        );
+    }
+
+    /// Record a transaction with its saved state, e.g. after a restart
+    pub(super) fn insert(&mut self, transaction: TrackedTransaction) {
+        self.entries.insert(transaction.hash.clone(), transaction);
+    }
+
+    /// Get a tracked transaction by hash
+    pub fn get(&self, hash: &str) -> Option<&TrackedTransaction> {
+        self.entries.get(hash)
+    }
+
+    /// Get the last known state of a tracked transaction
+    pub fn status(&self, hash: &str) -> Option<&TrackedStatus> {
+        self.entries.get(hash).map(|entry| &entry.status)
+    }
+
+    /// Iterate over all tracked transactions
+    pub fn iter(&self) -> impl Iterator<Item = &TrackedTransaction> {
+        self.entries.values()
+    }
+
+    /// Iterate over transactions that have not reached a terminal state
+    pub fn in_flight(&self) -> impl Iterator<Item = &TrackedTransaction> {
+        self.entries.values().filter(|e| !e.status.is_terminal())
+    }
+
+    /// Number of tracked transactions
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the tracker is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Stop tracking a transaction
+    pub fn remove(&mut self, hash: &str) -> Option<TrackedTransaction> {
+        self.entries.remove(hash)
+    }
+
+    /// Remove and return every transaction in a terminal state
+    pub fn prune_settled(&mut self) -> Vec<TrackedTransaction> {
+        let settled: Vec<String> = self
+            .entries
+            .values()
+            .filter(|e| e.status.is_terminal())
+            .map(|e| e.hash.clone())
+            .collect();
+        settled
+            .iter()
+            .filter_map(|hash| self.entries.remove(hash))
+            .collect()
+    }
+
+    /// Refresh the state of every in-flight transaction from a Currency L1 node
+    ///
+    /// Transactions still in the pending pool take the node's status. Ones that
+    /// left the pool are resolved against the source's last reference: the
+    /// transaction is confirmed when the last reference (or a tracked
+    /// descendant) points at it, dropped when its parent ordinal is still
+    /// unused, conflicted when the next ordinal went to another transaction,
+    /// and superseded when the chain has moved further than that.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first network error encountered; states updated before the
+    /// error are kept.
+    pub async fn reconcile(&mut self, client: &MetagraphClient) -> NetworkResult<()> {
+        let mut hashes: Vec<(String, i64)> = self
+            .in_flight()
+            .map(|e| (e.hash.clone(), e.parent.ordinal))
+            .collect();
+        // Resolve descendants first so ancestors can be confirmed through them
+        hashes.sort_by_key(|(_, ordinal)| std::cmp::Reverse(*ordinal));
+
+        let mut last_refs: HashMap<String, TransactionReference> = HashMap::new();
+
+        for (hash, _) in hashes {
+            if let Some(pending) = client.get_pending_transaction(&hash).await? {
+                self.set_status(&hash, TrackedStatus::Pending(pending.status));
+                continue;
+            }
+
+            let (source, parent) = match self.entries.get(&hash) {
+                Some(entry) => (entry.source.clone(), entry.parent.clone()),
+                None => continue,
+            };
+            let last_ref = match last_refs.get(&source) {
+                Some(r) => r.clone(),
+                None => {
+                    let r = client.get_last_reference(&source).await?;
+                    last_refs.insert(source.clone(), r.clone());
+                    r
+                }
+            };
+
+            let status = match settle(&hash, &parent, &last_ref) {
+                _ if self.has_live_descendant(&hash) => TrackedStatus::Confirmed,
+                Settlement::Confirmed => TrackedStatus::Confirmed,
+                Settlement::Dropped => TrackedStatus::Dropped,
+                Settlement::Conflicted => TrackedStatus::Conflicted,
+                Settlement::Superseded => TrackedStatus::Superseded,
+            };
+            self.set_status(&hash, status);
+        }
+
+        Ok(())
+    }
+
+    fn set_status(&mut self, hash: &str, status: TrackedStatus) {
+        if let Some(entry) = self.entries.get_mut(hash) {
+            if status == TrackedStatus::Confirmed && entry.status != TrackedStatus::Confirmed {
+                if let Some(metrics) = &self.metrics {
+                    let elapsed = entry.submitted_at.elapsed().unwrap_or_default();
+                    metrics.record_confirmation(elapsed);
+                }
+            }
+            entry.status = status;
+        }
+    }
+
+    fn has_live_descendant(&self, hash: &str) -> bool {
+        self.entries.values().any(|e| {
+            e.parent.hash == hash
+                && matches!(
+                    e.status,
+                    TrackedStatus::Pending(_) | TrackedStatus::Confirmed
+                )
+        })
+    }
+}
@@ -0,0 +1,131 @@
+//! Client-side rate limiting
+//!
+//! Public nodes throttle or ban clients that send too many requests. A
+//! [`RateLimiter`] is a token bucket: it holds up to `burst` tokens, refills
+//! at `requests_per_second`, and every request attempt, retries included,
+//! waits for a token before it is sent. Background jobs then slow down
+//! instead of running into 429 responses.
+//!
+//! Limiters are set per client through
+//! [`HttpClient::with_rate_limit`](super::HttpClient::with_rate_limit) or
+//! [`NetworkConfig::rate_limit`](super::NetworkConfig::rate_limit); one
+//! limiter can also be shared by several clients that hit the same host.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{NetworkConfig, RateLimitConfig};
+//!
+//! let config = NetworkConfig {
+//!     rate_limit: Some(RateLimitConfig {
+//!         requests_per_second: 10.0,
+//!         burst: 20,
+//!     }),
+//!     ..NetworkConfig::mainnet()
+//! };
+//! ```
+
+use std::sync::{Mutex, MutexGuard};
+use std::time::Duration;
+
+use super::rt::{self, Instant};
+
+/// Configuration for a [`RateLimiter`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitConfig {
+    /// Sustained request rate
+    pub requests_per_second: f64,
+    /// Requests that may be sent at once after a quiet period
+    pub burst: u32,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            requests_per_second: 10.0,
+            burst: 10,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// Token bucket limiting how fast requests are sent
+#[derive(Debug)]
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    /// Create a limiter with a full bucket
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            bucket: Mutex::new(Bucket {
+                tokens: capacity(&config),
+                refilled_at: Instant::now(),
+            }),
+            config,
+        }
+    }
+
+    /// The configuration in use
+    pub fn config(&self) -> &RateLimitConfig {
+        &self.config
+    }
+
+    /// Take a token if one is available, without waiting
+    pub fn try_acquire(&self) -> bool {
+        self.reserve(false).is_some()
+    }
+
+    /// Wait until a token is available and take it
+    pub async fn acquire(&self) {
+        if let Some(wait) = self.reserve(true) {
+            if !wait.is_zero() {
+                rt::sleep(wait).await;
+            }
+        }
+    }
+
+    /// Take a token, returning how long to wait before using it
+    ///
+    /// With `queue`, a token that is not available yet is still taken, so
+    /// concurrent callers queue up in order instead of racing for the next
+    /// refill. Without it, nothing is taken and `None` is returned unless a
+    /// whole token is available.
+    fn reserve(&self, queue: bool) -> Option<Duration> {
+        let rate = self.config.requests_per_second;
+        if rate <= 0.0 || !rate.is_finite() {
+            return Some(Duration::ZERO);
+        }
+        let mut bucket = self.bucket();
+        let now = Instant::now();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(capacity(&self.config));
+        bucket.refilled_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Some(Duration::ZERO);
+        }
+        if !queue {
+            return None;
+        }
+        let wait = (1.0 - bucket.tokens) / rate;
+        bucket.tokens -= 1.0;
+        Some(Duration::from_secs_f64(wait))
+    }
+
+    fn bucket(&self) -> MutexGuard<'_, Bucket> {
+        self.bucket.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn capacity(config: &RateLimitConfig) -> f64 {
+    f64::from(config.burst.max(1))
+}
@@ -0,0 +1,146 @@
+//! Receipts of submissions
+//!
+//! The high-level send and submit calls ([`Account`](super::Account),
+//! [`ReferenceManager`](super::ReferenceManager)) return a [`Receipt`]: the
+//! hash computed locally, the hash the node answered with, and when the
+//! submission was made. Once the submission is found in a snapshot the
+//! receipt also records the snapshot ordinal. Receipts serialize to JSON, so
+//! they can be kept as an audit trail.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut receipt = account.send(transfer_params).await?;
+//! assert!(receipt.hashes_match());
+//!
+//! // Later
+//! if explorer.confirm_receipt(&mut receipt).await? {
+//!     println!("included in snapshot {}", receipt.snapshot_ordinal.unwrap());
+//! }
+//! audit_log.write_all(serde_json::to_string(&receipt)?.as_bytes())?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+use super::block_explorer::BlockExplorerClient;
+use super::rt::SystemTime;
+use super::types::NetworkResult;
+use crate::currency_transaction::hash_currency_transaction;
+use crate::currency_types::CurrencyTransaction;
+use crate::hash::hash_data;
+
+/// What a [`Receipt`] was issued for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum ReceiptKind {
+    /// A currency transaction
+    Transaction,
+    /// A data update
+    DataUpdate,
+}
+
+/// Record of an accepted submission
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct Receipt {
+    /// What was submitted
+    pub kind: ReceiptKind,
+    /// Hash computed locally before submission
+    pub local_hash: String,
+    /// Hash the node answered with
+    pub node_hash: String,
+    /// When the node accepted the submission, in milliseconds since the Unix epoch
+    pub submitted_at: u64,
+    /// Ordinal of the snapshot that included the submission, once known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub snapshot_ordinal: Option<i64>,
+}
+
+impl Receipt {
+    /// Receipt for a currency transaction the node accepted as `node_hash`
+    pub fn for_transaction(
+        transaction: &CurrencyTransaction,
+        node_hash: impl Into<String>,
+    ) -> Self {
+        Self::new(
+            ReceiptKind::Transaction,
+            hash_currency_transaction(transaction).value,
+            node_hash.into(),
+        )
+    }
+
+    /// Receipt for a data update the node accepted as `node_hash`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the update cannot be hashed
+    pub fn for_data_update<T: Serialize>(
+        update: &T,
+        node_hash: impl Into<String>,
+    ) -> NetworkResult<Self> {
+        Ok(Self::new(
+            ReceiptKind::DataUpdate,
+            hash_data(update, true)?.value,
+            node_hash.into(),
+        ))
+    }
+
+    /// Receipt for a data update signed over `local_hash`
+    pub(super) fn for_data_update_hash(local_hash: String, node_hash: String) -> Self {
+        Self::new(ReceiptKind::DataUpdate, local_hash, node_hash)
+    }
+
+    fn new(kind: ReceiptKind, local_hash: String, node_hash: String) -> Self {
+        Self {
+            kind,
+            local_hash,
+            node_hash,
+            submitted_at: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+            snapshot_ordinal: None,
+        }
+    }
+
+    /// Whether the node reported the hash computed locally
+    pub fn hashes_match(&self) -> bool {
+        self.local_hash.eq_ignore_ascii_case(&self.node_hash)
+    }
+
+    /// Whether the including snapshot is known
+    pub fn is_confirmed(&self) -> bool {
+        self.snapshot_ordinal.is_some()
+    }
+
+    /// Record the ordinal of the snapshot that included the submission
+    pub fn confirm(&mut self, snapshot_ordinal: i64) {
+        self.snapshot_ordinal = Some(snapshot_ordinal);
+    }
+}
+
+impl BlockExplorerClient {
+    /// Record the including snapshot of a transaction receipt, if indexed yet
+    ///
+    /// Returns whether the receipt is confirmed afterwards. Data update
+    /// receipts are left as they are, since the explorer does not index
+    /// updates; confirm them with [`Receipt::confirm`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request fails
+    pub async fn confirm_receipt(&self, receipt: &mut Receipt) -> NetworkResult<bool> {
+        if receipt.is_confirmed() || receipt.kind != ReceiptKind::Transaction {
+            return Ok(receipt.is_confirmed());
+        }
+        let ordinal = self
+            .get_transaction(&receipt.node_hash)
+            .await?
+            .and_then(|transaction| transaction.snapshot_ordinal);
+        if let Some(ordinal) = ordinal {
+            receipt.confirm(ordinal);
+        }
+        Ok(receipt.is_confirmed())
+    }
+}
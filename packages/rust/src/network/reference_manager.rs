@@ -0,0 +1,211 @@
+//! Last-reference management shared by concurrent submitters
+//!
+//! Every currency transaction names its sender's previous transaction as its
+//! parent, so two tasks that fetch the same last reference and submit at the
+//! same time produce conflicting transactions. A [`ReferenceManager`] hands
+//! out each address's reference through a [`ReferenceLease`] that only one
+//! task holds at a time; other tasks sending from the same address wait for
+//! it, while different addresses proceed in parallel.
+//!
+//! References are cached after the first lookup and advanced locally when a
+//! lease is committed. A lease dropped without a commit, e.g. because the
+//! submission failed, clears the cache so the next lease fetches the
+//! reference from the node again.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::ReferenceManager;
+//! use std::sync::Arc;
+//!
+//! let manager = Arc::new(ReferenceManager::new(cl1)?);
+//!
+//! // From any number of tasks
+//! let receipt = manager.submit(transfer_params, &signer).await?;
+//!
+//! // Or build the transaction yourself
+//! let lease = manager.lease(&address).await?;
+//! let tx = create_currency_transaction(params, &private_key, lease.reference().clone())?;
+//! cl1.post_transaction(&tx).await?;
+//! lease.commit(&tx);
+//! ```
+
+use futures_util::lock::{Mutex as AsyncMutex, OwnedMutexGuard};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::receipt::Receipt;
+use super::types::NetworkResult;
+use crate::currency_transaction::{
+    create_currency_transaction_with_signer, get_transaction_reference,
+};
+use crate::currency_types::{CurrencyTransaction, TransactionReference, TransferParams};
+use crate::signer::{LocalSigner, Signer};
+
+type Slot = Arc<AsyncMutex<Option<TransactionReference>>>;
+
+/// Serializes last-reference use per address across concurrent tasks
+pub struct ReferenceManager {
+    client: MetagraphClient,
+    slots: Mutex<HashMap<String, Slot>>,
+}
+
+impl ReferenceManager {
+    /// Create a manager fetching references from a Currency L1 node
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `client` is not a CL1 client
+    pub fn new(client: MetagraphClient) -> NetworkResult<Self> {
+        client.assert_layer(&[LayerType::CL1], "ReferenceManager::new")?;
+        Ok(Self::unchecked(client))
+    }
+
+    /// A manager whose client's layer is checked by the first request
+    /// instead
+    pub(super) fn unchecked(client: MetagraphClient) -> Self {
+        Self {
+            client,
+            slots: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The Currency L1 client references are fetched from
+    pub fn client(&self) -> &MetagraphClient {
+        &self.client
+    }
+
+    /// Take exclusive use of an address's last reference
+    ///
+    /// Waits while another task holds the address's lease, then returns the
+    /// cached reference, fetching it from the node if none is cached.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the reference has to be fetched and the request
+    /// fails
+    pub async fn lease(&self, address: &str) -> NetworkResult<ReferenceLease> {
+        let slot = self.slots().entry(address.to_string()).or_default().clone();
+        let mut guard = slot.lock_owned().await;
+        let reference = match guard.clone() {
+            Some(reference) => reference,
+            None => {
+                let reference = self.client.get_last_reference(address).await?;
+                *guard = Some(reference.clone());
+                reference
+            }
+        };
+        Ok(ReferenceLease {
+            guard,
+            reference,
+            committed: false,
+        })
+    }
+
+    /// Build, sign, and submit a transfer
+    ///
+    /// Holds the sender's lease for the whole submission and commits it once
+    /// the node accepts the transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transfer parameters are invalid or a request
+    /// fails
+    pub async fn submit<S: Signer + ?Sized>(
+        &self,
+        params: TransferParams,
+        signer: &S,
+    ) -> NetworkResult<Receipt> {
+        let lease = self.lease(&signer.address()).await?;
+        let transaction =
+            create_currency_transaction_with_signer(params, signer, lease.reference().clone())?;
+        let response = self.client.post_transaction(&transaction).await?;
+        lease.commit(&transaction);
+        Ok(Receipt::for_transaction(&transaction, response.hash))
+    }
+
+    /// Like [`submit`](Self::submit), signing with a private key in hex
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the private key or the transfer parameters are
+    /// invalid, or a request fails
+    pub async fn transfer(
+        &self,
+        params: TransferParams,
+        private_key: &str,
+    ) -> NetworkResult<Receipt> {
+        self.submit(params, &LocalSigner::new(private_key)?).await
+    }
+
+    /// Drop an address's cached reference, e.g. after sending from it elsewhere
+    ///
+    /// Waits for a held lease to be released first.
+    pub async fn invalidate(&self, address: &str) {
+        let slot = self.slots().get(address).cloned();
+        if let Some(slot) = slot {
+            *slot.lock().await = None;
+        }
+    }
+
+    fn slots(&self) -> MutexGuard<'_, HashMap<String, Slot>> {
+        self.slots.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl fmt::Debug for ReferenceManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReferenceManager")
+            .field("addresses", &self.slots().len())
+            .finish()
+    }
+}
+
+/// Exclusive use of one address's last reference
+///
+/// Commit the lease once the transaction built on [`reference`](Self::reference)
+/// has been accepted. Dropping it uncommitted clears the cached reference.
+pub struct ReferenceLease {
+    guard: OwnedMutexGuard<Option<TransactionReference>>,
+    reference: TransactionReference,
+    committed: bool,
+}
+
+impl ReferenceLease {
+    /// The reference to use as the next transaction's parent
+    pub fn reference(&self) -> &TransactionReference {
+        &self.reference
+    }
+
+    /// Record `transaction` as the address's latest and release the lease
+    pub fn commit(mut self, transaction: &CurrencyTransaction) {
+        *self.guard = Some(get_transaction_reference(
+            transaction,
+            self.reference.ordinal + 1,
+        ));
+        self.committed = true;
+    }
+
+    /// Release the lease without sending, keeping the cached reference
+    pub fn release(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for ReferenceLease {
+    fn drop(&mut self) {
+        if !self.committed {
+            *self.guard = None;
+        }
+    }
+}
+
+impl fmt::Debug for ReferenceLease {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReferenceLease")
+            .field("reference", &self.reference)
+            .finish()
+    }
+}
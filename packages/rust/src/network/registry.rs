@@ -0,0 +1,191 @@
+//! Clients for several metagraphs, keyed by metagraph ID
+//!
+//! A [`MetagraphRegistry`] holds the Metagraph L0, Currency L1, and Data L1
+//! clients of every metagraph an application works with and routes each call
+//! to the right one by metagraph ID, so code talking to several metagraphs at
+//! once does not have to keep its own map of clients.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{MetagraphRegistry, NetworkConfig};
+//!
+//! let mut registry = MetagraphRegistry::new();
+//! registry.register(
+//!     "DAG...game",
+//!     &NetworkConfig {
+//!         data_l1_url: Some("http://game:9400".to_string()),
+//!         ..Default::default()
+//!     },
+//! )?;
+//! registry
+//!     .resolve(&NetworkConfig::mainnet(), "DAG...token")
+//!     .await?;
+//!
+//! registry.submit_data("DAG...game", &signed_update).await?;
+//! let balance = registry.get_balance("DAG...token", "DAG...").await?;
+//! ```
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+use super::config::NetworkConfig;
+use super::metagraph::Metagraph;
+use super::types::{
+    Balance, EstimateFeeResponse, NetworkError, NetworkResult, PostDataResponse,
+    PostTransactionResponse,
+};
+use crate::currency_types::{CurrencyTransaction, TransactionReference};
+use crate::types::Signed;
+
+/// Clients for several metagraphs, keyed by metagraph ID
+#[derive(Debug, Default)]
+pub struct MetagraphRegistry {
+    entries: HashMap<String, Metagraph>,
+}
+
+impl MetagraphRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a metagraph, returning the one it replaces
+    pub fn insert(&mut self, metagraph: Metagraph) -> Option<Metagraph> {
+        self.entries
+            .insert(metagraph.metagraph_id().to_string(), metagraph)
+    }
+
+    /// Add a metagraph with a client for every metagraph endpoint set in `config`
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a configured client cannot be created
+    pub fn register(&mut self, metagraph_id: &str, config: &NetworkConfig) -> NetworkResult<()> {
+        self.insert(Metagraph::from_config(metagraph_id, config)?);
+        Ok(())
+    }
+
+    /// Add a metagraph with the endpoints registered on the block explorer
+    ///
+    /// Resolves the endpoints with
+    /// [`NetworkConfig::resolve_metagraph`], keeping every other setting of
+    /// `base`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `base` has no block explorer, the metagraph cannot
+    /// be looked up, or a client cannot be created
+    pub async fn resolve(&mut self, base: &NetworkConfig, metagraph_id: &str) -> NetworkResult<()> {
+        let config = base.clone().resolve_metagraph(metagraph_id).await?;
+        self.register(metagraph_id, &config)
+    }
+
+    /// Remove a metagraph
+    pub fn remove(&mut self, metagraph_id: &str) -> Option<Metagraph> {
+        self.entries.remove(metagraph_id)
+    }
+
+    /// Get a registered metagraph
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the metagraph is not registered
+    pub fn get(&self, metagraph_id: &str) -> NetworkResult<&Metagraph> {
+        self.entries.get(metagraph_id).ok_or_else(|| {
+            NetworkError::ConfigError(format!("Metagraph {} is not registered", metagraph_id))
+        })
+    }
+
+    /// Whether a metagraph is registered
+    pub fn contains(&self, metagraph_id: &str) -> bool {
+        self.entries.contains_key(metagraph_id)
+    }
+
+    /// IDs of the registered metagraphs
+    pub fn metagraph_ids(&self) -> impl Iterator<Item = &str> {
+        self.entries.keys().map(String::as_str)
+    }
+
+    /// Number of registered metagraphs
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no metagraph is registered
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Submit a signed data update to a metagraph's Data L1
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the metagraph is not registered or has no
+    /// Data L1 client, or an error if the request fails
+    pub async fn submit_data<T: Serialize>(
+        &self,
+        metagraph_id: &str,
+        data: &Signed<T>,
+    ) -> NetworkResult<PostDataResponse> {
+        self.get(metagraph_id)?.data_l1()?.post_data(data).await
+    }
+
+    /// Estimate the fee of a data update on a metagraph's Data L1
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the metagraph is not registered or has no
+    /// Data L1 client, or an error if the request fails
+    pub async fn estimate_fee<T: Serialize>(
+        &self,
+        metagraph_id: &str,
+        data: &Signed<T>,
+    ) -> NetworkResult<EstimateFeeResponse> {
+        self.get(metagraph_id)?.data_l1()?.estimate_fee(data).await
+    }
+
+    /// Submit a currency transaction to a metagraph's Currency L1
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the metagraph is not registered or has no
+    /// Currency L1 client, or an error if the request fails
+    pub async fn post_transaction(
+        &self,
+        metagraph_id: &str,
+        transaction: &CurrencyTransaction,
+    ) -> NetworkResult<PostTransactionResponse> {
+        self.get(metagraph_id)?
+            .l1()?
+            .post_transaction(transaction)
+            .await
+    }
+
+    /// Get an address's last transaction reference on a metagraph
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the metagraph is not registered or has no
+    /// Currency L1 client, or an error if the request fails
+    pub async fn get_last_reference(
+        &self,
+        metagraph_id: &str,
+        address: &str,
+    ) -> NetworkResult<TransactionReference> {
+        self.get(metagraph_id)?
+            .l1()?
+            .get_last_reference(address)
+            .await
+    }
+
+    /// Get an address's token balance on a metagraph
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the metagraph is not registered or has no
+    /// Metagraph L0 client, or an error if the request fails
+    pub async fn get_balance(&self, metagraph_id: &str, address: &str) -> NetworkResult<Balance> {
+        self.get(metagraph_id)?.l0()?.get_balance(address).await
+    }
+}
@@ -0,0 +1,106 @@
+//! Metagraph endpoint discovery by metagraph ID
+//!
+//! The block explorer keeps a registry of metagraphs with the public
+//! endpoints of their layers. [`BlockExplorerClient::get_metagraph`] returns
+//! that entry, and [`NetworkConfig::resolve_metagraph`] turns it into a ready
+//! configuration, so applications working with several metagraphs only need
+//! their IDs.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::NetworkConfig;
+//!
+//! let config = NetworkConfig::mainnet()
+//!     .resolve_metagraph("DAG...")
+//!     .await?;
+//! let ml0 = config.currency_l0_client()?;
+//! let cl1 = config.currency_l1_client()?;
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::block_explorer::{BlockExplorerClient, DataResponse};
+use super::config::NetworkConfig;
+use super::types::{NetworkError, NetworkResult};
+
+/// A metagraph's registry entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetagraphEndpoints {
+    /// Metagraph ID (the DAG address of the metagraph)
+    pub id: String,
+    /// Display name
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Token symbol
+    #[serde(default)]
+    pub symbol: Option<String>,
+    /// Metagraph L0 endpoints
+    #[serde(default, alias = "l0Urls")]
+    pub metagraph_l0_urls: Vec<String>,
+    /// Currency L1 endpoints
+    #[serde(default, alias = "l1Urls")]
+    pub currency_l1_urls: Vec<String>,
+    /// Data L1 endpoints
+    #[serde(default)]
+    pub data_l1_urls: Vec<String>,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl BlockExplorerClient {
+    /// Look up a metagraph's registered endpoints, `None` if the metagraph
+    /// is not registered
+    pub async fn get_metagraph(
+        &self,
+        metagraph_id: &str,
+    ) -> NetworkResult<Option<MetagraphEndpoints>> {
+        let path = format!("/metagraphs/{}", metagraph_id);
+        match self
+            .http_client()
+            .get::<DataResponse<MetagraphEndpoints>>(&path)
+            .await
+        {
+            Ok(response) => Ok(Some(response.data)),
+            Err(e) if e.status_code() == Some(404) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Fill in a metagraph's endpoints from the block explorer registry
+    ///
+    /// Applies [`for_metagraph`](Self::for_metagraph), then sets the Metagraph
+    /// L0, Currency L1, and Data L1 URLs to the registered ones: the first of
+    /// each goes to `currency_l0_url` / `l1_url` / `data_l1_url`, the rest to
+    /// `l1_urls` / `data_l1_urls`. Layers the metagraph does not run stay
+    /// unset. All other settings are kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if `block_explorer_url` is not set or the
+    /// metagraph is not registered, or an error if the lookup fails
+    pub async fn resolve_metagraph(self, metagraph_id: &str) -> NetworkResult<Self> {
+        let endpoints = self
+            .block_explorer_client()?
+            .get_metagraph(metagraph_id)
+            .await?
+            .ok_or_else(|| {
+                NetworkError::ConfigError(format!("metagraph {} is not registered", metagraph_id))
+            })?;
+        let mut config = self.for_metagraph(metagraph_id);
+
+        config.currency_l0_url = endpoints.metagraph_l0_urls.first().cloned();
+        let mut l1_urls = endpoints.currency_l1_urls.into_iter();
+        config.l1_url = l1_urls.next();
+        config.l1_urls = l1_urls.collect();
+        let mut data_l1_urls = endpoints.data_l1_urls.into_iter();
+        config.data_l1_url = data_l1_urls.next();
+        config.data_l1_urls = data_l1_urls.collect();
+        Ok(config)
+    }
+}
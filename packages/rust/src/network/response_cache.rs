@@ -0,0 +1,177 @@
+//! Client-side response cache
+//!
+//! Bots that poll last references, balances, or cluster info many times a
+//! second mostly get the same answer back. A [`ResponseCache`] installed with
+//! [`MetagraphClient::with_response_cache`] keeps each answer for a
+//! per-endpoint TTL, and a successful
+//! [`post_transaction`](MetagraphClient::post_transaction) drops the entries
+//! it made stale.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{CacheConfig, ResponseCache};
+//! use std::sync::Arc;
+//!
+//! let cache = Arc::new(ResponseCache::new(CacheConfig::default()));
+//! let cl1 = MetagraphClient::new("http://localhost:9300", LayerType::CL1)?
+//!     .with_response_cache(cache.clone());
+//!
+//! let reference = cl1.get_last_reference("DAG...").await?; // from the node
+//! let reference = cl1.get_last_reference("DAG...").await?; // from the cache
+//! cache.invalidate_address("DAG...");
+//! ```
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::rt::Instant;
+use crate::currency_types::CurrencyTransaction;
+
+/// How long each kind of response stays fresh
+///
+/// A zero TTL disables caching for that endpoint.
+#[derive(Debug, Clone)]
+pub struct CacheConfig {
+    /// TTL of `get_last_reference` responses
+    pub last_reference_ttl: Duration,
+    /// TTL of `get_balance` responses
+    pub balance_ttl: Duration,
+    /// TTL of `get_cluster_info` responses
+    pub cluster_info_ttl: Duration,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            last_reference_ttl: Duration::from_secs(2),
+            balance_ttl: Duration::from_secs(5),
+            cluster_info_ttl: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum CachedEndpoint {
+    LastReference(String),
+    Balance(String),
+    ClusterInfo,
+}
+
+/// An endpoint's response as answered to one client
+///
+/// One cache can be shared by clients of different layers or metagraphs,
+/// so the layer and nodes asked are part of the key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) struct CacheKey {
+    layer: LayerType,
+    base_urls: Vec<String>,
+    endpoint: CachedEndpoint,
+}
+
+impl CacheKey {
+    pub(super) fn new(client: &MetagraphClient, endpoint: CachedEndpoint) -> Self {
+        Self {
+            layer: client.layer(),
+            base_urls: client.base_urls().into_iter().map(String::from).collect(),
+            endpoint,
+        }
+    }
+}
+
+struct Entry {
+    stored_at: Instant,
+    value: Arc<dyn Any + Send + Sync>,
+}
+
+/// Cache of recent responses, shared by the clients it is installed on
+pub struct ResponseCache {
+    config: CacheConfig,
+    entries: Mutex<HashMap<CacheKey, Entry>>,
+}
+
+impl ResponseCache {
+    /// Create an empty cache
+    pub fn new(config: CacheConfig) -> Self {
+        Self {
+            config,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The TTLs in use
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    /// Drop the cached last reference and balance of an address, as
+    /// answered to every client
+    pub fn invalidate_address(&self, address: &str) {
+        self.entries().retain(|key, _| match &key.endpoint {
+            CachedEndpoint::LastReference(cached) | CachedEndpoint::Balance(cached) => {
+                cached != address
+            }
+            CachedEndpoint::ClusterInfo => true,
+        });
+    }
+
+    /// Drop every entry
+    pub fn clear(&self) {
+        self.entries().clear();
+    }
+
+    /// Number of entries, fresh or not
+    pub fn len(&self) -> usize {
+        self.entries().len()
+    }
+
+    /// Whether the cache holds no entries
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop the entries a submitted transaction made stale
+    pub(super) fn invalidate_transaction(&self, transaction: &CurrencyTransaction) {
+        self.invalidate_address(&transaction.value.source);
+        self.invalidate_address(&transaction.value.destination);
+    }
+
+    pub(super) fn get<T: Clone + 'static>(&self, key: &CacheKey) -> Option<T> {
+        let ttl = self.ttl(key);
+        let mut entries = self.entries();
+        let entry = entries.get(key)?;
+        if entry.stored_at.elapsed() >= ttl {
+            entries.remove(key);
+            return None;
+        }
+        entry.value.downcast_ref::<T>().cloned()
+    }
+
+    pub(super) fn insert<T: Send + Sync + 'static>(&self, key: CacheKey, value: T) {
+        if self.ttl(&key).is_zero() {
+            return;
+        }
+        self.entries().insert(
+            key,
+            Entry {
+                stored_at: Instant::now(),
+                value: Arc::new(value),
+            },
+        );
+    }
+
+    fn entries(&self) -> MutexGuard<'_, HashMap<CacheKey, Entry>> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn ttl(&self, key: &CacheKey) -> Duration {
+        match key.endpoint {
+            CachedEndpoint::LastReference(_) => self.config.last_reference_ttl,
+            CachedEndpoint::Balance(_) => self.config.balance_ttl,
+            CachedEndpoint::ClusterInfo => self.config.cluster_info_ttl,
+        }
+    }
+}
@@ -0,0 +1,90 @@
+//! Retry policy for HTTP requests
+//!
+//! Busy L1 nodes regularly answer with transient 502/503 responses or drop
+//! connections. [`RetryPolicy`] describes how [`HttpClient`](super::HttpClient)
+//! retries such failures with exponential backoff and jitter.
+//!
+//! GET requests are idempotent and retried by default; POST requests are only
+//! retried when `retry_posts` is set, since resending a submission the node
+//! already accepted can surface as a duplicate.
+
+use rand::Rng;
+use std::time::Duration;
+
+use super::types::NetworkError;
+
+/// Configuration for retrying failed requests
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one (1 disables retries)
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound for any single delay
+    pub max_backoff: Duration,
+    /// Factor applied to the delay after every retry
+    pub multiplier: f64,
+    /// Fraction of each delay (0.0–1.0) that is randomized away
+    pub jitter: f64,
+    /// HTTP status codes that are retried
+    pub retry_on_status: Vec<u16>,
+    /// Whether POST requests are retried as well
+    pub retry_posts: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2.0,
+            jitter: 0.5,
+            retry_on_status: vec![429, 502, 503, 504],
+            retry_posts: false,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Delay to wait after the given (1-based) failed attempt
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1) as i32;
+        let base = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        let capped = base.min(self.max_backoff.as_secs_f64());
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let factor = if jitter > 0.0 {
+            1.0 - rand::thread_rng().gen_range(0.0..=jitter)
+        } else {
+            1.0
+        };
+        Duration::from_secs_f64(capped * factor)
+    }
+
+    /// Whether an error is worth retrying under this policy
+    ///
+    /// Timeouts, connection failures, and responses with a status listed in
+    /// `retry_on_status` are retried. Configuration, serialization, and SDK
+    /// errors are not.
+    pub fn should_retry(&self, error: &NetworkError) -> bool {
+        match error {
+            NetworkError::Timeout => true,
+            NetworkError::HttpError {
+                status_code: Some(status),
+                ..
+            } => self.retry_on_status.contains(status),
+            NetworkError::HttpError {
+                status_code: None, ..
+            } => true,
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+//! Timers and clocks that work on native targets and wasm32
+//!
+//! `std::time::Instant::now` panics in the browser and tokio's timer needs a
+//! tokio runtime, so the network module takes both from here. On native
+//! targets these are the `std` and tokio types; on wasm32 they are backed by
+//! `performance.now()`, `Date.now()`, and `setTimeout`.
+
+use std::time::Duration;
+
+pub(super) use web_time::{Instant, SystemTime};
+
+/// Wait for `duration` without blocking the executor
+#[cfg(not(target_arch = "wasm32"))]
+pub(super) async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+/// Wait for `duration` without blocking the executor
+#[cfg(target_arch = "wasm32")]
+pub(super) async fn sleep(duration: Duration) {
+    use wasm_bindgen::{JsCast, JsValue};
+
+    let millis = duration.as_millis().min(i32::MAX as u128) as i32;
+    let promise = js_sys::Promise::new(&mut |resolve, _reject| {
+        // `setTimeout` lives on the global object in windows and workers alike
+        let global = js_sys::global();
+        let set_timeout = js_sys::Reflect::get(&global, &JsValue::from_str("setTimeout"))
+            .ok()
+            .and_then(|f| f.dyn_into::<js_sys::Function>().ok());
+        match set_timeout {
+            Some(set_timeout) => {
+                let _ = set_timeout.call2(&global, &resolve, &JsValue::from(millis));
+            }
+            None => {
+                let _ = resolve.call0(&JsValue::UNDEFINED);
+            }
+        }
+    });
+    let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+}
@@ -0,0 +1,201 @@
+//! Submitting a signer's updates strictly in order
+//!
+//! Some data applications apply a signer's updates only in sequence: each
+//! update carries a nonce, and the application rejects one whose nonce is not
+//! one past the last it applied. Posting such updates back to back fails as
+//! soon as two land in the same block out of order. [`SequencedUpdates`]
+//! wraps each update in a [`Sequenced`] envelope with consecutive nonces and
+//! submits them one at a time, waiting for each to be confirmed before
+//! posting the next.
+//!
+//! Confirmation is application specific, so the caller supplies it: an async
+//! function returning the last nonce the metagraph applied for the signer,
+//! typically read from its calculated state.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::SequencedUpdates;
+//!
+//! let address = signer.address();
+//! let outcome = SequencedUpdates::new(next_nonce)
+//!     .push(open_poll)
+//!     .push(cast_vote)
+//!     .submit(&dl1, &signer, || async {
+//!         let state: VotingState = ml0.get_calculated_state().await?;
+//!         Ok(state.nonces.get(&address).copied())
+//!     })
+//!     .await;
+//! if let Some(stop) = outcome.stopped {
+//!     eprintln!("stopped at nonce {}: {stop:?}", outcome.next_nonce);
+//! }
+//! ```
+
+use std::future::Future;
+use std::ops::ControlFlow;
+
+use super::confirmation::{poll_until, PollOptions, Polled};
+use super::metagraph_client::MetagraphClient;
+use super::receipt::Receipt;
+use super::types::{NetworkError, NetworkResult};
+use crate::data_update::{DataUpdate, Sequenced};
+use crate::signer::Signer;
+
+/// Updates to submit in order, with consecutive nonces
+#[derive(Debug, Clone)]
+pub struct SequencedUpdates<U> {
+    first_nonce: u64,
+    updates: Vec<U>,
+    poll: PollOptions,
+}
+
+impl<U: DataUpdate + Clone> SequencedUpdates<U> {
+    /// An empty sequence whose first update gets `first_nonce`
+    ///
+    /// `first_nonce` is one past the last nonce the metagraph applied for
+    /// the signer.
+    pub fn new(first_nonce: u64) -> Self {
+        Self {
+            first_nonce,
+            updates: Vec::new(),
+            poll: PollOptions::default(),
+        }
+    }
+
+    /// Append an update, taking the next nonce
+    pub fn push(mut self, update: U) -> Self {
+        self.updates.push(update);
+        self
+    }
+
+    /// How to poll for each update's confirmation
+    ///
+    /// The timeout applies to each update separately.
+    pub fn poll_options(mut self, options: PollOptions) -> Self {
+        self.poll = options;
+        self
+    }
+
+    /// The updates wrapped with their nonces, without submitting them
+    pub fn sequenced(&self) -> Vec<Sequenced<U>> {
+        (self.first_nonce..)
+            .zip(&self.updates)
+            .map(|(nonce, update)| Sequenced {
+                nonce,
+                update: update.clone(),
+            })
+            .collect()
+    }
+
+    /// Sign and submit the updates in order, waiting for each to be
+    /// confirmed before the next
+    ///
+    /// `last_nonce` returns the last nonce the metagraph applied for the
+    /// signer, or `None` if it applied none; an update is confirmed once
+    /// that reaches its nonce. Submission stops at the first update that is
+    /// refused or not confirmed in time, and the outcome says which.
+    ///
+    /// Available on: DL1
+    pub async fn submit<S, F, Fut>(
+        self,
+        client: &MetagraphClient,
+        signer: &S,
+        mut last_nonce: F,
+    ) -> SequenceOutcome
+    where
+        S: Signer + ?Sized,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = NetworkResult<Option<u64>>>,
+    {
+        let mut outcome = SequenceOutcome {
+            confirmed: Vec::with_capacity(self.updates.len()),
+            next_nonce: self.first_nonce,
+            stopped: None,
+        };
+        for update in self.sequenced() {
+            let receipt = match client.submit(signer, &update).await {
+                Ok(receipt) => receipt,
+                Err(error) => {
+                    outcome.stopped = Some(SequenceStop::Failed(error));
+                    break;
+                }
+            };
+            match wait_for_nonce(&mut last_nonce, update.nonce, &self.poll).await {
+                Ok(true) => {
+                    outcome.confirmed.push(receipt);
+                    outcome.next_nonce += 1;
+                }
+                Ok(false) => {
+                    outcome.stopped = Some(SequenceStop::Unconfirmed(receipt));
+                    break;
+                }
+                Err(error) => {
+                    outcome.stopped = Some(SequenceStop::CheckFailed { receipt, error });
+                    break;
+                }
+            }
+        }
+        outcome
+    }
+}
+
+/// Result of submitting a [`SequencedUpdates`]
+#[derive(Debug)]
+pub struct SequenceOutcome {
+    /// Receipts of the confirmed updates, in order
+    pub confirmed: Vec<Receipt>,
+    /// Nonce of the first update that was not confirmed
+    pub next_nonce: u64,
+    /// Why submission stopped early, or `None` if every update was confirmed
+    pub stopped: Option<SequenceStop>,
+}
+
+impl SequenceOutcome {
+    /// Whether every update was confirmed
+    pub fn is_complete(&self) -> bool {
+        self.stopped.is_none()
+    }
+}
+
+/// Why a [`SequencedUpdates`] stopped at the update with
+/// [`next_nonce`](SequenceOutcome::next_nonce)
+#[derive(Debug)]
+pub enum SequenceStop {
+    /// Signing or posting the update failed; it was not accepted
+    Failed(NetworkError),
+    /// The update was accepted but not confirmed before the poll timeout, or
+    /// the wait was cancelled
+    Unconfirmed(Receipt),
+    /// The update was accepted, then checking for its confirmation failed
+    CheckFailed {
+        /// Receipt of the accepted update
+        receipt: Receipt,
+        /// The error `last_nonce` returned
+        error: NetworkError,
+    },
+}
+
+/// Poll `last_nonce` until it reaches `nonce`
+///
+/// Returns `false` on timeout or cancellation.
+async fn wait_for_nonce<F, Fut>(
+    last_nonce: &mut F,
+    nonce: u64,
+    options: &PollOptions,
+) -> NetworkResult<bool>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = NetworkResult<Option<u64>>>,
+{
+    let polled = poll_until(options, || {
+        let last = last_nonce();
+        async move {
+            Ok(match last.await? {
+                Some(last) if last >= nonce => ControlFlow::Break(()),
+                _ => ControlFlow::Continue(()),
+            })
+        }
+    })
+    .await?;
+    Ok(matches!(polled, Polled::Done(())))
+}
@@ -0,0 +1,178 @@
+//! Full snapshot bodies: blocks, transactions, and balances
+//!
+//! [`GlobalSnapshot`] and [`CurrencySnapshot`] keep their blocks as raw JSON.
+//! This module decodes them into [`SnapshotBlock`]s with typed transactions,
+//! and fetches the "combined" snapshot whose [`SnapshotInfo`] carries the
+//! balances and last transaction references of every address, so indexers can
+//! work from snapshots directly.
+//!
+//! Snapshots are read in their JSON encoding, both from nodes and from
+//! archived files via [`decode_global_snapshot`] / [`decode_currency_snapshot`].
+//! The Kryo encoding used between nodes is not supported.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::snapshot;
+//!
+//! let combined = gl0.get_latest_combined_snapshot().await?;
+//! for tx in snapshot::transactions(&combined.snapshot.value.blocks)? {
+//!     println!("{} -> {}: {}", tx.value.source, tx.value.destination, tx.value.amount);
+//! }
+//! let balance = combined.info.balances.get("DAG...");
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::global_l0_client::GlobalL0Client;
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::types::{CurrencySnapshot, GlobalSnapshot, NetworkError, NetworkResult};
+use crate::currency_types::{CurrencyTransaction, TransactionReference};
+use crate::types::Signed;
+
+/// Reference to a parent block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockReference {
+    /// Height of the parent block
+    pub height: i64,
+    /// Hash of the parent block
+    pub hash: String,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A block of currency transactions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Block {
+    /// Parent blocks
+    pub parent: Vec<BlockReference>,
+    /// Transactions included in the block
+    pub transactions: Vec<CurrencyTransaction>,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A block as stored in a snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotBlock {
+    /// The signed block
+    pub block: Signed<Block>,
+    /// How many later blocks referenced this one as a tip
+    #[serde(default)]
+    pub usage_count: i64,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Ledger state accompanying a snapshot
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotInfo {
+    /// Balance of every address with funds, in smallest units (1e-8)
+    #[serde(default)]
+    pub balances: BTreeMap<String, i64>,
+    /// Last accepted transaction of every address that has sent one
+    #[serde(default)]
+    pub last_tx_refs: BTreeMap<String, TransactionReference>,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// A snapshot together with the ledger state after it
+///
+/// Nodes serve it as a `[snapshot, info]` pair.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(from = "(Signed<S>, SnapshotInfo)")]
+#[serde(bound(deserialize = "S: DeserializeOwned"))]
+pub struct CombinedSnapshot<S> {
+    /// The signed snapshot
+    pub snapshot: Signed<S>,
+    /// Balances and last references after the snapshot
+    pub info: SnapshotInfo,
+}
+
+impl<S> From<(Signed<S>, SnapshotInfo)> for CombinedSnapshot<S> {
+    fn from((snapshot, info): (Signed<S>, SnapshotInfo)) -> Self {
+        Self { snapshot, info }
+    }
+}
+
+/// Decode the raw blocks of a snapshot
+///
+/// # Errors
+///
+/// Returns a `SerializationError` if a block does not have the expected shape
+pub fn decode_blocks(blocks: &[serde_json::Value]) -> NetworkResult<Vec<SnapshotBlock>> {
+    blocks
+        .iter()
+        .map(|block| serde_json::from_value(block.clone()).map_err(decode_error))
+        .collect()
+}
+
+/// All transactions of a snapshot's blocks, in block order
+///
+/// # Errors
+///
+/// Returns a `SerializationError` if a block does not have the expected shape
+pub fn transactions(blocks: &[serde_json::Value]) -> NetworkResult<Vec<CurrencyTransaction>> {
+    Ok(decode_blocks(blocks)?
+        .into_iter()
+        .flat_map(|block| block.block.value.transactions)
+        .collect())
+}
+
+/// Decode a JSON-encoded global snapshot, e.g. from an archive
+///
+/// # Errors
+///
+/// Returns a `SerializationError` if the bytes are not a signed global snapshot
+pub fn decode_global_snapshot(bytes: &[u8]) -> NetworkResult<Signed<GlobalSnapshot>> {
+    serde_json::from_slice(bytes).map_err(decode_error)
+}
+
+/// Decode a JSON-encoded currency snapshot, e.g. from an archive
+///
+/// # Errors
+///
+/// Returns a `SerializationError` if the bytes are not a signed currency snapshot
+pub fn decode_currency_snapshot(bytes: &[u8]) -> NetworkResult<Signed<CurrencySnapshot>> {
+    serde_json::from_slice(bytes).map_err(decode_error)
+}
+
+fn decode_error(e: serde_json::Error) -> NetworkError {
+    NetworkError::SerializationError(format!("invalid snapshot: {}", e))
+}
+
+impl GlobalL0Client {
+    /// Get the latest global snapshot with the balances and last references
+    /// of every address
+    pub async fn get_latest_combined_snapshot(
+        &self,
+    ) -> NetworkResult<CombinedSnapshot<GlobalSnapshot>> {
+        self.client.get("/global-snapshots/latest/combined").await
+    }
+}
+
+impl MetagraphClient {
+    /// Get the latest currency snapshot with the balances and last references
+    /// of every address
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer
+    pub async fn get_latest_combined_snapshot(
+        &self,
+    ) -> NetworkResult<CombinedSnapshot<CurrencySnapshot>> {
+        self.assert_layer(&[LayerType::ML0], "get_latest_combined_snapshot")?;
+        self.get("/snapshots/latest/combined").await
+    }
+}
@@ -0,0 +1,209 @@
+//! Budgeting for metagraph snapshot fees
+//!
+//! A metagraph pays the Global L0 a fee for every state channel snapshot it
+//! submits, and the fee grows with the snapshot's size. Operators need to
+//! know how fast that drains the owner wallet. [`GlobalL0Client::get_snapshot_usage`]
+//! scans recent global snapshots for one metagraph's state channel
+//! snapshots and collects their count, sizes, and the fees actually paid.
+//! [`SnapshotUsage::project`] turns that into the expected fee over a
+//! number of global snapshots under a [`SnapshotFeeSchedule`].
+//!
+//! The schedule computes the fee for one snapshot as
+//!
+//! ```text
+//! base_fee × size_kb × computational_cost / (1 + staked_dag × staking_weight)
+//! ```
+//!
+//! rounded up to the smallest unit, where `size_kb` is the snapshot size in
+//! whole kilobytes, rounded up. Its parameters are set by the network and
+//! change between releases, so take them from the network's configuration
+//! and compare the projection against [`SnapshotUsage::average_fee_paid`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{GlobalL0Client, SnapshotFeeSchedule};
+//!
+//! let gl0 = GlobalL0Client::new("http://localhost:9000")?;
+//! let usage = gl0.get_snapshot_usage("DAG...metagraph", 100).await?;
+//! let schedule = SnapshotFeeSchedule::new(10_000).staking_weight(0.000_01);
+//!
+//! // Global snapshots come roughly every 20 seconds
+//! let daily = usage.project(&schedule, staked_units, 4_320);
+//! if daily.total_fee > budget_units {
+//!     alert!("snapshot fees will exceed budget: {}", daily.total_fee);
+//! }
+//! ```
+
+use base64::Engine;
+
+use super::global_l0_client::GlobalL0Client;
+use super::types::NetworkResult;
+use crate::currency_types::TOKEN_DECIMALS;
+
+/// Parameters of the snapshot fee function
+///
+/// See the [module documentation](self) for the formula.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotFeeSchedule {
+    base_fee: u64,
+    staking_weight: f64,
+    computational_cost: f64,
+}
+
+impl SnapshotFeeSchedule {
+    /// A schedule charging `base_fee` smallest units per kilobyte, with no
+    /// staking discount and a computational cost of 1
+    pub fn new(base_fee: u64) -> Self {
+        Self {
+            base_fee,
+            staking_weight: 0.0,
+            computational_cost: 1.0,
+        }
+    }
+
+    /// Discount weight per DAG staked by the metagraph owner
+    pub fn staking_weight(mut self, weight: f64) -> Self {
+        self.staking_weight = weight;
+        self
+    }
+
+    /// Multiplier for the cost of processing the snapshot
+    pub fn computational_cost(mut self, cost: f64) -> Self {
+        self.computational_cost = cost;
+        self
+    }
+
+    /// Fee for one snapshot of `size_bytes`, with `staked` smallest units
+    /// staked, in smallest units
+    pub fn fee(&self, size_bytes: u64, staked: u64) -> u64 {
+        if size_bytes == 0 {
+            return 0;
+        }
+        let size_kb = ((size_bytes + 1023) / 1024) as f64;
+        let staked_dag = staked as f64 * TOKEN_DECIMALS;
+        let fee = self.base_fee as f64 * size_kb * self.computational_cost
+            / (1.0 + staked_dag * self.staking_weight);
+        fee.ceil() as u64
+    }
+}
+
+/// One metagraph's state channel snapshots over a range of global snapshots
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SnapshotUsage {
+    /// Global snapshots scanned
+    pub global_snapshots: u64,
+    /// State channel snapshots the metagraph submitted in them
+    pub metagraph_snapshots: u64,
+    /// Total size of those snapshots' content, in bytes
+    pub total_bytes: u64,
+    /// Total fee paid for them, in smallest units
+    pub fees_paid: u64,
+}
+
+impl SnapshotUsage {
+    /// Average snapshot size in bytes
+    pub fn average_size_bytes(&self) -> f64 {
+        ratio(self.total_bytes, self.metagraph_snapshots)
+    }
+
+    /// Average fee paid per snapshot, in smallest units
+    pub fn average_fee_paid(&self) -> f64 {
+        ratio(self.fees_paid, self.metagraph_snapshots)
+    }
+
+    /// Metagraph snapshots per global snapshot
+    pub fn snapshots_per_global_snapshot(&self) -> f64 {
+        ratio(self.metagraph_snapshots, self.global_snapshots)
+    }
+
+    /// Expected fees over the next `global_snapshots` global snapshots, if
+    /// the metagraph keeps its observed size and throughput
+    pub fn project(
+        &self,
+        schedule: &SnapshotFeeSchedule,
+        staked: u64,
+        global_snapshots: u64,
+    ) -> SnapshotFeeProjection {
+        let fee_per_snapshot = schedule.fee(self.average_size_bytes().ceil() as u64, staked);
+        let snapshots = self.snapshots_per_global_snapshot() * global_snapshots as f64;
+        SnapshotFeeProjection {
+            snapshots,
+            fee_per_snapshot,
+            total_fee: (snapshots * fee_per_snapshot as f64).ceil() as u64,
+        }
+    }
+
+    /// Add one state channel snapshot from the `stateChannelSnapshots` entry
+    /// of a global snapshot
+    fn record(&mut self, binary: &serde_json::Value) {
+        let value = binary.get("value").unwrap_or(binary);
+        self.metagraph_snapshots += 1;
+        self.total_bytes += content_len(value.get("content")) as u64;
+        self.fees_paid += value.get("fee").and_then(|fee| fee.as_u64()).unwrap_or(0);
+    }
+}
+
+/// Expected snapshot fees over a period
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SnapshotFeeProjection {
+    /// Expected number of metagraph snapshots
+    pub snapshots: f64,
+    /// Expected fee per snapshot, in smallest units
+    pub fee_per_snapshot: u64,
+    /// Expected total fee, in smallest units
+    pub total_fee: u64,
+}
+
+impl GlobalL0Client {
+    /// Collect a metagraph's state channel snapshots from the latest `count`
+    /// global snapshots
+    ///
+    /// Fetches each global snapshot in turn, so keep `count` to what the
+    /// estimate needs.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request fails
+    pub async fn get_snapshot_usage(
+        &self,
+        metagraph_id: &str,
+        count: u64,
+    ) -> NetworkResult<SnapshotUsage> {
+        let latest = self.get_latest_snapshot_ordinal().await?;
+        let first = (latest - count as i64 + 1).max(0);
+        let mut usage = SnapshotUsage::default();
+        for ordinal in first..=latest {
+            let snapshot = self.get_snapshot(ordinal).await?;
+            usage.global_snapshots += 1;
+            let binaries = snapshot
+                .value
+                .state_channel_snapshots
+                .get(metagraph_id)
+                .and_then(|binaries| binaries.as_array());
+            for binary in binaries.into_iter().flatten() {
+                usage.record(binary);
+            }
+        }
+        Ok(usage)
+    }
+}
+
+/// Length of a snapshot's content, encoded as a byte array or base64
+fn content_len(content: Option<&serde_json::Value>) -> usize {
+    match content {
+        Some(serde_json::Value::Array(bytes)) => bytes.len(),
+        Some(serde_json::Value::String(encoded)) => base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .map_or(encoded.len(), |bytes| bytes.len()),
+        _ => 0,
+    }
+}
+
+fn ratio(numerator: u64, denominator: u64) -> f64 {
+    if denominator == 0 {
+        0.0
+    } else {
+        numerator as f64 / denominator as f64
+    }
+}
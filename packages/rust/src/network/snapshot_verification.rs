@@ -0,0 +1,256 @@
+//! Snapshot signature verification against a validator set
+//!
+//! A node can serve any snapshot it likes. Light clients that do not want to
+//! trust a single node check that enough known validators signed it:
+//! [`verify_snapshot`] hashes the snapshot body, checks every proof, and counts
+//! the valid signatures from a [`ValidatorSet`] against its threshold.
+//!
+//! The `get_*verified_snapshot` methods on [`GlobalL0Client`] and on an ML0
+//! [`MetagraphClient`] verify the snapshot exactly as the node sent it, before
+//! decoding it into the typed snapshot, and fail with
+//! [`NetworkError::UntrustedSnapshot`] if too few validators signed it.
+//!
+//! The validator set should come from a source you trust, such as a pinned
+//! list or several nodes that agree. [`GlobalL0Client::get_validator_set`]
+//! builds one from a node's cluster membership, which is only as trustworthy
+//! as that node.
+//!
+//! Only snapshots hashed in their JSON encoding can be verified; snapshots
+//! from before the network moved away from Kryo hashing will not verify.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::ValidatorSet;
+//!
+//! let validators = ValidatorSet::new(pinned_ids).with_threshold(3);
+//! let snapshot = gl0.get_verified_snapshot(ordinal, &validators).await?;
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+use super::discovery::PeerInfo;
+use super::global_l0_client::GlobalL0Client;
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::types::{CurrencySnapshot, GlobalSnapshot, NetworkError, NetworkResult};
+use crate::hash::hash_data;
+use crate::types::Signed;
+use crate::verify::{signer_key, verify_hash};
+
+/// Node IDs allowed to sign snapshots, and how many signatures are required
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidatorSet {
+    ids: BTreeSet<String>,
+    threshold: usize,
+}
+
+impl ValidatorSet {
+    /// Create a set requiring signatures from a strict majority of `ids`
+    ///
+    /// IDs are public keys in hex, with or without the `04` prefix.
+    pub fn new<I, S>(ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let ids: BTreeSet<String> = ids.into_iter().map(|id| signer_key(id.as_ref())).collect();
+        let threshold = ids.len() / 2 + 1;
+        Self { ids, threshold }
+    }
+
+    /// Create a set from the ready peers of a cluster
+    pub fn from_peers(peers: &[PeerInfo]) -> Self {
+        Self::new(peers.iter().filter(|p| p.is_ready()).map(|p| &p.id))
+    }
+
+    /// Require `threshold` valid validator signatures instead of a majority
+    pub fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Number of validator signatures required
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Whether `id` belongs to the set
+    pub fn contains(&self, id: &str) -> bool {
+        self.ids.contains(&signer_key(id))
+    }
+
+    /// Number of validators
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Whether the set has no validators
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Validator IDs, without the `04` prefix
+    pub fn ids(&self) -> impl Iterator<Item = &str> {
+        self.ids.iter().map(String::as_str)
+    }
+}
+
+/// Outcome of checking a snapshot's proofs against a [`ValidatorSet`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotVerification {
+    /// Hash of the snapshot body, to compare with the next snapshot's
+    /// `lastSnapshotHash`
+    pub hash: String,
+    /// Validators with a valid signature
+    pub signers: Vec<String>,
+    /// Signers with a valid signature who are not in the set
+    pub unknown_signers: Vec<String>,
+    /// Signers whose signature does not match the snapshot
+    pub invalid_signers: Vec<String>,
+    /// Number of validator signatures required
+    pub threshold: usize,
+}
+
+impl SnapshotVerification {
+    /// Whether enough validators signed the snapshot
+    pub fn is_trusted(&self) -> bool {
+        self.threshold > 0 && self.signers.len() >= self.threshold
+    }
+}
+
+/// Check a snapshot's proofs against a validator set
+///
+/// A validator signing more than once is counted once.
+///
+/// # Errors
+///
+/// Returns an error if the snapshot body cannot be encoded for hashing
+pub fn verify_snapshot<S: Serialize>(
+    snapshot: &Signed<S>,
+    validators: &ValidatorSet,
+) -> NetworkResult<SnapshotVerification> {
+    let hash = hash_data(&snapshot.value, false)?.value;
+
+    let mut signers = BTreeSet::new();
+    let mut unknown_signers = BTreeSet::new();
+    let mut invalid_signers = BTreeSet::new();
+    for proof in &snapshot.proofs {
+        let id = signer_key(&proof.id);
+        if !matches!(verify_hash(&hash, &proof.signature, &id), Ok(true)) {
+            invalid_signers.insert(id);
+        } else if validators.ids.contains(&id) {
+            signers.insert(id);
+        } else {
+            unknown_signers.insert(id);
+        }
+    }
+
+    Ok(SnapshotVerification {
+        hash,
+        signers: signers.into_iter().collect(),
+        unknown_signers: unknown_signers.into_iter().collect(),
+        invalid_signers: invalid_signers.into_iter().collect(),
+        threshold: validators.threshold,
+    })
+}
+
+/// Verify a snapshot as received, then decode it
+fn verified<S: DeserializeOwned>(
+    raw: Signed<serde_json::Value>,
+    validators: &ValidatorSet,
+) -> NetworkResult<Signed<S>> {
+    let verification = verify_snapshot(&raw, validators)?;
+    if !verification.is_trusted() {
+        return Err(NetworkError::UntrustedSnapshot(format!(
+            "{} has {} of {} required validator signatures",
+            verification.hash,
+            verification.signers.len(),
+            verification.threshold
+        )));
+    }
+    Ok(Signed {
+        value: serde_json::from_value(raw.value)
+            .map_err(|e| NetworkError::SerializationError(format!("invalid snapshot: {}", e)))?,
+        proofs: raw.proofs,
+    })
+}
+
+impl GlobalL0Client {
+    /// Build a validator set from the ready peers of this node's cluster
+    pub async fn get_validator_set(&self) -> NetworkResult<ValidatorSet> {
+        Ok(ValidatorSet::from_peers(&self.discover_peers().await?))
+    }
+
+    /// Get the latest global snapshot, checking it was signed by `validators`
+    ///
+    /// # Errors
+    ///
+    /// Returns an `UntrustedSnapshot` error if too few validators signed it
+    pub async fn get_latest_verified_snapshot(
+        &self,
+        validators: &ValidatorSet,
+    ) -> NetworkResult<Signed<GlobalSnapshot>> {
+        verified(
+            self.client.get("/global-snapshots/latest").await?,
+            validators,
+        )
+    }
+
+    /// Get a global snapshot by ordinal, checking it was signed by `validators`
+    ///
+    /// # Errors
+    ///
+    /// Returns an `UntrustedSnapshot` error if too few validators signed it
+    pub async fn get_verified_snapshot(
+        &self,
+        ordinal: i64,
+        validators: &ValidatorSet,
+    ) -> NetworkResult<Signed<GlobalSnapshot>> {
+        verified(
+            self.client
+                .get(&format!("/global-snapshots/{}", ordinal))
+                .await?,
+            validators,
+        )
+    }
+}
+
+impl MetagraphClient {
+    /// Get the latest currency snapshot, checking it was signed by `validators`
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer, or an
+    /// `UntrustedSnapshot` error if too few validators signed it
+    pub async fn get_latest_verified_snapshot(
+        &self,
+        validators: &ValidatorSet,
+    ) -> NetworkResult<Signed<CurrencySnapshot>> {
+        self.assert_layer(&[LayerType::ML0], "get_latest_verified_snapshot")?;
+        verified(self.get("/snapshots/latest").await?, validators)
+    }
+
+    /// Get a currency snapshot by ordinal, checking it was signed by `validators`
+    ///
+    /// Available on: ML0
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer, or an
+    /// `UntrustedSnapshot` error if too few validators signed it
+    pub async fn get_verified_snapshot(
+        &self,
+        ordinal: i64,
+        validators: &ValidatorSet,
+    ) -> NetworkResult<Signed<CurrencySnapshot>> {
+        self.assert_layer(&[LayerType::ML0], "get_verified_snapshot")?;
+        verified(
+            self.get(&format!("/snapshots/{}", ordinal)).await?,
+            validators,
+        )
+    }
+}
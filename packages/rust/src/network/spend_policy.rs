@@ -0,0 +1,200 @@
+//! Spending limits for unattended accounts
+//!
+//! A [`SpendPolicy`] on an [`Account`](super::Account) is checked before a
+//! transfer is signed, so a bug or a compromised caller in a hot wallet
+//! cannot move more than the policy allows. A transfer that breaks a rule
+//! fails with [`NetworkError::PolicyViolation`] and nothing is signed.
+//!
+//! Limits count the amount plus the fee, in the smallest unit (1e-8 token).
+//! The daily cap is a rolling 24-hour window over the account's own sends;
+//! sends from other processes holding the same key, and data update fees,
+//! are not counted.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{Account, SpendPolicy};
+//!
+//! let account = Account::from_private_key(&hot_key)?
+//!     .with_config(&config)?
+//!     .with_spend_policy(
+//!         SpendPolicy::new()
+//!             .max_per_transaction(50.0)
+//!             .daily_cap(500.0)
+//!             .allow_destination("DAG0...treasury"),
+//!     );
+//! ```
+
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::time::Duration;
+
+use super::rt::Instant;
+use super::types::NetworkError;
+use crate::currency_transaction::token_to_units;
+use crate::currency_types::TransferParams;
+
+/// Length of the rolling window the daily cap applies to
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Rules a transfer must satisfy before it is signed
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpendPolicy {
+    max_per_transaction: Option<i64>,
+    daily_cap: Option<i64>,
+    allowed_destinations: Option<HashSet<String>>,
+}
+
+impl SpendPolicy {
+    /// A policy that allows everything until limits are added
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Refuse a transfer whose amount plus fee exceeds `amount` tokens
+    pub fn max_per_transaction(mut self, amount: f64) -> Self {
+        self.max_per_transaction = Some(token_to_units(amount));
+        self
+    }
+
+    /// Refuse a transfer that would take the amounts plus fees sent in the
+    /// last 24 hours above `amount` tokens
+    pub fn daily_cap(mut self, amount: f64) -> Self {
+        self.daily_cap = Some(token_to_units(amount));
+        self
+    }
+
+    /// Allow transfers to `address`; once any address is allowed, transfers
+    /// to every other address are refused
+    pub fn allow_destination(mut self, address: impl Into<String>) -> Self {
+        self.allowed_destinations
+            .get_or_insert_with(HashSet::new)
+            .insert(address.into());
+        self
+    }
+
+    fn check(&self, destination: &str, amount: i64, spent: i64) -> Result<(), PolicyViolation> {
+        if let Some(allowed) = &self.allowed_destinations {
+            if !allowed.contains(destination) {
+                return Err(PolicyViolation::DestinationNotAllowed(
+                    destination.to_string(),
+                ));
+            }
+        }
+        if let Some(limit) = self.max_per_transaction {
+            if amount > limit {
+                return Err(PolicyViolation::AmountExceedsLimit { amount, limit });
+            }
+        }
+        if let Some(cap) = self.daily_cap {
+            if spent.saturating_add(amount) > cap {
+                return Err(PolicyViolation::DailyCapExceeded { amount, spent, cap });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The rule a refused transfer broke
+///
+/// Amounts are in the smallest unit and include the fee.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PolicyViolation {
+    /// The transfer is larger than the per-transaction limit
+    AmountExceedsLimit {
+        /// Amount plus fee of the refused transfer
+        amount: i64,
+        /// Per-transaction limit
+        limit: i64,
+    },
+    /// The transfer would take the last 24 hours' spending over the cap
+    DailyCapExceeded {
+        /// Amount plus fee of the refused transfer
+        amount: i64,
+        /// Already sent in the last 24 hours
+        spent: i64,
+        /// Daily cap
+        cap: i64,
+    },
+    /// The destination is not on the allowlist
+    DestinationNotAllowed(String),
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PolicyViolation::AmountExceedsLimit { amount, limit } => write!(
+                f,
+                "transfer of {amount} units exceeds the per-transaction limit of {limit}"
+            ),
+            PolicyViolation::DailyCapExceeded { amount, spent, cap } => write!(
+                f,
+                "transfer of {amount} units after {spent} in the last 24 hours exceeds the daily cap of {cap}"
+            ),
+            PolicyViolation::DestinationNotAllowed(destination) => {
+                write!(f, "destination {destination} is not on the allowlist")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolicyViolation {}
+
+/// A send counted against the daily cap
+#[derive(Debug)]
+struct Counted {
+    id: u64,
+    at: Instant,
+    units: i64,
+}
+
+/// A policy and the sends counted against its daily cap
+#[derive(Debug)]
+pub(super) struct SpendLedger {
+    policy: SpendPolicy,
+    sends: VecDeque<Counted>,
+    next_id: u64,
+}
+
+impl SpendLedger {
+    pub(super) fn new(policy: SpendPolicy) -> Self {
+        Self {
+            policy,
+            sends: VecDeque::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Check a transfer and count it against the daily cap
+    ///
+    /// Returns an id to [`release`](Self::release) it by if the transfer
+    /// turns out not to have been sent.
+    pub(super) fn reserve(&mut self, params: &TransferParams) -> Result<u64, NetworkError> {
+        let now = Instant::now();
+        while self
+            .sends
+            .front()
+            .is_some_and(|send| now.duration_since(send.at) >= DAY)
+        {
+            self.sends.pop_front();
+        }
+        let units = token_to_units(params.amount).saturating_add(token_to_units(params.fee));
+        let spent = self
+            .sends
+            .iter()
+            .fold(0i64, |sum, send| sum.saturating_add(send.units));
+        self.policy
+            .check(&params.destination, units, spent)
+            .map_err(NetworkError::PolicyViolation)?;
+        let id = self.next_id;
+        self.next_id += 1;
+        self.sends.push_back(Counted { id, at: now, units });
+        Ok(id)
+    }
+
+    /// Stop counting the transfer reserved as `id`
+    pub(super) fn release(&mut self, id: u64) {
+        self.sends.retain(|send| send.id != id);
+    }
+}
@@ -0,0 +1,381 @@
+//! Local transaction history store
+//!
+//! Requires the `store` feature. A [`HistoryStore`] keeps every transaction a
+//! wallet submitted or saw on the block explorer in an embedded sled
+//! database, indexed by source and destination address, so history is
+//! available offline and does not have to be re-downloaded on every start.
+//!
+//! Transactions recorded at submission stay pending until a sync with the
+//! block explorer finds them in a snapshot. Each store holds one token's
+//! history; use a separate store per metagraph.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::store::HistoryStore;
+//!
+//! let store = HistoryStore::open("wallet-history")?;
+//!
+//! cl1.post_transaction(&tx).await?;
+//! store.record_submitted(&tx)?;
+//!
+//! store.sync_address(&explorer, &address, 100).await?;
+//! for record in store.history(&address)? {
+//!     println!("{} {} -> {}: {}", record.hash, record.source, record.destination, record.amount);
+//! }
+//! ```
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use super::block_explorer::{BlockExplorerClient, ExplorerTransaction};
+use super::rt::SystemTime;
+use super::types::{NetworkError, NetworkResult};
+use crate::currency_transaction::hash_currency_transaction;
+use crate::currency_types::{CurrencyTransaction, TransactionReference};
+
+/// How a transaction entered the store
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RecordOrigin {
+    /// Submitted from this wallet and not yet seen in a snapshot
+    Submitted,
+    /// Seen in a snapshot through the block explorer
+    Observed,
+}
+
+/// A transaction kept in a [`HistoryStore`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryRecord {
+    /// Transaction hash
+    pub hash: String,
+    /// Source DAG address
+    pub source: String,
+    /// Destination DAG address
+    pub destination: String,
+    /// Amount in smallest units (1e-8)
+    pub amount: i64,
+    /// Fee in smallest units (1e-8)
+    pub fee: i64,
+    /// Reference to the parent transaction
+    pub parent: TransactionReference,
+    /// How the transaction entered the store
+    pub origin: RecordOrigin,
+    /// Ordinal of the snapshot that included the transaction, once known
+    pub snapshot_ordinal: Option<i64>,
+    /// When the including snapshot was created, as reported by the explorer
+    pub timestamp: Option<String>,
+    /// When the transaction was first recorded, in seconds since the Unix epoch
+    pub recorded_at: u64,
+}
+
+impl HistoryRecord {
+    /// Whether the transaction has been seen in a snapshot
+    pub fn is_confirmed(&self) -> bool {
+        self.snapshot_ordinal.is_some()
+    }
+}
+
+/// Embedded store of the transactions of a wallet's addresses
+#[derive(Debug, Clone)]
+pub struct HistoryStore {
+    db: sled::Db,
+    records: sled::Tree,
+    index: sled::Tree,
+}
+
+impl HistoryStore {
+    /// Open or create a store in the directory at `path`
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StoreError` if the database cannot be opened
+    pub fn open(path: impl AsRef<Path>) -> NetworkResult<Self> {
+        Self::from_db(sled::open(path).map_err(store_error)?)
+    }
+
+    /// Create a store that is deleted when dropped, e.g. for tests
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StoreError` if the database cannot be created
+    pub fn temporary() -> NetworkResult<Self> {
+        Self::from_db(
+            sled::Config::new()
+                .temporary(true)
+                .open()
+                .map_err(store_error)?,
+        )
+    }
+
+    fn from_db(db: sled::Db) -> NetworkResult<Self> {
+        Ok(Self {
+            records: db.open_tree("transactions").map_err(store_error)?,
+            index: db.open_tree("by_address").map_err(store_error)?,
+            db,
+        })
+    }
+
+    /// Record a transaction submitted from this wallet, returning its hash
+    ///
+    /// A transaction already observed in a snapshot is left as it is.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StoreError` if the write fails
+    pub fn record_submitted(&self, transaction: &CurrencyTransaction) -> NetworkResult<String> {
+        let hash = hash_currency_transaction(transaction).value;
+        if self.get(&hash)?.is_none() {
+            let value = &transaction.value;
+            self.put(
+                &HistoryRecord {
+                    hash: hash.clone(),
+                    source: value.source.clone(),
+                    destination: value.destination.clone(),
+                    amount: value.amount,
+                    fee: value.fee,
+                    parent: value.parent.clone(),
+                    origin: RecordOrigin::Submitted,
+                    snapshot_ordinal: None,
+                    timestamp: None,
+                    recorded_at: now(),
+                },
+                None,
+            )?;
+        }
+        Ok(hash)
+    }
+
+    /// Record a transaction seen on the block explorer
+    ///
+    /// Returns whether the store changed: `false` if the transaction was
+    /// already recorded with the same snapshot.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StoreError` if the write fails
+    pub fn record_observed(&self, transaction: &ExplorerTransaction) -> NetworkResult<bool> {
+        let existing = self.get(&transaction.hash)?;
+        if let Some(existing) = &existing {
+            if existing.origin == RecordOrigin::Observed
+                && existing.snapshot_ordinal == transaction.snapshot_ordinal
+            {
+                return Ok(false);
+            }
+        }
+        let record = HistoryRecord {
+            hash: transaction.hash.clone(),
+            source: transaction.source.clone(),
+            destination: transaction.destination.clone(),
+            amount: transaction.amount,
+            fee: transaction.fee,
+            parent: transaction.parent.clone(),
+            origin: RecordOrigin::Observed,
+            snapshot_ordinal: transaction.snapshot_ordinal,
+            timestamp: transaction.timestamp.clone(),
+            recorded_at: existing.as_ref().map_or_else(now, |e| e.recorded_at),
+        };
+        self.put(&record, existing.as_ref())?;
+        Ok(true)
+    }
+
+    /// Get a transaction by hash
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StoreError` if the read fails
+    pub fn get(&self, hash: &str) -> NetworkResult<Option<HistoryRecord>> {
+        self.records
+            .get(hash)
+            .map_err(store_error)?
+            .map(|bytes| decode(&bytes))
+            .transpose()
+    }
+
+    /// Transactions sent or received by an address, newest first
+    ///
+    /// Pending transactions come first, then confirmed ones by descending
+    /// snapshot ordinal.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StoreError` if the read fails
+    pub fn history(&self, address: &str) -> NetworkResult<Vec<HistoryRecord>> {
+        self.index
+            .scan_prefix(address_prefix(address))
+            .rev()
+            .map(|entry| {
+                let (key, _) = entry.map_err(store_error)?;
+                let hash = &key[address.len() + 9..];
+                let bytes = self
+                    .records
+                    .get(hash)
+                    .map_err(store_error)?
+                    .ok_or_else(|| NetworkError::StoreError("dangling index entry".into()))?;
+                decode(&bytes)
+            })
+            .collect()
+    }
+
+    /// Transactions of an address not yet seen in a snapshot
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StoreError` if the read fails
+    pub fn pending(&self, address: &str) -> NetworkResult<Vec<HistoryRecord>> {
+        Ok(self
+            .history(address)?
+            .into_iter()
+            .take_while(|record| !record.is_confirmed())
+            .collect())
+    }
+
+    /// Remove a transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StoreError` if the write fails
+    pub fn remove(&self, hash: &str) -> NetworkResult<Option<HistoryRecord>> {
+        let Some(record) = self.get(hash)? else {
+            return Ok(None);
+        };
+        for key in index_keys(&record) {
+            self.index.remove(key).map_err(store_error)?;
+        }
+        self.records.remove(hash).map_err(store_error)?;
+        Ok(Some(record))
+    }
+
+    /// Number of stored transactions
+    pub fn len(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Whether the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.records.is_empty()
+    }
+
+    /// Write pending changes to disk
+    ///
+    /// # Errors
+    ///
+    /// Returns a `StoreError` if the flush fails
+    pub fn flush(&self) -> NetworkResult<()> {
+        self.db.flush().map_err(store_error)?;
+        Ok(())
+    }
+
+    /// Fetch an address's new transactions from the block explorer
+    ///
+    /// Walks the history newest first and stops at the first transaction
+    /// already stored unchanged, so repeated syncs only download what is new.
+    /// Returns the number of transactions added or updated.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request or a write fails; transactions stored
+    /// before the error are kept
+    pub async fn sync_address(
+        &self,
+        explorer: &BlockExplorerClient,
+        address: &str,
+        page_size: u32,
+    ) -> NetworkResult<usize> {
+        self.sync(explorer, address, page_size, true).await
+    }
+
+    /// Fetch an address's whole history from the block explorer
+    ///
+    /// Like [`sync_address`](Self::sync_address) but never stops early, for
+    /// completing a first sync that was interrupted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a request or a write fails; transactions stored
+    /// before the error are kept
+    pub async fn sync_address_full(
+        &self,
+        explorer: &BlockExplorerClient,
+        address: &str,
+        page_size: u32,
+    ) -> NetworkResult<usize> {
+        self.sync(explorer, address, page_size, false).await
+    }
+
+    async fn sync(
+        &self,
+        explorer: &BlockExplorerClient,
+        address: &str,
+        page_size: u32,
+        stop_at_known: bool,
+    ) -> NetworkResult<usize> {
+        let transactions = explorer.transactions_by_address(address, page_size);
+        futures_util::pin_mut!(transactions);
+
+        let mut changed = 0;
+        while let Some(transaction) = transactions.next().await {
+            if self.record_observed(&transaction?)? {
+                changed += 1;
+            } else if stop_at_known {
+                break;
+            }
+        }
+        Ok(changed)
+    }
+
+    fn put(&self, record: &HistoryRecord, previous: Option<&HistoryRecord>) -> NetworkResult<()> {
+        let bytes = serde_json::to_vec(record)
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))?;
+        self.records
+            .insert(record.hash.as_bytes(), bytes)
+            .map_err(store_error)?;
+        for key in previous.into_iter().flat_map(index_keys) {
+            self.index.remove(key).map_err(store_error)?;
+        }
+        for key in index_keys(record) {
+            self.index.insert(key, &[]).map_err(store_error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Index keys of a record: `address, 0, ordinal (big endian), hash` for the
+/// source and the destination, with pending transactions sorted last
+fn index_keys(record: &HistoryRecord) -> Vec<Vec<u8>> {
+    let ordinal = record
+        .snapshot_ordinal
+        .map_or(u64::MAX, |ordinal| ordinal.max(0) as u64);
+    let mut addresses = vec![&record.source, &record.destination];
+    addresses.dedup();
+    addresses
+        .into_iter()
+        .map(|address| {
+            let mut key = address_prefix(address);
+            key.extend_from_slice(&ordinal.to_be_bytes());
+            key.extend_from_slice(record.hash.as_bytes());
+            key
+        })
+        .collect()
+}
+
+fn address_prefix(address: &str) -> Vec<u8> {
+    let mut prefix = address.as_bytes().to_vec();
+    prefix.push(0);
+    prefix
+}
+
+fn decode(bytes: &[u8]) -> NetworkResult<HistoryRecord> {
+    serde_json::from_slice(bytes).map_err(|e| NetworkError::StoreError(e.to_string()))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+fn store_error(e: sled::Error) -> NetworkError {
+    NetworkError::StoreError(e.to_string())
+}
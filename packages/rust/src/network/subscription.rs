@@ -0,0 +1,169 @@
+//! Streams of new snapshots and accepted transactions
+//!
+//! [`MetagraphClient::subscribe`] turns repeated snapshot and last-reference
+//! lookups into a single [`Stream`] of [`SubscriptionEvent`]s, so indexers
+//! react to changes instead of re-fetching everything on every tick.
+//!
+//! Metagraph nodes do not expose WebSocket or SSE endpoints, so the stream
+//! polls once per `poll_interval` and only emits what changed. Consumers only
+//! depend on the `Stream`, which leaves room for a push transport later.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{SubscriptionEvent, SubscriptionOptions};
+//! use futures_util::StreamExt;
+//!
+//! let events = ml0.subscribe(SubscriptionOptions {
+//!     snapshots: true,
+//!     addresses: vec!["DAG...".to_string()],
+//!     ..Default::default()
+//! })?;
+//! futures_util::pin_mut!(events);
+//!
+//! while let Some(event) = events.next().await {
+//!     match event? {
+//!         SubscriptionEvent::Snapshot(snapshot) => println!("snapshot {}", snapshot.value.ordinal),
+//!         SubscriptionEvent::TransactionAccepted { address, reference } => {
+//!             println!("{} sent {}", address, reference.hash)
+//!         }
+//!     }
+//! }
+//! ```
+
+use futures_util::stream::{self, Stream};
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::rt;
+use super::types::{CurrencySnapshot, NetworkResult};
+use crate::currency_types::TransactionReference;
+use crate::types::Signed;
+
+/// What a subscription watches
+#[derive(Debug, Clone)]
+pub struct SubscriptionOptions {
+    /// Emit every new currency snapshot (ML0 only)
+    pub snapshots: bool,
+    /// Emit a transaction event whenever one of these addresses' last
+    /// reference advances (CL1 or ML0)
+    pub addresses: Vec<String>,
+    /// Delay between polls
+    pub poll_interval: Duration,
+}
+
+impl Default for SubscriptionOptions {
+    fn default() -> Self {
+        Self {
+            snapshots: false,
+            addresses: Vec::new(),
+            poll_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+/// A change observed by a subscription
+#[derive(Debug, Clone)]
+pub enum SubscriptionEvent {
+    /// A snapshot newer than the ones seen so far, in ordinal order
+    Snapshot(Box<Signed<CurrencySnapshot>>),
+    /// A new transaction sent from `address` was accepted
+    TransactionAccepted {
+        /// Watched source address
+        address: String,
+        /// The address's new last reference
+        reference: TransactionReference,
+    },
+}
+
+impl MetagraphClient {
+    /// Subscribe to new snapshots and accepted transactions
+    ///
+    /// The first poll only records the current state; events are emitted for
+    /// changes after that. A failed poll yields an error item and the stream
+    /// keeps polling. The stream never ends on its own; drop it to stop.
+    ///
+    /// Available on: ML0 (snapshots and addresses), CL1 (addresses)
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the layer cannot serve what `options` asks for
+    pub fn subscribe(
+        &self,
+        options: SubscriptionOptions,
+    ) -> NetworkResult<impl Stream<Item = NetworkResult<SubscriptionEvent>> + '_> {
+        if options.snapshots {
+            self.assert_layer(&[LayerType::ML0], "subscribe")?;
+        }
+        if !options.addresses.is_empty() {
+            self.assert_layer(&[LayerType::CL1, LayerType::ML0], "subscribe")?;
+        }
+
+        let poller = Poller {
+            client: self,
+            options,
+            last_ordinal: None,
+            last_references: HashMap::new(),
+            queue: VecDeque::new(),
+            polled: false,
+        };
+        Ok(stream::unfold(poller, |mut poller| async move {
+            let item = poller.next().await;
+            Some((item, poller))
+        }))
+    }
+}
+
+struct Poller<'a> {
+    client: &'a MetagraphClient,
+    options: SubscriptionOptions,
+    last_ordinal: Option<i64>,
+    last_references: HashMap<String, TransactionReference>,
+    queue: VecDeque<SubscriptionEvent>,
+    polled: bool,
+}
+
+impl Poller<'_> {
+    async fn next(&mut self) -> NetworkResult<SubscriptionEvent> {
+        loop {
+            if let Some(event) = self.queue.pop_front() {
+                return Ok(event);
+            }
+            if self.polled {
+                rt::sleep(self.options.poll_interval).await;
+            }
+            self.polled = true;
+            self.poll().await?;
+        }
+    }
+
+    async fn poll(&mut self) -> NetworkResult<()> {
+        if self.options.snapshots {
+            let latest = self.client.get_latest_snapshot_ordinal().await?;
+            let first = self.last_ordinal.map_or(latest + 1, |last| last + 1);
+            for ordinal in first..=latest {
+                let snapshot = self.client.get_snapshot(ordinal).await?;
+                self.queue
+                    .push_back(SubscriptionEvent::Snapshot(Box::new(snapshot)));
+                self.last_ordinal = Some(ordinal);
+            }
+            self.last_ordinal.get_or_insert(latest);
+        }
+
+        for address in &self.options.addresses {
+            let reference = self.client.get_last_reference(address).await?;
+            let previous = self
+                .last_references
+                .insert(address.clone(), reference.clone());
+            if previous.is_some_and(|previous| previous != reference) {
+                self.queue
+                    .push_back(SubscriptionEvent::TransactionAccepted {
+                        address: address.clone(),
+                        reference,
+                    });
+            }
+        }
+        Ok(())
+    }
+}
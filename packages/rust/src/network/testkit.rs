@@ -0,0 +1,259 @@
+//! A mock metagraph node over HTTP, for integration tests
+//!
+//! [`MockL1Client`](super::MockL1Client) replaces the client; a
+//! [`MockMetagraph`] replaces the node instead, so a service that builds its
+//! own [`MetagraphClient`] from a URL can be tested end to end, HTTP client,
+//! retries, and error handling included, without a local cluster.
+//!
+//! The server listens on a random local port and answers the Data L1 and
+//! Currency L1 submission routes:
+//!
+//! | Route | Answer |
+//! |-------|--------|
+//! | `POST /data` | `{"hash": ...}`, the hash of the update's value as a DataUpdate |
+//! | `POST /data/estimate-fee` | the fee set with [`MockMetagraph::set_fee`], zero by default |
+//! | `POST /transactions` | `{"hash": ...}`, the transaction's hash |
+//!
+//! Accepted submissions are recorded for assertions. Any route can be made to
+//! fail once with [`MockMetagraph::fail_next`] or until
+//! [`MockMetagraph::recover`] with [`MockMetagraph::fail_always`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::testkit::{Endpoint, Failure, MockMetagraph};
+//!
+//! let node = MockMetagraph::start().await;
+//! let service = Submitter::new(&node.uri());
+//!
+//! node.fail_next(Endpoint::Data, Failure::Status(503));
+//! service.submit(update).await?; // should retry past the 503
+//! assert_eq!(node.data().len(), 1);
+//!
+//! node.fail_always(Endpoint::Data, Failure::Rejected("InvalidSignature".into()));
+//! assert!(service.submit(update).await.is_err());
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request, Respond, ResponseTemplate};
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::types::EstimateFeeResponse;
+use crate::currency_transaction::hash_currency_transaction;
+use crate::currency_types::CurrencyTransaction;
+use crate::hash::hash_data;
+
+/// A route the mock answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endpoint {
+    /// `POST /data`
+    Data,
+    /// `POST /data/estimate-fee`
+    EstimateFee,
+    /// `POST /transactions`
+    Transactions,
+}
+
+impl Endpoint {
+    /// The route's path
+    pub fn path(self) -> &'static str {
+        match self {
+            Endpoint::Data => "/data",
+            Endpoint::EstimateFee => "/data/estimate-fee",
+            Endpoint::Transactions => "/transactions",
+        }
+    }
+}
+
+/// How a route misbehaves
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Failure {
+    /// Refuse with HTTP 400 and `{"errors": [{"message": ...}]}`, as a node
+    /// does when validation fails
+    Rejected(String),
+    /// Answer with this status code and an empty body, e.g. 503 for a node
+    /// that is restarting
+    Status(u16),
+    /// Answer normally, but only after this delay, to exercise timeouts
+    Slow(Duration),
+    /// Answer 200 with a body that is not JSON
+    Malformed,
+}
+
+#[derive(Default)]
+struct MockState {
+    data: Vec<Value>,
+    transactions: Vec<CurrencyTransaction>,
+    fee: EstimateFeeResponse,
+    fail_next: HashMap<Endpoint, VecDeque<Failure>>,
+    fail_always: HashMap<Endpoint, Failure>,
+}
+
+/// A mock Data L1 / Currency L1 node listening on a local port
+///
+/// The server stops when the value is dropped.
+pub struct MockMetagraph {
+    server: MockServer,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockMetagraph {
+    /// Start a server that accepts every submission
+    pub async fn start() -> Self {
+        let server = MockServer::start().await;
+        let state = Arc::new(Mutex::new(MockState::default()));
+        for endpoint in [
+            Endpoint::Data,
+            Endpoint::EstimateFee,
+            Endpoint::Transactions,
+        ] {
+            Mock::given(method("POST"))
+                .and(path(endpoint.path()))
+                .respond_with(Responder {
+                    endpoint,
+                    state: Arc::clone(&state),
+                })
+                .mount(&server)
+                .await;
+        }
+        Self { server, state }
+    }
+
+    /// Base URL of the server
+    pub fn uri(&self) -> String {
+        self.server.uri()
+    }
+
+    /// A Data L1 client for the server
+    pub fn data_l1(&self) -> MetagraphClient {
+        self.client(LayerType::DL1)
+    }
+
+    /// A Currency L1 client for the server
+    pub fn currency_l1(&self) -> MetagraphClient {
+        self.client(LayerType::CL1)
+    }
+
+    fn client(&self, layer: LayerType) -> MetagraphClient {
+        MetagraphClient::new(self.uri(), layer).expect("mock server URL is valid")
+    }
+
+    /// Answer fee estimates with `fee` smallest units payable to `address`
+    pub fn set_fee(&self, fee: i64, address: impl Into<String>) {
+        self.lock().fee = EstimateFeeResponse {
+            fee,
+            address: address.into(),
+            extra: HashMap::new(),
+        };
+    }
+
+    /// Fail the next request to `endpoint`
+    ///
+    /// Failures queue up, so calling this twice fails the next two requests.
+    pub fn fail_next(&self, endpoint: Endpoint, failure: Failure) {
+        self.lock()
+            .fail_next
+            .entry(endpoint)
+            .or_default()
+            .push_back(failure);
+    }
+
+    /// Fail every request to `endpoint` until [`recover`](Self::recover)
+    pub fn fail_always(&self, endpoint: Endpoint, failure: Failure) {
+        self.lock().fail_always.insert(endpoint, failure);
+    }
+
+    /// Clear the failures set for `endpoint`
+    pub fn recover(&self, endpoint: Endpoint) {
+        let mut state = self.lock();
+        state.fail_next.remove(&endpoint);
+        state.fail_always.remove(&endpoint);
+    }
+
+    /// Signed data updates accepted so far, as posted
+    pub fn data(&self) -> Vec<Value> {
+        self.lock().data.clone()
+    }
+
+    /// Currency transactions accepted so far
+    pub fn transactions(&self) -> Vec<CurrencyTransaction> {
+        self.lock().transactions.clone()
+    }
+
+    /// Number of requests received on `endpoint`, failed ones included
+    pub async fn requests(&self, endpoint: Endpoint) -> usize {
+        self.server
+            .received_requests()
+            .await
+            .unwrap_or_default()
+            .iter()
+            .filter(|request| request.url.path() == endpoint.path())
+            .count()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        lock(&self.state)
+    }
+}
+
+fn lock(state: &Mutex<MockState>) -> MutexGuard<'_, MockState> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+struct Responder {
+    endpoint: Endpoint,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl Respond for Responder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        let mut state = lock(&self.state);
+        let failure = match state.fail_next.get_mut(&self.endpoint) {
+            Some(queue) if !queue.is_empty() => queue.pop_front(),
+            _ => state.fail_always.get(&self.endpoint).cloned(),
+        };
+        match failure {
+            None => self.accept(&mut state, &request.body),
+            Some(Failure::Rejected(reason)) => rejected(&reason),
+            Some(Failure::Status(status)) => ResponseTemplate::new(status),
+            Some(Failure::Slow(delay)) => self.accept(&mut state, &request.body).set_delay(delay),
+            Some(Failure::Malformed) => ResponseTemplate::new(200).set_body_string("<html>"),
+        }
+    }
+}
+
+impl Responder {
+    fn accept(&self, state: &mut MockState, body: &[u8]) -> ResponseTemplate {
+        match self.endpoint {
+            Endpoint::Data => {
+                let Ok(signed) = serde_json::from_slice::<Value>(body) else {
+                    return rejected("body is not JSON");
+                };
+                let Ok(hash) = hash_data(&signed["value"], true) else {
+                    return rejected("value cannot be hashed");
+                };
+                state.data.push(signed);
+                ResponseTemplate::new(200).set_body_json(json!({ "hash": hash.value }))
+            }
+            Endpoint::EstimateFee => ResponseTemplate::new(200).set_body_json(&state.fee),
+            Endpoint::Transactions => {
+                let Ok(transaction) = serde_json::from_slice::<CurrencyTransaction>(body) else {
+                    return rejected("body is not a currency transaction");
+                };
+                let hash = hash_currency_transaction(&transaction).value;
+                state.transactions.push(transaction);
+                ResponseTemplate::new(200).set_body_json(json!({ "hash": hash }))
+            }
+        }
+    }
+}
+
+fn rejected(reason: &str) -> ResponseTemplate {
+    ResponseTemplate::new(400).set_body_json(json!({ "errors": [{ "message": reason }] }))
+}
@@ -0,0 +1,95 @@
+//! TLS settings for private deployments
+//!
+//! Private metagraphs are often served behind self-signed certificates or
+//! gateways that require client certificates. [`TlsConfig`] adds trusted root
+//! CAs and an optional client identity (mutual TLS) to the HTTP client.
+//!
+//! Not available on wasm32, where the browser validates certificates.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{NetworkConfig, TlsConfig};
+//!
+//! let config = NetworkConfig {
+//!     l1_url: Some("https://l1.internal:9300".to_string()),
+//!     tls: Some(TlsConfig {
+//!         root_certificates_pem: Some(std::fs::read("ca.pem")?),
+//!         client_identity_pem: Some(std::fs::read("client.pem")?),
+//!         ..Default::default()
+//!     }),
+//!     ..Default::default()
+//! };
+//! ```
+
+use reqwest::ClientBuilder;
+#[cfg(not(target_arch = "wasm32"))]
+use reqwest::{Certificate, Identity};
+use std::fmt;
+
+use super::types::{NetworkError, NetworkResult};
+
+/// Custom trust roots and client certificate for HTTPS endpoints
+#[derive(Clone, Default)]
+pub struct TlsConfig {
+    /// PEM bundle of additional root CAs to trust
+    pub root_certificates_pem: Option<Vec<u8>>,
+    /// PEM containing the client certificate chain and its private key, for mTLS
+    pub client_identity_pem: Option<Vec<u8>>,
+    /// Trust only `root_certificates_pem`, ignoring the built-in web roots
+    pub disable_built_in_roots: bool,
+}
+
+impl fmt::Debug for TlsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The identity contains a private key
+        f.debug_struct("TlsConfig")
+            .field(
+                "root_certificates_pem",
+                &self.root_certificates_pem.as_ref().map(|pem| pem.len()),
+            )
+            .field(
+                "client_identity_pem",
+                &self.client_identity_pem.as_ref().map(|_| "<redacted>"),
+            )
+            .field("disable_built_in_roots", &self.disable_built_in_roots)
+            .finish()
+    }
+}
+
+impl TlsConfig {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn apply(&self, mut builder: ClientBuilder) -> NetworkResult<ClientBuilder> {
+        if let Some(pem) = &self.root_certificates_pem {
+            let certificates = Certificate::from_pem_bundle(pem).map_err(|e| {
+                NetworkError::ConfigError(format!("invalid root certificates: {}", e))
+            })?;
+            if certificates.is_empty() {
+                return Err(NetworkError::ConfigError(
+                    "root_certificates_pem contains no certificates".to_string(),
+                ));
+            }
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        if let Some(pem) = &self.client_identity_pem {
+            let identity = Identity::from_pem(pem).map_err(|e| {
+                NetworkError::ConfigError(format!("invalid client identity: {}", e))
+            })?;
+            builder = builder.identity(identity);
+        }
+        if self.disable_built_in_roots {
+            builder = builder.tls_built_in_root_certs(false);
+        }
+        Ok(builder)
+    }
+
+    /// The browser owns certificate validation, so custom TLS cannot be honored
+    #[cfg(target_arch = "wasm32")]
+    pub(super) fn apply(&self, _builder: ClientBuilder) -> NetworkResult<ClientBuilder> {
+        Err(NetworkError::ConfigError(
+            "custom TLS settings are not supported on wasm32".to_string(),
+        ))
+    }
+}
@@ -0,0 +1,297 @@
+//! Lifecycle monitoring of submitted transactions
+//!
+//! A [`TransactionMonitor`] owns a Currency L1 client and a [`PendingTracker`],
+//! reconciles the tracked transactions on every poll, and reports each state
+//! change (submitted → waiting → in progress → confirmed, dropped,
+//! conflicted, or superseded) as a [`MonitorEvent`], both to registered
+//! callbacks and through [`TransactionMonitor::events`] as a `Stream`.
+//!
+//! A [`MonitorStore`] persists every state change, so a process that restarts
+//! can [`restore`](TransactionMonitor::restore) its in-flight transactions
+//! instead of losing track of them.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::TransactionMonitor;
+//! use futures_util::StreamExt;
+//!
+//! let mut monitor = TransactionMonitor::new(cl1)?
+//!     .on_event(|event| println!("{}: {} -> {}", event.hash, event.previous, event.status));
+//!
+//! monitor.submit(&tx).await?;
+//!
+//! let events = monitor.events();
+//! futures_util::pin_mut!(events);
+//! while let Some(event) = events.next().await {
+//!     let event = event?;
+//! }
+//! ```
+
+use futures_util::stream::{self, Stream};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::pending_tracker::{PendingTracker, TrackedStatus, TrackedTransaction};
+use super::rt;
+use super::types::{NetworkError, NetworkResult};
+use crate::currency_types::{CurrencyTransaction, TransactionReference};
+
+/// Default delay between polls of a [`TransactionMonitor`]
+pub const DEFAULT_MONITOR_INTERVAL: Duration = Duration::from_secs(5);
+
+/// A state change of a monitored transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorEvent {
+    /// Transaction hash
+    pub hash: String,
+    /// Source DAG address
+    pub source: String,
+    /// State before the change
+    pub previous: TrackedStatus,
+    /// State after the change
+    pub status: TrackedStatus,
+}
+
+/// Persistence hook for a [`TransactionMonitor`]
+///
+/// `save` is called whenever a transaction is tracked or changes state, and
+/// `remove` once it has settled and is no longer monitored.
+pub trait MonitorStore: Send + Sync {
+    /// Load the transactions saved by a previous run
+    fn load(&self) -> NetworkResult<Vec<TrackedTransaction>>;
+
+    /// Save the current state of a transaction
+    fn save(&self, transaction: &TrackedTransaction) -> NetworkResult<()>;
+
+    /// Forget a transaction
+    fn remove(&self, hash: &str) -> NetworkResult<()>;
+}
+
+type Callback = Box<dyn Fn(&MonitorEvent) + Send + Sync>;
+
+/// Tracks submitted transactions and reports their state changes
+pub struct TransactionMonitor {
+    client: MetagraphClient,
+    tracker: PendingTracker,
+    store: Option<Arc<dyn MonitorStore>>,
+    callbacks: Vec<Callback>,
+    interval: Duration,
+}
+
+impl TransactionMonitor {
+    /// Create a monitor polling a Currency L1 node
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `client` is not a CL1 client
+    pub fn new(client: MetagraphClient) -> NetworkResult<Self> {
+        client.assert_layer(&[LayerType::CL1], "TransactionMonitor::new")?;
+        Ok(Self {
+            client,
+            tracker: PendingTracker::new(),
+            store: None,
+            callbacks: Vec::new(),
+            interval: DEFAULT_MONITOR_INTERVAL,
+        })
+    }
+
+    /// Use a different tracker, e.g. one reporting to a metrics registry
+    pub fn with_tracker(mut self, tracker: PendingTracker) -> Self {
+        self.tracker = tracker;
+        self
+    }
+
+    /// Persist state changes to `store`
+    pub fn with_store(mut self, store: Arc<dyn MonitorStore>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// Delay between polls of [`events`](Self::events)
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Call `callback` for every state change
+    pub fn on_event(mut self, callback: impl Fn(&MonitorEvent) + Send + Sync + 'static) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    /// The Currency L1 client being polled
+    pub fn client(&self) -> &MetagraphClient {
+        &self.client
+    }
+
+    /// The tracked transactions
+    pub fn tracker(&self) -> &PendingTracker {
+        &self.tracker
+    }
+
+    /// Resume monitoring the transactions saved in the store
+    ///
+    /// Returns how many transactions were restored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails to load
+    pub fn restore(&mut self) -> NetworkResult<usize> {
+        let Some(store) = &self.store else {
+            return Ok(0);
+        };
+        let saved = store.load()?;
+        let count = saved.len();
+        for transaction in saved {
+            self.tracker.insert(transaction);
+        }
+        Ok(count)
+    }
+
+    /// Start monitoring a submitted transaction, returning its hash
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails to save it
+    pub fn track(&mut self, transaction: &CurrencyTransaction) -> NetworkResult<String> {
+        let hash = self.tracker.track(transaction);
+        self.save(&hash)?;
+        Ok(hash)
+    }
+
+    /// Start monitoring a submitted transaction by hash, source, and parent
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the store fails to save it
+    pub fn track_hash(
+        &mut self,
+        hash: impl Into<String>,
+        source: impl Into<String>,
+        parent: TransactionReference,
+    ) -> NetworkResult<()> {
+        let hash = hash.into();
+        self.tracker.track_hash(hash.clone(), source, parent);
+        self.save(&hash)
+    }
+
+    /// Submit a transaction through the monitor's client and monitor it
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the submission fails, in which case the
+    /// transaction is not monitored, or if the store fails to save it
+    pub async fn submit(&mut self, transaction: &CurrencyTransaction) -> NetworkResult<String> {
+        self.client.post_transaction(transaction).await?;
+        self.track(transaction)
+    }
+
+    /// Reconcile every in-flight transaction once and report what changed
+    ///
+    /// Settled transactions are removed from the monitor and the store after
+    /// their final event.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first network or store error. Changes observed before it
+    /// are still passed to the callbacks, and [`events`](Self::events)
+    /// yields them before the error.
+    pub async fn poll(&mut self) -> NetworkResult<Vec<MonitorEvent>> {
+        match self.poll_events().await {
+            (events, None) => Ok(events),
+            (_, Some(error)) => Err(error),
+        }
+    }
+
+    /// Reconcile once and pass the changes to the callbacks, returning them
+    /// with the first error encountered
+    async fn poll_events(&mut self) -> (Vec<MonitorEvent>, Option<NetworkError>) {
+        let before: Vec<(String, TrackedStatus)> = self
+            .tracker
+            .in_flight()
+            .map(|entry| (entry.hash.clone(), entry.status.clone()))
+            .collect();
+
+        // States updated before a failure are kept, so report them anyway
+        let mut error = self.tracker.reconcile(&self.client).await.err();
+
+        let mut events = Vec::new();
+        for (hash, previous) in before {
+            let Some(entry) = self.tracker.get(&hash) else {
+                continue;
+            };
+            if entry.status == previous {
+                continue;
+            }
+            events.push(MonitorEvent {
+                hash: hash.clone(),
+                source: entry.source.clone(),
+                previous,
+                status: entry.status.clone(),
+            });
+            if let Err(e) = self.save(&hash) {
+                error.get_or_insert(e);
+            }
+        }
+
+        for settled in self.tracker.prune_settled() {
+            if let Some(store) = &self.store {
+                if let Err(e) = store.remove(&settled.hash) {
+                    error.get_or_insert(e);
+                }
+            }
+        }
+        for event in &events {
+            for callback in &self.callbacks {
+                callback(event);
+            }
+        }
+        (events, error)
+    }
+
+    /// Stream state changes, polling every interval
+    ///
+    /// A failed poll yields the changes it observed, then an error item, and
+    /// the stream keeps polling. The stream ends once no transaction is in
+    /// flight.
+    pub fn events(&mut self) -> impl Stream<Item = NetworkResult<MonitorEvent>> + '_ {
+        let state = (self, VecDeque::new(), false);
+        stream::unfold(state, |(monitor, mut queue, mut polled)| async move {
+            loop {
+                if let Some(item) = queue.pop_front() {
+                    return Some((item, (monitor, queue, polled)));
+                }
+                monitor.tracker.in_flight().next()?;
+                if polled {
+                    rt::sleep(monitor.interval).await;
+                }
+                polled = true;
+                let (events, error) = monitor.poll_events().await;
+                queue.extend(events.into_iter().map(Ok));
+                queue.extend(error.map(Err));
+            }
+        })
+    }
+
+    fn save(&self, hash: &str) -> NetworkResult<()> {
+        match (&self.store, self.tracker.get(hash)) {
+            (Some(store), Some(entry)) => store.save(entry),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl fmt::Debug for TransactionMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TransactionMonitor")
+            .field("tracker", &self.tracker)
+            .field("store", &self.store.is_some())
+            .field("callbacks", &self.callbacks.len())
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
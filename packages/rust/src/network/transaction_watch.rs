@@ -0,0 +1,166 @@
+//! Streams of newly confirmed transactions for one address
+//!
+//! [`BlockExplorerClient::watch_transactions`] and
+//! [`Account::confirmed_transactions`] poll the block explorer and yield each
+//! transaction sent or received by an address once it appears in a snapshot,
+//! for notification and reconciliation services that would otherwise write
+//! their own polling loop.
+//!
+//! Only transactions confirmed after the stream starts are yielded, oldest
+//! first. The explorer lists an address's history newest first, so every poll
+//! reads pages until it reaches a transaction it has already seen.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use futures_util::StreamExt;
+//! use std::time::Duration;
+//!
+//! let transactions = account.confirmed_transactions(Duration::from_secs(10))?;
+//! futures_util::pin_mut!(transactions);
+//!
+//! while let Some(tx) = transactions.next().await {
+//!     let tx = tx?;
+//!     println!("{} -> {}: {} at snapshot {:?}", tx.source, tx.destination, tx.amount, tx.snapshot_ordinal);
+//! }
+//! ```
+
+use futures_util::stream::{self, Stream, StreamExt};
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+
+use super::account::Account;
+use super::block_explorer::{BlockExplorerClient, ExplorerTransaction, PageRequest};
+use super::rt;
+use super::types::NetworkResult;
+
+/// Transactions fetched per explorer page while catching up
+const PAGE_SIZE: u32 = 50;
+
+/// Newest snapshot seen so far and the transactions already seen in it
+struct Cursor {
+    ordinal: i64,
+    hashes: HashSet<String>,
+}
+
+impl Cursor {
+    fn is_new(&self, transaction: &ExplorerTransaction) -> bool {
+        match transaction.snapshot_ordinal {
+            Some(ordinal) => {
+                ordinal > self.ordinal
+                    || (ordinal == self.ordinal && !self.hashes.contains(&transaction.hash))
+            }
+            None => false,
+        }
+    }
+
+    fn advance(&mut self, transaction: &ExplorerTransaction) {
+        let Some(ordinal) = transaction.snapshot_ordinal else {
+            return;
+        };
+        if ordinal > self.ordinal {
+            self.ordinal = ordinal;
+            self.hashes.clear();
+        }
+        if ordinal == self.ordinal {
+            self.hashes.insert(transaction.hash.clone());
+        }
+    }
+}
+
+impl BlockExplorerClient {
+    /// Stream the transactions of an address as they are confirmed
+    ///
+    /// The first poll only records the latest confirmed transactions; later
+    /// polls yield every transaction confirmed since, oldest first.
+    /// Transactions without a snapshot ordinal are skipped. A failed poll
+    /// yields an error item and the stream keeps polling. The stream never
+    /// ends on its own; drop it to stop.
+    pub fn watch_transactions(
+        &self,
+        address: &str,
+        poll_interval: Duration,
+    ) -> impl Stream<Item = NetworkResult<ExplorerTransaction>> + '_ {
+        let state = (address.to_string(), None::<Cursor>, VecDeque::new());
+        stream::unfold(state, move |(address, mut cursor, mut queue)| async move {
+            loop {
+                if let Some(transaction) = queue.pop_front() {
+                    return Some((Ok(transaction), (address, cursor, queue)));
+                }
+                let result = match &mut cursor {
+                    None => self.baseline(&address).await.map(|start| {
+                        cursor = Some(start);
+                    }),
+                    Some(cursor) => {
+                        rt::sleep(poll_interval).await;
+                        self.confirmed_since(&address, cursor)
+                            .await
+                            .map(|new| queue.extend(new))
+                    }
+                };
+                if let Err(e) = result {
+                    return Some((Err(e), (address, cursor, queue)));
+                }
+            }
+        })
+    }
+
+    /// Cursor at the newest confirmed transactions of an address
+    async fn baseline(&self, address: &str) -> NetworkResult<Cursor> {
+        let page = PageRequest {
+            limit: Some(PAGE_SIZE),
+            next: None,
+        };
+        let mut cursor = Cursor {
+            ordinal: i64::MIN,
+            hashes: HashSet::new(),
+        };
+        for transaction in &self.get_transactions_by_address(address, &page).await?.data {
+            cursor.advance(transaction);
+        }
+        Ok(cursor)
+    }
+
+    /// Transactions confirmed after `cursor`, oldest first, advancing it
+    async fn confirmed_since(
+        &self,
+        address: &str,
+        cursor: &mut Cursor,
+    ) -> NetworkResult<Vec<ExplorerTransaction>> {
+        let history = self.transactions_by_address(address, PAGE_SIZE);
+        futures_util::pin_mut!(history);
+
+        let mut new = Vec::new();
+        while let Some(transaction) = history.next().await {
+            let transaction = transaction?;
+            if cursor.is_new(&transaction) {
+                new.push(transaction);
+            } else if transaction.snapshot_ordinal.is_some() {
+                break;
+            }
+        }
+        new.reverse();
+        for transaction in &new {
+            cursor.advance(transaction);
+        }
+        Ok(new)
+    }
+}
+
+impl Account {
+    /// Stream the account's transactions as they are confirmed in snapshots
+    ///
+    /// See [`BlockExplorerClient::watch_transactions`].
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ConfigError` if the account has no block explorer client
+    pub fn confirmed_transactions(
+        &self,
+        poll_interval: Duration,
+    ) -> NetworkResult<impl Stream<Item = NetworkResult<ExplorerTransaction>> + '_> {
+        Ok(self
+            .block_explorer()?
+            .watch_transactions(&self.address(), poll_interval))
+    }
+}
@@ -0,0 +1,320 @@
+//! High-level currency transfer operations
+//!
+//! Combines last-reference lookup, transaction creation, signing, and
+//! submission into single calls on a Currency L1 [`MetagraphClient`].
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::{MetagraphClient, LayerType};
+//! use constellation_sdk::TransferParams;
+//!
+//! let cl1 = MetagraphClient::new("http://localhost:9300", LayerType::CL1)?;
+//! let hash = cl1
+//!     .transfer(
+//!         TransferParams {
+//!             destination: "DAG...".to_string(),
+//!             amount: 10.0,
+//!             fee: 0.0,
+//!         },
+//!         &private_key,
+//!     )
+//!     .await?;
+//! ```
+
+use super::confirmation::{settle, Settlement};
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::types::{NetworkResult, TransactionStatus};
+use crate::currency_transaction::{
+    build_currency_transaction, create_currency_transaction, hash_currency_transaction,
+    token_to_units,
+};
+use crate::currency_types::{CurrencyTransaction, TransactionReference, TransferParams};
+use crate::types::SdkError;
+use crate::wallet::key_pair_from_private_key;
+
+/// Result of a single transfer within a batch payout
+#[derive(Debug)]
+pub struct TransferOutcome {
+    /// Destination address of the transfer
+    pub destination: String,
+    /// Submitted transaction hash, or the error that prevented submission
+    pub result: NetworkResult<String>,
+}
+
+/// Result of sweeping an address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SweepOutcome {
+    /// Submitted transaction hash
+    pub hash: String,
+    /// Amount moved to the destination in smallest units (balance minus fee)
+    pub amount: i64,
+}
+
+/// Result of checking and possibly rebroadcasting a transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResubmitOutcome {
+    /// Still in the pending pool; nothing was sent
+    StillPending(TransactionStatus),
+    /// The source's last reference points at the transaction; nothing was sent
+    Confirmed,
+    /// Dropped with its parent ordinal unused; rebroadcast unchanged
+    Resubmitted(String),
+    /// Parent ordinal consumed by a different transaction; nothing was sent
+    Conflicted,
+    /// The source's chain advanced past the transaction's ordinal by more than
+    /// one step, so inclusion cannot be decided from the last reference alone;
+    /// nothing was sent
+    Ambiguous,
+    /// Parent ordinal consumed by a different transaction; the same transfer
+    /// was rebuilt on the fresh last reference and submitted
+    Rebuilt {
+        /// Submitted transaction hash
+        hash: String,
+        /// The rebuilt transaction
+        transaction: CurrencyTransaction,
+    },
+}
+
+impl MetagraphClient {
+    /// Build, sign, and submit a token transfer in one call
+    ///
+    /// Fetches the sender's last reference, creates and signs the
+    /// transaction on top of it, submits it, and returns the transaction hash.
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer, if the private key
+    /// or transfer parameters are invalid, or if a network call fails
+    #[cfg_attr(feature = "tracing", tracing::instrument(
+        level = "info",
+        skip_all,
+        fields(destination = %params.destination, amount = params.amount, tx_hash)
+    ))]
+    pub async fn transfer(
+        &self,
+        params: TransferParams,
+        private_key: &str,
+    ) -> NetworkResult<String> {
+        self.assert_layer(&[LayerType::CL1], "transfer")?;
+
+        let source = key_pair_from_private_key(private_key)?.address;
+        let last_ref = self.get_last_reference(&source).await?;
+        let (hash, _) = self
+            .submit_on_reference(params, private_key, &last_ref)
+            .await?;
+        record_span!("tx_hash", hash.as_str());
+
+        Ok(hash)
+    }
+
+    /// Send several transfers from one sender, chaining parent references locally
+    ///
+    /// The sender's last reference is fetched once. Transfers are then built
+    /// and submitted sequentially, each on top of the previous accepted
+    /// submission. A transfer that fails to build or submit does not advance
+    /// the chain, so the following transfer reuses its parent reference.
+    ///
+    /// Returns one [`TransferOutcome`] per input, in order.
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer, if the private key
+    /// is invalid, or if the initial last-reference lookup fails. Failures of
+    /// individual transfers are reported in their outcomes instead.
+    pub async fn transfer_many(
+        &self,
+        transfers: Vec<TransferParams>,
+        private_key: &str,
+    ) -> NetworkResult<Vec<TransferOutcome>> {
+        self.assert_layer(&[LayerType::CL1], "transfer_many")?;
+
+        let source = key_pair_from_private_key(private_key)?.address;
+        let mut current_ref = self.get_last_reference(&source).await?;
+        let mut outcomes = Vec::with_capacity(transfers.len());
+
+        for params in transfers {
+            let destination = params.destination.clone();
+            let result = self
+                .submit_on_reference(params, private_key, &current_ref)
+                .await;
+
+            if let Ok((_, next_ref)) = &result {
+                current_ref = next_ref.clone();
+            }
+
+            outcomes.push(TransferOutcome {
+                destination,
+                result: result.map(|(hash, _)| hash),
+            });
+        }
+
+        Ok(outcomes)
+    }
+
+    /// Build, sign, and submit a transfer on a given parent reference,
+    /// returning the node hash and the reference the next transfer should use
+    async fn submit_on_reference(
+        &self,
+        params: TransferParams,
+        private_key: &str,
+        parent: &TransactionReference,
+    ) -> NetworkResult<(String, TransactionReference)> {
+        let transaction = create_currency_transaction(params, private_key, parent.clone())?;
+        let response = self.post_transaction(&transaction).await?;
+        let next_ref = TransactionReference {
+            hash: hash_currency_transaction(&transaction).value,
+            ordinal: parent.ordinal + 1,
+        };
+        Ok((response.hash, next_ref))
+    }
+
+    /// Drain the sender's full balance to a destination
+    ///
+    /// Reads the sender's balance from a Metagraph L0 client, subtracts the
+    /// fee, and submits a transfer of the remainder through this client. The
+    /// amount is computed in smallest units, so no dust is left behind by
+    /// floating-point rounding.
+    ///
+    /// Available on: CL1 (with an ML0 client for the balance lookup)
+    ///
+    /// # Arguments
+    ///
+    /// * `l0` - Metagraph L0 client used to read the balance
+    /// * `destination` - Address receiving the funds
+    /// * `fee` - Fee in token units
+    /// * `private_key` - Private key of the address being swept
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either client is on the wrong layer, if the
+    /// balance does not cover the fee plus the minimum transfer amount, or if
+    /// a network call fails
+    pub async fn sweep(
+        &self,
+        l0: &MetagraphClient,
+        destination: &str,
+        fee: f64,
+        private_key: &str,
+    ) -> NetworkResult<SweepOutcome> {
+        self.assert_layer(&[LayerType::CL1], "sweep")?;
+
+        let source = key_pair_from_private_key(private_key)?.address;
+        let balance = l0.get_balance(&source).await?;
+        let fee = token_to_units(fee);
+        let amount = balance.amount - fee;
+        if amount < 1 {
+            return Err(SdkError::InvalidAmount(format!(
+                "Balance of {} does not cover fee of {}",
+                balance.amount, fee
+            ))
+            .into());
+        }
+
+        let last_ref = self.get_last_reference(&source).await?;
+        let transaction = build_currency_transaction(
+            destination.to_string(),
+            amount,
+            fee,
+            private_key,
+            last_ref,
+        )?;
+        let hash = self.post_transaction(&transaction).await?.hash;
+
+        Ok(SweepOutcome { hash, amount })
+    }
+
+    /// Rebroadcast a transaction if it was dropped
+    ///
+    /// A transaction is only resent when it is no longer pending and the
+    /// source's last reference still sits at its parent ordinal, so the node
+    /// cannot have accepted it. Every other case is reported without sending.
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer or if a network
+    /// call fails
+    pub async fn resubmit(
+        &self,
+        transaction: &CurrencyTransaction,
+    ) -> NetworkResult<ResubmitOutcome> {
+        self.assert_layer(&[LayerType::CL1], "resubmit")?;
+        self.check_and_resubmit(transaction).await
+    }
+
+    /// Rebroadcast a dropped transaction, rebuilding it if its ordinal was taken
+    ///
+    /// Behaves like [`resubmit`](Self::resubmit), except that when the parent
+    /// ordinal was consumed by a different transaction the same transfer
+    /// (destination, amount, fee) is rebuilt on the fresh last reference,
+    /// signed with `private_key`, and submitted.
+    ///
+    /// Available on: CL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer, if `private_key`
+    /// does not belong to the transaction source, or if a network call fails
+    pub async fn resubmit_or_rebuild(
+        &self,
+        transaction: &CurrencyTransaction,
+        private_key: &str,
+    ) -> NetworkResult<ResubmitOutcome> {
+        self.assert_layer(&[LayerType::CL1], "resubmit_or_rebuild")?;
+
+        let source = key_pair_from_private_key(private_key)?.address;
+        if source != transaction.value.source {
+            return Err(SdkError::InvalidPrivateKey(
+                "Private key does not match transaction source".to_string(),
+            )
+            .into());
+        }
+
+        match self.check_and_resubmit(transaction).await? {
+            ResubmitOutcome::Conflicted => {
+                let last_ref = self.get_last_reference(&source).await?;
+                let rebuilt = build_currency_transaction(
+                    transaction.value.destination.clone(),
+                    transaction.value.amount,
+                    transaction.value.fee,
+                    private_key,
+                    last_ref,
+                )?;
+                let hash = self.post_transaction(&rebuilt).await?.hash;
+                Ok(ResubmitOutcome::Rebuilt {
+                    hash,
+                    transaction: rebuilt,
+                })
+            }
+            outcome => Ok(outcome),
+        }
+    }
+
+    async fn check_and_resubmit(
+        &self,
+        transaction: &CurrencyTransaction,
+    ) -> NetworkResult<ResubmitOutcome> {
+        let hash = hash_currency_transaction(transaction).value;
+        if let Some(pending) = self.get_pending_transaction(&hash).await? {
+            return Ok(ResubmitOutcome::StillPending(pending.status));
+        }
+
+        let last_ref = self.get_last_reference(&transaction.value.source).await?;
+
+        match settle(&hash, &transaction.value.parent, &last_ref) {
+            Settlement::Confirmed => Ok(ResubmitOutcome::Confirmed),
+            Settlement::Dropped => {
+                let hash = self.post_transaction(transaction).await?.hash;
+                Ok(ResubmitOutcome::Resubmitted(hash))
+            }
+            Settlement::Conflicted => Ok(ResubmitOutcome::Conflicted),
+            Settlement::Superseded => Ok(ResubmitOutcome::Ambiguous),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Submitting [`DataUpdate`] types
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::data_update::DataUpdate;
+//! use constellation_sdk::network::{LayerType, MetagraphClient};
+//!
+//! #[derive(Clone, serde::Serialize)]
+//! struct CastVote {
+//!     poll: String,
+//!     option: u32,
+//! }
+//!
+//! impl DataUpdate for CastVote {
+//!     const ENDPOINT: &'static str = "/data-application/votes";
+//! }
+//!
+//! let dl1 = MetagraphClient::new("http://localhost:9400", LayerType::DL1)?;
+//! let receipt = dl1.submit(&signer, &CastVote { poll: "p1".into(), option: 2 }).await?;
+//! ```
+
+use super::metagraph_client::MetagraphClient;
+use super::receipt::Receipt;
+use super::types::{NetworkResult, RequestOptions};
+use crate::data_update::{sign_update, update_hash, DataUpdate};
+use crate::signer::Signer;
+
+impl MetagraphClient {
+    /// Sign a data update and post it to its type's
+    /// [`ENDPOINT`](DataUpdate::ENDPOINT)
+    ///
+    /// The update is signed over its
+    /// [`serialize_update`](DataUpdate::serialize_update) bytes, and the
+    /// receipt's local hash is the hash of those bytes.
+    ///
+    /// Available on: DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if called on an unsupported layer, or if signing or
+    /// the request fails
+    pub async fn submit<S, U>(&self, signer: &S, update: &U) -> NetworkResult<Receipt>
+    where
+        S: Signer + ?Sized,
+        U: DataUpdate + Clone,
+    {
+        let local_hash = update_hash(update)?;
+        let signed = sign_update(signer, update)?;
+        let response = self
+            .post_data_to(
+                U::ENDPOINT,
+                &signed,
+                || Ok(local_hash.clone()),
+                &RequestOptions::default(),
+            )
+            .await?;
+        Ok(Receipt::for_data_update_hash(
+            local_hash.value,
+            response.hash,
+        ))
+    }
+}
@@ -1,36 +1,87 @@
 //! Network types for L1 client operations
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fmt;
 use thiserror::Error;
 
+use super::node_error::NodeError;
+use super::retry::RetryPolicy;
+use super::spend_policy::PolicyViolation;
+use crate::currency_transaction::units_to_token;
 use crate::currency_types::CurrencyTransaction;
+use crate::types::SdkError;
 
 /// Request options for individual requests
+///
+/// Passed to the `*_with_options` client methods; unset fields fall back to
+/// the client's configuration.
 #[derive(Debug, Clone, Default)]
 pub struct RequestOptions {
     /// Request timeout in seconds
     pub timeout: Option<u64>,
+    /// Extra headers sent with the request
+    pub headers: HashMap<String, String>,
+    /// Retry policy overriding the client's policy
+    pub retry: Option<RetryPolicy>,
+    /// Query parameters appended to the URL
+    pub query: Vec<(String, String)>,
+    /// Sent as an `Idempotency-Key` header on every attempt of the request
+    pub idempotency_key: Option<String>,
 }
 
 /// Transaction status in the network
+///
+/// Statuses added by newer node versions deserialize as
+/// [`Unknown`](Self::Unknown) instead of failing.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum TransactionStatus {
+    /// Waiting to be included in a block
     Waiting,
+    /// Included in a block that is not yet in a snapshot
     InProgress,
+    /// Accepted in a snapshot
     Accepted,
+    /// A status this SDK does not know
+    Unknown(String),
 }
 
-impl fmt::Display for TransactionStatus {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl TransactionStatus {
+    /// Name of the status as reported by nodes
+    pub fn as_str(&self) -> &str {
         match self {
-            TransactionStatus::Waiting => write!(f, "Waiting"),
-            TransactionStatus::InProgress => write!(f, "InProgress"),
-            TransactionStatus::Accepted => write!(f, "Accepted"),
+            TransactionStatus::Waiting => "Waiting",
+            TransactionStatus::InProgress => "InProgress",
+            TransactionStatus::Accepted => "Accepted",
+            TransactionStatus::Unknown(status) => status,
+        }
+    }
+}
+
+impl From<String> for TransactionStatus {
+    fn from(status: String) -> Self {
+        match status.as_str() {
+            "Waiting" => TransactionStatus::Waiting,
+            "InProgress" => TransactionStatus::InProgress,
+            "Accepted" => TransactionStatus::Accepted,
+            _ => TransactionStatus::Unknown(status),
         }
     }
 }
 
+impl From<TransactionStatus> for String {
+    fn from(status: TransactionStatus) -> Self {
+        status.as_str().to_string()
+    }
+}
+
+impl fmt::Display for TransactionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Pending transaction response from L1
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PendingTransaction {
@@ -40,6 +91,9 @@ pub struct PendingTransaction {
     pub status: TransactionStatus,
     /// The transaction
     pub transaction: CurrencyTransaction,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Response from posting a transaction
@@ -47,15 +101,21 @@ pub struct PendingTransaction {
 pub struct PostTransactionResponse {
     /// Transaction hash
     pub hash: String,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Response from estimating data transaction fee
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EstimateFeeResponse {
     /// Estimated fee in smallest units
     pub fee: i64,
     /// Fee destination address
     pub address: String,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Response from posting data
@@ -63,6 +123,206 @@ pub struct EstimateFeeResponse {
 pub struct PostDataResponse {
     /// Data hash
     pub hash: String,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+/// Address balance at a snapshot ordinal
+///
+/// Nodes only serve the balance as of their latest snapshot; `ordinal` tells
+/// which one. Neither L0 API exposes historical balances. Unlike the other
+/// responses it keeps no `extra` map, so it stays a plain record for the
+/// mobile bindings; unknown fields are ignored.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct Balance {
+    /// Balance in smallest units (1e-8)
+    #[serde(rename = "balance")]
+    pub amount: i64,
+    /// Snapshot ordinal the balance was read at
+    pub ordinal: i64,
+}
+
+impl Balance {
+    /// Balance in tokens
+    pub fn to_tokens(&self) -> f64 {
+        units_to_token(self.amount)
+    }
+}
+
+/// Total token supply at a snapshot ordinal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TotalSupply {
+    /// Total supply in smallest units (1e-8)
+    pub total: i64,
+    /// Snapshot ordinal the supply was read at
+    pub ordinal: i64,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl TotalSupply {
+    /// Total supply in tokens
+    pub fn to_tokens(&self) -> f64 {
+        units_to_token(self.total)
+    }
+}
+
+/// Circulating token supply at a snapshot ordinal
+///
+/// The tokens held by addresses, as opposed to the total minted amount.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CirculatingSupply {
+    /// Circulating supply in smallest units (1e-8)
+    #[serde(rename = "total")]
+    pub circulating: i64,
+    /// Snapshot ordinal the supply was read at
+    pub ordinal: i64,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl CirculatingSupply {
+    /// Circulating supply in tokens
+    pub fn to_tokens(&self) -> f64 {
+        units_to_token(self.circulating)
+    }
+}
+
+/// Summary of a global or currency snapshot
+///
+/// L0 nodes serve whole snapshots, so headers are built from the snapshot
+/// body. `hash` and `timestamp` are only filled in when the body carries them,
+/// as block explorer responses do; L0 nodes omit both.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotHeader {
+    /// Snapshot ordinal
+    pub ordinal: i64,
+    /// Hash of this snapshot, if known
+    pub hash: Option<String>,
+    /// Hash of the previous snapshot
+    pub last_snapshot_hash: String,
+    /// Snapshot height
+    pub height: i64,
+    /// Snapshot sub-height
+    pub sub_height: i64,
+    /// Creation time as reported by the source, if known
+    pub timestamp: Option<String>,
+    /// Number of blocks in the snapshot
+    pub block_count: usize,
+}
+
+impl SnapshotHeader {
+    fn from_parts(
+        ordinal: i64,
+        last_snapshot_hash: &str,
+        height: i64,
+        sub_height: i64,
+        block_count: usize,
+        extra: &HashMap<String, serde_json::Value>,
+    ) -> Self {
+        let text = |key: &str| extra.get(key).and_then(|v| v.as_str()).map(String::from);
+        Self {
+            ordinal,
+            hash: text("hash"),
+            last_snapshot_hash: last_snapshot_hash.to_string(),
+            height,
+            sub_height,
+            timestamp: text("timestamp"),
+            block_count,
+        }
+    }
+}
+
+/// Global (incremental) snapshot produced by the Global L0
+///
+/// Only the commonly used fields are typed; the rest of the snapshot body is
+/// kept in `extra`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GlobalSnapshot {
+    /// Snapshot ordinal
+    pub ordinal: i64,
+    /// Snapshot height
+    #[serde(default)]
+    pub height: i64,
+    /// Snapshot sub-height
+    #[serde(default)]
+    pub sub_height: i64,
+    /// Hash of the previous snapshot
+    pub last_snapshot_hash: String,
+    /// DAG blocks included in the snapshot
+    #[serde(default)]
+    pub blocks: Vec<serde_json::Value>,
+    /// Metagraph state channel snapshots keyed by metagraph address
+    #[serde(default)]
+    pub state_channel_snapshots: HashMap<String, serde_json::Value>,
+    /// Epoch progress counter
+    #[serde(default)]
+    pub epoch_progress: i64,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl GlobalSnapshot {
+    /// Summary of this snapshot
+    pub fn header(&self) -> SnapshotHeader {
+        SnapshotHeader::from_parts(
+            self.ordinal,
+            &self.last_snapshot_hash,
+            self.height,
+            self.sub_height,
+            self.blocks.len(),
+            &self.extra,
+        )
+    }
+}
+
+/// Currency (incremental) snapshot produced by a metagraph L0
+///
+/// Only the commonly used fields are typed; the rest of the snapshot body is
+/// kept in `extra`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CurrencySnapshot {
+    /// Snapshot ordinal
+    pub ordinal: i64,
+    /// Snapshot height
+    #[serde(default)]
+    pub height: i64,
+    /// Snapshot sub-height
+    #[serde(default)]
+    pub sub_height: i64,
+    /// Hash of the previous snapshot
+    pub last_snapshot_hash: String,
+    /// Currency blocks included in the snapshot
+    #[serde(default)]
+    pub blocks: Vec<serde_json::Value>,
+    /// Data application part of the snapshot, if the metagraph has one
+    #[serde(default)]
+    pub data_application: Option<serde_json::Value>,
+    /// Additional fields
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
+}
+
+impl CurrencySnapshot {
+    /// Summary of this snapshot
+    pub fn header(&self) -> SnapshotHeader {
+        SnapshotHeader::from_parts(
+            self.ordinal,
+            &self.last_snapshot_hash,
+            self.height,
+            self.sub_height,
+            self.blocks.len(),
+            &self.extra,
+        )
+    }
 }
 
 /// Network error with status code and response details
@@ -78,11 +338,52 @@ pub enum NetworkError {
     #[error("Request timeout")]
     Timeout,
 
+    #[error("Circuit open for {0}")]
+    CircuitOpen(String),
+
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    #[error("Invalid submission: {}", .0.join("; "))]
+    InvalidSubmission(Vec<String>),
+
+    #[error("Node error (HTTP {status_code}): {error}")]
+    Node {
+        status_code: u16,
+        error: NodeError,
+        response: String,
+    },
+
+    #[error("Rejected by node: {}", .reasons.join("; "))]
+    Rejected {
+        status_code: u16,
+        reasons: Vec<String>,
+    },
+
+    #[error("Response larger than {limit} bytes")]
+    ResponseTooLarge { limit: usize },
+
+    #[error("Broadcast acknowledged by {acknowledged} of {required} required nodes: {}", .errors.join("; "))]
+    QuorumNotReached {
+        acknowledged: usize,
+        required: usize,
+        errors: Vec<String>,
+    },
+
+    #[error("Spend policy violation: {0}")]
+    PolicyViolation(PolicyViolation),
+
+    #[error("Untrusted snapshot: {0}")]
+    UntrustedSnapshot(String),
+
+    #[error("Store error: {0}")]
+    StoreError(String),
+
+    #[error("SDK error: {0}")]
+    SdkError(#[from] SdkError),
 }
 
 impl NetworkError {
@@ -101,9 +402,70 @@ impl NetworkError {
     pub fn status_code(&self) -> Option<u16> {
         match self {
             NetworkError::HttpError { status_code, .. } => *status_code,
+            NetworkError::Node { status_code, .. } | NetworkError::Rejected { status_code, .. } => {
+                Some(*status_code)
+            }
             _ => None,
         }
     }
+
+    /// What kind of failure this is, for retry and alerting decisions
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            NetworkError::Timeout => ErrorKind::Timeout,
+            NetworkError::HttpError {
+                status_code: None, ..
+            } => ErrorKind::Connection,
+            NetworkError::HttpError {
+                status_code: Some(status),
+                ..
+            }
+            | NetworkError::Node {
+                status_code: status,
+                ..
+            }
+            | NetworkError::Rejected {
+                status_code: status,
+                ..
+            } => match status {
+                429 => ErrorKind::RateLimited,
+                400..=499 => ErrorKind::ClientError,
+                500..=599 => ErrorKind::ServerError,
+                _ => ErrorKind::Other,
+            },
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// Whether sending the same request again may succeed
+    ///
+    /// Timeouts, connection failures, 429, and the 502/503/504 responses of
+    /// an overloaded or restarting node are retryable. Other server errors,
+    /// client errors, and local failures are not.
+    pub fn is_retryable(&self) -> bool {
+        match self.kind() {
+            ErrorKind::Timeout | ErrorKind::Connection | ErrorKind::RateLimited => true,
+            ErrorKind::ServerError => matches!(self.status_code(), Some(502..=504)),
+            ErrorKind::ClientError | ErrorKind::Other => false,
+        }
+    }
+}
+
+/// Classification of a [`NetworkError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The request timed out
+    Timeout,
+    /// The node could not be reached or the connection dropped
+    Connection,
+    /// The node answered 429 Too Many Requests
+    RateLimited,
+    /// The node answered with another 4xx status
+    ClientError,
+    /// The node answered with a 5xx status
+    ServerError,
+    /// A local failure: configuration, serialization, signing, or storage
+    Other,
 }
 
 /// Result type for network operations
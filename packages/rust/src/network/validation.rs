@@ -0,0 +1,126 @@
+//! Dry-run validation of data updates
+//!
+//! [`MetagraphClient::validate_data`] asks a Data L1 node whether it would
+//! accept an update without submitting it, so applications can pre-flight
+//! updates and show the node's reason for rejecting one before paying a fee.
+//!
+//! Metagraphs that expose a validation-only route at `/data/validate` are
+//! asked there. Where that route does not exist, `/data/estimate-fee` is used
+//! as a proxy, since the node runs the same checks before pricing an update.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use constellation_sdk::network::NetworkError;
+//!
+//! match dl1.validate_data(&signed_update).await {
+//!     Ok(()) => dl1.post_data(&signed_update).await?,
+//!     Err(NetworkError::Rejected { reasons, .. }) => {
+//!         eprintln!("node would reject the update: {}", reasons.join("; "));
+//!     }
+//!     Err(e) => return Err(e),
+//! }
+//! ```
+
+use serde::Serialize;
+
+use super::metagraph_client::{LayerType, MetagraphClient};
+use super::node_error;
+use super::types::{NetworkError, NetworkResult, RequestOptions};
+use crate::types::Signed;
+use crate::verify::verify_with_policy;
+
+/// Validation-only route of metagraphs that provide one
+const VALIDATE_PATH: &str = "/data/validate";
+
+impl MetagraphClient {
+    /// Check whether the node would accept a data update, without submitting it
+    ///
+    /// Verifies the proofs locally first, under the client's
+    /// [`signature_policy`](Self::signature_policy), then asks the node through
+    /// `/data/validate`, falling back to `/data/estimate-fee` if the node has
+    /// no validation route.
+    ///
+    /// Available on: DL1
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidSubmission` if a proof does not verify, `Rejected` with
+    /// the node's reasons if the node refuses the update, or an error if
+    /// called on an unsupported layer or the request fails
+    pub async fn validate_data<T: Serialize>(&self, data: &Signed<T>) -> NetworkResult<()> {
+        self.validate_data_with_options(data, &RequestOptions::default())
+            .await
+    }
+
+    /// [`validate_data`](Self::validate_data) with per-request options
+    ///
+    /// Available on: DL1
+    ///
+    /// # Errors
+    ///
+    /// As [`validate_data`](Self::validate_data)
+    pub async fn validate_data_with_options<T: Serialize>(
+        &self,
+        data: &Signed<T>,
+        options: &RequestOptions,
+    ) -> NetworkResult<()> {
+        self.assert_layer(&[LayerType::DL1], "validate_data")?;
+        let result = verify_with_policy(data, true, self.signature_policy());
+        if !result.is_valid {
+            let mut problems: Vec<String> = result
+                .invalid_proofs
+                .iter()
+                .map(|proof| format!("invalid signature from {}", proof.id))
+                .collect();
+            if data.proofs.is_empty() {
+                problems.push("no proofs".to_string());
+            }
+            return Err(NetworkError::InvalidSubmission(problems));
+        }
+
+        let client = self.http_client();
+        let response = match client.post_text(VALIDATE_PATH, data, options).await {
+            Err(e) if matches!(e.status_code(), Some(404 | 405 | 501)) => {
+                client.post_text("/data/estimate-fee", data, options).await
+            }
+            response => response,
+        };
+        response.map(drop).map_err(into_rejection)
+    }
+}
+
+/// Turn a client error response into `Rejected`, keeping other errors
+fn into_rejection(error: NetworkError) -> NetworkError {
+    match error {
+        NetworkError::Node {
+            status_code: status_code @ (400 | 409 | 422),
+            error,
+            response,
+        } => NetworkError::Rejected {
+            status_code,
+            reasons: match node_error::reasons(&response) {
+                reasons if reasons.is_empty() => vec![error.to_string()],
+                reasons => reasons,
+            },
+        },
+        NetworkError::HttpError {
+            message,
+            status_code: Some(status_code @ (400 | 409 | 422)),
+            response,
+        } => {
+            let mut reasons = response
+                .as_deref()
+                .map(node_error::reasons)
+                .unwrap_or_default();
+            if reasons.is_empty() {
+                reasons.push(message);
+            }
+            NetworkError::Rejected {
+                status_code,
+                reasons,
+            }
+        }
+        error => error,
+    }
+}
@@ -0,0 +1,204 @@
+//! Node.js bindings through napi-rs
+//!
+//! With the `napi` feature the crate builds as a Node native addon exporting
+//! the signing, verification, and encoding functions, so Node services can
+//! swap the pure-JS crypto path for this crate and get identical output
+//! faster. JSON values are passed as plain JavaScript values, bytes as
+//! `Buffer`s, and failures are thrown as `Error`s carrying the
+//! [`SdkError`](crate::SdkError) message.
+//!
+//! Build the addon as a `cdylib` and give it the `.node` extension:
+//!
+//! ```bash
+//! cargo rustc --lib --release --features napi --crate-type cdylib
+//! cp target/release/libconstellation_sdk.so constellation_sdk.node   # .dylib on macOS
+//! ```
+//!
+//! # Example
+//!
+//! ```js
+//! const sdk = require("./constellation_sdk.node");
+//!
+//! const keyPair = sdk.generateKeyPair();
+//! const signed = sdk.createSignedObject({ id: "sensor-1" }, keyPair.privateKey, true);
+//! sdk.verify(signed, true); // true
+//! sdk.encodeDataUpdate({ id: "sensor-1" }); // Buffer
+//! ```
+
+use napi::bindgen_prelude::Buffer;
+use napi_derive::napi;
+use serde_json::Value;
+
+use crate::types::{SignatureProof, Signed};
+
+fn to_napi(e: impl std::fmt::Display) -> napi::Error {
+    napi::Error::from_reason(e.to_string())
+}
+
+/// A key pair as seen from JavaScript
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct JsKeyPair {
+    /// Private key in hex format (64 characters)
+    pub private_key: String,
+    /// Public key in hex format (uncompressed, with 04 prefix - 130 characters)
+    pub public_key: String,
+    /// DAG address derived from the public key
+    pub address: String,
+}
+
+impl From<crate::types::KeyPair> for JsKeyPair {
+    fn from(key_pair: crate::types::KeyPair) -> Self {
+        Self {
+            private_key: key_pair.private_key,
+            public_key: key_pair.public_key,
+            address: key_pair.address,
+        }
+    }
+}
+
+/// A signature proof as seen from JavaScript
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct JsSignatureProof {
+    /// Public key ID (uncompressed, without 04 prefix)
+    pub id: String,
+    /// DER signature in hex
+    pub signature: String,
+}
+
+impl From<SignatureProof> for JsSignatureProof {
+    fn from(proof: SignatureProof) -> Self {
+        Self {
+            id: proof.id,
+            signature: proof.signature,
+        }
+    }
+}
+
+/// Generate a random key pair
+#[napi]
+pub fn generate_key_pair() -> JsKeyPair {
+    crate::wallet::generate_key_pair().into()
+}
+
+/// Derive the key pair of a private key in hex
+#[napi]
+pub fn key_pair_from_private_key(private_key: String) -> napi::Result<JsKeyPair> {
+    Ok(crate::wallet::key_pair_from_private_key(&private_key)
+        .map_err(to_napi)?
+        .into())
+}
+
+/// DAG address of a public key in hex
+#[napi]
+pub fn get_address(public_key: String) -> napi::Result<String> {
+    crate::wallet::try_get_address(&public_key).map_err(to_napi)
+}
+
+/// Whether a string is a well-formed private key in hex
+#[napi]
+pub fn is_valid_private_key(private_key: String) -> bool {
+    crate::wallet::is_valid_private_key(&private_key)
+}
+
+/// RFC 8785 canonical JSON of a value
+#[napi]
+pub fn canonicalize(value: Value) -> napi::Result<String> {
+    crate::canonicalize::canonicalize(&value).map_err(to_napi)
+}
+
+/// Bytes a value is signed over, with the DataUpdate prefix if requested
+#[napi]
+pub fn to_bytes(value: Value, is_data_update: Option<bool>) -> napi::Result<Buffer> {
+    let bytes =
+        crate::binary::to_bytes(&value, is_data_update.unwrap_or(false)).map_err(to_napi)?;
+    Ok(bytes.into())
+}
+
+/// Encode a value as a DataUpdate
+#[napi]
+pub fn encode_data_update(value: Value) -> napi::Result<Buffer> {
+    let bytes = crate::binary::encode_data_update(&value).map_err(to_napi)?;
+    Ok(bytes.into())
+}
+
+/// Decode DataUpdate bytes back to a value
+#[napi]
+pub fn decode_data_update(bytes: Buffer) -> napi::Result<Value> {
+    crate::codec::decode_data_update(&bytes).map_err(to_napi)
+}
+
+/// SHA-256 of bytes, in hex
+#[napi]
+pub fn hash_bytes(bytes: Buffer) -> String {
+    crate::hash::hash_bytes(&bytes).value
+}
+
+/// Sign a value and return the proof
+#[napi]
+pub fn sign(
+    value: Value,
+    private_key: String,
+    is_data_update: Option<bool>,
+) -> napi::Result<JsSignatureProof> {
+    let proof = if is_data_update.unwrap_or(false) {
+        crate::sign::sign_data_update(&value, &private_key)
+    } else {
+        crate::sign::sign(&value, &private_key)
+    };
+    Ok(proof.map_err(to_napi)?.into())
+}
+
+/// Sign a SHA-256 hash in hex, returning the DER signature in hex
+#[napi]
+pub fn sign_hash(hash_hex: String, private_key: String) -> napi::Result<String> {
+    crate::sign::sign_hash(&hash_hex, &private_key).map_err(to_napi)
+}
+
+/// Sign a value and return the signed object
+#[napi]
+pub fn create_signed_object(
+    value: Value,
+    private_key: String,
+    is_data_update: Option<bool>,
+) -> napi::Result<Value> {
+    let signed = crate::signed_object::create_signed_object(
+        &value,
+        &private_key,
+        is_data_update.unwrap_or(false),
+    )
+    .map_err(to_napi)?;
+    serde_json::to_value(signed).map_err(to_napi)
+}
+
+/// Add a signature to a signed object
+#[napi]
+pub fn add_signature(
+    signed: Value,
+    private_key: String,
+    is_data_update: Option<bool>,
+) -> napi::Result<Value> {
+    let signed: Signed<Value> = serde_json::from_value(signed).map_err(to_napi)?;
+    let signed =
+        crate::signed_object::add_signature(signed, &private_key, is_data_update.unwrap_or(false))
+            .map_err(to_napi)?;
+    serde_json::to_value(signed).map_err(to_napi)
+}
+
+/// Verify every proof of a signed object
+#[napi]
+pub fn verify(signed: Value, is_data_update: Option<bool>) -> napi::Result<bool> {
+    let signed: Signed<Value> = serde_json::from_value(signed).map_err(to_napi)?;
+    Ok(crate::verify::verify(&signed, is_data_update.unwrap_or(false)).is_valid)
+}
+
+/// Verify a signature over a SHA-256 hash in hex
+#[napi]
+pub fn verify_hash(
+    hash_hex: String,
+    signature: String,
+    public_key_id: String,
+) -> napi::Result<bool> {
+    crate::verify::verify_hash(&hash_hex, &signature, &public_key_id).map_err(to_napi)
+}
@@ -0,0 +1,248 @@
+//! Offline signing round trip
+//!
+//! An [`UnsignedPayload`] is a JSON envelope carrying everything an
+//! air-gapped machine or hardware signer needs: the value, its canonical
+//! bytes, the hash to sign, and free-form metadata for display. The online
+//! side exports it, the offline side checks it and signs the hash, and
+//! [`import_signature`] attaches the returned proof to produce the signed
+//! object, after checking it against the payload.
+//!
+//! Both sides recompute the bytes and hash from the value, so an envelope
+//! altered in transit is rejected instead of signed or imported.
+//!
+//! # Example
+//!
+//! ```
+//! use constellation_sdk::offline::{import_signature, UnsignedPayload};
+//! use constellation_sdk::signer::LocalSigner;
+//! use constellation_sdk::verify::verify;
+//! use serde_json::{json, Value};
+//!
+//! // Online machine
+//! let payload = UnsignedPayload::data(&json!({"id": "test"}), true)
+//!     .unwrap()
+//!     .with_metadata("description", "Register sensor");
+//! let exported = payload.to_json().unwrap();
+//!
+//! // Offline machine
+//! let received = UnsignedPayload::from_json(&exported).unwrap();
+//! let proof = received.sign(&LocalSigner::generate()).unwrap();
+//!
+//! // Online machine again
+//! let signed = import_signature::<Value>(&payload, proof).unwrap();
+//! assert!(verify(&signed, true).is_valid);
+//! ```
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::binary::to_bytes;
+use crate::currency_transaction::transaction_bytes;
+use crate::currency_types::{CurrencyTransaction, CurrencyTransactionValue};
+use crate::hash::hash_bytes;
+use crate::signer::Signer;
+use crate::types::{Result, SdkError, SignatureProof, Signed};
+use crate::verify::verify_hash;
+
+/// Version of the [`UnsignedPayload`] format written by this SDK
+pub const OFFLINE_FORMAT_VERSION: u32 = 1;
+
+/// What an [`UnsignedPayload`] signs, which determines its bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PayloadKind {
+    /// A value signed as canonical JSON
+    Data,
+    /// A value signed as a DataUpdate (Constellation prefix)
+    DataUpdate,
+    /// A currency transaction signed in its Kryo encoding
+    CurrencyTransaction,
+}
+
+/// A value prepared for signing on another machine
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnsignedPayload {
+    /// Format version
+    pub version: u32,
+    /// How the value is encoded for signing
+    pub kind: PayloadKind,
+    /// The value to sign
+    pub value: serde_json::Value,
+    /// Bytes the hash is computed from, in hex
+    pub bytes: String,
+    /// SHA-256 of `bytes` in hex - the hash that gets signed
+    pub hash: String,
+    /// Free-form information for the signer, e.g. a description to display
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl UnsignedPayload {
+    /// Prepare a value for signing, as a DataUpdate or as regular data
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the value cannot be serialized
+    pub fn data<T: Serialize>(value: &T, is_data_update: bool) -> Result<Self> {
+        let kind = if is_data_update {
+            PayloadKind::DataUpdate
+        } else {
+            PayloadKind::Data
+        };
+        let value = serde_json::to_value(value).map_err(SdkError::from)?;
+        Self::new(kind, value)
+    }
+
+    /// Prepare a currency transaction for signing
+    ///
+    /// Existing proofs of `transaction` are not carried over.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be serialized
+    pub fn currency_transaction(transaction: &CurrencyTransaction) -> Result<Self> {
+        let value = serde_json::to_value(&transaction.value).map_err(SdkError::from)?;
+        Self::new(PayloadKind::CurrencyTransaction, value)
+    }
+
+    fn new(kind: PayloadKind, value: serde_json::Value) -> Result<Self> {
+        let bytes = encode(kind, &value)?;
+        Ok(Self {
+            version: OFFLINE_FORMAT_VERSION,
+            kind,
+            value,
+            hash: hash_bytes(&bytes).value,
+            bytes: hex::encode(bytes),
+            metadata: BTreeMap::new(),
+        })
+    }
+
+    /// Attach a metadata entry
+    pub fn with_metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Serialize the payload to JSON for export
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(SdkError::from)
+    }
+
+    /// Parse an exported payload and check its integrity
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SerializationError` if the JSON is malformed, or an
+    /// `InvalidPayload` error if [`check`](Self::check) fails
+    pub fn from_json(json: &str) -> Result<Self> {
+        let payload: Self = serde_json::from_str(json).map_err(SdkError::from)?;
+        payload.check()?;
+        Ok(payload)
+    }
+
+    /// Check that the version is supported and that `bytes` and `hash` match
+    /// the value
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error describing the first mismatch
+    pub fn check(&self) -> Result<()> {
+        if self.version != OFFLINE_FORMAT_VERSION {
+            return Err(SdkError::InvalidPayload(format!(
+                "unsupported version {}",
+                self.version
+            )));
+        }
+        let bytes = encode(self.kind, &self.value)?;
+        if !self.bytes.eq_ignore_ascii_case(&hex::encode(&bytes)) {
+            return Err(SdkError::InvalidPayload(
+                "bytes do not match the value".to_string(),
+            ));
+        }
+        if !self.hash.eq_ignore_ascii_case(&hash_bytes(&bytes).value) {
+            return Err(SdkError::InvalidPayload(
+                "hash does not match the bytes".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check the payload and sign its hash
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error if the check fails, or an error if
+    /// signing fails
+    pub fn sign<S: Signer + ?Sized>(&self, signer: &S) -> Result<SignatureProof> {
+        self.check()?;
+        signer.sign_hash_proof(&self.hash)
+    }
+}
+
+/// Attach an externally produced proof to a payload's value
+///
+/// `T` is the value type: `CurrencyTransactionValue` for currency
+/// transactions, so the result is a `CurrencyTransaction`.
+///
+/// # Errors
+///
+/// Returns an `InvalidPayload` error if the payload fails its check or the
+/// value does not decode as `T`, and an `InvalidSignature` error if the proof
+/// does not sign the payload's hash
+pub fn import_signature<T: DeserializeOwned>(
+    payload: &UnsignedPayload,
+    proof: SignatureProof,
+) -> Result<Signed<T>> {
+    import_signatures(payload, [proof])
+}
+
+/// Attach several externally produced proofs to a payload's value
+///
+/// # Errors
+///
+/// Same as [`import_signature`], for the first proof that fails
+pub fn import_signatures<T: DeserializeOwned>(
+    payload: &UnsignedPayload,
+    proofs: impl IntoIterator<Item = SignatureProof>,
+) -> Result<Signed<T>> {
+    payload.check()?;
+    let proofs: Vec<SignatureProof> = proofs.into_iter().collect();
+    for proof in &proofs {
+        if !verify_hash(&payload.hash, &proof.signature, &proof.id).unwrap_or(false) {
+            return Err(SdkError::InvalidSignature(format!(
+                "proof by {} does not sign {}",
+                proof.id, payload.hash
+            )));
+        }
+    }
+    let value = T::deserialize(&payload.value)
+        .map_err(|e| SdkError::InvalidPayload(format!("unexpected value: {e}")))?;
+    Ok(Signed { value, proofs })
+}
+
+fn encode(kind: PayloadKind, value: &serde_json::Value) -> Result<Vec<u8>> {
+    match kind {
+        PayloadKind::Data => to_bytes(value, false),
+        PayloadKind::DataUpdate => to_bytes(value, true),
+        PayloadKind::CurrencyTransaction => {
+            let value = CurrencyTransactionValue::deserialize(value)
+                .map_err(|e| SdkError::InvalidPayload(format!("invalid transaction: {e}")))?;
+            if value.salt.is_empty() || !value.salt.bytes().all(|b| b.is_ascii_digit()) {
+                return Err(SdkError::InvalidPayload(format!(
+                    "invalid salt {:?}",
+                    value.salt
+                )));
+            }
+            Ok(transaction_bytes(&Signed {
+                value,
+                proofs: vec![],
+            }))
+        }
+    }
+}
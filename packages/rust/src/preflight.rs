@@ -0,0 +1,192 @@
+//! Pre-flight validation of transfers and data updates
+//!
+//! [`create_currency_transaction`](crate::create_currency_transaction) and the
+//! signing functions stop at the first problem they meet. Wallet frontends
+//! want to show every problem with a form at once, so
+//! [`TransferParams::validate`] and [`validate_data_update`] collect them all
+//! into a [`ValidationReport`] without signing anything.
+//!
+//! # Example
+//!
+//! ```rust
+//! use constellation_sdk::{generate_key_pair, TransferParams};
+//!
+//! let sender = generate_key_pair();
+//! let params = TransferParams {
+//!     destination: "DAG-not-an-address".to_string(),
+//!     amount: 0.0,
+//!     fee: 0.0,
+//! };
+//!
+//! let report = params.validate(&sender.address);
+//! assert_eq!(report.issues.len(), 2);
+//! for issue in &report.issues {
+//!     println!("{}", issue);
+//! }
+//! ```
+
+use core::fmt;
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::canonicalize::canonicalize_bytes;
+use crate::currency_transaction::{is_valid_dag_address, token_to_units};
+use crate::currency_types::TransferParams;
+
+/// Default limit on the canonical JSON size of a data update, in bytes
+pub const DEFAULT_MAX_PAYLOAD_SIZE: usize = 1024 * 1024;
+
+/// Limits a transfer or data update is checked against
+///
+/// Metagraphs set their own minimum fee and payload limit; the defaults accept
+/// fee-less transfers and updates up to [`DEFAULT_MAX_PAYLOAD_SIZE`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationRules {
+    /// Minimum fee in smallest units (1e-8)
+    pub min_fee: i64,
+    /// Maximum canonical JSON size of a data update, in bytes
+    pub max_payload_size: usize,
+}
+
+impl Default for ValidationRules {
+    fn default() -> Self {
+        Self {
+            min_fee: 0,
+            max_payload_size: DEFAULT_MAX_PAYLOAD_SIZE,
+        }
+    }
+}
+
+/// A problem found by pre-flight validation
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    /// The source is not a DAG address
+    #[error("invalid source address {0:?}")]
+    InvalidSourceAddress(String),
+
+    /// The destination is not a DAG address
+    #[error("invalid destination address {0:?}")]
+    InvalidDestinationAddress(String),
+
+    /// Source and destination are the same address
+    #[error("source and destination are the same address")]
+    SameAddress,
+
+    /// The amount is below the smallest unit (1e-8) or not a number
+    #[error("amount must be at least 1e-8, got {0}")]
+    InvalidAmount(f64),
+
+    /// The fee is negative or not a number
+    #[error("fee must not be negative, got {0}")]
+    InvalidFee(f64),
+
+    /// The fee is below the metagraph's minimum
+    #[error("fee of {fee} is below the minimum of {minimum}")]
+    FeeBelowMinimum {
+        /// Fee in smallest units
+        fee: i64,
+        /// Minimum fee in smallest units
+        minimum: i64,
+    },
+
+    /// The data update does not serialize to JSON
+    #[error("payload cannot be serialized: {0}")]
+    Unserializable(String),
+
+    /// The data update is larger than allowed
+    #[error("payload of {size} bytes exceeds the limit of {limit}")]
+    PayloadTooLarge {
+        /// Canonical JSON size in bytes
+        size: usize,
+        /// Limit in bytes
+        limit: usize,
+    },
+}
+
+/// Every problem found with a transfer or data update
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ValidationReport {
+    /// Problems in the order they were checked; empty if there are none
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Whether no problems were found
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl TransferParams {
+    /// Check a transfer from `source` against the default rules
+    ///
+    /// Reports every problem [`create_currency_transaction`] would fail on
+    /// instead of only the first.
+    ///
+    /// [`create_currency_transaction`]: crate::create_currency_transaction
+    pub fn validate(&self, source: &str) -> ValidationReport {
+        self.validate_with(source, &ValidationRules::default())
+    }
+
+    /// Check a transfer from `source` against `rules`
+    pub fn validate_with(&self, source: &str, rules: &ValidationRules) -> ValidationReport {
+        let mut issues = Vec::new();
+
+        if !is_valid_dag_address(source) {
+            issues.push(ValidationIssue::InvalidSourceAddress(source.to_string()));
+        }
+        if !is_valid_dag_address(&self.destination) {
+            issues.push(ValidationIssue::InvalidDestinationAddress(
+                self.destination.clone(),
+            ));
+        }
+        if source == self.destination {
+            issues.push(ValidationIssue::SameAddress);
+        }
+        if !self.amount.is_finite() || token_to_units(self.amount) < 1 {
+            issues.push(ValidationIssue::InvalidAmount(self.amount));
+        }
+        if !self.fee.is_finite() || self.fee < 0.0 {
+            issues.push(ValidationIssue::InvalidFee(self.fee));
+        } else if token_to_units(self.fee) < rules.min_fee {
+            issues.push(ValidationIssue::FeeBelowMinimum {
+                fee: token_to_units(self.fee),
+                minimum: rules.min_fee,
+            });
+        }
+
+        ValidationReport { issues }
+    }
+}
+
+/// Check a data update against `rules` before signing it
+///
+/// The size is that of the canonical JSON the proofs are computed over.
+pub fn validate_data_update<T: Serialize>(data: &T, rules: &ValidationRules) -> ValidationReport {
+    let mut issues = Vec::new();
+
+    match canonicalize_bytes(data) {
+        Ok(bytes) if bytes.len() > rules.max_payload_size => {
+            issues.push(ValidationIssue::PayloadTooLarge {
+                size: bytes.len(),
+                limit: rules.max_payload_size,
+            });
+        }
+        Ok(_) => {}
+        Err(e) => issues.push(ValidationIssue::Unserializable(e.to_string())),
+    }
+
+    ValidationReport { issues }
+}
@@ -0,0 +1,384 @@
+//! Partially signed currency transactions
+//!
+//! A [`PartiallySignedTransaction`] is the interchange format for currency
+//! transactions that are built in one place and signed in another: by a
+//! hardware wallet, an air-gapped machine, or a custodian's signing service.
+//! It carries the unsigned transaction, the digest to sign, the proofs
+//! collected so far, and [`SignerHint`]s telling each signer which key to use.
+//!
+//! It travels as JSON or as base64 of that JSON. Each party
+//! [`parse`](PartiallySignedTransaction::parse)s it, signs, and passes it on;
+//! copies signed in parallel are [`merge`](PartiallySignedTransaction::merge)d,
+//! and [`finalize`](PartiallySignedTransaction::finalize) produces the
+//! `CurrencyTransaction` to submit once the source address has signed.
+//!
+//! The digest and every proof are checked whenever a container is parsed or
+//! proofs enter it, so one received from an untrusted party cannot smuggle in
+//! a different transaction or a forged signature.
+//!
+//! # Example
+//!
+//! ```
+//! use constellation_sdk::currency_types::{CurrencyTransactionValue, TransactionReference};
+//! use constellation_sdk::psct::{PartiallySignedTransaction, SignerHint};
+//! use constellation_sdk::signer::{LocalSigner, Signer};
+//! use constellation_sdk::verify_currency_transaction;
+//!
+//! let signer = LocalSigner::generate();
+//! let value = CurrencyTransactionValue {
+//!     source: signer.address(),
+//!     destination: "DAG4o41NzhfX6DyYBTTXu6sJa6awm36abJpv89jB".to_string(),
+//!     amount: 100_000_000,
+//!     fee: 0,
+//!     parent: TransactionReference { hash: "0".repeat(64), ordinal: 0 },
+//!     salt: "8940500530129793".to_string(),
+//! };
+//!
+//! // Wallet backend
+//! let psct = PartiallySignedTransaction::new(value)
+//!     .unwrap()
+//!     .with_hint(SignerHint::new(signer.address()).with_derivation_path("m/44'/1137'/0'/0/0"));
+//! let exported = psct.to_base64().unwrap();
+//!
+//! // Signing device
+//! let mut received = PartiallySignedTransaction::parse(&exported).unwrap();
+//! received.sign(&signer).unwrap();
+//!
+//! // Wallet backend again
+//! let transaction = received.finalize().unwrap();
+//! assert!(verify_currency_transaction(&transaction).is_valid);
+//! ```
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::currency_transaction::hash_currency_transaction;
+use crate::currency_types::{CurrencyTransaction, CurrencyTransactionValue};
+use crate::signer::Signer;
+use crate::types::{Result, SdkError, SignatureProof, Signed};
+use crate::verify::{signer_key, verify_hash};
+use crate::wallet::try_get_address;
+
+/// Version of the [`PartiallySignedTransaction`] format written by this SDK
+pub const PSCT_FORMAT_VERSION: u32 = 1;
+
+/// Which key is expected to sign, for signers holding more than one
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignerHint {
+    /// DAG address of the key
+    pub address: String,
+    /// Public key ID, without the 04 prefix, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key_id: Option<String>,
+    /// BIP-32 derivation path of the key on a hardware wallet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+    /// Free-form label to display, e.g. the custodian's account name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl SignerHint {
+    /// Hint for the key of an address
+    pub fn new(address: impl Into<String>) -> Self {
+        Self {
+            address: address.into(),
+            public_key_id: None,
+            derivation_path: None,
+            label: None,
+        }
+    }
+
+    /// Set the public key ID
+    pub fn with_public_key_id(mut self, public_key_id: impl Into<String>) -> Self {
+        self.public_key_id = Some(signer_key(&public_key_id.into()));
+        self
+    }
+
+    /// Set the derivation path
+    pub fn with_derivation_path(mut self, derivation_path: impl Into<String>) -> Self {
+        self.derivation_path = Some(derivation_path.into());
+        self
+    }
+
+    /// Set the label
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+}
+
+/// A currency transaction on its way between signers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartiallySignedTransaction {
+    /// Format version
+    pub version: u32,
+    /// The unsigned transaction
+    pub transaction: CurrencyTransactionValue,
+    /// SHA-256 of the transaction's Kryo encoding in hex - the hash that gets
+    /// signed
+    pub digest: String,
+    /// Proofs collected so far, at most one per key
+    #[serde(default)]
+    pub proofs: Vec<SignatureProof>,
+    /// Keys expected to sign
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hints: Vec<SignerHint>,
+}
+
+impl PartiallySignedTransaction {
+    /// Wrap an unsigned transaction
+    ///
+    /// The source address is added as the first hint.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error if the salt is not a decimal number
+    pub fn new(transaction: CurrencyTransactionValue) -> Result<Self> {
+        let psct = Self {
+            version: PSCT_FORMAT_VERSION,
+            digest: digest(&transaction),
+            hints: vec![SignerHint::new(transaction.source.clone())],
+            transaction,
+            proofs: Vec::new(),
+        };
+        psct.validate()?;
+        Ok(psct)
+    }
+
+    /// Wrap a transaction, keeping the proofs it already has
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of [`new`](Self::new), or of
+    /// [`add_proof`](Self::add_proof) for the first invalid proof
+    pub fn from_transaction(transaction: &CurrencyTransaction) -> Result<Self> {
+        let mut psct = Self::new(transaction.value.clone())?;
+        for proof in &transaction.proofs {
+            psct.add_proof(proof.clone())?;
+        }
+        Ok(psct)
+    }
+
+    /// Add a hint, replacing any earlier hint for the same address
+    pub fn with_hint(mut self, hint: SignerHint) -> Self {
+        self.add_hint(hint);
+        self
+    }
+
+    /// Serialize to JSON
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(SdkError::from)
+    }
+
+    /// Serialize to base64 of compact JSON, for QR codes and copy-paste
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if serialization fails
+    pub fn to_base64(&self) -> Result<String> {
+        let json = serde_json::to_vec(self).map_err(SdkError::from)?;
+        Ok(base64::engine::general_purpose::STANDARD.encode(json))
+    }
+
+    /// Parse a container from JSON or base64 and [`validate`](Self::validate) it
+    ///
+    /// # Errors
+    ///
+    /// Returns a `SerializationError` if the input is neither, or the error
+    /// of [`validate`](Self::validate)
+    pub fn parse(input: &str) -> Result<Self> {
+        let input = input.trim();
+        let json = if input.starts_with('{') {
+            input.as_bytes().to_vec()
+        } else {
+            base64::engine::general_purpose::STANDARD
+                .decode(input)
+                .map_err(|e| SdkError::SerializationError(format!("invalid base64: {e}")))?
+        };
+        let mut psct: Self = serde_json::from_slice(&json).map_err(SdkError::from)?;
+        for proof in &mut psct.proofs {
+            proof.id = signer_key(&proof.id);
+        }
+        psct.validate()?;
+        Ok(psct)
+    }
+
+    /// Check the version, the digest, and every proof
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error if the digest does not match the
+    /// transaction or a key signed twice, and an `InvalidSignature` error if a
+    /// proof does not sign the digest
+    pub fn validate(&self) -> Result<()> {
+        if self.version != PSCT_FORMAT_VERSION {
+            return Err(SdkError::InvalidPayload(format!(
+                "unsupported PSCT version {}",
+                self.version
+            )));
+        }
+        let salt = &self.transaction.salt;
+        if salt.is_empty() || !salt.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(SdkError::InvalidPayload(format!("invalid salt {salt:?}")));
+        }
+        if !self.digest.eq_ignore_ascii_case(&digest(&self.transaction)) {
+            return Err(SdkError::InvalidPayload(
+                "digest does not match the transaction".to_string(),
+            ));
+        }
+        for (i, proof) in self.proofs.iter().enumerate() {
+            self.check_proof(proof)?;
+            let id = signer_key(&proof.id);
+            if self.proofs[..i].iter().any(|p| signer_key(&p.id) == id) {
+                return Err(SdkError::InvalidPayload(format!(
+                    "more than one proof by {}",
+                    proof.id
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sign the digest and add the proof
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if signing fails
+    pub fn sign<S: Signer + ?Sized>(&mut self, signer: &S) -> Result<&SignatureProof> {
+        let proof = signer.sign_hash_proof(&self.digest)?;
+        self.add_proof(proof)
+    }
+
+    /// Add a proof produced elsewhere
+    ///
+    /// A proof from a key that already signed replaces the earlier one.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidSignature` error if the proof does not sign the
+    /// digest
+    pub fn add_proof(&mut self, mut proof: SignatureProof) -> Result<&SignatureProof> {
+        self.check_proof(&proof)?;
+        proof.id = signer_key(&proof.id);
+        let index = match self.proofs.iter().position(|p| p.id == proof.id) {
+            Some(index) => {
+                self.proofs[index] = proof;
+                index
+            }
+            None => {
+                self.proofs.push(proof);
+                self.proofs.len() - 1
+            }
+        };
+        Ok(&self.proofs[index])
+    }
+
+    /// Add the proofs and hints of another copy of this container
+    ///
+    /// Returns the number of proofs that were new.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error if `other` is for a different
+    /// transaction, or the error of [`add_proof`](Self::add_proof) for the
+    /// first invalid proof. Nothing is added on error.
+    pub fn merge(&mut self, other: &PartiallySignedTransaction) -> Result<usize> {
+        if other.transaction != self.transaction {
+            return Err(SdkError::InvalidPayload(
+                "containers are for different transactions".to_string(),
+            ));
+        }
+        for proof in &other.proofs {
+            self.check_proof(proof)?;
+        }
+        let mut added = 0;
+        for proof in &other.proofs {
+            let id = signer_key(&proof.id);
+            if !self.proofs.iter().any(|p| p.id == id) {
+                self.proofs.push(SignatureProof {
+                    id,
+                    signature: proof.signature.clone(),
+                });
+                added += 1;
+            }
+        }
+        for hint in &other.hints {
+            if !self.hints.iter().any(|h| h.address == hint.address) {
+                self.hints.push(hint.clone());
+            }
+        }
+        Ok(added)
+    }
+
+    /// Addresses of the keys that have signed
+    pub fn signed_addresses(&self) -> Vec<String> {
+        self.proofs
+            .iter()
+            .filter_map(|p| try_get_address(&p.id).ok())
+            .collect()
+    }
+
+    /// Hints whose address has not signed yet
+    pub fn missing_signers(&self) -> Vec<&SignerHint> {
+        let signed = self.signed_addresses();
+        self.hints
+            .iter()
+            .filter(|h| !signed.contains(&h.address))
+            .collect()
+    }
+
+    /// Whether the source address has signed
+    pub fn is_complete(&self) -> bool {
+        self.signed_addresses().contains(&self.transaction.source)
+    }
+
+    /// Produce the signed transaction once the source address has signed
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InvalidPayload` error if the source address has not signed
+    pub fn finalize(&self) -> Result<CurrencyTransaction> {
+        if !self.is_complete() {
+            return Err(SdkError::InvalidPayload(format!(
+                "no proof by the source address {}",
+                self.transaction.source
+            )));
+        }
+        Ok(Signed {
+            value: self.transaction.clone(),
+            proofs: self.proofs.clone(),
+        })
+    }
+
+    fn add_hint(&mut self, hint: SignerHint) {
+        match self.hints.iter_mut().find(|h| h.address == hint.address) {
+            Some(existing) => *existing = hint,
+            None => self.hints.push(hint),
+        }
+    }
+
+    fn check_proof(&self, proof: &SignatureProof) -> Result<()> {
+        if !verify_hash(&self.digest, &proof.signature, &signer_key(&proof.id)).unwrap_or(false) {
+            return Err(SdkError::InvalidSignature(format!(
+                "proof by {} does not sign {}",
+                proof.id, self.digest
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn digest(transaction: &CurrencyTransactionValue) -> String {
+    hash_currency_transaction(&Signed {
+        value: transaction.clone(),
+        proofs: vec![],
+    })
+    .value
+}
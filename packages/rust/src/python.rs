@@ -0,0 +1,433 @@
+//! Python bindings through pyo3
+//!
+//! With the `python` feature the wallet functions, JSON signing and
+//! verification, the blocking network clients, and the high-level
+//! [`Account`] are exported as the `constellation_sdk_rs` extension module.
+//! JSON values cross the boundary as Python `dict`s and `list`s; every call
+//! that waits on the network releases the GIL, and every failure is raised as
+//! `constellation_sdk_rs.SdkError`.
+//!
+//! Build a wheel with [maturin](https://www.maturin.rs/), which reads the
+//! module name and features from `pyproject.toml`:
+//!
+//! ```bash
+//! maturin build --release
+//! ```
+//!
+//! # Example
+//!
+//! ```python
+//! import constellation_sdk_rs as sdk
+//!
+//! key_pair = sdk.generate_key_pair()
+//! signed = sdk.create_signed_object({"id": "sensor-1"}, key_pair["private_key"], True)
+//! assert sdk.verify(signed, True)
+//!
+//! account = sdk.Account(key_pair["private_key"], network="mainnet")
+//! print(account.balance())
+//! ```
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use tokio::runtime::{Builder, Runtime};
+
+use crate::currency_types::TransferParams;
+use crate::network::blocking;
+use crate::network::{Account as AsyncAccount, LayerType, NetworkConfig, NetworkError};
+use crate::types::{KeyPair, Signed};
+
+create_exception!(
+    constellation_sdk_rs,
+    SdkError,
+    PyException,
+    "Key, signing, serialization, or network failure"
+);
+
+fn sdk_err(e: impl std::fmt::Display) -> PyErr {
+    SdkError::new_err(e.to_string())
+}
+
+/// Convert a Python object to JSON through the `json` module
+fn to_value<T: DeserializeOwned>(value: &Bound<'_, PyAny>) -> PyResult<T> {
+    let json: String = value
+        .py()
+        .import("json")?
+        .call_method1("dumps", (value,))?
+        .extract()?;
+    serde_json::from_str(&json).map_err(sdk_err)
+}
+
+/// Convert JSON to a Python object through the `json` module
+fn to_py<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json = serde_json::to_string(value).map_err(sdk_err)?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+fn key_pair_dict(py: Python<'_>, key_pair: KeyPair) -> PyResult<Bound<'_, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("private_key", key_pair.private_key)?;
+    dict.set_item("public_key", key_pair.public_key)?;
+    dict.set_item("address", key_pair.address)?;
+    Ok(dict)
+}
+
+/// Generate a random key pair as a dict of `private_key`, `public_key`, and `address`
+#[pyfunction]
+fn generate_key_pair(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    key_pair_dict(py, crate::wallet::generate_key_pair())
+}
+
+/// Derive the key pair of a private key in hex
+#[pyfunction]
+fn key_pair_from_private_key<'py>(
+    py: Python<'py>,
+    private_key: &str,
+) -> PyResult<Bound<'py, PyDict>> {
+    let key_pair = crate::wallet::key_pair_from_private_key(private_key).map_err(sdk_err)?;
+    key_pair_dict(py, key_pair)
+}
+
+/// DAG address of a public key in hex
+#[pyfunction]
+fn get_address(public_key: &str) -> PyResult<String> {
+    crate::wallet::try_get_address(public_key).map_err(sdk_err)
+}
+
+/// Whether a string is a well-formed private key in hex
+#[pyfunction]
+fn is_valid_private_key(private_key: &str) -> bool {
+    crate::wallet::is_valid_private_key(private_key)
+}
+
+/// Whether a string is a well-formed DAG address
+#[pyfunction]
+fn is_valid_dag_address(address: &str) -> bool {
+    crate::currency_transaction::is_valid_dag_address(address)
+}
+
+/// RFC 8785 canonical JSON of a value
+#[pyfunction]
+fn canonicalize(value: &Bound<'_, PyAny>) -> PyResult<String> {
+    let value: Value = to_value(value)?;
+    crate::canonicalize::canonicalize(&value).map_err(sdk_err)
+}
+
+/// Sign a value and return the signed object
+#[pyfunction]
+#[pyo3(signature = (value, private_key, is_data_update = false))]
+fn create_signed_object(
+    value: &Bound<'_, PyAny>,
+    private_key: &str,
+    is_data_update: bool,
+) -> PyResult<PyObject> {
+    let data: Value = to_value(value)?;
+    let signed = crate::signed_object::create_signed_object(&data, private_key, is_data_update)
+        .map_err(sdk_err)?;
+    to_py(value.py(), &signed)
+}
+
+/// Add a signature to a signed object
+#[pyfunction]
+#[pyo3(signature = (signed, private_key, is_data_update = false))]
+fn add_signature(
+    signed: &Bound<'_, PyAny>,
+    private_key: &str,
+    is_data_update: bool,
+) -> PyResult<PyObject> {
+    let data: Signed<Value> = to_value(signed)?;
+    let data =
+        crate::signed_object::add_signature(data, private_key, is_data_update).map_err(sdk_err)?;
+    to_py(signed.py(), &data)
+}
+
+/// Verify every proof of a signed object
+#[pyfunction]
+#[pyo3(signature = (signed, is_data_update = false))]
+fn verify(signed: &Bound<'_, PyAny>, is_data_update: bool) -> PyResult<bool> {
+    let signed: Signed<Value> = to_value(signed)?;
+    Ok(crate::verify::verify(&signed, is_data_update).is_valid)
+}
+
+/// Sign a SHA-256 hash in hex, returning the DER signature in hex
+#[pyfunction]
+fn sign_hash(hash_hex: &str, private_key: &str) -> PyResult<String> {
+    crate::sign::sign_hash(hash_hex, private_key).map_err(sdk_err)
+}
+
+/// Verify a signature over a SHA-256 hash in hex
+#[pyfunction]
+fn verify_hash(hash_hex: &str, signature: &str, public_key_id: &str) -> PyResult<bool> {
+    crate::verify::verify_hash(hash_hex, signature, public_key_id).map_err(sdk_err)
+}
+
+/// Blocking client for a metagraph node
+///
+/// `layer` is `"ml0"`, `"cl1"`, or `"dl1"`.
+#[pyclass(name = "MetagraphClient")]
+struct PyMetagraphClient {
+    inner: blocking::MetagraphClient,
+}
+
+#[pymethods]
+impl PyMetagraphClient {
+    #[new]
+    fn new(base_url: &str, layer: &str) -> PyResult<Self> {
+        let layer = match layer.to_ascii_lowercase().as_str() {
+            "ml0" => LayerType::ML0,
+            "cl1" => LayerType::CL1,
+            "dl1" => LayerType::DL1,
+            other => return Err(sdk_err(format!("Unknown layer {other}"))),
+        };
+        let inner = blocking::MetagraphClient::new(base_url, layer).map_err(sdk_err)?;
+        Ok(Self { inner })
+    }
+
+    /// Whether the node answers its health check
+    fn check_health(&self, py: Python<'_>) -> bool {
+        py.allow_threads(|| self.inner.check_health())
+    }
+
+    /// Token balance of an address as `{"balance", "ordinal"}` (ML0)
+    fn get_balance(&self, py: Python<'_>, address: &str) -> PyResult<PyObject> {
+        let balance = py
+            .allow_threads(|| self.inner.get_balance(address))
+            .map_err(sdk_err)?;
+        to_py(py, &balance)
+    }
+
+    /// Latest snapshot ordinal (ML0)
+    fn get_latest_snapshot_ordinal(&self, py: Python<'_>) -> PyResult<i64> {
+        py.allow_threads(|| self.inner.get_latest_snapshot_ordinal())
+            .map_err(sdk_err)
+    }
+
+    /// Last transaction reference of an address as `{"hash", "ordinal"}` (CL1)
+    fn get_last_reference(&self, py: Python<'_>, address: &str) -> PyResult<PyObject> {
+        let reference = py
+            .allow_threads(|| self.inner.get_last_reference(address))
+            .map_err(sdk_err)?;
+        to_py(py, &reference)
+    }
+
+    /// Sign and send a token transfer, returning the transaction hash (CL1)
+    #[pyo3(signature = (destination, amount, private_key, fee = 0.0))]
+    fn transfer(
+        &self,
+        py: Python<'_>,
+        destination: String,
+        amount: f64,
+        private_key: &str,
+        fee: f64,
+    ) -> PyResult<String> {
+        let params = TransferParams {
+            destination,
+            amount,
+            fee,
+        };
+        py.allow_threads(|| self.inner.transfer(params, private_key))
+            .map_err(sdk_err)
+    }
+
+    /// Post a signed DataUpdate, returning `{"hash"}` (DL1)
+    fn post_data(&self, signed: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        let py = signed.py();
+        let signed: Signed<Value> = to_value(signed)?;
+        let response = py
+            .allow_threads(|| self.inner.post_data(&signed))
+            .map_err(sdk_err)?;
+        to_py(py, &response)
+    }
+
+    /// GET a path and return the decoded JSON
+    fn get(&self, py: Python<'_>, path: &str) -> PyResult<PyObject> {
+        let value: Value = py.allow_threads(|| self.inner.get(path)).map_err(sdk_err)?;
+        to_py(py, &value)
+    }
+}
+
+/// Blocking client for a Global L0 node
+#[pyclass(name = "GlobalL0Client")]
+struct PyGlobalL0Client {
+    inner: blocking::GlobalL0Client,
+}
+
+#[pymethods]
+impl PyGlobalL0Client {
+    #[new]
+    fn new(base_url: &str) -> PyResult<Self> {
+        let inner = blocking::GlobalL0Client::new(base_url).map_err(sdk_err)?;
+        Ok(Self { inner })
+    }
+
+    /// Whether the node answers its health check
+    fn check_health(&self, py: Python<'_>) -> bool {
+        py.allow_threads(|| self.inner.check_health())
+    }
+
+    /// DAG balance of an address as `{"balance", "ordinal"}`
+    fn get_balance(&self, py: Python<'_>, address: &str) -> PyResult<PyObject> {
+        let balance = py
+            .allow_threads(|| self.inner.get_balance(address))
+            .map_err(sdk_err)?;
+        to_py(py, &balance)
+    }
+
+    /// Latest global snapshot ordinal
+    fn get_latest_snapshot_ordinal(&self, py: Python<'_>) -> PyResult<i64> {
+        py.allow_threads(|| self.inner.get_latest_snapshot_ordinal())
+            .map_err(sdk_err)
+    }
+}
+
+/// A signing key with the clients it sends to
+///
+/// `network` names a public network preset (`mainnet`, `testnet`, or
+/// `integrationnet`); the URLs add to or override it.
+#[pyclass(name = "Account")]
+struct PyAccount {
+    inner: AsyncAccount,
+    runtime: Runtime,
+}
+
+#[pymethods]
+impl PyAccount {
+    #[new]
+    #[pyo3(signature = (
+        private_key,
+        network = None,
+        l0_url = None,
+        currency_l0_url = None,
+        l1_url = None,
+        data_l1_url = None,
+        block_explorer_url = None,
+    ))]
+    fn new(
+        private_key: &str,
+        network: Option<&str>,
+        l0_url: Option<String>,
+        currency_l0_url: Option<String>,
+        l1_url: Option<String>,
+        data_l1_url: Option<String>,
+        block_explorer_url: Option<String>,
+    ) -> PyResult<Self> {
+        let mut config = match network {
+            None => NetworkConfig::default(),
+            Some("mainnet") => NetworkConfig::mainnet(),
+            Some("testnet") => NetworkConfig::testnet(),
+            Some("integrationnet") => NetworkConfig::integrationnet(),
+            Some(other) => return Err(sdk_err(format!("Unknown network {other}"))),
+        };
+        let urls = [
+            (l0_url, &mut config.l0_url),
+            (currency_l0_url, &mut config.currency_l0_url),
+            (l1_url, &mut config.l1_url),
+            (data_l1_url, &mut config.data_l1_url),
+            (block_explorer_url, &mut config.block_explorer_url),
+        ];
+        for (url, field) in urls {
+            if url.is_some() {
+                *field = url;
+            }
+        }
+        let inner = AsyncAccount::from_private_key(private_key)
+            .and_then(|account| account.with_config(&config))
+            .map_err(sdk_err)?;
+        let runtime = Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| sdk_err(NetworkError::ConfigError(e.to_string())))?;
+        Ok(Self { inner, runtime })
+    }
+
+    /// DAG address of the account
+    #[getter]
+    fn address(&self) -> String {
+        self.inner.address()
+    }
+
+    /// Public key ID of the account
+    #[getter]
+    fn public_key_id(&self) -> String {
+        self.inner.public_key_id()
+    }
+
+    /// Token balance on the Metagraph L0, or DAG balance on the Global L0
+    fn balance(&self, py: Python<'_>) -> PyResult<PyObject> {
+        let balance = py
+            .allow_threads(|| self.runtime.block_on(self.inner.balance()))
+            .map_err(sdk_err)?;
+        to_py(py, &balance)
+    }
+
+    /// Send tokens, chaining on the cached last reference; returns the receipt
+    #[pyo3(signature = (destination, amount, fee = 0.0))]
+    fn send(
+        &self,
+        py: Python<'_>,
+        destination: String,
+        amount: f64,
+        fee: f64,
+    ) -> PyResult<PyObject> {
+        let params = TransferParams {
+            destination,
+            amount,
+            fee,
+        };
+        let receipt = py
+            .allow_threads(|| self.runtime.block_on(self.inner.send(params)))
+            .map_err(sdk_err)?;
+        to_py(py, &receipt)
+    }
+
+    /// Sign a value as a DataUpdate and return the signed object
+    fn sign_data(&self, data: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        let value: Value = to_value(data)?;
+        let signed = self.inner.sign_data(&value).map_err(sdk_err)?;
+        to_py(data.py(), &signed)
+    }
+
+    /// Sign a value as a DataUpdate and submit it to the Data L1; returns the receipt
+    fn submit_data(&self, data: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        let py = data.py();
+        let value: Value = to_value(data)?;
+        let receipt = py
+            .allow_threads(|| self.runtime.block_on(self.inner.submit_data(&value)))
+            .map_err(sdk_err)?;
+        to_py(py, &receipt)
+    }
+
+    /// Drop the cached last reference so the next send fetches it again
+    fn reset_reference(&self, py: Python<'_>) {
+        py.allow_threads(|| self.runtime.block_on(self.inner.reset_reference()))
+    }
+}
+
+/// The `constellation_sdk_rs` extension module
+///
+/// Public so that Rust programs embedding Python can register the bindings
+/// on a module of their own.
+#[pymodule]
+#[pyo3(name = "constellation_sdk_rs")]
+pub fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("SdkError", m.py().get_type::<SdkError>())?;
+    m.add_function(wrap_pyfunction!(generate_key_pair, m)?)?;
+    m.add_function(wrap_pyfunction!(key_pair_from_private_key, m)?)?;
+    m.add_function(wrap_pyfunction!(get_address, m)?)?;
+    m.add_function(wrap_pyfunction!(is_valid_private_key, m)?)?;
+    m.add_function(wrap_pyfunction!(is_valid_dag_address, m)?)?;
+    m.add_function(wrap_pyfunction!(canonicalize, m)?)?;
+    m.add_function(wrap_pyfunction!(create_signed_object, m)?)?;
+    m.add_function(wrap_pyfunction!(add_signature, m)?)?;
+    m.add_function(wrap_pyfunction!(verify, m)?)?;
+    m.add_function(wrap_pyfunction!(sign_hash, m)?)?;
+    m.add_function(wrap_pyfunction!(verify_hash, m)?)?;
+    m.add_class::<PyMetagraphClient>()?;
+    m.add_class::<PyGlobalL0Client>()?;
+    m.add_class::<PyAccount>()?;
+    Ok(())
+}
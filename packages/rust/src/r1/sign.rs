@@ -23,7 +23,7 @@ use crate::types::{Result, SignatureProof};
 pub fn sign<T: Serialize>(data: &T, private_key: &str) -> Result<SignatureProof> {
     let bytes = to_bytes(data, false)?;
     let hash = hash_bytes(&bytes);
-    let signature = sign_hash(&hash.value, private_key)?;
+    let signature = sign_hash_as(&hash.value, private_key, "r1_sign")?;
     let id = get_public_key_id(private_key)?;
 
     Ok(SignatureProof { id, signature })
@@ -37,7 +37,7 @@ pub fn sign<T: Serialize>(data: &T, private_key: &str) -> Result<SignatureProof>
 pub fn sign_data_update<T: Serialize>(data: &T, private_key: &str) -> Result<SignatureProof> {
     let bytes = to_bytes(data, true)?;
     let hash = hash_bytes(&bytes);
-    let signature = sign_hash(&hash.value, private_key)?;
+    let signature = sign_hash_as(&hash.value, private_key, "r1_sign_data_update")?;
     let id = get_public_key_id(private_key)?;
 
     Ok(SignatureProof { id, signature })
@@ -55,6 +55,12 @@ pub fn sign_data_update<T: Serialize>(data: &T, private_key: &str) -> Result<Sig
 /// # Returns
 /// DER-encoded signature in hex format
 pub fn sign_hash(hash_hex: &str, private_key: &str) -> Result<String> {
+    sign_hash_as(hash_hex, private_key, "r1_sign_hash")
+}
+
+/// [`sign_hash`], reporting the signature to the
+/// [signing audit hook](crate::signing_audit) as `operation`
+fn sign_hash_as(hash_hex: &str, private_key: &str, operation: &'static str) -> Result<String> {
     let private_key_bytes = hex::decode(private_key)?;
     let signing_key = SigningKey::from_slice(&private_key_bytes)?;
 
@@ -71,6 +77,7 @@ pub fn sign_hash(hash_hex: &str, private_key: &str) -> Result<String> {
     // signatures. Fixing this requires coordinated changes across
     // every existing on-chain proof; tracked separately.
     let signature: Signature = signing_key.sign(&digest);
+    audit_signing!(operation, hash_hex, get_public_key_id(private_key));
 
     Ok(hex::encode(signature.to_der().as_bytes()))
 }
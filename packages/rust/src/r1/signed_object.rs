@@ -5,6 +5,7 @@
 use serde::Serialize;
 
 use crate::r1::sign::{sign, sign_data_update};
+use crate::signed_object::ensure_new_signer;
 use crate::types::{Result, SdkError, Signed};
 
 /// Create a signed object with a single P-256 signature.
@@ -26,6 +27,8 @@ pub fn create_signed_object<T: Serialize + Clone>(
 }
 
 /// Add an additional P-256 signature to an existing signed object.
+///
+/// Fails with `InvalidPayload` if the key has already signed.
 pub fn add_signature<T: Serialize + Clone>(
     signed: Signed<T>,
     private_key: &str,
@@ -36,6 +39,7 @@ pub fn add_signature<T: Serialize + Clone>(
     } else {
         sign(&signed.value, private_key)?
     };
+    ensure_new_signer(&signed.proofs, &new_proof)?;
 
     let mut proofs = signed.proofs;
     proofs.push(new_proof);
@@ -47,6 +51,8 @@ pub fn add_signature<T: Serialize + Clone>(
 }
 
 /// Create a signed object with multiple P-256 signatures at once.
+///
+/// Fails with `InvalidPayload` if a key appears twice.
 pub fn batch_sign<T: Serialize + Clone>(
     value: &T,
     private_keys: &[&str],
@@ -56,20 +62,20 @@ pub fn batch_sign<T: Serialize + Clone>(
         return Err(SdkError::NoPrivateKeys);
     }
 
-    let proofs: Result<Vec<_>> = private_keys
-        .iter()
-        .map(|key| {
-            if is_data_update {
-                sign_data_update(value, key)
-            } else {
-                sign(value, key)
-            }
-        })
-        .collect();
+    let mut proofs = Vec::with_capacity(private_keys.len());
+    for key in private_keys {
+        let proof = if is_data_update {
+            sign_data_update(value, key)?
+        } else {
+            sign(value, key)?
+        };
+        ensure_new_signer(&proofs, &proof)?;
+        proofs.push(proof);
+    }
 
     Ok(Signed {
         value: value.clone(),
-        proofs: proofs?,
+        proofs,
     })
 }
 
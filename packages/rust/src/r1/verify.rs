@@ -9,6 +9,7 @@ use crate::binary::to_bytes;
 use crate::hash::{compute_digest_from_hash, hash_bytes};
 use crate::r1::wallet::id_to_public_key;
 use crate::types::{Result, SdkError, SignatureProof, Signed, VerificationResult};
+use crate::verify::{signer_key, MAX_SIGNATURE_HEX_LEN};
 
 /// Verify a signed object using P-256.
 ///
@@ -33,8 +34,14 @@ pub fn verify<T: Serialize>(signed: &Signed<T>, is_data_update: bool) -> Verific
 
     let mut valid_proofs = Vec::new();
     let mut invalid_proofs = Vec::new();
+    let mut signers = std::collections::BTreeSet::new();
 
     for proof in &signed.proofs {
+        // A repeated signer is invalid, as in the K1 `verify`
+        if !signers.insert(signer_key(&proof.id)) {
+            invalid_proofs.push(proof.clone());
+            continue;
+        }
         match verify_hash(&hash.value, &proof.signature, &proof.id) {
             Ok(true) => valid_proofs.push(proof.clone()),
             Ok(false) | Err(_) => invalid_proofs.push(proof.clone()),
@@ -61,9 +68,16 @@ pub fn verify_hash(hash_hex: &str, signature_hex: &str, public_key_id: &str) ->
     let public_key = id_to_public_key(public_key_id)?;
     let verifying_key = VerifyingKey::from(&public_key);
 
-    let signature_bytes = hex::decode(signature_hex)?;
+    if signature_hex.len() > MAX_SIGNATURE_HEX_LEN {
+        return Err(SdkError::InvalidSignature(format!(
+            "signature is {} hex characters; DER signatures are at most {MAX_SIGNATURE_HEX_LEN}",
+            signature_hex.len()
+        )));
+    }
+    let signature_bytes = hex::decode(signature_hex)
+        .map_err(|e| SdkError::InvalidSignature(format!("signature is not hex: {e}")))?;
     let signature = Signature::from_der(&signature_bytes)
-        .map_err(|e| SdkError::InvalidSignature(e.to_string()))?;
+        .map_err(|e| SdkError::InvalidSignature(format!("malformed DER signature: {e}")))?;
 
     let digest = compute_digest_from_hash(hash_hex);
 
@@ -4,8 +4,9 @@
 //! address derivation differs only in the DER PKCS prefix.
 
 use p256::ecdsa::SigningKey;
-use p256::elliptic_curve::sec1::FromEncodedPoint;
+use p256::elliptic_curve::sec1::{FromEncodedPoint, ToEncodedPoint};
 use p256::PublicKey;
+use rand::{CryptoRng, RngCore};
 use sha2::{Digest, Sha256};
 
 use crate::types::{KeyPair, Result, SdkError};
@@ -14,6 +15,12 @@ use crate::types::{KeyPair, Result, SdkError};
 /// Stripped when creating an Id, prepended when reconstructing.
 const PKCS_PREFIX: &str = "3059301306072a8648ce3d020106082a8648ce3d03010703420004";
 
+/// [`PKCS_PREFIX`] as bytes
+const PKCS_PREFIX_BYTES: [u8; 27] = [
+    0x30, 0x59, 0x30, 0x13, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x08, 0x2a,
+    0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07, 0x03, 0x42, 0x00, 0x04,
+];
+
 /// Generate a new random P-256 key pair.
 ///
 /// # Example
@@ -26,14 +33,22 @@ const PKCS_PREFIX: &str = "3059301306072a8648ce3d020106082a8648ce3d0301070342000
 /// println!("Public key: {}", key_pair.public_key);
 /// ```
 pub fn generate_key_pair() -> KeyPair {
-    let signing_key = SigningKey::random(&mut rand::thread_rng());
+    generate_key_pair_with_rng(&mut rand::thread_rng())
+}
+
+/// Generate a new P-256 key pair from the given random number generator.
+///
+/// See [`crate::wallet::generate_key_pair_with_rng`].
+pub fn generate_key_pair_with_rng<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> KeyPair {
+    let mut rng = rng;
+    let signing_key = SigningKey::random(&mut rng);
     let private_key_hex = hex::encode(signing_key.to_bytes());
 
     let public_key = signing_key.verifying_key();
     let point = public_key.to_encoded_point(false);
     let public_key_hex = hex::encode(point.as_bytes());
 
-    let address = get_address(&public_key_hex);
+    let address = address_of_point(point.as_bytes());
 
     KeyPair {
         private_key: private_key_hex,
@@ -55,7 +70,7 @@ pub fn key_pair_from_private_key(private_key: &str) -> Result<KeyPair> {
     let public_key = signing_key.verifying_key();
     let point = public_key.to_encoded_point(false);
     let public_key_hex = hex::encode(point.as_bytes());
-    let address = get_address(&public_key_hex);
+    let address = address_of_point(point.as_bytes());
 
     Ok(KeyPair {
         private_key: private_key.to_string(),
@@ -90,13 +105,39 @@ pub fn get_public_key_hex(private_key: &str, compressed: bool) -> Result<String>
 ///
 /// Uses the same Constellation address derivation as secp256k1 but
 /// with the P-256 PKCS prefix.
+///
+/// # Errors
+///
+/// Returns `InvalidPublicKey` if the key is not hex or not a point on the
+/// curve.
+pub fn try_get_address(public_key: &str) -> Result<String> {
+    let public_key = id_to_public_key(public_key)
+        .map_err(|e| SdkError::InvalidPublicKey(format!("Invalid P-256 public key: {e}")))?;
+    Ok(address_of_point(
+        public_key.to_encoded_point(false).as_bytes(),
+    ))
+}
+
+/// Get DAG address from a P-256 public key, without checking it.
+///
+/// Input that is not hex is hashed as if it were empty, producing an address
+/// that belongs to no key.
+#[deprecated(note = "returns a bogus address for malformed keys; use `try_get_address`")]
 pub fn get_address(public_key: &str) -> String {
     let normalized_key = normalize_public_key(public_key);
     let pkcs_encoded = format!("{PKCS_PREFIX}{normalized_key}");
+    address_from_pkcs(&hex::decode(pkcs_encoded).unwrap_or_default())
+}
+
+/// DAG address of an uncompressed SEC1 point
+fn address_of_point(point: &[u8]) -> String {
+    address_from_pkcs(&[&PKCS_PREFIX_BYTES[..], point].concat())
+}
 
-    let pkcs_bytes = hex::decode(&pkcs_encoded).unwrap_or_default();
+/// DAG address of a DER-encoded public key
+fn address_from_pkcs(pkcs_bytes: &[u8]) -> String {
     let mut hasher = Sha256::new();
-    hasher.update(&pkcs_bytes);
+    hasher.update(pkcs_bytes);
     let hash = hasher.finalize();
 
     let encoded = bs58::encode(&hash).into_string();
@@ -119,6 +160,13 @@ pub fn get_address(public_key: &str) -> String {
 
 /// Parse a public key ID (128 hex chars) back into a P-256 PublicKey.
 pub fn id_to_public_key(public_key_id: &str) -> Result<PublicKey> {
+    // Uncompressed with `04` prefix is the longest form; reject more before decoding
+    if public_key_id.len() > 130 {
+        return Err(SdkError::InvalidPublicKey(format!(
+            "P-256 public key is {} hex characters; at most 130 expected",
+            public_key_id.len()
+        )));
+    }
     let full_hex = normalize_public_key(public_key_id);
     let bytes = hex::decode(&full_hex)?;
     let point = p256::EncodedPoint::from_bytes(&bytes)
@@ -167,6 +215,21 @@ mod tests {
         assert!(key_pair.address.starts_with("DAG"));
     }
 
+    #[test]
+    fn test_generate_key_pair_with_rng() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let key_pair = generate_key_pair_with_rng(&mut StdRng::seed_from_u64(1));
+        assert_eq!(
+            key_pair,
+            generate_key_pair_with_rng(&mut StdRng::seed_from_u64(1))
+        );
+        assert_eq!(
+            key_pair_from_private_key(&key_pair.private_key).unwrap(),
+            key_pair
+        );
+    }
+
     #[test]
     fn test_key_pair_from_private_key() {
         let key_pair = generate_key_pair();
@@ -192,6 +255,21 @@ mod tests {
         assert_eq!(recovered_hex, key_pair.public_key);
     }
 
+    #[test]
+    #[allow(deprecated)]
+    fn test_try_get_address() {
+        let key_pair = generate_key_pair();
+        assert_eq!(
+            try_get_address(&key_pair.public_key).unwrap(),
+            key_pair.address
+        );
+        assert_eq!(get_address(&key_pair.public_key), key_pair.address);
+        assert!(matches!(
+            try_get_address("zz"),
+            Err(SdkError::InvalidPublicKey(_))
+        ));
+    }
+
     #[test]
     fn test_is_valid_private_key() {
         assert!(is_valid_private_key(&"a".repeat(64)));
@@ -5,7 +5,27 @@
 use serde::Serialize;
 
 use crate::sign::{sign, sign_data_update};
-use crate::types::{Result, SdkError, Signed};
+use crate::types::{Result, SdkError, SignatureProof, Signed};
+use crate::verify::signer_key;
+
+impl<T: Serialize> Signed<T> {
+    /// Sign a value with one key, taking ownership of it
+    ///
+    /// Same as [`create_signed_object_owned`].
+    ///
+    /// # Example
+    /// ```
+    /// use constellation_sdk::{generate_key_pair, Signed};
+    /// use serde_json::json;
+    ///
+    /// let key_pair = generate_key_pair();
+    /// let signed = Signed::new(json!({"id": "test"}), &key_pair.private_key, false).unwrap();
+    /// assert_eq!(signed.proofs.len(), 1);
+    /// ```
+    pub fn new(value: T, private_key: &str, is_data_update: bool) -> Result<Self> {
+        create_signed_object_owned(value, private_key, is_data_update)
+    }
+}
 
 /// Create a signed object with a single signature
 ///
@@ -36,11 +56,7 @@ pub fn create_signed_object<T: Serialize + Clone>(
     private_key: &str,
     is_data_update: bool,
 ) -> Result<Signed<T>> {
-    let proof = if is_data_update {
-        sign_data_update(value, private_key)?
-    } else {
-        sign(value, private_key)?
-    };
+    let proof = sign_as(value, private_key, is_data_update)?;
 
     Ok(Signed {
         value: value.clone(),
@@ -48,6 +64,32 @@ pub fn create_signed_object<T: Serialize + Clone>(
     })
 }
 
+/// Create a signed object with a single signature, taking ownership of the value
+///
+/// Like [`create_signed_object`], but moves `value` into the result instead
+/// of cloning it, which matters for large payloads. If signing fails the
+/// value is dropped.
+///
+/// # Arguments
+/// * `value` - Any serializable object
+/// * `private_key` - Private key in hex format
+/// * `is_data_update` - Whether to sign as DataUpdate
+///
+/// # Returns
+/// Signed object ready for submission
+pub fn create_signed_object_owned<T: Serialize>(
+    value: T,
+    private_key: &str,
+    is_data_update: bool,
+) -> Result<Signed<T>> {
+    let proof = sign_as(&value, private_key, is_data_update)?;
+
+    Ok(Signed {
+        value,
+        proofs: vec![proof],
+    })
+}
+
 /// Add an additional signature to an existing signed object
 ///
 /// This allows building multi-signature objects where multiple parties
@@ -61,6 +103,10 @@ pub fn create_signed_object<T: Serialize + Clone>(
 /// # Returns
 /// New signed object with additional proof
 ///
+/// # Errors
+///
+/// Returns an `InvalidPayload` error if the key has already signed
+///
 /// # Example
 /// ```
 /// use constellation_sdk::signed_object::{create_signed_object, add_signature};
@@ -84,11 +130,8 @@ pub fn add_signature<T: Serialize + Clone>(
     private_key: &str,
     is_data_update: bool,
 ) -> Result<Signed<T>> {
-    let new_proof = if is_data_update {
-        sign_data_update(&signed.value, private_key)?
-    } else {
-        sign(&signed.value, private_key)?
-    };
+    let new_proof = sign_as(&signed.value, private_key, is_data_update)?;
+    ensure_new_signer(&signed.proofs, &new_proof)?;
 
     let mut proofs = signed.proofs;
     proofs.push(new_proof);
@@ -112,6 +155,11 @@ pub fn add_signature<T: Serialize + Clone>(
 /// # Returns
 /// Signed object with multiple proofs
 ///
+/// # Errors
+///
+/// Returns `NoPrivateKeys` if `private_keys` is empty, and an
+/// `InvalidPayload` error if a key appears twice
+///
 /// # Example
 /// ```
 /// use constellation_sdk::signed_object::batch_sign;
@@ -135,27 +183,78 @@ pub fn batch_sign<T: Serialize + Clone>(
     private_keys: &[&str],
     is_data_update: bool,
 ) -> Result<Signed<T>> {
-    if private_keys.is_empty() {
-        return Err(SdkError::NoPrivateKeys);
-    }
-
-    let proofs: Result<Vec<_>> = private_keys
-        .iter()
-        .map(|key| {
-            if is_data_update {
-                sign_data_update(value, key)
-            } else {
-                sign(value, key)
-            }
-        })
-        .collect();
+    let proofs = sign_all(value, private_keys, is_data_update)?;
 
     Ok(Signed {
         value: value.clone(),
-        proofs: proofs?,
+        proofs,
     })
 }
 
+/// Create a signed object with multiple signatures, taking ownership of the value
+///
+/// Like [`batch_sign`], but moves `value` into the result instead of cloning
+/// it.
+///
+/// # Arguments
+/// * `value` - Any serializable object
+/// * `private_keys` - Array of private keys in hex format
+/// * `is_data_update` - Whether to sign as DataUpdate
+///
+/// # Returns
+/// Signed object with multiple proofs
+pub fn batch_sign_owned<T: Serialize>(
+    value: T,
+    private_keys: &[&str],
+    is_data_update: bool,
+) -> Result<Signed<T>> {
+    let proofs = sign_all(&value, private_keys, is_data_update)?;
+
+    Ok(Signed { value, proofs })
+}
+
+fn sign_as<T: Serialize>(
+    value: &T,
+    private_key: &str,
+    is_data_update: bool,
+) -> Result<SignatureProof> {
+    if is_data_update {
+        sign_data_update(value, private_key)
+    } else {
+        sign(value, private_key)
+    }
+}
+
+/// Refuse a proof by a key that already has one in `proofs`
+pub(crate) fn ensure_new_signer(proofs: &[SignatureProof], proof: &SignatureProof) -> Result<()> {
+    let id = signer_key(&proof.id);
+    if proofs.iter().any(|p| signer_key(&p.id) == id) {
+        return Err(SdkError::InvalidPayload(format!(
+            "{} has already signed",
+            proof.id
+        )));
+    }
+    Ok(())
+}
+
+fn sign_all<T: Serialize>(
+    value: &T,
+    private_keys: &[&str],
+    is_data_update: bool,
+) -> Result<Vec<SignatureProof>> {
+    if private_keys.is_empty() {
+        return Err(SdkError::NoPrivateKeys);
+    }
+
+    let mut proofs = Vec::with_capacity(private_keys.len());
+    for key in private_keys {
+        let proof = sign_as(value, key, is_data_update)?;
+        ensure_new_signer(&proofs, &proof)?;
+        proofs.push(proof);
+    }
+    Ok(proofs)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -230,4 +329,34 @@ mod tests {
         let result = batch_sign::<serde_json::Value>(&data, &[], false);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_owned_constructors_match_borrowing_ones() {
+        // Not `Clone`, so only the by-value constructors accept it
+        #[derive(serde::Serialize)]
+        struct Payload {
+            id: String,
+        }
+
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let reference = json!({"id": "test"});
+
+        let payload = || Payload {
+            id: "test".to_string(),
+        };
+        let signed = Signed::new(payload(), &key1.private_key, true).unwrap();
+        let expected = create_signed_object(&reference, &key1.private_key, true).unwrap();
+        assert_eq!(signed.proofs, expected.proofs);
+        assert!(verify(&signed, true).is_valid);
+
+        let signed = create_signed_object_owned(payload(), &key1.private_key, false).unwrap();
+        assert!(verify(&signed, false).is_valid);
+
+        let signed =
+            batch_sign_owned(payload(), &[&key1.private_key, &key2.private_key], false).unwrap();
+        assert_eq!(signed.proofs.len(), 2);
+        assert!(verify(&signed, false).is_valid);
+        assert!(batch_sign_owned(payload(), &[], false).is_err());
+    }
 }
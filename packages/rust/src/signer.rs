@@ -0,0 +1,149 @@
+//! Signer abstraction
+//!
+//! Most signing functions take a private key in hex. A [`Signer`] hides the key
+//! behind two operations, exposing the public key ID and signing a hash, so
+//! higher-level code such as `network::Account` works the same with an
+//! in-memory key ([`LocalSigner`]) or a key held elsewhere.
+//!
+//! # Example
+//!
+//! ```
+//! use constellation_sdk::signer::{sign_object, LocalSigner, Signer};
+//! use constellation_sdk::verify::verify;
+//! use serde_json::json;
+//!
+//! let signer = LocalSigner::generate();
+//! let signed = sign_object(&signer, &json!({"id": "test"}), false).unwrap();
+//! assert!(verify(&signed, false).is_valid);
+//! assert_eq!(signed.proofs[0].id, signer.public_key_id());
+//! ```
+
+use rand::{CryptoRng, RngCore};
+use serde::Serialize;
+use std::fmt;
+
+use crate::hash::hash_data;
+use crate::sign::sign_hash;
+use crate::types::{KeyPair, Result, SignatureProof, Signed};
+use crate::wallet::{
+    generate_key_pair, generate_key_pair_with_rng, key_pair_from_private_key, try_get_address,
+};
+
+/// Something that can sign on behalf of one secp256k1 key
+pub trait Signer: Send + Sync {
+    /// Public key ID (uncompressed, without `04` prefix) - 128 characters
+    fn public_key_id(&self) -> String;
+
+    /// Sign a SHA-256 hash with the Constellation protocol
+    ///
+    /// Returns the DER-encoded signature in hex, as [`sign_hash`] does.
+    fn sign_hash(&self, hash_hex: &str) -> Result<String>;
+
+    /// DAG address of the key
+    ///
+    /// Empty if `public_key_id` is not a valid key, which every address
+    /// check rejects.
+    fn address(&self) -> String {
+        try_get_address(&self.public_key_id()).unwrap_or_default()
+    }
+
+    /// Sign a hash and wrap the signature in a proof
+    fn sign_hash_proof(&self, hash_hex: &str) -> Result<SignatureProof> {
+        Ok(SignatureProof {
+            id: self.public_key_id(),
+            signature: self.sign_hash(hash_hex)?,
+        })
+    }
+}
+
+/// A [`Signer`] holding the private key in memory
+#[derive(Clone)]
+pub struct LocalSigner {
+    key_pair: KeyPair,
+}
+
+impl LocalSigner {
+    /// Create a signer from a private key in hex
+    pub fn new(private_key: &str) -> Result<Self> {
+        Ok(Self {
+            key_pair: key_pair_from_private_key(private_key)?,
+        })
+    }
+
+    /// Create a signer with a freshly generated key
+    pub fn generate() -> Self {
+        Self {
+            key_pair: generate_key_pair(),
+        }
+    }
+
+    /// Create a signer with a key drawn from `rng`
+    ///
+    /// See [`generate_key_pair_with_rng`].
+    pub fn generate_with_rng<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> Self {
+        Self {
+            key_pair: generate_key_pair_with_rng(rng),
+        }
+    }
+
+    /// The underlying key pair
+    pub fn key_pair(&self) -> &KeyPair {
+        &self.key_pair
+    }
+}
+
+impl From<KeyPair> for LocalSigner {
+    fn from(key_pair: KeyPair) -> Self {
+        Self { key_pair }
+    }
+}
+
+impl fmt::Debug for LocalSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSigner")
+            .field("address", &self.key_pair.address)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Signer for LocalSigner {
+    fn public_key_id(&self) -> String {
+        self.key_pair.public_key[2..].to_string()
+    }
+
+    fn sign_hash(&self, hash_hex: &str) -> Result<String> {
+        sign_hash(hash_hex, &self.key_pair.private_key)
+    }
+
+    fn address(&self) -> String {
+        self.key_pair.address.clone()
+    }
+}
+
+/// Sign data with a [`Signer`], returning the proof
+///
+/// The counterpart of [`sign`](crate::sign::sign) and
+/// [`sign_data_update`](crate::sign::sign_data_update).
+pub fn sign_with<S, T>(signer: &S, data: &T, is_data_update: bool) -> Result<SignatureProof>
+where
+    S: Signer + ?Sized,
+    T: Serialize,
+{
+    let hash = hash_data(data, is_data_update)?;
+    signer.sign_hash_proof(&hash.value)
+}
+
+/// Create a signed object with a [`Signer`]
+///
+/// The counterpart of
+/// [`create_signed_object`](crate::signed_object::create_signed_object).
+pub fn sign_object<S, T>(signer: &S, value: &T, is_data_update: bool) -> Result<Signed<T>>
+where
+    S: Signer + ?Sized,
+    T: Serialize + Clone,
+{
+    Ok(Signed {
+        value: value.clone(),
+        proofs: vec![sign_with(signer, value, is_data_update)?],
+    })
+}
@@ -0,0 +1,232 @@
+//! Audit callback for every signature the SDK produces
+//!
+//! Custodial deployments need a record of what was signed by which key.
+//! Install a [`SigningAuditHook`] with [`set_signing_audit_hook`] and it
+//! receives a [`SigningRecord`] each time a private key held by the SDK
+//! signs something: `sign`, `sign_data_update`, `sign_hash`, their P-256
+//! counterparts, currency transactions, and [`LocalSigner`](crate::signer::LocalSigner).
+//! Signatures made by an external [`Signer`](crate::signer::Signer), such as
+//! an HSM, never reach these functions and are audited by that signer.
+//!
+//! Wrap a call in [`with_context`] to tag its records, e.g. with a
+//! withdrawal id, and feed the records through an [`AuditChain`] to make the
+//! log tamper-evident: each link commits to every record before it.
+//!
+//! The hook is process-wide, since the signing functions are free functions.
+//! While none is installed, signing costs one extra atomic load.
+//!
+//! # Example
+//!
+//! ```rust
+//! use constellation_sdk::signing_audit::{self, AuditChain, SigningRecord};
+//! use constellation_sdk::{sign, wallet::generate_key_pair};
+//! use serde_json::json;
+//! use std::sync::Mutex;
+//!
+//! let chain = Mutex::new(AuditChain::new());
+//! signing_audit::set_signing_audit_hook(move |record: &SigningRecord| {
+//!     let head = chain.lock().unwrap().append(record);
+//!     println!("{} {} {}", head, record.signer, record.hash);
+//! });
+//!
+//! let key_pair = generate_key_pair();
+//! signing_audit::with_context("withdrawal-42", || {
+//!     sign(&json!({"amount": 5}), &key_pair.private_key)
+//! })
+//! .unwrap();
+//! # signing_audit::clear_signing_audit_hook();
+//! ```
+
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::canonicalize::canonicalize_bytes;
+use crate::hash::hash_bytes;
+use crate::types::Result;
+
+/// One signature produced by the SDK
+///
+/// Never contains a private key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SigningRecord {
+    /// SHA-256 hash that was signed, as hex
+    pub hash: String,
+    /// Public key id of the signing key (uncompressed, without `04` prefix)
+    pub signer: String,
+    /// When the signature was made, in milliseconds since the Unix epoch
+    /// (zero on wasm32, which has no system clock)
+    pub timestamp: u64,
+    /// SDK function that signed, e.g. `sign_data_update` or
+    /// `currency_transaction`
+    pub operation: String,
+    /// Tag set by the innermost enclosing [`with_context`] call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub context: Option<String>,
+}
+
+/// Receiver of [`SigningRecord`]s
+///
+/// Called synchronously on the signing thread after each signature is made
+/// and before it is returned, so it should hand records off rather than
+/// block. Implemented for closures taking a `&SigningRecord`.
+pub trait SigningAuditHook: Send + Sync {
+    /// Record one signature
+    fn record(&self, record: &SigningRecord);
+}
+
+impl<F> SigningAuditHook for F
+where
+    F: Fn(&SigningRecord) + Send + Sync,
+{
+    fn record(&self, record: &SigningRecord) {
+        self(record)
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static HOOK: RwLock<Option<Arc<dyn SigningAuditHook>>> = RwLock::new(None);
+
+std::thread_local! {
+    static CONTEXT: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Install `hook`, replacing any previous one
+pub fn set_signing_audit_hook(hook: impl SigningAuditHook + 'static) {
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(hook));
+    ENABLED.store(true, Ordering::Release);
+}
+
+/// Remove the installed hook, if any
+pub fn clear_signing_audit_hook() {
+    ENABLED.store(false, Ordering::Release);
+    *HOOK.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+/// Whether a hook is installed
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+/// Run `f` with `tag` as the context of every signature it makes on this
+/// thread
+///
+/// Calls nest; the innermost tag wins and the outer one is restored
+/// afterwards. The tag does not follow work onto other threads or across
+/// an `.await` that resumes elsewhere.
+pub fn with_context<R>(tag: &str, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<String>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            let _ = CONTEXT.try_with(|context| *context.borrow_mut() = previous);
+        }
+    }
+
+    let previous = CONTEXT.with(|context| context.borrow_mut().replace(tag.to_string()));
+    let _restore = Restore(previous);
+    f()
+}
+
+/// Pass a signature to the installed hook
+///
+/// `signer` is only called while a hook is installed, so callers can derive
+/// the public key id lazily. A signer that fails to derive is recorded as
+/// an empty id rather than failing a signature that was already made.
+pub(crate) fn record(
+    operation: &'static str,
+    hash_hex: &str,
+    signer: impl FnOnce() -> Result<String>,
+) {
+    if !is_enabled() {
+        return;
+    }
+    // Clone the hook out so it can replace itself without deadlocking
+    let hook = HOOK.read().unwrap_or_else(|e| e.into_inner()).clone();
+    if let Some(hook) = hook {
+        hook.record(&SigningRecord {
+            hash: hash_hex.to_string(),
+            signer: signer().unwrap_or_default(),
+            timestamp: now_millis(),
+            operation: operation.to_string(),
+            context: CONTEXT
+                .try_with(|context| context.borrow().clone())
+                .ok()
+                .flatten(),
+        });
+    }
+}
+
+// `SystemTime::now` panics in the browser
+#[cfg(not(target_arch = "wasm32"))]
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn now_millis() -> u64 {
+    0
+}
+
+/// Hash chain over signing records
+///
+/// Each link is the SHA-256 of the previous link followed by the record's
+/// canonical JSON, starting from 64 zeros. Storing the latest link apart
+/// from the log (or publishing it) lets anyone holding the records detect
+/// an edited, dropped, or reordered entry with [`AuditChain::verify`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditChain {
+    head: String,
+}
+
+impl AuditChain {
+    /// Start an empty chain
+    pub fn new() -> Self {
+        Self {
+            head: "0".repeat(64),
+        }
+    }
+
+    /// Continue a chain whose latest link is `head`
+    pub fn resume(head: impl Into<String>) -> Self {
+        Self { head: head.into() }
+    }
+
+    /// Latest link, as hex
+    pub fn head(&self) -> &str {
+        &self.head
+    }
+
+    /// Add a record, returning the new link
+    pub fn append(&mut self, record: &SigningRecord) -> String {
+        let mut bytes = self.head.clone().into_bytes();
+        // Serializing a record of strings and integers cannot fail
+        bytes.extend(canonicalize_bytes(record).unwrap_or_default());
+        self.head = hash_bytes(&bytes).value;
+        self.head.clone()
+    }
+
+    /// Whether chaining `records` from the start gives `head`
+    pub fn verify(records: &[SigningRecord], head: &str) -> bool {
+        let mut chain = Self::new();
+        for record in records {
+            chain.append(record);
+        }
+        crate::core::ct::ct_eq(chain.head.as_bytes(), head.as_bytes())
+    }
+}
+
+impl Default for AuditChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,266 @@
+//! Signatures that expire
+//!
+//! A signature over a login challenge or an API request stays valid forever,
+//! so anyone who captures one can replay it. Wrapping the payload in a
+//! [`Timestamped`] envelope before signing binds the signature to a moment
+//! and a random nonce:
+//!
+//! ```json
+//! {"nonce": "9f2c…", "payload": {...}, "timestamp": 1718000000000}
+//! ```
+//!
+//! The envelope is signed as ordinary data, so any Constellation SDK can
+//! produce or check the signature. [`verify_within`] additionally refuses an
+//! envelope whose timestamp is more than a window away from now, and a
+//! [`ReplayGuard`] refuses one whose nonce it has already accepted.
+//!
+//! The functions that read the clock are not available on wasm32, which has
+//! no system clock; pass the time to the `_at` variants instead.
+//!
+//! # Example
+//!
+//! ```
+//! use constellation_sdk::timestamped::{sign_timestamped, ReplayGuard};
+//! use constellation_sdk::wallet::generate_key_pair;
+//! use serde_json::json;
+//! use std::time::Duration;
+//!
+//! let key_pair = generate_key_pair();
+//! let signed = sign_timestamped(json!({"login": "alice"}), &key_pair.private_key).unwrap();
+//!
+//! let guard = ReplayGuard::new(Duration::from_secs(60));
+//! assert!(guard.check(&signed).is_ok());
+//! assert!(guard.check(&signed).is_err()); // replayed
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::signer::{sign_with, Signer};
+use crate::types::{Result, Signed};
+use crate::verify::{signer_key, verify};
+
+/// A payload with the time it was signed and a random nonce
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Timestamped<T> {
+    /// The signed value
+    pub payload: T,
+    /// Milliseconds since the Unix epoch
+    pub timestamp: u64,
+    /// Random value, unique per envelope, in hex
+    pub nonce: String,
+}
+
+impl<T> Timestamped<T> {
+    /// Wrap `payload` with the current time and a fresh 128-bit nonce
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new(payload: T) -> Self {
+        Self::at(payload, now_millis(), random_nonce())
+    }
+
+    /// Wrap `payload` with a given time and nonce
+    pub fn at(payload: T, timestamp: u64, nonce: impl Into<String>) -> Self {
+        Self {
+            payload,
+            timestamp,
+            nonce: nonce.into(),
+        }
+    }
+}
+
+/// Sign `payload` in a [`Timestamped`] envelope with a private key in hex
+#[cfg(not(target_arch = "wasm32"))]
+pub fn sign_timestamped<T: Serialize>(
+    payload: T,
+    private_key: &str,
+) -> Result<Signed<Timestamped<T>>> {
+    sign_timestamped_with(&crate::signer::LocalSigner::new(private_key)?, payload)
+}
+
+/// Sign `payload` in a [`Timestamped`] envelope with a [`Signer`]
+#[cfg(not(target_arch = "wasm32"))]
+pub fn sign_timestamped_with<S, T>(signer: &S, payload: T) -> Result<Signed<Timestamped<T>>>
+where
+    S: Signer + ?Sized,
+    T: Serialize,
+{
+    sign_envelope(signer, Timestamped::new(payload))
+}
+
+/// Sign an envelope built with [`Timestamped::at`]
+pub fn sign_envelope<S, T>(signer: &S, envelope: Timestamped<T>) -> Result<Signed<Timestamped<T>>>
+where
+    S: Signer + ?Sized,
+    T: Serialize,
+{
+    let proof = sign_with(signer, &envelope, false)?;
+    Ok(Signed {
+        value: envelope,
+        proofs: vec![proof],
+    })
+}
+
+/// Why a timestamped envelope was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FreshnessError {
+    /// A signature is missing or does not verify
+    InvalidSignature,
+    /// The envelope was signed longer ago than the window
+    Expired {
+        /// Milliseconds between the timestamp and now
+        age_ms: u64,
+    },
+    /// The timestamp is further in the future than the window
+    FromTheFuture {
+        /// Milliseconds between now and the timestamp
+        ahead_ms: u64,
+    },
+    /// A [`ReplayGuard`] has already accepted this nonce from this signer
+    Replayed,
+}
+
+impl fmt::Display for FreshnessError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FreshnessError::InvalidSignature => write!(f, "signature does not verify"),
+            FreshnessError::Expired { age_ms } => {
+                write!(f, "signed {age_ms} ms ago, outside the window")
+            }
+            FreshnessError::FromTheFuture { ahead_ms } => {
+                write!(
+                    f,
+                    "timestamp is {ahead_ms} ms in the future, outside the window"
+                )
+            }
+            FreshnessError::Replayed => write!(f, "nonce has already been used"),
+        }
+    }
+}
+
+impl std::error::Error for FreshnessError {}
+
+/// Verify a timestamped envelope and check it was signed within `window`
+/// of now
+///
+/// The window applies both ways, to allow for clock skew between signer and
+/// verifier. This does not stop a replay inside the window; use a
+/// [`ReplayGuard`] for that.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn verify_within<T: Serialize>(
+    signed: &Signed<Timestamped<T>>,
+    window: Duration,
+) -> std::result::Result<(), FreshnessError> {
+    verify_within_at(signed, window, now_millis())
+}
+
+/// [`verify_within`] against a given time in milliseconds since the Unix
+/// epoch
+pub fn verify_within_at<T: Serialize>(
+    signed: &Signed<Timestamped<T>>,
+    window: Duration,
+    now_ms: u64,
+) -> std::result::Result<(), FreshnessError> {
+    if !verify(signed, false).is_valid {
+        return Err(FreshnessError::InvalidSignature);
+    }
+    let window_ms = u64::try_from(window.as_millis()).unwrap_or(u64::MAX);
+    let timestamp = signed.value.timestamp;
+    if timestamp > now_ms {
+        let ahead_ms = timestamp - now_ms;
+        if ahead_ms > window_ms {
+            return Err(FreshnessError::FromTheFuture { ahead_ms });
+        }
+    } else {
+        let age_ms = now_ms - timestamp;
+        if age_ms > window_ms {
+            return Err(FreshnessError::Expired { age_ms });
+        }
+    }
+    Ok(())
+}
+
+/// Accepts each timestamped envelope at most once
+///
+/// Remembers the nonce of every envelope it accepts, with each of its
+/// signers, until the envelope falls outside the window, after which [`verify_within`] refuses
+/// it anyway. Share one guard between the handlers that accept the same
+/// kind of message.
+#[derive(Debug)]
+pub struct ReplayGuard {
+    window: Duration,
+    /// Normalized signer and nonce of each accepted proof, to its timestamp
+    seen: Mutex<HashMap<(String, String), u64>>,
+}
+
+impl ReplayGuard {
+    /// A guard accepting envelopes signed within `window` of now
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            seen: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Verify an envelope as [`verify_within`] does and refuse a nonce that
+    /// was already accepted from any of its signers
+    ///
+    /// Signers are compared by normalized public key, so re-encoding a key
+    /// or reordering the proofs does not get an envelope past the guard.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn check<T: Serialize>(
+        &self,
+        signed: &Signed<Timestamped<T>>,
+    ) -> std::result::Result<(), FreshnessError> {
+        self.check_at(signed, now_millis())
+    }
+
+    /// [`check`](Self::check) against a given time in milliseconds since
+    /// the Unix epoch
+    pub fn check_at<T: Serialize>(
+        &self,
+        signed: &Signed<Timestamped<T>>,
+        now_ms: u64,
+    ) -> std::result::Result<(), FreshnessError> {
+        verify_within_at(signed, self.window, now_ms)?;
+        let window_ms = u64::try_from(self.window.as_millis()).unwrap_or(u64::MAX);
+        let mut seen = self.seen.lock().unwrap_or_else(|e| e.into_inner());
+        seen.retain(|_, timestamp| timestamp.saturating_add(window_ms) >= now_ms);
+        let keys: Vec<(String, String)> = signed
+            .proofs
+            .iter()
+            .map(|proof| (signer_key(&proof.id), signed.value.nonce.clone()))
+            .collect();
+        if keys.iter().any(|key| seen.contains_key(key)) {
+            return Err(FreshnessError::Replayed);
+        }
+        for key in keys {
+            seen.insert(key, signed.value.timestamp);
+        }
+        Ok(())
+    }
+}
+
+/// Milliseconds since the Unix epoch
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn random_nonce() -> String {
+    use rand::RngCore;
+
+    let mut nonce = [0u8; 16];
+    rand::rngs::OsRng.fill_bytes(&mut nonce);
+    hex::encode(nonce)
+}
@@ -1,7 +1,13 @@
 //! Core type definitions for the Constellation Metagraph SDK
 
+use alloc::string::String;
+#[cfg(feature = "r1")]
+use alloc::string::ToString;
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
-use thiserror::Error;
+
+use crate::core::ct::ct_eq;
+use crate::core::verify::signer_key;
 
 /// Supported signature algorithms
 pub const ALGORITHM: &str = "SECP256K1_RFC8785_V1";
@@ -10,6 +16,9 @@ pub const ALGORITHM_R1: &str = "SECP256R1_RFC8785_V1";
 /// Constellation prefix for DataUpdate signing
 pub const CONSTELLATION_PREFIX: &str = "\x19Constellation Signed Data:\n";
 
+/// Constellation prefix for domain-separated signing
+pub const DOMAIN_PREFIX: &str = "\x19Constellation Domain:";
+
 /// Signing scheme identifying the curve and serialization format
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SigningScheme {
@@ -30,6 +39,14 @@ pub struct SignatureProof {
     pub signature: String,
 }
 
+impl SignatureProof {
+    /// Whether two proofs are equal, compared in constant time
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq(self.id.as_bytes(), other.id.as_bytes())
+            & ct_eq(self.signature.as_bytes(), other.signature.as_bytes())
+    }
+}
+
 /// A signed object wrapping a value with one or more signature proofs
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Signed<T> {
@@ -39,8 +56,48 @@ pub struct Signed<T> {
     pub proofs: Vec<SignatureProof>,
 }
 
+impl<T> Signed<T> {
+    /// Public key IDs with more than one proof, lowercase and without the
+    /// `04` prefix
+    ///
+    /// Nodes reject a submission in which a key signs twice, whether with
+    /// identical or different signatures.
+    pub fn duplicate_signers(&self) -> Vec<String> {
+        let mut signers: Vec<String> = self.proofs.iter().map(|p| signer_key(&p.id)).collect();
+        signers.sort();
+        let mut duplicates: Vec<String> = signers
+            .windows(2)
+            .filter(|pair| pair[0] == pair[1])
+            .map(|pair| pair[0].clone())
+            .collect();
+        duplicates.dedup();
+        duplicates
+    }
+
+    /// Keep one proof per signer and sort the proofs by signer
+    ///
+    /// Proofs are ordered by public key ID, then signature, and the first
+    /// proof of each signer is kept, so the result does not depend on the
+    /// order the proofs were collected in. Signatures are not checked; a
+    /// signer's surviving proof may be invalid where a dropped one was not,
+    /// so verify before deduplicating proofs from untrusted sources.
+    ///
+    /// Returns the number of proofs removed.
+    pub fn dedupe_proofs(&mut self) -> usize {
+        let before = self.proofs.len();
+        self.proofs
+            .sort_by_cached_key(|p| (signer_key(&p.id), p.signature.to_ascii_lowercase()));
+        self.proofs
+            .dedup_by(|b, a| signer_key(&a.id) == signer_key(&b.id));
+        before - self.proofs.len()
+    }
+}
+
 /// A key pair for signing operations
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Equality compares in constant time, since the pair holds a private key.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
 pub struct KeyPair {
     /// Private key in hex format (64 characters)
     pub private_key: String,
@@ -50,6 +107,16 @@ pub struct KeyPair {
     pub address: String,
 }
 
+impl PartialEq for KeyPair {
+    fn eq(&self, other: &Self) -> bool {
+        ct_eq(self.private_key.as_bytes(), other.private_key.as_bytes())
+            & ct_eq(self.public_key.as_bytes(), other.public_key.as_bytes())
+            & ct_eq(self.address.as_bytes(), other.address.as_bytes())
+    }
+}
+
+impl Eq for KeyPair {}
+
 /// A hash result containing both hex string and raw bytes
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Hash {
@@ -59,6 +126,16 @@ pub struct Hash {
     pub bytes: Vec<u8>,
 }
 
+impl Hash {
+    /// Whether two hashes are equal, compared in constant time
+    ///
+    /// Use this rather than `==` when one side is secret, such as a MAC or a
+    /// commitment an attacker is trying to match.
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        ct_eq(&self.bytes, &other.bytes) & ct_eq(self.value.as_bytes(), other.value.as_bytes())
+    }
+}
+
 /// Result of signature verification
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VerificationResult {
@@ -78,45 +155,128 @@ pub struct SigningOptions {
 }
 
 /// SDK error types
-#[derive(Error, Debug)]
+///
+/// Every variant has a stable numeric [`code`](Self::code) and a
+/// [`category`](Self::category), for FFI callers and log aggregation that
+/// cannot rely on messages. Errors converted from `hex`, `secp256k1`, and
+/// `serde_json` keep the original error, available through
+/// [`source`](core::error::Error::source) with the `std` feature.
+#[derive(Debug)]
+#[non_exhaustive]
 pub enum SdkError {
-    #[error("Invalid private key: {0}")]
     InvalidPrivateKey(String),
-
-    #[error("Invalid public key: {0}")]
     InvalidPublicKey(String),
-
-    #[error("Invalid signature: {0}")]
     InvalidSignature(String),
-
-    #[error("Serialization error: {0}")]
     SerializationError(String),
-
-    #[error("Cryptographic error: {0}")]
     CryptoError(String),
-
-    #[error("Invalid hex string: {0}")]
     HexError(String),
-
-    #[error("At least one private key is required")]
     NoPrivateKeys,
-
-    #[error("Invalid address: {0}")]
     InvalidAddress(String),
-
-    #[error("Invalid amount: {0}")]
     InvalidAmount(String),
+    InvalidPayload(String),
+    /// A hex string failed to decode
+    Hex(hex::FromHexError),
+    /// secp256k1 rejected a key, message, or signature
+    Secp256k1(secp256k1::Error),
+    /// JSON serialization or deserialization failed
+    Json(serde_json::Error),
+}
+
+/// Broad class of an [`SdkError`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// A private or public key is malformed or missing
+    Key,
+    /// A signature is malformed or does not verify
+    Signature,
+    /// A value could not be encoded or decoded
+    Encoding,
+    /// A cryptographic operation failed
+    Crypto,
+    /// An address, amount, or payload was rejected
+    Input,
+}
+
+impl SdkError {
+    /// Stable numeric code of this error
+    ///
+    /// Codes never change meaning between releases. The thousands digit is
+    /// the [`category`](Self::category): 1xxx key, 2xxx signature, 3xxx
+    /// encoding, 4xxx crypto, 5xxx input.
+    pub fn code(&self) -> u32 {
+        match self {
+            SdkError::InvalidPrivateKey(_) => 1001,
+            SdkError::InvalidPublicKey(_) => 1002,
+            SdkError::NoPrivateKeys => 1003,
+            SdkError::InvalidSignature(_) => 2001,
+            SdkError::SerializationError(_) => 3001,
+            SdkError::HexError(_) => 3002,
+            SdkError::Hex(_) => 3003,
+            SdkError::Json(_) => 3004,
+            SdkError::CryptoError(_) => 4001,
+            SdkError::Secp256k1(_) => 4002,
+            SdkError::InvalidAddress(_) => 5001,
+            SdkError::InvalidAmount(_) => 5002,
+            SdkError::InvalidPayload(_) => 5003,
+        }
+    }
+
+    /// Broad class of this error
+    pub fn category(&self) -> ErrorCategory {
+        match self.code() / 1000 {
+            1 => ErrorCategory::Key,
+            2 => ErrorCategory::Signature,
+            3 => ErrorCategory::Encoding,
+            4 => ErrorCategory::Crypto,
+            _ => ErrorCategory::Input,
+        }
+    }
+}
+
+impl core::fmt::Display for SdkError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            SdkError::InvalidPrivateKey(msg) => write!(f, "Invalid private key: {msg}"),
+            SdkError::InvalidPublicKey(msg) => write!(f, "Invalid public key: {msg}"),
+            SdkError::InvalidSignature(msg) => write!(f, "Invalid signature: {msg}"),
+            SdkError::SerializationError(msg) => write!(f, "Serialization error: {msg}"),
+            SdkError::CryptoError(msg) => write!(f, "Cryptographic error: {msg}"),
+            SdkError::HexError(msg) => write!(f, "Invalid hex string: {msg}"),
+            SdkError::NoPrivateKeys => f.write_str("At least one private key is required"),
+            SdkError::InvalidAddress(msg) => write!(f, "Invalid address: {msg}"),
+            SdkError::InvalidAmount(msg) => write!(f, "Invalid amount: {msg}"),
+            SdkError::InvalidPayload(msg) => write!(f, "Invalid payload: {msg}"),
+            SdkError::Hex(err) => write!(f, "Invalid hex string: {err}"),
+            SdkError::Secp256k1(err) => write!(f, "Cryptographic error: {err}"),
+            SdkError::Json(err) => write!(f, "Serialization error: {err}"),
+        }
+    }
+}
+
+// `hex` and `secp256k1` only implement `Error` with their `std` features
+impl core::error::Error for SdkError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            #[cfg(feature = "std")]
+            SdkError::Hex(err) => Some(err),
+            #[cfg(feature = "std")]
+            SdkError::Secp256k1(err) => Some(err),
+            #[cfg(feature = "std")]
+            SdkError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 impl From<hex::FromHexError> for SdkError {
     fn from(err: hex::FromHexError) -> Self {
-        SdkError::HexError(err.to_string())
+        SdkError::Hex(err)
     }
 }
 
 impl From<secp256k1::Error> for SdkError {
     fn from(err: secp256k1::Error) -> Self {
-        SdkError::CryptoError(err.to_string())
+        SdkError::Secp256k1(err)
     }
 }
 
@@ -138,9 +298,9 @@ impl From<elliptic_curve::Error> for SdkError {
 
 impl From<serde_json::Error> for SdkError {
     fn from(err: serde_json::Error) -> Self {
-        SdkError::SerializationError(err.to_string())
+        SdkError::Json(err)
     }
 }
 
 /// Result type for SDK operations
-pub type Result<T> = std::result::Result<T, SdkError>;
+pub type Result<T> = core::result::Result<T, SdkError>;
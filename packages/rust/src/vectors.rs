@@ -0,0 +1,154 @@
+//! Golden test vectors shared with the other SDKs
+//!
+//! The same fixtures are checked by the TypeScript, Python, Go, and Java
+//! SDKs: each gives a payload with its canonical JSON, the bytes that are
+//! signed, their SHA-256 hash, and a signature made by the SDK that produced
+//! it. [`verify_vector`] runs this SDK over a vector and reports the first
+//! step whose output differs, so an integrator with their own serializer can
+//! add their vectors to the set and prove byte-level compatibility.
+//!
+//! # Example
+//!
+//! ```
+//! use constellation_sdk::vectors::{test_vectors, verify_vector};
+//!
+//! for vector in test_vectors() {
+//!     verify_vector(&vector).unwrap();
+//! }
+//! ```
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::binary::to_bytes;
+use crate::core::canonicalize::canonicalize;
+use crate::core::hash::hash_bytes;
+use crate::core::verify::verify_hash;
+use crate::types::{Result, SdkError};
+
+/// The fixtures in `shared/test_vectors.json`, embedded at build time
+const TEST_VECTORS_JSON: &str = include_str!("../vectors/test_vectors.json");
+
+/// How a vector's payload is signed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VectorKind {
+    /// Regular data, signed over its canonical JSON
+    TestData,
+    /// A DataUpdate, signed over the prefixed base64 encoding
+    TestDataUpdate,
+}
+
+impl VectorKind {
+    /// Whether the payload is signed as a DataUpdate
+    pub fn is_data_update(self) -> bool {
+        self == VectorKind::TestDataUpdate
+    }
+}
+
+/// One payload with the expected output of every signing step
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TestVector {
+    /// SDK that produced the vector (`javascript`, `python`, `go`, `rust`, ...)
+    pub source: String,
+    /// How the payload is signed
+    #[serde(rename = "type")]
+    pub kind: VectorKind,
+    /// The payload
+    pub data: Value,
+    /// RFC 8785 canonical JSON of the payload
+    pub canonical_json: String,
+    /// Bytes that are hashed, in hex
+    pub utf8_bytes_hex: String,
+    /// SHA-256 of those bytes, in hex
+    pub sha256_hash_hex: String,
+    /// DER signature over the hash, in hex
+    pub signature_hex: String,
+    /// Uncompressed public key of the signer, in hex
+    pub public_key_hex: String,
+}
+
+/// The golden vectors shipped with the SDK
+pub fn test_vectors() -> Vec<TestVector> {
+    serde_json::from_str(TEST_VECTORS_JSON).expect("embedded test vectors are valid")
+}
+
+/// Check that this SDK reproduces a vector byte for byte
+///
+/// Canonicalizes the payload, encodes it, hashes it, and verifies the
+/// signature, comparing each step with the vector.
+///
+/// # Errors
+///
+/// Returns a `SerializationError` naming the first step whose output differs
+/// from the vector, an `InvalidSignature` error if the signature does not
+/// verify, or the error of the step that failed
+pub fn verify_vector(vector: &TestVector) -> Result<()> {
+    let name = format!("{} {:?} vector", vector.source, vector.kind);
+
+    let canonical = canonicalize(&vector.data)?;
+    check(&name, "canonical JSON", &vector.canonical_json, &canonical)?;
+
+    let bytes = to_bytes(&vector.data, vector.kind.is_data_update())?;
+    check(&name, "bytes", &vector.utf8_bytes_hex, &hex::encode(&bytes))?;
+
+    let hash = hash_bytes(&bytes);
+    check(&name, "SHA-256 hash", &vector.sha256_hash_hex, &hash.value)?;
+
+    if !verify_hash(&hash.value, &vector.signature_hex, &vector.public_key_hex)? {
+        return Err(SdkError::InvalidSignature(format!(
+            "{name}: signature does not verify"
+        )));
+    }
+    Ok(())
+}
+
+fn check(name: &str, step: &str, expected: &str, actual: &str) -> Result<()> {
+    if expected.eq_ignore_ascii_case(actual) {
+        Ok(())
+    } else {
+        Err(SdkError::SerializationError(format!(
+            "{name}: {step} mismatch, expected {expected}, got {actual}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_vectors_verify() {
+        let vectors = test_vectors();
+        assert!(!vectors.is_empty());
+        for vector in &vectors {
+            verify_vector(vector).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_reports_mismatched_step() {
+        let mut vector = test_vectors().remove(0);
+        vector.sha256_hash_hex = "00".repeat(32);
+        let err = verify_vector(&vector).unwrap_err().to_string();
+        assert!(err.contains("SHA-256 hash mismatch"), "{err}");
+
+        let mut vector = test_vectors().remove(0);
+        vector.data["extra"] = Value::Bool(true);
+        let err = verify_vector(&vector).unwrap_err().to_string();
+        assert!(err.contains("canonical JSON mismatch"), "{err}");
+    }
+
+    #[test]
+    fn test_rejects_foreign_signature() {
+        let vectors = test_vectors();
+        let mut vector = vectors[0].clone();
+        vector.signature_hex = vectors[1].signature_hex.clone();
+        assert!(matches!(
+            verify_vector(&vector),
+            Err(SdkError::InvalidSignature(_))
+        ));
+    }
+}
@@ -2,14 +2,27 @@
 //!
 //! Functions for generating and managing cryptographic keys.
 
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
 use rand::rngs::OsRng;
-use secp256k1::{PublicKey, Secp256k1, SecretKey};
+#[cfg(feature = "std")]
+use rand::{CryptoRng, RngCore};
+use secp256k1::{PublicKey, SecretKey};
 use sha2::{Digest, Sha256};
 
+use crate::core::context::secp;
 use crate::types::{KeyPair, Result, SdkError};
 
 const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
 
+/// PKCS prefix for X.509 DER encoding (secp256k1)
+const PKCS_PREFIX: [u8; 23] = [
+    0x30, 0x56, 0x30, 0x10, 0x06, 0x07, 0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01, 0x06, 0x05, 0x2b,
+    0x81, 0x04, 0x00, 0x0a, 0x03, 0x42, 0x00,
+];
+
 /// Generate a new random key pair
 ///
 /// # Example
@@ -21,13 +34,44 @@ const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmno
 /// println!("Private key: {}", key_pair.private_key);
 /// println!("Public key: {}", key_pair.public_key);
 /// ```
+///
+/// Draws from the operating system's RNG, so it needs the `std` feature;
+/// `no_std` builds import keys with [`key_pair_from_private_key`].
+#[cfg(feature = "std")]
 pub fn generate_key_pair() -> KeyPair {
-    let secp = Secp256k1::new();
-    let (secret_key, public_key) = secp.generate_keypair(&mut OsRng);
+    generate_key_pair_with_rng(&mut OsRng)
+}
+
+/// Generate a new key pair from the given random number generator
+///
+/// Use this to draw keys from an HSM-seeded or audited entropy source, or
+/// from a seeded RNG for deterministic keys in tests. Never use a seeded RNG
+/// for real keys.
+///
+/// # Example
+/// ```
+/// use constellation_sdk::wallet::generate_key_pair_with_rng;
+/// use rand::{rngs::StdRng, SeedableRng};
+///
+/// let a = generate_key_pair_with_rng(&mut StdRng::seed_from_u64(7));
+/// let b = generate_key_pair_with_rng(&mut StdRng::seed_from_u64(7));
+/// assert_eq!(a, b);
+/// ```
+#[cfg(feature = "std")]
+pub fn generate_key_pair_with_rng<R: RngCore + CryptoRng + ?Sized>(rng: &mut R) -> KeyPair {
+    // Fewer than one in 2^127 draws is not a valid key
+    let secret_key = loop {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        if let Ok(secret_key) = SecretKey::from_slice(&bytes) {
+            break secret_key;
+        }
+    };
+    let public_key = PublicKey::from_secret_key(&secp(), &secret_key);
 
     let private_key_hex = hex::encode(secret_key.secret_bytes());
     let public_key_hex = hex::encode(public_key.serialize_uncompressed());
-    let address = get_address(&public_key_hex);
+    let address = address_of(&public_key);
 
     KeyPair {
         private_key: private_key_hex,
@@ -56,13 +100,13 @@ pub fn key_pair_from_private_key(private_key: &str) -> Result<KeyPair> {
         ));
     }
 
-    let secp = Secp256k1::new();
+    let secp = secp();
     let private_key_bytes = hex::decode(private_key)?;
     let secret_key = SecretKey::from_slice(&private_key_bytes)?;
     let public_key = PublicKey::from_secret_key(&secp, &secret_key);
 
     let public_key_hex = hex::encode(public_key.serialize_uncompressed());
-    let address = get_address(&public_key_hex);
+    let address = address_of(&public_key);
 
     Ok(KeyPair {
         private_key: private_key.to_string(),
@@ -78,7 +122,7 @@ pub fn key_pair_from_private_key(private_key: &str) -> Result<KeyPair> {
 /// * `compressed` - If true, returns compressed public key (33 bytes)
 pub fn get_public_key_hex(private_key: &str, compressed: bool) -> Result<String> {
     let private_key_bytes = hex::decode(private_key)?;
-    let secp = Secp256k1::new();
+    let secp = secp();
     let secret_key = SecretKey::from_slice(&private_key_bytes)?;
     let public_key = PublicKey::from_secret_key(&secp, &secret_key);
 
@@ -115,21 +159,52 @@ pub fn get_public_key_id(private_key: &str) -> Result<String> {
 /// 7. Result: DAG + parity + last36
 ///
 /// # Arguments
-/// * `public_key` - Public key in hex format (with or without 04 prefix)
-pub fn get_address(public_key: &str) -> String {
-    // PKCS prefix for X.509 DER encoding (secp256k1)
-    const PKCS_PREFIX: &str = "3056301006072a8648ce3d020106052b8104000a034200";
+/// * `public_key` - Public key in hex, uncompressed (with or without 04
+///   prefix) or compressed
+///
+/// # Errors
+///
+/// Returns `InvalidPublicKey` if the key is not hex or not a point on the
+/// curve
+///
+/// # Example
+/// ```
+/// use constellation_sdk::wallet::{generate_key_pair, try_get_address};
+///
+/// let key_pair = generate_key_pair();
+/// assert_eq!(try_get_address(&key_pair.public_key).unwrap(), key_pair.address);
+/// assert!(try_get_address("not a key").is_err());
+/// ```
+pub fn try_get_address(public_key: &str) -> Result<String> {
+    let bytes = hex::decode(normalize_public_key(public_key))
+        .map_err(|e| SdkError::InvalidPublicKey(format!("Invalid public key hex: {e}")))?;
+    let public_key =
+        PublicKey::from_slice(&bytes).map_err(|e| SdkError::InvalidPublicKey(e.to_string()))?;
+    Ok(address_of(&public_key))
+}
 
-    // Normalize public key to include 04 prefix
-    let normalized_key = normalize_public_key(public_key);
+/// Get DAG address from a public key, without checking it
+///
+/// Input that is not hex is hashed as if it were empty, producing an address
+/// that belongs to no key.
+#[deprecated(note = "returns a bogus address for malformed keys; use `try_get_address`")]
+pub fn get_address(public_key: &str) -> String {
+    match hex::decode(normalize_public_key(public_key)) {
+        Ok(key) => address_from_pkcs(&[&PKCS_PREFIX[..], &key].concat()),
+        Err(_) => address_from_pkcs(&[]),
+    }
+}
 
-    // Prepend PKCS prefix
-    let pkcs_encoded = format!("{PKCS_PREFIX}{normalized_key}");
+/// DAG address of a parsed public key
+pub(crate) fn address_of(public_key: &PublicKey) -> String {
+    address_from_pkcs(&[&PKCS_PREFIX[..], &public_key.serialize_uncompressed()].concat())
+}
 
+/// DAG address of a DER-encoded public key
+fn address_from_pkcs(pkcs_bytes: &[u8]) -> String {
     // SHA-256 hash
-    let pkcs_bytes = hex::decode(&pkcs_encoded).unwrap_or_default();
     let mut hasher = Sha256::new();
-    hasher.update(&pkcs_bytes);
+    hasher.update(pkcs_bytes);
     let hash = hasher.finalize();
 
     // Base58 encode
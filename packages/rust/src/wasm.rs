@@ -0,0 +1,161 @@
+//! JavaScript bindings for browser builds
+//!
+//! With the `wasm` feature the core signing API is exported through
+//! wasm-bindgen, so browser extensions and dapps can use the SDK in place of
+//! dag4.js crypto. Values cross the boundary as JSON strings, hex strings, and
+//! `Uint8Array`s; errors are thrown as JavaScript `Error`s carrying the
+//! [`SdkError`](crate::SdkError) message.
+//!
+//! Build the module as a `cdylib` and generate the JavaScript glue with
+//! wasm-bindgen:
+//!
+//! ```bash
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/constellation_sdk.wasm
+//! ```
+//!
+//! # Example
+//!
+//! ```js
+//! import init, { generateKeyPair, createSignedObject, verify } from "constellation_sdk";
+//!
+//! await init();
+//! const keyPair = generateKeyPair();
+//! const signed = createSignedObject(JSON.stringify({ id: "sensor-1" }), keyPair.privateKey, true);
+//! console.log(verify(signed, true)); // true
+//! ```
+
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+use crate::types::{KeyPair, SignatureProof, Signed};
+
+/// A key pair as seen from JavaScript
+#[wasm_bindgen(js_name = KeyPair, getter_with_clone)]
+#[derive(Debug, Clone)]
+pub struct JsKeyPair {
+    /// Private key in hex format (64 characters)
+    #[wasm_bindgen(js_name = privateKey)]
+    pub private_key: String,
+    /// Public key in hex format (uncompressed, with 04 prefix - 130 characters)
+    #[wasm_bindgen(js_name = publicKey)]
+    pub public_key: String,
+    /// DAG address derived from the public key
+    pub address: String,
+}
+
+impl From<KeyPair> for JsKeyPair {
+    fn from(key_pair: KeyPair) -> Self {
+        Self {
+            private_key: key_pair.private_key,
+            public_key: key_pair.public_key,
+            address: key_pair.address,
+        }
+    }
+}
+
+/// Generate a random key pair
+#[wasm_bindgen(js_name = generateKeyPair)]
+pub fn generate_key_pair() -> JsKeyPair {
+    crate::wallet::generate_key_pair().into()
+}
+
+/// Derive the key pair of a private key in hex
+#[wasm_bindgen(js_name = keyPairFromPrivateKey)]
+pub fn key_pair_from_private_key(private_key: &str) -> Result<JsKeyPair, JsError> {
+    Ok(crate::wallet::key_pair_from_private_key(private_key)?.into())
+}
+
+/// DAG address of a public key in hex
+#[wasm_bindgen(js_name = getAddress)]
+pub fn get_address(public_key: &str) -> Result<String, JsError> {
+    Ok(crate::wallet::try_get_address(public_key)?)
+}
+
+/// Sign a JSON value and return the signed object as JSON
+///
+/// Set `is_data_update` to sign with the DataUpdate prefix for L1 submission.
+#[wasm_bindgen(js_name = createSignedObject)]
+pub fn create_signed_object(
+    value_json: &str,
+    private_key: &str,
+    is_data_update: bool,
+) -> Result<String, JsError> {
+    let value: Value = serde_json::from_str(value_json)?;
+    let signed = crate::signed_object::create_signed_object(&value, private_key, is_data_update)?;
+    Ok(serde_json::to_string(&signed)?)
+}
+
+/// Add a signature to a signed object given as JSON
+#[wasm_bindgen(js_name = addSignature)]
+pub fn add_signature(
+    signed_json: &str,
+    private_key: &str,
+    is_data_update: bool,
+) -> Result<String, JsError> {
+    let signed: Signed<Value> = serde_json::from_str(signed_json)?;
+    let signed = crate::signed_object::add_signature(signed, private_key, is_data_update)?;
+    Ok(serde_json::to_string(&signed)?)
+}
+
+/// Sign a JSON value and return the proof as JSON (`{id, signature}`)
+#[wasm_bindgen]
+pub fn sign(value_json: &str, private_key: &str, is_data_update: bool) -> Result<String, JsError> {
+    let value: Value = serde_json::from_str(value_json)?;
+    let proof: SignatureProof = if is_data_update {
+        crate::sign::sign_data_update(&value, private_key)?
+    } else {
+        crate::sign::sign(&value, private_key)?
+    };
+    Ok(serde_json::to_string(&proof)?)
+}
+
+/// Sign a SHA-256 hash in hex, returning the DER signature in hex
+#[wasm_bindgen(js_name = signHash)]
+pub fn sign_hash(hash_hex: &str, private_key: &str) -> Result<String, JsError> {
+    Ok(crate::sign::sign_hash(hash_hex, private_key)?)
+}
+
+/// Verify every proof of a signed object given as JSON
+#[wasm_bindgen]
+pub fn verify(signed_json: &str, is_data_update: bool) -> Result<bool, JsError> {
+    let signed: Signed<Value> = serde_json::from_str(signed_json)?;
+    Ok(crate::verify::verify(&signed, is_data_update).is_valid)
+}
+
+/// Verify a signature over a SHA-256 hash in hex
+#[wasm_bindgen(js_name = verifyHash)]
+pub fn verify_hash(hash_hex: &str, signature: &str, public_key_id: &str) -> Result<bool, JsError> {
+    Ok(crate::verify::verify_hash(
+        hash_hex,
+        signature,
+        public_key_id,
+    )?)
+}
+
+/// Bytes a JSON value is signed over, with the DataUpdate prefix if requested
+#[wasm_bindgen(js_name = toBytes)]
+pub fn to_bytes(value_json: &str, is_data_update: bool) -> Result<Vec<u8>, JsError> {
+    let value: Value = serde_json::from_str(value_json)?;
+    Ok(crate::binary::to_bytes(&value, is_data_update)?)
+}
+
+/// Encode a JSON value as a DataUpdate
+#[wasm_bindgen(js_name = encodeDataUpdate)]
+pub fn encode_data_update(value_json: &str) -> Result<Vec<u8>, JsError> {
+    let value: Value = serde_json::from_str(value_json)?;
+    Ok(crate::binary::encode_data_update(&value)?)
+}
+
+/// Decode DataUpdate bytes back to a JSON string
+#[wasm_bindgen(js_name = decodeDataUpdate)]
+pub fn decode_data_update(bytes: &[u8]) -> Result<String, JsError> {
+    let value: Value = crate::codec::decode_data_update(bytes)?;
+    Ok(serde_json::to_string(&value)?)
+}
+
+/// SHA-256 of bytes in hex
+#[wasm_bindgen(js_name = hashBytes)]
+pub fn hash_bytes(bytes: &[u8]) -> String {
+    crate::hash::hash_bytes(bytes).value
+}
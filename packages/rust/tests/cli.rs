@@ -0,0 +1,144 @@
+//! Tests for the `metakit` command-line tool
+
+#[cfg(feature = "cli")]
+mod cli_tests {
+    use serde_json::{json, Value};
+    use std::io::{ErrorKind, Write};
+    use std::process::{Command, Output, Stdio};
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn metakit(args: &[&str], stdin: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_metakit"))
+            .args(args)
+            .env_remove("METAKIT_PRIVATE_KEY")
+            .env_remove("METAKIT_NETWORK")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        // The binary may exit on bad arguments before reading its input
+        let written = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+        if let Err(e) = written {
+            assert_eq!(e.kind(), ErrorKind::BrokenPipe, "{e}");
+        }
+        child.wait_with_output().unwrap()
+    }
+
+    fn json_stdout(output: &Output) -> Value {
+        serde_json::from_slice(&output.stdout).unwrap()
+    }
+
+    #[test]
+    fn test_keygen_sign_verify() {
+        let key_pair = json_stdout(&metakit(&["keygen"], ""));
+        let private_key = key_pair["privateKey"].as_str().unwrap();
+
+        let address = json_stdout(&metakit(&["address", "--private-key", private_key], ""));
+        assert_eq!(address["address"], key_pair["address"]);
+
+        let signed = metakit(
+            &["sign", "--data-update", "--private-key", private_key],
+            r#"{"id": "sensor-1"}"#,
+        );
+        assert!(signed.status.success());
+        let signed = String::from_utf8(signed.stdout).unwrap();
+
+        let valid = metakit(&["verify", "--data-update"], &signed);
+        assert!(valid.status.success());
+        assert_eq!(json_stdout(&valid)["isValid"], true);
+
+        // Signed as a DataUpdate, so it does not verify as regular data
+        let invalid = metakit(&["verify"], &signed);
+        assert_eq!(invalid.status.code(), Some(1));
+        assert_eq!(json_stdout(&invalid)["isValid"], false);
+    }
+
+    #[test]
+    fn test_sign_without_private_key_fails() {
+        let output = metakit(&["sign"], "{}");
+        assert_eq!(output.status.code(), Some(2));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("METAKIT_PRIVATE_KEY"));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_balance() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/currency/DAG0abc/balance"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"balance": 250_000_000, "ordinal": 42})),
+            )
+            .mount(&server)
+            .await;
+
+        let uri = server.uri();
+        let output = tokio::task::spawn_blocking(move || {
+            metakit(&["--currency-l0-url", &uri, "balance", "DAG0abc"], "")
+        })
+        .await
+        .unwrap();
+        assert!(output.status.success());
+        assert_eq!(
+            json_stdout(&output),
+            json!({"address": "DAG0abc", "balance": 250_000_000, "tokens": 2.5, "ordinal": 42})
+        );
+    }
+}
+
+#[cfg(feature = "fixtures-cli")]
+mod fixtures_cli_tests {
+    use serde_json::Value;
+    use std::io::{ErrorKind, Write};
+    use std::process::{Command, Output, Stdio};
+
+    fn metakit_fixtures(args: &[&str], stdin: &str) -> Output {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_metakit-fixtures"))
+            .args(args)
+            .env_remove("METAKIT_PRIVATE_KEY")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        // The binary may exit on bad arguments before reading its input
+        let written = child.stdin.take().unwrap().write_all(stdin.as_bytes());
+        if let Err(e) = written {
+            assert_eq!(e.kind(), ErrorKind::BrokenPipe, "{e}");
+        }
+        child.wait_with_output().unwrap()
+    }
+
+    #[test]
+    fn test_writes_and_checks_fixtures() {
+        let key = "22".repeat(32);
+        let samples = r#"{"vote": {"poll": "p1", "option": 2}, "empty": {}}"#;
+        let output = metakit_fixtures(&["--data-update", "--private-key", &key], samples);
+        assert!(output.status.success(), "{output:?}");
+
+        let fixtures: Vec<Value> = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(fixtures.len(), 2);
+        assert_eq!(fixtures[1]["name"], "vote");
+        assert_eq!(fixtures[1]["type"], "TestDataUpdate");
+
+        let dir = std::env::temp_dir().join(format!("metakit-fixtures-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let committed = dir.join("fixtures.json");
+        std::fs::write(&committed, &output.stdout).unwrap();
+        let committed = committed.to_str().unwrap();
+
+        let args = ["--data-update", "--private-key", &key, "--check", committed];
+        assert!(metakit_fixtures(&args, samples).status.success());
+        let drifted = r#"{"vote": {"poll": "p1", "option": 3}, "empty": {}}"#;
+        assert_eq!(metakit_fixtures(&args, drifted).status.code(), Some(1));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_requires_a_key() {
+        let output = metakit_fixtures(&[], "{}");
+        assert_eq!(output.status.code(), Some(2));
+    }
+}
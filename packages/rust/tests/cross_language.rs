@@ -220,3 +220,13 @@ mod by_source_language {
         test_language_vectors("go");
     }
 }
+
+#[test]
+fn embedded_vectors_match_shared_file() {
+    let shared = serde_json::to_value(load_test_vectors()).unwrap();
+    let embedded = serde_json::to_value(constellation_sdk::vectors::test_vectors()).unwrap();
+    assert_eq!(
+        embedded, shared,
+        "vectors/test_vectors.json is out of date; copy it from shared/test_vectors.json"
+    );
+}
@@ -1,10 +1,11 @@
 //! Tests for currency transaction functionality
 
 use constellation_sdk::{
-    create_currency_transaction, create_currency_transaction_batch, encode_currency_transaction,
-    generate_key_pair, get_transaction_reference, hash_currency_transaction, is_valid_dag_address,
+    create_currency_transaction, create_currency_transaction_batch,
+    create_currency_transaction_with_signer, encode_currency_transaction, generate_key_pair,
+    get_transaction_reference, hash_currency_transaction, is_valid_dag_address,
     sign_currency_transaction, token_to_units, units_to_token, verify_currency_transaction,
-    SignatureProof, TransactionReference, TransferParams, TOKEN_DECIMALS,
+    LocalSigner, SignatureProof, TransactionReference, TransferParams, TOKEN_DECIMALS,
 };
 
 #[cfg(test)]
@@ -75,6 +76,33 @@ mod transaction_creation {
         assert!(!tx.proofs[0].signature.is_empty());
     }
 
+    #[test]
+    fn test_create_currency_transaction_with_signer() {
+        let signer = LocalSigner::generate();
+        let recipient = generate_key_pair();
+        let last_ref = TransactionReference {
+            hash: "a".repeat(64),
+            ordinal: 3,
+        };
+
+        let tx = create_currency_transaction_with_signer(
+            TransferParams {
+                destination: recipient.address.clone(),
+                amount: 2.5,
+                fee: 0.1,
+            },
+            &signer,
+            last_ref.clone(),
+        )
+        .unwrap();
+
+        assert_eq!(tx.value.source, signer.key_pair().address);
+        assert_eq!(tx.value.amount, 250000000);
+        assert_eq!(tx.value.fee, 10000000);
+        assert_eq!(tx.value.parent, last_ref);
+        assert!(verify_currency_transaction(&tx).is_valid);
+    }
+
     #[test]
     fn test_create_currency_transaction_throws_on_invalid_destination() {
         let key_pair = generate_key_pair();
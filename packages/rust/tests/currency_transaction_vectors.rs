@@ -5,7 +5,7 @@
 use constellation_sdk::currency_transaction::*;
 use constellation_sdk::currency_types::{TransactionReference, TransferParams};
 use constellation_sdk::types::{SignatureProof, Signed};
-use constellation_sdk::wallet::get_address;
+use constellation_sdk::wallet::try_get_address;
 use secp256k1::{Secp256k1, SecretKey};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -159,7 +159,7 @@ fn test_address_derivation() {
     let secp = Secp256k1::new();
     let public_key = secp256k1::PublicKey::from_secret_key(&secp, &secret_key);
     let public_key_hex = hex::encode(public_key.serialize_uncompressed());
-    let address = get_address(&public_key_hex);
+    let address = try_get_address(&public_key_hex).unwrap();
 
     assert_eq!(address, basic.address);
 }
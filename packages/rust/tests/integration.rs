@@ -48,6 +48,55 @@ mod key_generation {
         assert_ne!(key1.public_key, key2.public_key);
         assert_ne!(key1.address, key2.address);
     }
+
+    #[test]
+    fn generates_deterministic_keys_from_seeded_rng() {
+        use constellation_sdk::{generate_key_pair_with_rng, LocalSigner};
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let key_pair = generate_key_pair_with_rng(&mut StdRng::seed_from_u64(42));
+        assert_eq!(
+            key_pair,
+            generate_key_pair_with_rng(&mut StdRng::seed_from_u64(42))
+        );
+        assert_ne!(
+            key_pair,
+            generate_key_pair_with_rng(&mut StdRng::seed_from_u64(43))
+        );
+        assert_eq!(
+            key_pair_from_private_key(&key_pair.private_key).unwrap(),
+            key_pair
+        );
+
+        let signer = LocalSigner::generate_with_rng(&mut StdRng::seed_from_u64(42));
+        assert_eq!(signer.key_pair(), &key_pair);
+    }
+
+    #[test]
+    fn derives_addresses_without_panicking() {
+        use constellation_sdk::{try_get_address, SdkError};
+
+        let key_pair = generate_key_pair();
+        assert_eq!(
+            try_get_address(&key_pair.public_key).unwrap(),
+            key_pair.address
+        );
+        // Public key ID (no 04 prefix) and compressed forms derive the same address
+        assert_eq!(
+            try_get_address(&key_pair.public_key[2..]).unwrap(),
+            key_pair.address
+        );
+        let compressed =
+            constellation_sdk::get_public_key_hex(&key_pair.private_key, true).unwrap();
+        assert_eq!(try_get_address(&compressed).unwrap(), key_pair.address);
+
+        for bad in ["", "zz", &"0".repeat(130)] {
+            assert!(matches!(
+                try_get_address(bad),
+                Err(SdkError::InvalidPublicKey(_))
+            ));
+        }
+    }
 }
 
 mod regular_signing {
@@ -95,6 +144,27 @@ mod regular_signing {
         assert_eq!(proof.id.len(), 128);
         assert_eq!(proof.id, key_pair.public_key[2..]); // Skip 04 prefix
     }
+
+    #[test]
+    fn signs_and_verifies_from_many_threads() {
+        // Every thread shares the process-wide secp256k1 context
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    let key_pair = generate_key_pair();
+                    let data = json!({"thread": i});
+                    for _ in 0..16 {
+                        let proof = sign(&data, &key_pair.private_key).unwrap();
+                        assert!(verify_signature(&data, &proof, false).unwrap());
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
 }
 
 mod data_update_signing {
@@ -142,6 +212,54 @@ mod data_update_signing {
     }
 }
 
+mod domain_separation {
+    use super::*;
+    use constellation_sdk::{
+        canonicalize_bytes, hash_bytes, hash_with_domain, sign_with_domain,
+        verify_signature_with_domain, verify_with_domain, SdkError, DOMAIN_PREFIX,
+    };
+
+    #[test]
+    fn hashes_length_prefixed_tag_before_canonical_json() {
+        let data = json!({"b": 2, "a": 1});
+        let mut bytes = format!("{DOMAIN_PREFIX}6\nvoting").into_bytes();
+        bytes.extend(canonicalize_bytes(&data).unwrap());
+        assert_eq!(
+            hash_with_domain(&data, "voting").unwrap(),
+            hash_bytes(&bytes)
+        );
+    }
+
+    #[test]
+    fn signatures_only_verify_under_their_domain() {
+        let key_pair = generate_key_pair();
+        let data = json!({"vote": "yes", "poll": 7});
+        let proof = sign_with_domain(&data, "app-a", &key_pair.private_key).unwrap();
+        let signed = Signed {
+            value: data.clone(),
+            proofs: vec![proof.clone()],
+        };
+
+        assert!(verify_with_domain(&signed, "app-a").is_valid);
+        assert!(!verify_with_domain(&signed, "app-b").is_valid);
+        assert!(!verify(&signed, false).is_valid);
+        assert!(!verify(&signed, true).is_valid);
+        assert!(!verify_signature_with_domain(&data, &proof, "app-a/").unwrap());
+
+        let regular = create_signed_object(&data, &key_pair.private_key, false).unwrap();
+        assert!(!verify_with_domain(&regular, "app-a").is_valid);
+    }
+
+    #[test]
+    fn rejects_empty_domain() {
+        let key_pair = generate_key_pair();
+        assert!(matches!(
+            sign_with_domain(&json!({}), "", &key_pair.private_key),
+            Err(SdkError::InvalidPayload(_))
+        ));
+    }
+}
+
 mod multi_signature {
     use super::*;
 
@@ -193,6 +311,75 @@ mod multi_signature {
         assert_ne!(signed.proofs[0].id, signed.proofs[1].id);
         assert_ne!(signed.proofs[0].signature, signed.proofs[1].signature);
     }
+
+    #[test]
+    fn refuses_a_key_that_already_signed() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"id": "test"});
+
+        let signed = create_signed_object(&data, &key1.private_key, false).unwrap();
+        assert!(matches!(
+            add_signature(signed, &key1.private_key, false),
+            Err(constellation_sdk::SdkError::InvalidPayload(_))
+        ));
+        assert!(batch_sign(
+            &data,
+            &[&key1.private_key, &key2.private_key, &key1.private_key],
+            false
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verify_rejects_repeated_signers() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let data = json!({"id": "test"});
+        let mut signed = batch_sign(&data, &[&key1.private_key, &key2.private_key], false).unwrap();
+
+        // Identical proof, and the same key with its id in another form
+        let mut upper = signed.proofs[0].clone();
+        upper.id = format!("04{}", upper.id.to_uppercase());
+        signed.proofs.push(signed.proofs[0].clone());
+        signed.proofs.push(upper);
+
+        let result = verify(&signed, false);
+        assert!(!result.is_valid);
+        assert_eq!(result.valid_proofs.len(), 2);
+        assert_eq!(result.invalid_proofs.len(), 2);
+        assert_eq!(
+            signed.duplicate_signers(),
+            vec![signed.proofs[0].id.clone()]
+        );
+    }
+
+    #[test]
+    fn dedupe_proofs_keeps_one_per_signer_in_a_fixed_order() {
+        let key1 = generate_key_pair();
+        let key2 = generate_key_pair();
+        let key3 = generate_key_pair();
+        let data = json!({"id": "test"});
+        let signed = batch_sign(
+            &data,
+            &[&key1.private_key, &key2.private_key, &key3.private_key],
+            false,
+        )
+        .unwrap();
+
+        let mut forward = signed.clone();
+        forward.proofs.push(signed.proofs[1].clone());
+        let mut backward = signed.clone();
+        backward.proofs.reverse();
+        backward.proofs.push(signed.proofs[2].clone());
+
+        assert_eq!(forward.dedupe_proofs(), 1);
+        assert_eq!(backward.dedupe_proofs(), 1);
+        assert_eq!(forward, backward);
+        assert!(forward.duplicate_signers().is_empty());
+        assert!(verify(&forward, false).is_valid);
+        assert!(forward.proofs.windows(2).all(|p| p[0].id < p[1].id));
+    }
 }
 
 mod tamper_detection {
@@ -334,6 +521,22 @@ mod hashing {
 
         assert_ne!(hash1.value, hash2.value);
     }
+
+    #[test]
+    fn compares_in_constant_time() {
+        let hash = hash_data(&json!({"value": 1}), false).unwrap();
+        let other = hash_data(&json!({"value": 2}), false).unwrap();
+
+        assert!(hash.ct_eq(&hash.clone()));
+        assert!(!hash.ct_eq(&other));
+
+        let key_pair = generate_key_pair();
+        let proof = sign(&json!({"value": 1}), &key_pair.private_key).unwrap();
+        assert!(proof.ct_eq(&proof.clone()));
+        assert!(!proof.ct_eq(&sign(&json!({"value": 2}), &key_pair.private_key).unwrap()));
+        assert_eq!(key_pair, key_pair.clone());
+        assert_ne!(key_pair, generate_key_pair());
+    }
 }
 
 mod error_handling {
@@ -352,4 +555,1703 @@ mod error_handling {
         let result = batch_sign::<serde_json::Value>(&data, &[], false);
         assert!(matches!(result, Err(SdkError::NoPrivateKeys)));
     }
+
+    #[test]
+    fn errors_have_stable_codes_and_categories() {
+        use constellation_sdk::ErrorCategory;
+
+        let error = key_pair_from_private_key("invalid").unwrap_err();
+        assert_eq!(error.code(), 1001);
+        assert_eq!(error.category(), ErrorCategory::Key);
+
+        let error = SdkError::InvalidPayload("x".to_string());
+        assert_eq!(error.code(), 5003);
+        assert_eq!(error.category(), ErrorCategory::Input);
+    }
+
+    #[test]
+    fn keeps_underlying_errors_as_sources() {
+        use std::error::Error;
+
+        let error = constellation_sdk::UnsignedPayload::from_json("not json").unwrap_err();
+        assert_eq!(error.code(), 3004);
+        assert!(error.to_string().starts_with("Serialization error: "));
+        assert!(error
+            .source()
+            .unwrap()
+            .downcast_ref::<serde_json::Error>()
+            .is_some());
+
+        let error = SdkError::from(hex::decode("zz").unwrap_err());
+        assert_eq!(error.category(), constellation_sdk::ErrorCategory::Encoding);
+        assert!(error.source().unwrap().is::<hex::FromHexError>());
+    }
+
+    #[test]
+    fn rejects_malformed_signatures_without_panicking() {
+        use constellation_sdk::verify_hash;
+
+        let key_pair = generate_key_pair();
+        let hash = "a".repeat(64);
+        let id = &key_pair.public_key[2..];
+        let signature = constellation_sdk::sign_hash(&hash, &key_pair.private_key).unwrap();
+
+        for bad in [
+            String::new(),
+            "zz".to_string(),
+            "30".repeat(100),
+            signature[..signature.len() - 2].to_string(),
+        ] {
+            assert!(matches!(
+                verify_hash(&hash, &bad, id),
+                Err(SdkError::InvalidSignature(_))
+            ));
+        }
+        assert!(matches!(
+            verify_hash(&hash, &signature, &"0".repeat(10_000)),
+            Err(SdkError::InvalidPublicKey(_))
+        ));
+        assert!(verify_hash(&hash, &signature, id).unwrap());
+    }
+}
+
+mod signer {
+    use super::*;
+    use constellation_sdk::{sign_object, sign_with, LocalSigner, SdkError, Signer};
+
+    /// A signer that only exposes the two required operations
+    struct Remote(LocalSigner);
+
+    impl Signer for Remote {
+        fn public_key_id(&self) -> String {
+            self.0.public_key_id()
+        }
+
+        fn sign_hash(&self, hash_hex: &str) -> constellation_sdk::Result<String> {
+            self.0.sign_hash(hash_hex)
+        }
+    }
+
+    #[test]
+    fn local_signer_matches_key_pair() {
+        let key_pair = generate_key_pair();
+        let signer = LocalSigner::new(&key_pair.private_key).unwrap();
+
+        assert_eq!(signer.address(), key_pair.address);
+        assert_eq!(signer.public_key_id(), key_pair.public_key[2..]);
+        assert!(!format!("{:?}", signer).contains(&key_pair.private_key));
+    }
+
+    #[test]
+    fn signs_regular_and_data_update_objects() {
+        let signer = LocalSigner::generate();
+        let data = json!({"action": "test", "value": 42});
+
+        let regular = sign_object(&signer, &data, false).unwrap();
+        let update = sign_object(&signer, &data, true).unwrap();
+
+        assert!(verify(&regular, false).is_valid);
+        assert!(verify(&update, true).is_valid);
+        assert!(!verify(&update, false).is_valid);
+    }
+
+    #[test]
+    fn custom_signer_uses_default_methods() {
+        let local = LocalSigner::generate();
+        let remote = Remote(local.clone());
+        let data = json!({"id": "test"});
+
+        assert_eq!(remote.address(), local.address());
+        let proof = sign_with(&remote as &dyn Signer, &data, false).unwrap();
+        let signed = Signed {
+            value: data,
+            proofs: vec![proof],
+        };
+        assert!(verify(&signed, false).is_valid);
+    }
+
+    #[test]
+    fn rejects_invalid_private_key() {
+        assert!(matches!(
+            LocalSigner::new("invalid"),
+            Err(SdkError::InvalidPrivateKey(_))
+        ));
+    }
+}
+
+mod offline_signing {
+    use super::*;
+    use constellation_sdk::{
+        create_currency_transaction, hash_currency_transaction, import_signature,
+        import_signatures, verify_currency_transaction, CurrencyTransactionValue, LocalSigner,
+        PayloadKind, SdkError, Signer, TransactionReference, TransferParams, UnsignedPayload,
+    };
+    use serde_json::Value;
+
+    #[test]
+    fn data_update_round_trip() {
+        let data = json!({"action": "test", "value": 42});
+        let exported = UnsignedPayload::data(&data, true)
+            .unwrap()
+            .with_metadata("description", "test update")
+            .to_json()
+            .unwrap();
+
+        let payload = UnsignedPayload::from_json(&exported).unwrap();
+        assert_eq!(payload.kind, PayloadKind::DataUpdate);
+        assert_eq!(payload.metadata["description"], "test update");
+        assert_eq!(payload.bytes, hex::encode(to_bytes(&data, true).unwrap()));
+
+        let first = LocalSigner::generate();
+        let second = LocalSigner::generate();
+        let signed: Signed<Value> = import_signatures(
+            &payload,
+            [
+                payload.sign(&first).unwrap(),
+                payload.sign(&second).unwrap(),
+            ],
+        )
+        .unwrap();
+        assert_eq!(signed.value, data);
+        assert_eq!(signed.proofs.len(), 2);
+        assert!(verify(&signed, true).is_valid);
+    }
+
+    #[test]
+    fn currency_transaction_round_trip() {
+        let key_pair = generate_key_pair();
+        let unsigned = create_currency_transaction(
+            TransferParams {
+                destination: generate_key_pair().address,
+                amount: 1.5,
+                fee: 0.0,
+            },
+            &key_pair.private_key,
+            TransactionReference {
+                hash: "a".repeat(64),
+                ordinal: 3,
+            },
+        )
+        .unwrap();
+        let payload = UnsignedPayload::currency_transaction(&unsigned).unwrap();
+        assert_eq!(payload.hash, hash_currency_transaction(&unsigned).value);
+
+        let signer = LocalSigner::new(&key_pair.private_key).unwrap();
+        let received = UnsignedPayload::from_json(&payload.to_json().unwrap()).unwrap();
+        let transaction =
+            import_signature::<CurrencyTransactionValue>(&payload, received.sign(&signer).unwrap())
+                .unwrap();
+
+        assert_eq!(transaction.value, unsigned.value);
+        assert!(verify_currency_transaction(&transaction).is_valid);
+        assert_eq!(transaction.proofs[0].id, signer.public_key_id());
+    }
+
+    #[test]
+    fn rejects_tampered_payload() {
+        let payload = UnsignedPayload::data(&json!({"amount": 1}), false).unwrap();
+        let mut tampered = payload.clone();
+        tampered.value = json!({"amount": 1000});
+
+        assert!(matches!(
+            UnsignedPayload::from_json(&tampered.to_json().unwrap()),
+            Err(SdkError::InvalidPayload(_))
+        ));
+        assert!(matches!(
+            tampered.sign(&LocalSigner::generate()),
+            Err(SdkError::InvalidPayload(_))
+        ));
+
+        let mut future = payload;
+        future.version += 1;
+        assert!(matches!(future.check(), Err(SdkError::InvalidPayload(_))));
+    }
+
+    #[test]
+    fn rejects_proof_for_another_payload() {
+        let payload = UnsignedPayload::data(&json!({"id": 1}), false).unwrap();
+        let other = UnsignedPayload::data(&json!({"id": 2}), false).unwrap();
+        let proof = other.sign(&LocalSigner::generate()).unwrap();
+
+        assert!(matches!(
+            import_signature::<Value>(&payload, proof),
+            Err(SdkError::InvalidSignature(_))
+        ));
+    }
+}
+
+mod multisig_envelope {
+    use super::*;
+    use constellation_sdk::{LocalSigner, SdkError, Signer, SigningEnvelope, UnsignedPayload};
+    use serde_json::Value;
+
+    fn envelope(signers: &[&LocalSigner], threshold: usize) -> SigningEnvelope {
+        let payload =
+            UnsignedPayload::data(&json!({"action": "release", "amount": 5}), true).unwrap();
+        let ids = signers.iter().map(|s| s.public_key_id());
+        SigningEnvelope::new(payload, ids, threshold).unwrap()
+    }
+
+    #[test]
+    fn merges_copies_until_threshold() {
+        let (alice, bob, carol) = (
+            LocalSigner::generate(),
+            LocalSigner::generate(),
+            LocalSigner::generate(),
+        );
+        let exported = envelope(&[&alice, &bob, &carol], 2).to_json().unwrap();
+
+        let mut first = SigningEnvelope::from_json(&exported).unwrap();
+        first.sign(&alice).unwrap();
+        assert!(!first.is_complete());
+        assert!(first.finalize::<Value>().is_err());
+
+        let mut second = SigningEnvelope::from_json(&exported).unwrap();
+        second.sign(&bob).unwrap();
+        second.sign(&alice).unwrap();
+        let second = SigningEnvelope::from_json(&second.to_json().unwrap()).unwrap();
+
+        assert_eq!(first.merge(&second).unwrap(), 1);
+        assert_eq!(first.merge(&second).unwrap(), 0);
+        assert!(first.is_complete());
+        assert_eq!(
+            first.missing_signers().into_iter().collect::<Vec<_>>(),
+            [carol.public_key_id()]
+        );
+
+        let signed = first.finalize::<Value>().unwrap();
+        assert_eq!(signed.proofs.len(), 2);
+        assert!(verify(&signed, true).is_valid);
+    }
+
+    #[test]
+    fn rejects_outside_and_forged_proofs() {
+        let (alice, mallory) = (LocalSigner::generate(), LocalSigner::generate());
+        let mut envelope = envelope(&[&alice], 1);
+
+        assert!(matches!(
+            envelope.sign(&mallory),
+            Err(SdkError::InvalidPayload(_))
+        ));
+
+        let mut forged = alice.sign_hash_proof(&"0".repeat(64)).unwrap();
+        assert!(matches!(
+            envelope.add_proof(forged.clone()),
+            Err(SdkError::InvalidSignature(_))
+        ));
+
+        forged.signature = envelope.sign(&alice).unwrap().signature.clone();
+        let mut tampered = envelope.clone();
+        tampered.proofs.push(forged);
+        assert!(matches!(
+            SigningEnvelope::from_json(&tampered.to_json().unwrap()),
+            Err(SdkError::InvalidPayload(_))
+        ));
+    }
+
+    #[test]
+    fn counts_each_signer_once_toward_the_threshold() {
+        let (alice, bob) = (LocalSigner::generate(), LocalSigner::generate());
+        let mut forged = envelope(&[&alice, &bob], 2);
+        let proof = forged.sign(&alice).unwrap().clone();
+        let mut reencoded = proof.clone();
+        reencoded.id = format!("04{}", proof.id.to_uppercase());
+        forged.proofs.push(reencoded);
+
+        assert!(!forged.is_complete());
+        assert!(matches!(
+            forged.finalize::<Value>(),
+            Err(SdkError::InvalidPayload(_))
+        ));
+
+        let mut fresh = envelope(&[&alice, &bob], 2);
+        assert_eq!(fresh.merge(&forged).unwrap(), 1);
+        assert_eq!(fresh.proofs.len(), 1);
+        assert!(!fresh.is_complete());
+    }
+
+    #[test]
+    fn refuses_mismatched_envelopes_and_thresholds() {
+        let (alice, bob) = (LocalSigner::generate(), LocalSigner::generate());
+        let payload = UnsignedPayload::data(&json!({"a": 1}), false).unwrap();
+        assert!(SigningEnvelope::new(payload.clone(), [alice.public_key_id()], 2).is_err());
+        assert!(SigningEnvelope::new(payload, Vec::<String>::new(), 0).is_err());
+
+        let mut pair = envelope(&[&alice, &bob], 1);
+        let other = envelope(&[&alice, &bob], 2);
+        assert!(matches!(
+            pair.merge(&other),
+            Err(SdkError::InvalidPayload(_))
+        ));
+    }
+}
+
+mod partially_signed_transaction {
+    use constellation_sdk::currency_types::{CurrencyTransactionValue, TransactionReference};
+    use constellation_sdk::{
+        verify_currency_transaction, LocalSigner, PartiallySignedTransaction, SdkError, Signer,
+        SignerHint,
+    };
+
+    fn value(source: &LocalSigner) -> CurrencyTransactionValue {
+        CurrencyTransactionValue {
+            source: source.address(),
+            destination: "DAG4o41NzhfX6DyYBTTXu6sJa6awm36abJpv89jB".to_string(),
+            amount: 250_000_000,
+            fee: 1,
+            parent: TransactionReference {
+                hash: "a".repeat(64),
+                ordinal: 7,
+            },
+            salt: "8940500530129793".to_string(),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_base64_and_json() {
+        let owner = LocalSigner::generate();
+        let psct = PartiallySignedTransaction::new(value(&owner))
+            .unwrap()
+            .with_hint(SignerHint::new(owner.address()).with_label("treasury"));
+        assert_eq!(psct.hints.len(), 1);
+        assert_eq!(psct.hints[0].label.as_deref(), Some("treasury"));
+
+        let from_base64 = PartiallySignedTransaction::parse(&psct.to_base64().unwrap()).unwrap();
+        let from_json = PartiallySignedTransaction::parse(&psct.to_json().unwrap()).unwrap();
+        assert_eq!(from_base64, psct);
+        assert_eq!(from_json, psct);
+        assert!(matches!(
+            PartiallySignedTransaction::parse("not base64!"),
+            Err(SdkError::SerializationError(_))
+        ));
+    }
+
+    #[test]
+    fn merges_and_finalizes_once_the_source_signs() {
+        let (owner, cosigner) = (LocalSigner::generate(), LocalSigner::generate());
+        let psct = PartiallySignedTransaction::new(value(&owner))
+            .unwrap()
+            .with_hint(SignerHint::new(cosigner.address()));
+        let exported = psct.to_base64().unwrap();
+
+        let mut from_cosigner = PartiallySignedTransaction::parse(&exported).unwrap();
+        from_cosigner.sign(&cosigner).unwrap();
+        assert!(!from_cosigner.is_complete());
+        assert_eq!(from_cosigner.missing_signers()[0].address, owner.address());
+        assert!(matches!(
+            from_cosigner.finalize(),
+            Err(SdkError::InvalidPayload(_))
+        ));
+
+        let mut from_owner = PartiallySignedTransaction::parse(&exported).unwrap();
+        from_owner.sign(&owner).unwrap();
+        assert_eq!(from_owner.merge(&from_cosigner).unwrap(), 1);
+        assert_eq!(from_owner.merge(&from_cosigner).unwrap(), 0);
+        assert!(from_owner.missing_signers().is_empty());
+
+        let transaction = from_owner.finalize().unwrap();
+        assert_eq!(transaction.proofs.len(), 2);
+        assert!(verify_currency_transaction(&transaction).is_valid);
+        assert_eq!(
+            PartiallySignedTransaction::from_transaction(&transaction).unwrap(),
+            PartiallySignedTransaction {
+                hints: vec![SignerHint::new(owner.address())],
+                ..from_owner
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_containers() {
+        let owner = LocalSigner::generate();
+        let mut psct = PartiallySignedTransaction::new(value(&owner)).unwrap();
+        psct.sign(&owner).unwrap();
+
+        let mut altered = psct.clone();
+        altered.transaction.amount += 1;
+        assert!(matches!(
+            PartiallySignedTransaction::parse(&altered.to_json().unwrap()),
+            Err(SdkError::InvalidPayload(_))
+        ));
+        assert!(matches!(
+            psct.merge(&PartiallySignedTransaction::new(altered.transaction).unwrap()),
+            Err(SdkError::InvalidPayload(_))
+        ));
+
+        let forged = owner.sign_hash_proof(&"0".repeat(64)).unwrap();
+        assert!(matches!(
+            psct.add_proof(forged.clone()),
+            Err(SdkError::InvalidSignature(_))
+        ));
+        let mut smuggled = psct.clone();
+        smuggled.proofs[0] = forged;
+        assert!(matches!(
+            PartiallySignedTransaction::parse(&smuggled.to_base64().unwrap()),
+            Err(SdkError::InvalidSignature(_))
+        ));
+    }
+}
+
+mod key_cache {
+    use super::*;
+    use constellation_sdk::key_cache::{
+        disable_public_key_cache, enable_public_key_cache, public_key_cache_stats,
+    };
+
+    #[test]
+    fn reuses_parsed_keys() {
+        let validators: Vec<_> = (0..3).map(|_| generate_key_pair()).collect();
+        let data = json!({"block": 7});
+        let proofs: Vec<_> = validators
+            .iter()
+            .map(|key_pair| sign(&data, &key_pair.private_key).unwrap())
+            .collect();
+
+        // Room to spare for keys verified by tests running alongside
+        enable_public_key_cache(64);
+        for _ in 0..4 {
+            for proof in &proofs {
+                assert!(verify_signature(&data, proof, false).unwrap());
+            }
+        }
+        let stats = public_key_cache_stats().unwrap();
+        assert_eq!(stats.capacity, 64);
+        assert!(stats.len >= 3);
+        // Only the first round parses
+        assert!(stats.hits >= 9);
+
+        // Forged signatures still fail, and bad keys are not cached
+        let mut forged = proofs[0].clone();
+        forged.signature = proofs[1].signature.clone();
+        assert!(!verify_signature(&data, &forged, false).unwrap());
+        let mut garbage = proofs[0].clone();
+        garbage.id = "zz".repeat(64);
+        assert!(verify_signature(&data, &garbage, false).is_err());
+
+        disable_public_key_cache();
+        assert!(public_key_cache_stats().is_none());
+        assert!(verify_signature(&data, &proofs[2], false).unwrap());
+    }
+}
+
+mod log_hook {
+    use super::*;
+    use constellation_sdk::log_hook::{clear_log_hook, is_enabled, set_log_hook, LogLevel};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn receives_signing_events() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        set_log_hook(move |event: &constellation_sdk::log_hook::LogEvent<'_>| {
+            sink.lock()
+                .unwrap()
+                .push((event.level, event.message, event.field("hash")));
+        });
+        assert!(is_enabled());
+
+        let key_pair = generate_key_pair();
+        let data = json!({"action": "log-hook-test"});
+        let signed = create_signed_object(&data, &key_pair.private_key, false).unwrap();
+        assert!(verify(&signed, false).is_valid);
+        clear_log_hook();
+        assert!(!is_enabled());
+
+        let hash = Some(hash_data(&data, false).unwrap().value);
+        let events = events.lock().unwrap();
+        assert!(events.contains(&(LogLevel::Debug, "signed data", hash.clone())));
+        assert!(events.contains(&(LogLevel::Debug, "verified signed object", hash)));
+    }
+}
+
+mod signing_audit {
+    use super::*;
+    use constellation_sdk::currency_types::{TransactionReference, TransferParams};
+    use constellation_sdk::signing_audit::{
+        clear_signing_audit_hook, set_signing_audit_hook, with_context, AuditChain, SigningRecord,
+    };
+    use constellation_sdk::{create_currency_transaction, get_public_key_id};
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn records_every_signature_with_its_context() {
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let sink = records.clone();
+        // Other tests sign concurrently, so keep only this test's records
+        set_signing_audit_hook(move |record: &SigningRecord| {
+            if record
+                .context
+                .as_deref()
+                .is_some_and(|c| c.starts_with("audit-test"))
+            {
+                sink.lock().unwrap().push(record.clone());
+            }
+        });
+
+        let key_pair = generate_key_pair();
+        let data = json!({"action": "audit"});
+        with_context("audit-test-outer", || {
+            sign(&data, &key_pair.private_key).unwrap();
+            with_context("audit-test-inner", || {
+                sign_data_update(&data, &key_pair.private_key).unwrap();
+            });
+            let recipient = generate_key_pair();
+            create_currency_transaction(
+                TransferParams {
+                    destination: recipient.address,
+                    amount: 1.0,
+                    fee: 0.0,
+                },
+                &key_pair.private_key,
+                TransactionReference {
+                    hash: "0".repeat(64),
+                    ordinal: 0,
+                },
+            )
+            .unwrap();
+        });
+        sign(&data, &key_pair.private_key).unwrap();
+        clear_signing_audit_hook();
+
+        let records = records.lock().unwrap();
+        let summary: Vec<_> = records
+            .iter()
+            .map(|r| (r.operation.as_str(), r.context.as_deref().unwrap()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("sign", "audit-test-outer"),
+                ("sign_data_update", "audit-test-inner"),
+                ("currency_transaction", "audit-test-outer"),
+            ]
+        );
+        let id = get_public_key_id(&key_pair.private_key).unwrap();
+        assert!(records.iter().all(|r| r.signer == id && r.timestamp > 0));
+        assert_eq!(records[0].hash, hash_data(&data, false).unwrap().value);
+    }
+
+    #[test]
+    fn audit_chain_detects_tampering() {
+        let record = |n: u64| SigningRecord {
+            hash: format!("{n:064x}"),
+            signer: "ab".repeat(64),
+            timestamp: n,
+            operation: "sign".to_string(),
+            context: None,
+        };
+        let mut records: Vec<_> = (1..=3).map(record).collect();
+        let mut chain = AuditChain::new();
+        for r in &records {
+            chain.append(r);
+        }
+        let head = chain.head().to_string();
+        assert!(AuditChain::verify(&records, &head));
+
+        let mut resumed = AuditChain::resume(head.clone());
+        assert_ne!(resumed.append(&record(4)), head);
+
+        records[1].timestamp += 1;
+        assert!(!AuditChain::verify(&records, &head));
+        records[1].timestamp -= 1;
+        records.swap(0, 1);
+        assert!(!AuditChain::verify(&records, &head));
+        records.swap(0, 1);
+        records.pop();
+        assert!(!AuditChain::verify(&records, &head));
+    }
+}
+
+mod delegation {
+    use super::*;
+    use constellation_sdk::delegation::{
+        sign_delegated, verify_delegated, verify_delegated_at, Delegation, DelegationError,
+    };
+    use constellation_sdk::{sign_with_domain, LocalSigner, Signer};
+
+    fn setup() -> (LocalSigner, LocalSigner, Signed<Delegation>) {
+        let owner = LocalSigner::generate();
+        let session = LocalSigner::generate();
+        let authorization = Delegation::new(owner.public_key_id(), session.public_key_id())
+            .expires_at(2_000)
+            .sign(&owner)
+            .unwrap();
+        (owner, session, authorization)
+    }
+
+    #[test]
+    fn verifies_the_chain_and_returns_the_owner() {
+        let (owner, session, authorization) = setup();
+        let update = sign_delegated(&session, &authorization, json!({"move": "e4"}), true).unwrap();
+
+        assert_eq!(
+            verify_delegated_at(&update, true, 1_000),
+            Ok(owner.public_key_id())
+        );
+        assert_eq!(
+            verify_delegated_at(&update, false, 1_000),
+            Err(DelegationError::InvalidSignature)
+        );
+        assert_eq!(
+            verify_delegated_at(&update, true, 2_000),
+            Err(DelegationError::Expired(2_000))
+        );
+
+        let open = Delegation::new(owner.public_key_id(), session.public_key_id())
+            .sign(&owner)
+            .unwrap();
+        let update = sign_delegated(&session, &open, json!({"move": "e4"}), true).unwrap();
+        assert!(verify_delegated(&update, true).is_ok());
+    }
+
+    #[test]
+    fn matches_keys_regardless_of_encoding() {
+        let owner = LocalSigner::generate();
+        let session = LocalSigner::generate();
+        let prefixed = |id: String| format!("04{}", id.to_uppercase());
+
+        let delegation = Delegation::new(
+            prefixed(owner.public_key_id()),
+            prefixed(session.public_key_id()),
+        );
+        assert_eq!(delegation.owner, owner.public_key_id());
+        let authorization = delegation.sign(&owner).unwrap();
+
+        let mut update = sign_delegated(&session, &authorization, json!({"n": 1}), true).unwrap();
+        update.proof.id = prefixed(update.proof.id);
+        assert_eq!(
+            verify_delegated_at(&update, true, 1_000),
+            Ok(owner.public_key_id())
+        );
+    }
+
+    #[test]
+    fn refuses_the_wrong_signers() {
+        let (owner, session, authorization) = setup();
+        let stranger = LocalSigner::generate();
+
+        assert!(
+            Delegation::new(owner.public_key_id(), session.public_key_id())
+                .sign(&stranger)
+                .is_err()
+        );
+        assert!(sign_delegated(&stranger, &authorization, json!({}), true).is_err());
+
+        let mut update = sign_delegated(&session, &authorization, json!({}), true).unwrap();
+        update.proof = stranger
+            .sign_hash_proof(&hash_data(&json!({}), true).unwrap().value)
+            .unwrap();
+        assert_eq!(
+            verify_delegated_at(&update, true, 0),
+            Err(DelegationError::DelegateMismatch)
+        );
+    }
+
+    #[test]
+    fn refuses_a_forged_or_undomained_authorization() {
+        let (owner, session, authorization) = setup();
+        let update = sign_delegated(&session, &authorization, json!({}), false).unwrap();
+
+        let mut extended = update.clone();
+        extended.authorization.value.expires_at = None;
+        assert_eq!(
+            verify_delegated_at(&extended, false, 0),
+            Err(DelegationError::InvalidAuthorization)
+        );
+
+        // An owner signature on the delegation as plain data does not count
+        let mut plain = update.clone();
+        plain.authorization.proofs =
+            vec![sign(&authorization.value, &owner.key_pair().private_key).unwrap()];
+        assert_eq!(
+            verify_delegated_at(&plain, false, 0),
+            Err(DelegationError::InvalidAuthorization)
+        );
+
+        // Nor does a domain-separated signature by someone else
+        let stranger = LocalSigner::generate();
+        let mut other = update;
+        other.authorization.proofs = vec![sign_with_domain(
+            &authorization.value,
+            "delegation",
+            &stranger.key_pair().private_key,
+        )
+        .unwrap()];
+        assert_eq!(
+            verify_delegated_at(&other, false, 0),
+            Err(DelegationError::InvalidAuthorization)
+        );
+    }
+}
+
+mod timestamped {
+    use super::*;
+    use constellation_sdk::timestamped::{
+        sign_envelope, sign_timestamped, verify_within, verify_within_at, FreshnessError,
+        ReplayGuard, Timestamped,
+    };
+    use constellation_sdk::{LocalSigner, Signer};
+    use std::time::Duration;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    #[test]
+    fn signs_an_envelope_any_sdk_can_verify() {
+        let key_pair = generate_key_pair();
+        let signed = sign_timestamped(json!({"challenge": "abc"}), &key_pair.private_key).unwrap();
+
+        assert_eq!(signed.value.nonce.len(), 32);
+        assert!(verify(&signed, false).is_valid);
+        assert!(verify_within(&signed, MINUTE).is_ok());
+
+        let json = serde_json::to_value(&signed.value).unwrap();
+        assert_eq!(json["payload"], json!({"challenge": "abc"}));
+        assert_eq!(json["timestamp"], json!(signed.value.timestamp));
+    }
+
+    #[test]
+    fn refuses_envelopes_outside_the_window() {
+        let signer = LocalSigner::generate();
+        let signed = sign_envelope(&signer, Timestamped::at("login", 1_000_000, "n1")).unwrap();
+
+        assert!(verify_within_at(&signed, MINUTE, 1_060_000).is_ok());
+        assert!(verify_within_at(&signed, MINUTE, 940_000).is_ok());
+        assert_eq!(
+            verify_within_at(&signed, MINUTE, 1_060_001),
+            Err(FreshnessError::Expired { age_ms: 60_001 })
+        );
+        assert_eq!(
+            verify_within_at(&signed, MINUTE, 939_999),
+            Err(FreshnessError::FromTheFuture { ahead_ms: 60_001 })
+        );
+
+        let mut tampered = signed.clone();
+        tampered.value.timestamp = 2_000_000;
+        assert_eq!(
+            verify_within_at(&tampered, MINUTE, 2_000_000),
+            Err(FreshnessError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn replay_guard_accepts_each_nonce_once_per_signer() {
+        let guard = ReplayGuard::new(MINUTE);
+        let alice = LocalSigner::generate();
+        let bob = LocalSigner::generate();
+        let first = sign_envelope(&alice, Timestamped::at("login", 1_000_000, "n1")).unwrap();
+        let second = sign_envelope(&alice, Timestamped::at("login", 1_000_000, "n2")).unwrap();
+        let other = sign_envelope(&bob, Timestamped::at("login", 1_000_000, "n1")).unwrap();
+
+        assert!(guard.check_at(&first, 1_000_000).is_ok());
+        assert_eq!(
+            guard.check_at(&first, 1_010_000),
+            Err(FreshnessError::Replayed)
+        );
+        assert!(guard.check_at(&second, 1_010_000).is_ok());
+        assert!(guard.check_at(&other, 1_010_000).is_ok());
+        assert!(matches!(
+            guard.check_at(&first, 1_070_000),
+            Err(FreshnessError::Expired { .. })
+        ));
+    }
+
+    #[test]
+    fn replay_guard_ignores_key_encoding_and_proof_order() {
+        let guard = ReplayGuard::new(MINUTE);
+        let alice = LocalSigner::generate();
+        let bob = LocalSigner::generate();
+        let mut signed = sign_envelope(&alice, Timestamped::at("login", 1_000_000, "n1")).unwrap();
+        assert!(guard.check_at(&signed, 1_000_000).is_ok());
+
+        signed.proofs[0].id = format!("04{}", signed.proofs[0].id.to_uppercase());
+        assert!(verify(&signed, false).is_valid);
+        assert_eq!(
+            guard.check_at(&signed, 1_000_000),
+            Err(FreshnessError::Replayed)
+        );
+
+        let mut joint = sign_envelope(&bob, Timestamped::at("login", 1_000_000, "n2")).unwrap();
+        let hash = hash_data(&joint.value, false).unwrap();
+        joint
+            .proofs
+            .push(alice.sign_hash_proof(&hash.value).unwrap());
+        assert!(guard.check_at(&joint, 1_000_000).is_ok());
+        joint.proofs.reverse();
+        assert_eq!(
+            guard.check_at(&joint, 1_000_000),
+            Err(FreshnessError::Replayed)
+        );
+    }
+}
+
+mod fixtures {
+    use constellation_sdk::data_update::DataUpdate;
+    use constellation_sdk::fixtures::FixtureSet;
+    use constellation_sdk::vectors::{verify_vector, VectorKind};
+    use constellation_sdk::{LocalSigner, SdkError};
+    use serde::Serialize;
+
+    #[derive(Serialize)]
+    struct CastVote {
+        poll: String,
+        option: u32,
+    }
+
+    impl DataUpdate for CastVote {}
+
+    /// Signed over a custom encoding, as a data application with its own
+    /// `serializeUpdate` would
+    #[derive(Serialize)]
+    struct Checkpoint {
+        height: u64,
+    }
+
+    impl DataUpdate for Checkpoint {
+        fn serialize_update(&self) -> Result<Vec<u8>, SdkError> {
+            Ok(format!("checkpoint:{}", self.height).into_bytes())
+        }
+    }
+
+    fn signer() -> LocalSigner {
+        LocalSigner::new(&"11".repeat(32)).unwrap()
+    }
+
+    fn vote() -> CastVote {
+        CastVote {
+            poll: "p1".to_string(),
+            option: 2,
+        }
+    }
+
+    #[test]
+    fn fixtures_reproduce_as_golden_vectors() {
+        let signer = signer();
+        let mut fixtures = FixtureSet::new(&signer);
+        fixtures
+            .data("vote-as-data", &vote())
+            .unwrap()
+            .data_update("vote", &vote())
+            .unwrap();
+
+        let [data, update] = fixtures.fixtures() else {
+            panic!("two fixtures");
+        };
+        assert_eq!(data.vector.kind, VectorKind::TestData);
+        assert_eq!(update.vector.kind, VectorKind::TestDataUpdate);
+        assert_eq!(update.vector.canonical_json, r#"{"option":2,"poll":"p1"}"#);
+        for fixture in fixtures.fixtures() {
+            verify_vector(&fixture.vector).unwrap();
+        }
+    }
+
+    #[test]
+    fn records_the_type_serializer_bytes() {
+        let signer = signer();
+        let mut fixtures = FixtureSet::new(&signer);
+        fixtures
+            .data_update("checkpoint", &Checkpoint { height: 7 })
+            .unwrap();
+
+        let vector = &fixtures.fixtures()[0].vector;
+        assert_eq!(vector.utf8_bytes_hex, hex::encode("checkpoint:7"));
+        assert_eq!(vector.data, serde_json::json!({"height": 7}));
+    }
+
+    #[test]
+    fn regenerating_is_deterministic_and_drift_is_reported() {
+        let signer = signer();
+        let mut fixtures = FixtureSet::new(&signer);
+        fixtures.data_update("vote", &vote()).unwrap();
+        let committed = fixtures.to_json().unwrap();
+
+        let mut again = FixtureSet::new(&signer);
+        again.data_update("vote", &vote()).unwrap();
+        assert_eq!(again.to_json().unwrap(), committed);
+        again.check(&committed).unwrap();
+
+        let mut drifted = FixtureSet::new(&signer);
+        drifted
+            .data_update("vote", &Checkpoint { height: 2 })
+            .unwrap();
+        let err = drifted.check(&committed).unwrap_err().to_string();
+        assert!(err.contains("fixture vote: data drifted"), "{err}");
+
+        let mut renamed = FixtureSet::new(&signer);
+        renamed.data_update("ballot", &vote()).unwrap();
+        let err = renamed.check(&committed).unwrap_err().to_string();
+        assert!(err.contains("not in the committed file"), "{err}");
+    }
+
+    #[test]
+    fn rejects_duplicate_names() {
+        let signer = signer();
+        let mut fixtures = FixtureSet::new(&signer);
+        fixtures.data("vote", &vote()).unwrap();
+        assert!(fixtures.data("vote", &vote()).is_err());
+    }
+}
+
+mod merkle {
+    use constellation_sdk::data_update::{update_hash, DataUpdate};
+    use constellation_sdk::merkle::{MerkleProof, MerkleTree};
+    use constellation_sdk::{hash_bytes, Hash};
+    use serde::Serialize;
+    use sha2::{Digest, Sha256};
+
+    fn hashes(count: u8) -> Vec<Hash> {
+        (0..count).map(|i| hash_bytes(&[i])).collect()
+    }
+
+    fn sha256(parts: &[&[u8]]) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize().to_vec()
+    }
+
+    #[test]
+    fn proves_every_leaf() {
+        for count in 1..=17 {
+            let hashes = hashes(count);
+            let tree = MerkleTree::new(&hashes);
+            let root = tree.root();
+            assert_eq!(tree.len(), count as usize);
+            for (index, hash) in hashes.iter().enumerate() {
+                let proof = tree.proof(index).unwrap();
+                assert!(proof.verify(hash, &root), "{count} leaves, index {index}");
+                let other = &hashes[(index + 1) % hashes.len()];
+                assert_eq!(proof.verify(other, &root), count == 1);
+            }
+            assert!(tree.proof(count as usize).is_none());
+        }
+    }
+
+    #[test]
+    fn separates_leaves_from_nodes_and_carries_odd_nodes_up() {
+        let hashes = hashes(3);
+        let leaf = |hash: &Hash| sha256(&[&[0], &hash.bytes]);
+        let node = |left: &[u8], right: &[u8]| sha256(&[&[1], left, right]);
+
+        let left = node(&leaf(&hashes[0]), &leaf(&hashes[1]));
+        let expected = node(&left, &leaf(&hashes[2]));
+        assert_eq!(MerkleTree::new(&hashes).root().bytes, expected);
+    }
+
+    #[test]
+    fn empty_tree_has_the_empty_hash_as_root() {
+        let tree = MerkleTree::new(&[]);
+        assert!(tree.is_empty());
+        assert_eq!(tree.root().bytes, sha256(&[]));
+        assert!(tree.proof(0).is_none());
+    }
+
+    #[test]
+    fn rejects_tampered_proofs() {
+        let hashes = hashes(6);
+        let tree = MerkleTree::new(&hashes);
+        let root = tree.root();
+        let proof = tree.proof(4).unwrap();
+
+        let json = serde_json::to_value(&proof).unwrap();
+        assert_eq!(json["leafCount"], 6);
+        let parsed: MerkleProof = serde_json::from_value(json).unwrap();
+        assert!(parsed.verify(&hashes[4], &root));
+
+        let mut moved = proof.clone();
+        moved.index = 5;
+        assert!(!moved.verify(&hashes[4], &root));
+
+        let mut resized = proof.clone();
+        resized.leaf_count = 5;
+        assert!(!resized.verify(&hashes[4], &root));
+
+        let mut truncated = proof.clone();
+        truncated.siblings.pop();
+        assert!(!truncated.verify(&hashes[4], &root));
+
+        let mut corrupted = proof;
+        corrupted.siblings[0] = "zz".to_string();
+        assert!(corrupted.compute_root(&hashes[4]).is_none());
+    }
+
+    #[test]
+    fn commits_to_data_updates() {
+        #[derive(Serialize)]
+        struct Vote {
+            option: u32,
+        }
+
+        impl DataUpdate for Vote {}
+
+        let votes: Vec<Vote> = (0..4).map(|option| Vote { option }).collect();
+        let tree = MerkleTree::from_updates(&votes).unwrap();
+        let leaf = update_hash(&votes[2]).unwrap();
+        assert!(tree.proof(2).unwrap().verify(&leaf, &tree.root()));
+    }
+}
+
+mod preflight_validation {
+    use super::*;
+    use constellation_sdk::{
+        validate_data_update, TransferParams, ValidationIssue, ValidationRules,
+    };
+
+    const DESTINATION: &str = "DAG4o41NzhfX6DyYBTTXu6sJa6awm36abJpv89jB";
+
+    #[test]
+    fn reports_every_transfer_problem() {
+        let params = TransferParams {
+            destination: "DAGnope".to_string(),
+            amount: 0.0,
+            fee: -1.0,
+        };
+        let report = params.validate("not-an-address");
+
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.issues,
+            vec![
+                ValidationIssue::InvalidSourceAddress("not-an-address".to_string()),
+                ValidationIssue::InvalidDestinationAddress("DAGnope".to_string()),
+                ValidationIssue::InvalidAmount(0.0),
+                ValidationIssue::InvalidFee(-1.0),
+            ]
+        );
+        assert!(report.to_string().contains("; "));
+    }
+
+    #[test]
+    fn checks_minimum_fee_and_accepts_valid_transfers() {
+        let sender = generate_key_pair();
+        let params = TransferParams {
+            destination: DESTINATION.to_string(),
+            amount: 1.5,
+            fee: 0.0,
+        };
+        assert!(params.validate(&sender.address).is_valid());
+
+        let rules = ValidationRules {
+            min_fee: 100,
+            ..Default::default()
+        };
+        assert_eq!(
+            params.validate_with(&sender.address, &rules).issues,
+            vec![ValidationIssue::FeeBelowMinimum {
+                fee: 0,
+                minimum: 100
+            }]
+        );
+
+        let to_self = TransferParams {
+            destination: sender.address.clone(),
+            ..params
+        };
+        assert_eq!(
+            to_self.validate(&sender.address).issues,
+            vec![ValidationIssue::SameAddress]
+        );
+    }
+
+    #[test]
+    fn limits_data_update_size() {
+        let update = json!({"payload": "x".repeat(100)});
+        assert!(validate_data_update(&update, &ValidationRules::default()).is_valid());
+
+        let rules = ValidationRules {
+            max_payload_size: 64,
+            ..Default::default()
+        };
+        let report = validate_data_update(&update, &rules);
+        assert!(matches!(
+            report.issues[..],
+            [ValidationIssue::PayloadTooLarge { limit: 64, .. }]
+        ));
+    }
+}
+
+// Error paths throw JavaScript errors and can only run under wasm32.
+#[cfg(feature = "wasm")]
+mod wasm_bindings {
+    use super::*;
+    use constellation_sdk::wasm;
+
+    #[test]
+    fn signs_and_verifies_json_strings() {
+        let key_pair = wasm::generate_key_pair();
+        let derived = wasm::key_pair_from_private_key(&key_pair.private_key).unwrap();
+        assert_eq!(derived.address, key_pair.address);
+        assert_eq!(
+            wasm::get_address(&key_pair.public_key).unwrap(),
+            key_pair.address
+        );
+
+        let value = r#"{"id":"sensor-1","reading":42}"#;
+        let signed = wasm::create_signed_object(value, &key_pair.private_key, true).unwrap();
+        let signed = wasm::add_signature(&signed, &generate_key_pair().private_key, true).unwrap();
+        assert!(wasm::verify(&signed, true).unwrap());
+        assert!(!wasm::verify(&signed, false).unwrap());
+
+        let parsed: Signed<serde_json::Value> = serde_json::from_str(&signed).unwrap();
+        assert_eq!(parsed.proofs.len(), 2);
+        assert!(verify(&parsed, true).is_valid);
+    }
+
+    #[test]
+    fn encodes_and_hashes_bytes() {
+        let value = r#"{"b":2,"a":1}"#;
+        let bytes = wasm::encode_data_update(value).unwrap();
+        assert_eq!(bytes, wasm::to_bytes(value, true).unwrap());
+        assert_eq!(
+            wasm::decode_data_update(&bytes).unwrap(),
+            r#"{"a":1,"b":2}"#
+        );
+
+        let key_pair = generate_key_pair();
+        let hash = wasm::hash_bytes(&bytes);
+        let signature = wasm::sign_hash(&hash, &key_pair.private_key).unwrap();
+        let proof: constellation_sdk::SignatureProof =
+            serde_json::from_str(&wasm::sign(value, &key_pair.private_key, true).unwrap()).unwrap();
+        assert!(wasm::verify_hash(&hash, &signature, &proof.id).unwrap());
+        assert!(wasm::verify_hash(&hash, &proof.signature, &proof.id).unwrap());
+    }
+}
+
+// Functions taking or returning JavaScript values need a Node runtime; the
+// ones over plain strings are exercised here.
+#[cfg(feature = "napi")]
+mod node_bindings {
+    use super::*;
+    use constellation_sdk::node;
+
+    #[test]
+    fn derives_keys_and_signs_hashes() {
+        let key_pair = node::generate_key_pair();
+        let derived = node::key_pair_from_private_key(key_pair.private_key.clone()).unwrap();
+        assert_eq!(derived.address, key_pair.address);
+        assert_eq!(
+            node::get_address(key_pair.public_key.clone()).unwrap(),
+            key_pair.address
+        );
+        assert!(node::is_valid_private_key(key_pair.private_key.clone()));
+        assert!(node::key_pair_from_private_key("not hex".to_string()).is_err());
+
+        let hash = hash_data(&serde_json::json!({"id": "sensor-1"}), true)
+            .unwrap()
+            .value;
+        let signature = node::sign_hash(hash.clone(), key_pair.private_key.clone()).unwrap();
+        let id = key_pair.public_key[2..].to_string();
+        assert!(node::verify_hash(hash, signature, id).unwrap());
+    }
+}
+
+#[cfg(feature = "ffi")]
+mod c_ffi {
+    use super::*;
+    use constellation_sdk::ffi::*;
+    use std::ffi::{c_char, CStr, CString};
+    use std::ptr;
+
+    unsafe fn take(s: *mut c_char) -> String {
+        let owned = CStr::from_ptr(s).to_str().unwrap().to_string();
+        constellation_string_free(s);
+        owned
+    }
+
+    #[test]
+    fn signs_verifies_and_encodes() {
+        unsafe {
+            let (mut private_key, mut public_key, mut address) =
+                (ptr::null_mut(), ptr::null_mut(), ptr::null_mut());
+            assert_eq!(
+                constellation_generate_key_pair(&mut private_key, &mut public_key, &mut address),
+                FfiStatus::Ok
+            );
+            let key = CString::new(take(private_key)).unwrap();
+            let (public_key, address) = (take(public_key), take(address));
+            assert_eq!(
+                constellation_sdk::try_get_address(&public_key).unwrap(),
+                address
+            );
+
+            let value = CString::new(r#"{"id":1}"#).unwrap();
+            let mut signed = ptr::null_mut();
+            assert_eq!(
+                constellation_create_signed_object(value.as_ptr(), key.as_ptr(), true, &mut signed),
+                FfiStatus::Ok
+            );
+            let signed = CString::new(take(signed)).unwrap();
+            let mut valid = false;
+            assert_eq!(
+                constellation_verify(signed.as_ptr(), true, &mut valid),
+                FfiStatus::Ok
+            );
+            assert!(valid);
+
+            let (mut bytes, mut len) = (ptr::null_mut(), 0);
+            assert_eq!(
+                constellation_encode_data_update(value.as_ptr(), &mut bytes, &mut len),
+                FfiStatus::Ok
+            );
+            let encoded = std::slice::from_raw_parts(bytes, len).to_vec();
+            assert_eq!(encoded, encode_data_update(&json!({"id": 1})).unwrap());
+
+            let mut hash = ptr::null_mut();
+            assert_eq!(
+                constellation_hash_bytes(bytes, len, &mut hash),
+                FfiStatus::Ok
+            );
+            constellation_bytes_free(bytes, len);
+            let hash = CString::new(take(hash)).unwrap();
+
+            let mut signature = ptr::null_mut();
+            assert_eq!(
+                constellation_sign_hash(hash.as_ptr(), key.as_ptr(), &mut signature),
+                FfiStatus::Ok
+            );
+            let signature = CString::new(take(signature)).unwrap();
+            let id = CString::new(&public_key[2..]).unwrap();
+            assert_eq!(
+                constellation_verify_hash(
+                    hash.as_ptr(),
+                    signature.as_ptr(),
+                    id.as_ptr(),
+                    &mut valid
+                ),
+                FfiStatus::Ok
+            );
+            assert!(valid);
+        }
+    }
+
+    #[test]
+    fn reports_errors_through_status_and_last_error() {
+        unsafe {
+            let mut out = ptr::null_mut();
+            let key = CString::new("not a key").unwrap();
+            let value = CString::new("{}").unwrap();
+            assert_eq!(
+                constellation_create_signed_object(value.as_ptr(), key.as_ptr(), false, &mut out),
+                FfiStatus::Failed
+            );
+            assert!(out.is_null());
+            assert!(!take(constellation_last_error()).is_empty());
+            assert_ne!(constellation_last_error_code(), 0);
+
+            assert_eq!(
+                constellation_create_signed_object(ptr::null(), key.as_ptr(), false, &mut out),
+                FfiStatus::InvalidArgument
+            );
+            assert_eq!(take(constellation_last_error()), "value_json is null");
+            assert_eq!(constellation_last_error_code(), 0);
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+mod python_bindings {
+    use constellation_sdk::python::python_module;
+    use pyo3::prelude::*;
+    use pyo3::types::PyDict;
+    use std::ffi::CString;
+
+    fn run(code: &str) -> PyResult<()> {
+        run_with(code, "")
+    }
+
+    fn run_with(code: &str, url: &str) -> PyResult<()> {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let sdk = PyModule::new(py, "constellation_sdk_rs")?;
+            python_module(&sdk)?;
+            let globals = PyDict::new(py);
+            globals.set_item("sdk", sdk)?;
+            globals.set_item("url", url)?;
+            py.run(&CString::new(code).unwrap(), Some(&globals), None)
+        })
+    }
+
+    #[test]
+    fn signs_and_verifies_python_values() {
+        run(r#"
+key_pair = sdk.generate_key_pair()
+assert sdk.key_pair_from_private_key(key_pair["private_key"]) == key_pair
+assert sdk.is_valid_dag_address(key_pair["address"])
+
+signed = sdk.create_signed_object({"id": "sensor-1", "reading": [1, 2.5]}, key_pair["private_key"], True)
+assert signed["value"] == {"id": "sensor-1", "reading": [1, 2.5]}
+assert sdk.verify(signed, True)
+assert not sdk.verify(signed)
+
+other = sdk.generate_key_pair()
+signed = sdk.add_signature(signed, other["private_key"], True)
+assert len(signed["proofs"]) == 2 and sdk.verify(signed, True)
+assert sdk.canonicalize({"b": 1, "a": 2}) == '{"a":2,"b":1}'
+"#)
+        .unwrap();
+    }
+
+    #[test]
+    fn raises_sdk_error() {
+        run(r#"
+try:
+    sdk.create_signed_object({}, "not a key")
+    raise AssertionError("expected SdkError")
+except sdk.SdkError:
+    pass
+
+try:
+    sdk.MetagraphClient("http://localhost:9000", "xl1")
+    raise AssertionError("expected SdkError")
+except sdk.SdkError as e:
+    assert "Unknown layer" in str(e)
+"#)
+        .unwrap();
+    }
+
+    #[test]
+    fn reads_balance_through_account() {
+        use serde_json::json;
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        // The mock server runs on its own runtime; the bindings block on theirs
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server = runtime.block_on(async {
+            let server = MockServer::start().await;
+            Mock::given(method("GET"))
+                .and(path(format!(
+                    "/currency/{}/balance",
+                    constellation_sdk::key_pair_from_private_key(&"11".repeat(32))
+                        .unwrap()
+                        .address
+                )))
+                .respond_with(
+                    ResponseTemplate::new(200).set_body_json(json!({"balance": 7, "ordinal": 3})),
+                )
+                .mount(&server)
+                .await;
+            server
+        });
+
+        run_with(
+            r#"
+account = sdk.Account("11" * 32, currency_l0_url=url)
+assert account.balance() == {"balance": 7, "ordinal": 3}
+"#,
+            &server.uri(),
+        )
+        .unwrap();
+    }
+}
+
+#[cfg(feature = "daemon")]
+mod signing_daemon {
+    use super::*;
+    use constellation_sdk::daemon::{error_codes, AuditEntry, Keystore, SigningDaemon};
+    use constellation_sdk::{verify_hash, LocalSigner, SdkError, Signer};
+    use serde_json::Value;
+    use std::sync::{Arc, Mutex};
+
+    fn call(daemon: &SigningDaemon, method: &str, params: Value) -> Value {
+        let request = json!({"jsonrpc": "2.0", "id": 7, "method": method, "params": params});
+        serde_json::from_str(&daemon.handle(&request.to_string(), None)).unwrap()
+    }
+
+    #[test]
+    fn keystore_round_trips() {
+        let signer = LocalSigner::generate();
+        let keystore = Keystore::encrypt(&signer.key_pair().private_key, "hunter2").unwrap();
+        let keystore = Keystore::from_json(&keystore.to_json().unwrap()).unwrap();
+        assert_eq!(keystore.address.as_deref(), Some(signer.address().as_str()));
+
+        let unlocked = keystore.decrypt("hunter2").unwrap();
+        assert_eq!(unlocked.public_key_id(), signer.public_key_id());
+        assert!(matches!(
+            keystore.decrypt("wrong"),
+            Err(SdkError::CryptoError(_))
+        ));
+    }
+
+    #[test]
+    fn keystore_mac_check_accepts_any_case_and_rejects_malformed() {
+        let signer = LocalSigner::generate();
+        let mut keystore = Keystore::encrypt(&signer.key_pair().private_key, "pass").unwrap();
+        keystore.crypto.mac = keystore.crypto.mac.to_ascii_uppercase();
+        assert!(keystore.decrypt("pass").is_ok());
+
+        for mac in ["", "zz", &keystore.crypto.mac[..62]] {
+            let mut tampered = keystore.clone();
+            tampered.crypto.mac = mac.to_string();
+            assert!(matches!(
+                tampered.decrypt("pass"),
+                Err(SdkError::CryptoError(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn decrypts_web3_secret_storage_pbkdf2_vector() {
+        let keystore = Keystore::from_json(
+            r#"{
+                "crypto": {
+                    "cipher": "aes-128-ctr",
+                    "cipherparams": {"iv": "6087dab2f9fdbbfaddc31a909735c1e6"},
+                    "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                    "kdf": "pbkdf2",
+                    "kdfparams": {
+                        "c": 262144,
+                        "dklen": 32,
+                        "prf": "hmac-sha256",
+                        "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+                    },
+                    "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+                },
+                "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+                "version": 3
+            }"#,
+        )
+        .unwrap();
+        let signer = keystore.decrypt("testpassword").unwrap();
+        assert_eq!(
+            signer.key_pair().private_key,
+            "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d"
+        );
+    }
+
+    #[test]
+    fn signs_over_json_rpc_once_unlocked() {
+        let owner = LocalSigner::generate();
+        let keystore = Keystore::encrypt(&owner.key_pair().private_key, "pass").unwrap();
+        let entries = Arc::new(Mutex::new(Vec::<AuditEntry>::new()));
+        let recorded = entries.clone();
+        let daemon = SigningDaemon::new()
+            .with_keystore("validator", keystore)
+            .with_signer("hot", LocalSigner::generate())
+            .with_audit(move |entry: &AuditEntry| recorded.lock().unwrap().push(entry.clone()));
+
+        let keys = call(&daemon, "listKeys", Value::Null);
+        assert_eq!(keys["result"][1]["name"], "validator");
+        assert_eq!(keys["result"][1]["unlocked"], false);
+        assert_eq!(keys["result"][1]["address"], owner.address());
+
+        let params = json!({"key": "validator", "value": {"id": 1}, "isDataUpdate": true});
+        let locked = call(&daemon, "sign", params.clone());
+        assert_eq!(locked["error"]["code"], error_codes::KEY_LOCKED);
+
+        let wrong = call(
+            &daemon,
+            "unlock",
+            json!({"key": "validator", "passphrase": "nope"}),
+        );
+        assert_eq!(wrong["error"]["code"], error_codes::SIGNING_FAILED);
+        let unlocked = call(
+            &daemon,
+            "unlock",
+            json!({"key": "validator", "passphrase": "pass"}),
+        );
+        assert_eq!(unlocked["result"], true);
+
+        let signed = call(&daemon, "signObject", params.clone());
+        let signed: Signed<Value> = serde_json::from_value(signed["result"].clone()).unwrap();
+        assert_eq!(signed.proofs[0].id, owner.public_key_id());
+        assert!(verify(&signed, true).is_valid);
+
+        let hash = hash_data(&json!({"id": 1}), false).unwrap();
+        let proof = call(
+            &daemon,
+            "signHash",
+            json!({"key": "validator", "hash": hash.value}),
+        );
+        let signature = proof["result"]["signature"].as_str().unwrap();
+        assert!(verify_hash(&hash.value, signature, &owner.public_key_id()).unwrap());
+
+        assert_eq!(
+            call(&daemon, "lock", json!({"key": "validator"}))["result"],
+            true
+        );
+        assert_eq!(
+            call(&daemon, "sign", params)["error"]["code"],
+            error_codes::KEY_LOCKED
+        );
+        assert_eq!(
+            call(&daemon, "lock", json!({"key": "hot"}))["error"]["code"],
+            error_codes::SIGNING_FAILED
+        );
+        assert_eq!(
+            call(&daemon, "sign", json!({"key": "cold", "value": 1}))["error"]["code"],
+            error_codes::UNKNOWN_KEY
+        );
+        assert_eq!(
+            call(&daemon, "export", Value::Null)["error"]["code"],
+            error_codes::METHOD_NOT_FOUND
+        );
+        assert_eq!(
+            call(&daemon, "sign", json!({"value": 1}))["error"]["code"],
+            error_codes::INVALID_PARAMS
+        );
+
+        let entries = entries.lock().unwrap();
+        assert_eq!(entries.len(), 12);
+        let signing = &entries[4];
+        assert_eq!(signing.method, "signObject");
+        assert_eq!(signing.key.as_deref(), Some("validator"));
+        assert_eq!(
+            signing.hash,
+            Some(hash_data(&json!({"id": 1}), true).unwrap().value)
+        );
+        assert!(signing.ok);
+        let audit = serde_json::to_string(&*entries).unwrap();
+        assert!(!audit.contains("pass\""));
+        assert!(!audit.contains(&owner.key_pair().private_key));
+    }
+
+    #[test]
+    fn keystore_rejects_weak_kdf_parameters() {
+        let signer = LocalSigner::generate();
+        let keystore = Keystore::encrypt(&signer.key_pair().private_key, "pass").unwrap();
+        let mut json: Value = serde_json::from_str(&keystore.to_json().unwrap()).unwrap();
+
+        json["crypto"]["kdfparams"]["n"] = json!(2);
+        let weak = Keystore::from_json(&json.to_string()).unwrap();
+        assert!(matches!(
+            weak.decrypt("pass"),
+            Err(SdkError::CryptoError(_))
+        ));
+
+        json["crypto"]["kdf"] = json!("pbkdf2");
+        json["crypto"]["kdfparams"] = json!({
+            "c": 0,
+            "dklen": 32,
+            "prf": "hmac-sha256",
+            "salt": "00".repeat(32),
+        });
+        let weak = Keystore::from_json(&json.to_string()).unwrap();
+        assert!(matches!(
+            weak.decrypt("pass"),
+            Err(SdkError::CryptoError(_))
+        ));
+    }
+
+    #[test]
+    fn throttles_failed_unlocks() {
+        let owner = LocalSigner::generate();
+        let keystore = Keystore::encrypt(&owner.key_pair().private_key, "pass").unwrap();
+        let daemon = SigningDaemon::new().with_keystore("validator", keystore);
+
+        for _ in 0..4 {
+            let error = daemon.unlock("validator", "nope").unwrap_err();
+            assert!(!error.to_string().contains("too many"));
+        }
+        let throttled = daemon.unlock("validator", "pass").unwrap_err();
+        assert!(throttled.to_string().contains("too many failed attempts"));
+    }
+
+    #[test]
+    fn rejects_malformed_requests() {
+        let daemon = SigningDaemon::new();
+        let parse: Value = serde_json::from_str(&daemon.handle("{", None)).unwrap();
+        assert_eq!(parse["error"]["code"], error_codes::PARSE_ERROR);
+        let version: Value = serde_json::from_str(
+            &daemon.handle(r#"{"jsonrpc":"1.0","id":1,"method":"listKeys"}"#, None),
+        )
+        .unwrap();
+        assert_eq!(version["error"]["code"], error_codes::INVALID_REQUEST);
+        assert_eq!(version["id"], 1);
+    }
+
+    #[cfg(feature = "network")]
+    #[tokio::test]
+    async fn serves_http_with_bearer_token() {
+        let daemon = SigningDaemon::new()
+            .with_signer("hot", LocalSigner::generate())
+            .with_token("secret");
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(Arc::new(daemon).serve(listener));
+
+        let client = constellation_sdk::network::reqwest::Client::new();
+        let body = json!({"jsonrpc": "2.0", "id": 1, "method": "listKeys"});
+        let denied = client.post(&url).json(&body).send().await.unwrap();
+        assert_eq!(denied.status().as_u16(), 401);
+
+        let reply: Value = client
+            .post(&url)
+            .bearer_auth("secret")
+            .json(&body)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(reply["result"][0]["name"], "hot");
+        assert_eq!(reply["result"][0]["unlocked"], true);
+
+        let from_page = client
+            .post(&url)
+            .bearer_auth("secret")
+            .header("Origin", "https://attacker.example")
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(from_page.status().as_u16(), 403);
+        let rebound = client
+            .post(&url)
+            .bearer_auth("secret")
+            .header("Host", "attacker.example:9777")
+            .json(&body)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(rebound.status().as_u16(), 403);
+    }
+}
+
+#[cfg(feature = "memsec")]
+mod protected_signer {
+    use super::*;
+    use constellation_sdk::memsec::ProtectedSigner;
+    use constellation_sdk::signer::sign_object;
+    use constellation_sdk::{LocalSigner, Signer};
+
+    #[test]
+    fn signs_like_the_local_signer_with_the_same_key() {
+        let local = LocalSigner::generate();
+        let protected = ProtectedSigner::new(&local.key_pair().private_key).unwrap();
+        assert_eq!(protected.address(), local.address());
+
+        let data = json!({"id": "test", "value": 42});
+        let signed = sign_object(&protected, &data, true).unwrap();
+        assert!(verify(&signed, true).is_valid);
+        assert_eq!(
+            signed.proofs,
+            sign_object(&local, &data, true).unwrap().proofs
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_keys_and_hides_them_from_debug() {
+        assert!(ProtectedSigner::new("not hex").is_err());
+        assert!(ProtectedSigner::new(&"00".repeat(32)).is_err());
+
+        let local = LocalSigner::generate();
+        let protected = ProtectedSigner::new(&local.key_pair().private_key).unwrap();
+        let debug = format!("{protected:?}");
+        assert!(debug.contains(&local.address()));
+        assert!(!debug.contains(&local.key_pair().private_key));
+    }
+
+    #[cfg(feature = "daemon")]
+    #[test]
+    fn decrypts_a_keystore_into_protected_memory() {
+        use constellation_sdk::daemon::Keystore;
+
+        let local = LocalSigner::generate();
+        let keystore = Keystore::encrypt(&local.key_pair().private_key, "hunter2").unwrap();
+        let protected = keystore.decrypt_protected("hunter2").unwrap();
+        assert_eq!(protected.public_key_id(), local.public_key_id());
+        assert!(keystore.decrypt_protected("wrong").is_err());
+    }
 }